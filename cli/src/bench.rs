@@ -0,0 +1,191 @@
+//! Quick throughput check of the codec on the current machine.
+//!
+//! Far less rigorous than the criterion benchmarks in the core crate, but
+//! needs nothing beyond the installed binary. Build with `--features simd`
+//! to compare the SIMD text decoder.
+
+use crate::error::Result;
+use dollcode::{
+    from_dollcode,
+    text::{TextDecoder, TextIterator},
+    to_dollcode, Dollcode,
+};
+use std::{
+    hint::black_box,
+    io::Write,
+    time::{Duration, Instant},
+};
+
+/// Characters of sample text per iteration
+const TEXT_LEN: usize = 16 * 1024;
+
+/// Result of one benchmark
+#[derive(Debug)]
+struct Measurement {
+    name: &'static str,
+    ns_per_op: f64,
+    mb_per_sec: f64,
+}
+
+/// Runs `f` until `duration` has passed, counting `ops` operations and
+/// `bytes` bytes of dollcode per call
+fn measure(
+    name: &'static str,
+    duration: Duration,
+    ops: usize,
+    bytes: usize,
+    mut f: impl FnMut() -> Result<()>,
+) -> Result<Measurement> {
+    // Warm up caches and catch errors before timing
+    f()?;
+
+    let start = Instant::now();
+    let mut iterations = 0u64;
+    while iterations == 0 || start.elapsed() < duration {
+        f()?;
+        iterations += 1;
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+
+    Ok(Measurement {
+        name,
+        ns_per_op: elapsed * 1e9 / (iterations as f64 * ops as f64),
+        mb_per_sec: iterations as f64 * bytes as f64 / elapsed / 1e6,
+    })
+}
+
+fn sample_text() -> String {
+    "The quick brown fox jumps over the lazy dog. 0123456789!"
+        .chars()
+        .cycle()
+        .take(TEXT_LEN)
+        .collect()
+}
+
+/// Times number and text conversions for `duration` each and prints a row
+/// per conversion. Throughput counts the UTF-8 bytes of dollcode produced or
+/// consumed; an op is one number or one text character.
+pub fn bench(duration: Duration, out: &mut impl Write) -> Result<()> {
+    // Smallest number of every length, so all glyph counts are covered
+    let numbers: Vec<u64> = (1..=40)
+        .map(|digits| (0..digits).fold(0, |acc, _| acc * 3 + 1))
+        .chain([u64::MAX])
+        .collect();
+    let encoded: Vec<Dollcode> = numbers
+        .iter()
+        .map(|&n| to_dollcode(n))
+        .collect::<dollcode::Result<_>>()?;
+    let number_bytes = encoded.iter().map(|d| d.to_string().len()).sum();
+
+    let text = sample_text();
+    let encoded_text = TextIterator::new(&text)
+        .map(|segment| segment.map(|s| s.to_string()))
+        .collect::<dollcode::Result<String>>()?;
+
+    let measurements = [
+        measure(
+            "encode number",
+            duration,
+            numbers.len(),
+            number_bytes,
+            || {
+                for &n in &numbers {
+                    black_box(to_dollcode(black_box(n))?);
+                }
+                Ok(())
+            },
+        )?,
+        measure(
+            "decode number",
+            duration,
+            encoded.len(),
+            number_bytes,
+            || {
+                for dollcode in &encoded {
+                    black_box(from_dollcode(black_box(dollcode.as_chars()))?);
+                }
+                Ok(())
+            },
+        )?,
+        measure(
+            "encode text",
+            duration,
+            TEXT_LEN,
+            encoded_text.len(),
+            || {
+                for segment in TextIterator::new(black_box(&text)) {
+                    black_box(segment?);
+                }
+                Ok(())
+            },
+        )?,
+        measure(
+            "decode text",
+            duration,
+            TEXT_LEN,
+            encoded_text.len(),
+            || {
+                for c in TextDecoder::new(black_box(&encoded_text)) {
+                    black_box(c?);
+                }
+                Ok(())
+            },
+        )?,
+    ];
+
+    let build = if cfg!(debug_assertions) {
+        "debug build, numbers will be low"
+    } else {
+        "release build"
+    };
+    let features = if cfg!(feature = "simd") {
+        "simd"
+    } else {
+        "none"
+    };
+    writeln!(
+        out,
+        "dollcode {} ({build}), features: {features}",
+        env!("CARGO_PKG_VERSION")
+    )?;
+    writeln!(out, "{:<14}  {:>10}  {:>10}", "benchmark", "ns/op", "MB/s")?;
+    for Measurement {
+        name,
+        ns_per_op,
+        mb_per_sec,
+    } in measurements
+    {
+        writeln!(out, "{name:<14}  {ns_per_op:>10.2}  {mb_per_sec:>10.2}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let mut calls = 0;
+        let measurement = measure("test", Duration::ZERO, 4, 1000, || {
+            calls += 1;
+            Ok(())
+        })
+        .unwrap();
+        // Warm-up plus at least one timed call
+        assert_eq!(calls, 2);
+        assert!(measurement.ns_per_op >= 0.0);
+        assert!(measurement.mb_per_sec > 0.0);
+    }
+
+    #[test]
+    fn test_report() {
+        let mut out = Vec::new();
+        bench(Duration::ZERO, &mut out).unwrap();
+        let report = String::from_utf8(out).unwrap();
+        let rows: Vec<&str> = report.lines().collect();
+        assert_eq!(rows.len(), 6);
+        assert!(rows[1].starts_with("benchmark"));
+        assert!(rows[5].starts_with("decode text"));
+    }
+}
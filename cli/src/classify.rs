@@ -0,0 +1,177 @@
+//! Per-line triage of mixed input, such as log files with dollcode in them.
+
+use crate::{convert, error::Result, json::RecordError};
+use dollcode::{digit_value, text::DELIMITER};
+use serde::Serialize;
+use std::io::{BufRead, Write};
+
+/// What a line looks like, named as in the website's `detect`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Kind {
+    /// Blank line
+    Empty,
+    /// Decimal digits
+    Decimal,
+    /// `0x` followed by hex digits
+    Hex,
+    /// Anything else without glyphs
+    Text,
+    /// Glyphs without delimiters
+    DollcodeNumber,
+    /// Glyphs with delimiters
+    DollcodeText,
+}
+
+impl Kind {
+    /// Detects the kind of `line` from its shape alone
+    pub fn detect(line: &str) -> Self {
+        if line.trim().is_empty() {
+            Self::Empty
+        } else if line.contains(DELIMITER) {
+            Self::DollcodeText
+        } else if line.chars().any(|c| digit_value(c).is_some()) {
+            Self::DollcodeNumber
+        } else if line.starts_with("0x") && convert::is_number(line) {
+            Self::Hex
+        } else if convert::is_number(line) {
+            Self::Decimal
+        } else {
+            Self::Text
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Empty => "empty",
+            Self::Decimal => "decimal",
+            Self::Hex => "hex",
+            Self::Text => "text",
+            Self::DollcodeNumber => "dollcode-number",
+            Self::DollcodeText => "dollcode-text",
+        }
+    }
+}
+
+/// Classification of one line
+#[derive(Debug, Serialize)]
+struct Classified {
+    /// 1-based line number
+    line: usize,
+    kind: Kind,
+    /// Decoded value of dollcode, or the dollcode of anything else
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RecordError>,
+}
+
+impl Classified {
+    fn new(line: usize, input: &str) -> Self {
+        let kind = Kind::detect(input);
+        let mut classified = Self {
+            line,
+            kind,
+            value: None,
+            error: None,
+        };
+        if kind == Kind::Empty {
+            return classified;
+        }
+
+        let mut out = Vec::new();
+        match convert::auto(input, &mut out) {
+            Ok(()) => classified.value = Some(String::from_utf8_lossy(&out).into_owned()),
+            Err(e) => classified.error = Some(e.into()),
+        }
+        classified
+    }
+}
+
+/// Writes the kind and conversion of every line of `reader`, tab-separated
+/// after the line number, or as JSON records.
+///
+/// Lines that do not convert are reported in place and do not stop the
+/// scan. Bytes that are not UTF-8 are replaced, so such lines show up as
+/// unsupported characters instead of ending the scan.
+pub fn classify(mut reader: impl BufRead, json: bool, out: &mut impl Write) -> Result<()> {
+    let mut buf = Vec::new();
+    for line in 1.. {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+        let input = String::from_utf8_lossy(&buf);
+        let input = input.strip_suffix('\n').unwrap_or(&input);
+        let input = input.strip_suffix('\r').unwrap_or(input);
+        write(&Classified::new(line, input), json, out)?;
+    }
+    Ok(())
+}
+
+/// Classifies `input` as a single line
+pub fn classify_line(input: &str, json: bool, out: &mut impl Write) -> Result<()> {
+    write(&Classified::new(1, input), json, out)
+}
+
+fn write(classified: &Classified, json: bool, out: &mut impl Write) -> Result<()> {
+    if json {
+        serde_json::to_writer(&mut *out, classified).map_err(std::io::Error::from)?;
+        writeln!(out)?;
+        return Ok(());
+    }
+
+    write!(out, "{}\t{}\t", classified.line, classified.kind.name())?;
+    match (&classified.value, &classified.error) {
+        (_, Some(error)) => writeln!(out, "error: {}", error.message)?,
+        (Some(value), None) => writeln!(out, "{value}")?,
+        (None, None) => writeln!(out)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Kind::detect("  "), Kind::Empty);
+        assert_eq!(Kind::detect("42"), Kind::Decimal);
+        assert_eq!(Kind::detect("0xFF"), Kind::Hex);
+        assert_eq!(Kind::detect("0x"), Kind::Text);
+        assert_eq!(Kind::detect("▖▖ ▖▌"), Kind::DollcodeNumber);
+        assert_eq!(Kind::detect("▘▖▘▌\u{200d}"), Kind::DollcodeText);
+        assert_eq!(Kind::detect("id=▖▖▖▌"), Kind::DollcodeNumber);
+    }
+
+    #[test]
+    fn test_classify() {
+        let mut input = "▖▖▖▌\nHi\r\n\n▘▖▘▌\u{200d}▌▘▖▌\u{200d}\nid=▖▖▖▌\n"
+            .as_bytes()
+            .to_vec();
+        input.extend_from_slice(b"\xFF\n");
+        let mut out = Vec::new();
+        classify(input.as_slice(), false, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "1\tdollcode-number\t42\n\
+             2\ttext\t▘▖▘▌\u{200d}▌▘▖▌\u{200d}\n\
+             3\tempty\t\n\
+             4\tdollcode-text\tHi\n\
+             5\tdollcode-number\terror: invalid dollcode sequence\n\
+             6\ttext\terror: unsupported character '\u{fffd}' at position 0\n"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let mut out = Vec::new();
+        classify("0x2A\nx▖\n".as_bytes(), true, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"line\":1,\"kind\":\"hex\",\"value\":\"▖▖▖▌\"}\n\
+             {\"line\":2,\"kind\":\"dollcode-number\",\"error\":{\"message\":\"invalid dollcode sequence\",\"kind\":\"validation\"}}\n"
+        );
+    }
+}
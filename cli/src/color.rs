@@ -0,0 +1,156 @@
+//! Terminal colors, for glyphs on stdout and the error prefix on stderr.
+//!
+//! Colors are off unless the stream is a terminal, following
+//! <https://no-color.org> and `--color`.
+
+use clap::ColorChoice;
+use dollcode::digit_value;
+use std::{
+    env,
+    io::{self, IsTerminal, Write},
+};
+
+/// Resets colors after a colored run of output
+const RESET: &str = "\x1b[0m";
+
+/// Color of each digit, as ▖, ▘ and ▌
+const DIGIT_COLORS: [&str; 3] = ["\x1b[36m", "\x1b[35m", "\x1b[33m"];
+
+/// Red, for the error prefix
+pub const ERROR: &str = "\x1b[1;31m";
+
+/// Whether to color output to `stream` under `choice`.
+///
+/// `auto` colors terminals, except when `NO_COLOR` is set to anything or
+/// `TERM` is `dumb`.
+pub fn enabled(choice: ColorChoice, stream: &impl IsTerminal) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            stream.is_terminal()
+                && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+                && env::var_os("TERM").is_none_or(|term| term != "dumb")
+        }
+    }
+}
+
+/// Wraps `text` in `color` when `enabled`
+pub fn paint(text: &str, color: &str, enabled: bool) -> String {
+    if enabled {
+        format!("{color}{text}{RESET}")
+    } else {
+        text.to_string()
+    }
+}
+
+/// Writer that colors every glyph by its digit, passing everything else
+/// through unchanged
+#[derive(Debug)]
+pub struct GlyphColors<W: Write> {
+    inner: W,
+    enabled: bool,
+    /// Start of a character split across writes
+    pending: Vec<u8>,
+}
+
+impl<W: Write> GlyphColors<W> {
+    /// Colors output to `inner` if `enabled`
+    pub fn new(inner: W, enabled: bool) -> Self {
+        Self {
+            inner,
+            enabled,
+            pending: Vec::new(),
+        }
+    }
+
+    /// The wrapped writer
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+}
+
+impl<W: Write> Write for GlyphColors<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.enabled {
+            return self.inner.write(buf);
+        }
+
+        self.pending.extend_from_slice(buf);
+        let (text, rest) = match std::str::from_utf8(&self.pending) {
+            Ok(text) => (text, 0),
+            // An incomplete character at the end waits for the next write
+            Err(e) if e.error_len().is_none() => (
+                // Valid up to here, checked above
+                std::str::from_utf8(&self.pending[..e.valid_up_to()]).unwrap_or_default(),
+                self.pending.len() - e.valid_up_to(),
+            ),
+            // Not UTF-8, so there are no glyphs to color
+            Err(_) => {
+                self.inner.write_all(&self.pending)?;
+                self.pending.clear();
+                return Ok(buf.len());
+            }
+        };
+
+        let mut colored = String::with_capacity(text.len());
+        for c in text.chars() {
+            match digit_value(c) {
+                Some(digit) => {
+                    colored.push_str(DIGIT_COLORS[usize::from(digit) - 1]);
+                    colored.push(c);
+                    colored.push_str(RESET);
+                }
+                None => colored.push(c),
+            }
+        }
+        self.inner.write_all(colored.as_bytes())?;
+        self.pending.drain(..self.pending.len() - rest);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.write_all(&self.pending)?;
+        self.pending.clear();
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enabled() {
+        // A file is never a terminal
+        let file = std::fs::File::open(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml")).unwrap();
+        assert!(enabled(ColorChoice::Always, &file));
+        assert!(!enabled(ColorChoice::Never, &file));
+        assert!(!enabled(ColorChoice::Auto, &file));
+    }
+
+    fn colored(writes: &[&[u8]], enabled: bool) -> String {
+        let mut out = GlyphColors::new(Vec::new(), enabled);
+        for write in writes {
+            out.write_all(write).unwrap();
+        }
+        out.flush().unwrap();
+        String::from_utf8(out.inner).unwrap()
+    }
+
+    #[test]
+    fn test_glyph_colors() {
+        assert_eq!(
+            colored(&["a▖▘▌\u{200d}".as_bytes()], true),
+            "a\x1b[36m▖\x1b[0m\x1b[35m▘\x1b[0m\x1b[33m▌\x1b[0m\u{200d}"
+        );
+        assert_eq!(colored(&["▖▘".as_bytes()], false), "▖▘");
+
+        // A glyph split across writes is still colored
+        let glyph = "▌".as_bytes();
+        assert_eq!(
+            colored(&[b"x", &glyph[..1], &glyph[1..], b"\n"], true),
+            "x\x1b[33m▌\x1b[0m\n"
+        );
+    }
+}
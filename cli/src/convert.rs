@@ -0,0 +1,288 @@
+//! Conversions between plain input and dollcode, written straight to an
+//! output stream.
+
+use crate::error::{CliError, Result};
+use dollcode::{
+    bytes::{self, ByteDecoder},
+    digit_value, from_dollcode,
+    text::{TextDecoder, DELIMITER},
+    DollcodeError, Sink, ToDollcode, MAX_DOLLCODE_SIZE,
+};
+use std::io::{self, Write};
+
+/// Whether `input` should be encoded as a number: decimal digits, or `0x`
+/// followed by hex digits, as on the website
+pub fn is_number(input: &str) -> bool {
+    match input.strip_prefix("0x") {
+        Some(hex) => !hex.is_empty() && hex.bytes().all(|b| b.is_ascii_hexdigit()),
+        None => !input.is_empty() && input.bytes().all(|b| b.is_ascii_digit()),
+    }
+}
+
+/// Parses a decimal or `0x`-prefixed hex number
+pub fn parse_number(input: &str) -> Result<u64> {
+    let parsed = match input.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => input.parse().ok(),
+    };
+    parsed.ok_or_else(|| CliError::InvalidNumber(input.to_string()))
+}
+
+/// Encodes a number or text, detected with [`is_number`]
+pub fn encode(input: &str, out: &mut impl Write) -> Result<()> {
+    if is_number(input) {
+        encode_number(input, out)
+    } else {
+        encode_text(input, out)
+    }
+}
+
+/// Encodes a decimal or `0x`-prefixed hex number
+pub fn encode_number(input: &str, out: &mut impl Write) -> Result<()> {
+    write_dollcode(&parse_number(input)?, out)
+}
+
+/// Encodes a decimal or `0x`-prefixed hex number as exactly `width` base-3
+/// digits, with the glyphs of the byte encoding
+pub fn encode_fixed_number(input: &str, width: usize, out: &mut impl Write) -> Result<()> {
+    let value = parse_number(input)?;
+    write_sink(out, |sink| bytes::encode_fixed(value, width, sink))
+}
+
+/// Encodes printable ASCII text segment by segment
+pub fn encode_text(input: &str, out: &mut impl Write) -> Result<()> {
+    if input.is_empty() {
+        return Err(CliError::EmptyInput);
+    }
+
+    write_dollcode(input, out)
+}
+
+/// Encodes printable ASCII text without delimiters, as fixed groups of
+/// [`GLYPHS_PER_BYTE`](dollcode::bytes::GLYPHS_PER_BYTE) glyphs, for places that strip zero-width joiners.
+/// Same as the website's fixed-width text.
+pub fn encode_fixed_width(input: &str, out: &mut impl Write) -> Result<()> {
+    if input.is_empty() {
+        return Err(CliError::EmptyInput);
+    }
+    if let Some((position, c)) = input
+        .chars()
+        .enumerate()
+        .find(|&(_, c)| !matches!(c, ' '..='~'))
+    {
+        return Err(DollcodeError::InvalidChar(c, position).into());
+    }
+
+    write_dollcode(input.as_bytes(), out)
+}
+
+/// Writes any [`ToDollcode`] value to `out` as it is encoded
+fn write_dollcode(value: &(impl ToDollcode + ?Sized), out: &mut impl Write) -> Result<()> {
+    write_sink(out, |sink| value.encode_dollcode(sink))
+}
+
+/// Runs `encode` with a [`Sink`] writing to `out`
+fn write_sink<W: Write>(
+    out: &mut W,
+    encode: impl FnOnce(&mut WriteSink<'_, W>) -> dollcode::Result<()>,
+) -> Result<()> {
+    let mut sink = WriteSink { out, error: None };
+    let encoded = encode(&mut sink);
+    match sink.error {
+        Some(e) => Err(e.into()),
+        None => Ok(encoded?),
+    }
+}
+
+/// Adapts an output stream to a [`Sink`], keeping the write error that the
+/// sink can only report as an overflow
+struct WriteSink<'a, W> {
+    out: &'a mut W,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> Sink for WriteSink<'_, W> {
+    fn push(&mut self, c: char) -> dollcode::Result<()> {
+        write!(self.out, "{c}").map_err(|e| {
+            self.error = Some(e);
+            DollcodeError::Overflow
+        })
+    }
+}
+
+/// Decodes text written by [`encode_fixed_width`]. Whitespace between glyphs
+/// is ignored.
+pub fn decode_fixed_width(input: &str, out: &mut impl Write) -> Result<()> {
+    let glyphs: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    if glyphs.is_empty() {
+        return Err(CliError::EmptyInput);
+    }
+
+    for byte in ByteDecoder::new(&glyphs) {
+        let byte = byte?;
+        if !(b' '..=b'~').contains(&byte) {
+            return Err(DollcodeError::InvalidInput.into());
+        }
+        write!(out, "{}", char::from(byte))?;
+    }
+    Ok(())
+}
+
+/// Decodes a number written by [`encode_fixed_number`] with the same
+/// `width`. Whitespace between glyphs is ignored.
+pub fn decode_fixed_number(input: &str, width: usize, out: &mut impl Write) -> Result<()> {
+    let glyphs: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    if glyphs.is_empty() {
+        return Err(CliError::EmptyInput);
+    }
+    if glyphs.chars().count() != width {
+        return Err(DollcodeError::InvalidInput.into());
+    }
+
+    write!(out, "{}", bytes::decode_fixed(&glyphs)?)?;
+    Ok(())
+}
+
+/// Whether `input` contains any glyph or delimiter, and so is taken as
+/// dollcode, as on the website
+pub fn is_dollcode(input: &str) -> bool {
+    input
+        .chars()
+        .any(|c| c == DELIMITER || digit_value(c).is_some())
+}
+
+/// Decodes input containing any glyph or delimiter, and encodes anything
+/// else with [`encode`], as the website does
+pub fn auto(input: &str, out: &mut impl Write) -> Result<()> {
+    if is_dollcode(input) {
+        decode(input, out)
+    } else {
+        encode(input, out)
+    }
+}
+
+/// Decodes dollcode into text if it contains delimiters, or a number
+/// otherwise. Whitespace between glyphs is ignored.
+pub fn decode(input: &str, out: &mut impl Write) -> Result<()> {
+    let glyphs: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    if glyphs.is_empty() {
+        return Err(CliError::EmptyInput);
+    }
+
+    if glyphs.contains(DELIMITER) {
+        for c in TextDecoder::new(&glyphs) {
+            write!(out, "{}", c?)?;
+        }
+    } else {
+        let chars: Vec<char> = glyphs.chars().collect();
+        if chars.len() > MAX_DOLLCODE_SIZE {
+            return Err(DollcodeError::Overflow.into());
+        }
+        write!(out, "{}", from_dollcode(&chars)?)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(f: fn(&str, &mut Vec<u8>) -> Result<()>, input: &str) -> Result<String> {
+        run_with(f, input)
+    }
+
+    fn run_with(f: impl Fn(&str, &mut Vec<u8>) -> Result<()>, input: &str) -> Result<String> {
+        let mut out = Vec::new();
+        f(input, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_detection() {
+        assert!(is_number("42"));
+        assert!(is_number("0xFF"));
+        assert!(!is_number("0x"));
+        assert!(!is_number("4 2"));
+        assert!(!is_number(""));
+        assert!(!is_number("Hi"));
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(run(encode, "42").unwrap(), "▖▖▖▌");
+        assert_eq!(run(encode, "0xFF").unwrap(), "▘▘▌▌▌");
+        assert_eq!(run(encode, "Hi").unwrap(), "▘▖▘▌\u{200d}▌▘▖▌\u{200d}");
+        assert_eq!(run(encode_text, "42").unwrap(), "▖▘▘▖\u{200d}▖▘▖▘\u{200d}");
+        assert!(matches!(
+            run(encode_number, "18446744073709551616"),
+            Err(CliError::InvalidNumber(_))
+        ));
+        assert!(matches!(run(encode_text, ""), Err(CliError::EmptyInput)));
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(run(decode, "▖▖▖▌").unwrap(), "42");
+        assert_eq!(run(decode, "▘▖▘▌\u{200d}▌▘▖▌\u{200d}\n").unwrap(), "Hi");
+        assert_eq!(run(decode, "▖▖ ▖▌").unwrap(), "42");
+        assert!(matches!(run(decode, " \n"), Err(CliError::EmptyInput)));
+        assert!(matches!(
+            run(decode, &"▌".repeat(42)),
+            Err(CliError::Dollcode(DollcodeError::Overflow))
+        ));
+        assert!(run(decode, "▖x").is_err());
+    }
+
+    #[test]
+    fn test_fixed_width() {
+        let encoded = run(encode_fixed_width, "Hi!").unwrap();
+        assert_eq!(
+            encoded.chars().count(),
+            3 * dollcode::bytes::GLYPHS_PER_BYTE
+        );
+        assert!(!encoded.contains(DELIMITER));
+        assert_eq!(run(decode_fixed_width, &encoded).unwrap(), "Hi!");
+
+        assert!(matches!(
+            run(encode_fixed_width, "ok☺"),
+            Err(CliError::Dollcode(DollcodeError::InvalidChar('☺', 2)))
+        ));
+        // Incomplete group, and a group for a control character
+        assert!(run(decode_fixed_width, &encoded[..encoded.len() - 3]).is_err());
+        assert!(run(decode_fixed_width, "▖▖▖▖▖▖").is_err());
+    }
+
+    #[test]
+    fn test_fixed_number() {
+        let encode =
+            |width| move |input: &str, out: &mut Vec<u8>| encode_fixed_number(input, width, out);
+        let decode =
+            |width| move |input: &str, out: &mut Vec<u8>| decode_fixed_number(input, width, out);
+        assert_eq!(run_with(encode(8), "42").unwrap(), "▖▖▖▖▘▘▌▖");
+        assert_eq!(run_with(decode(8), "▖▖▖▖ ▘▘▌▖").unwrap(), "42");
+        assert_eq!(run_with(encode(2), "0x8").unwrap(), "▌▌");
+
+        assert!(matches!(
+            run_with(encode(2), "9"),
+            Err(CliError::Dollcode(DollcodeError::Overflow))
+        ));
+        assert!(matches!(
+            run_with(encode(4), "Hi"),
+            Err(CliError::InvalidNumber(_))
+        ));
+        // The width must match
+        assert!(matches!(
+            run_with(decode(4), "▖▖▖▖▘▘▌▖"),
+            Err(CliError::Dollcode(DollcodeError::InvalidInput))
+        ));
+    }
+
+    #[test]
+    fn test_auto() {
+        assert_eq!(run(auto, "42").unwrap(), "▖▖▖▌");
+        assert_eq!(run(auto, "▖▖▖▌").unwrap(), "42");
+        assert_eq!(run(auto, "▘▖▘▌\u{200d}▌▘▖▌\u{200d}").unwrap(), "Hi");
+        assert_eq!(run(auto, "Hi").unwrap(), "▘▖▘▌\u{200d}▌▘▖▌\u{200d}");
+        assert!(run(auto, "▖Hi").is_err());
+    }
+}
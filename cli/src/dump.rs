@@ -0,0 +1,216 @@
+//! Side-by-side dump of input and its dollcode, one row per character or
+//! segment, for tracking down corrupted sequences.
+//!
+//! Rows start with the character offset into the input in hex, like
+//! `hexdump -C`. Problems are marked with `!!` on their row and the dump
+//! carries on past them.
+
+use crate::error::Result;
+use dollcode::{
+    digit_value, glyph_name,
+    text::{StreamDecoder, TextIterator, DELIMITER},
+    DollcodeError,
+};
+use std::io::Write;
+
+/// Longest segment of a printable ASCII character, in glyphs
+const SEGMENT_WIDTH: usize = 5;
+
+/// Dumps `input` as dollcode if it contains any glyph or delimiter, or as
+/// text otherwise, returning whether every row converted cleanly
+pub fn dump(input: &str, out: &mut impl Write) -> Result<bool> {
+    if input.chars().any(|c| c == DELIMITER) {
+        dump_segments(input, out)
+    } else if input.chars().any(|c| digit_value(c).is_some()) {
+        dump_glyphs(input, out)
+    } else {
+        dump_text(input, out)
+    }
+}
+
+/// Plain text, a row per character with its ASCII code and segment
+fn dump_text(input: &str, out: &mut impl Write) -> Result<bool> {
+    let mut ok = true;
+    writeln!(out, "offset    char  ascii  dollcode")?;
+    for (offset, c) in input.chars().enumerate() {
+        write!(out, "{offset:08x}  {:<4}  ", display(c))?;
+        match TextIterator::new(c.encode_utf8(&mut [0; 4])).next() {
+            Some(Ok(segment)) => writeln!(out, "0x{:02X}   {segment}", c as u32)?,
+            _ => {
+                ok = false;
+                writeln!(out, "!!     unsupported character")?;
+            }
+        }
+    }
+    Ok(ok)
+}
+
+/// Text dollcode, a row per delimited segment with the character it decodes to
+fn dump_segments(input: &str, out: &mut impl Write) -> Result<bool> {
+    let mut ok = true;
+    let mut segment = String::new();
+    let mut start = None;
+
+    writeln!(out, "offset    dollcode  ascii  char")?;
+    for (offset, c) in input.chars().enumerate() {
+        if c.is_whitespace() {
+            continue;
+        }
+        start.get_or_insert(offset);
+        if c != DELIMITER {
+            segment.push(c);
+            continue;
+        }
+        if let Some(start) = start.take() {
+            ok &= dump_segment(start, &segment, true, out)?;
+        }
+        segment.clear();
+    }
+    if let Some(start) = start {
+        ok &= dump_segment(start, &segment, false, out)?;
+    }
+    Ok(ok)
+}
+
+fn dump_segment(
+    offset: usize,
+    segment: &str,
+    delimited: bool,
+    out: &mut impl Write,
+) -> Result<bool> {
+    write!(out, "{offset:08x}  {segment:<SEGMENT_WIDTH$}     ")?;
+
+    let mut decoder = StreamDecoder::new();
+    let decoded = segment
+        .chars()
+        .try_for_each(|c| decoder.push(c).map(drop))
+        .and_then(|()| decoder.finish());
+    let problem = match decoded {
+        Ok(Some(c)) if delimited => {
+            writeln!(out, "0x{:02X}   {}", c as u32, display(c))?;
+            return Ok(true);
+        }
+        Ok(Some(c)) => format!("0x{:02X}   {} missing delimiter", c as u32, display(c)),
+        Ok(None) => "!!     empty segment".to_string(),
+        Err(DollcodeError::InvalidChar(c, _)) => {
+            format!("!!     unsupported character {}", display(c))
+        }
+        Err(_) => "!!     not printable ASCII".to_string(),
+    };
+    writeln!(out, "{problem}")?;
+    Ok(false)
+}
+
+/// Number dollcode, a row per glyph with its digit and the running value
+fn dump_glyphs(input: &str, out: &mut impl Write) -> Result<bool> {
+    let mut ok = true;
+    let mut value = Some(0u64);
+
+    writeln!(out, "offset    glyph  digit  name  value")?;
+    for (offset, c) in input.chars().enumerate() {
+        if c.is_whitespace() {
+            continue;
+        }
+        write!(out, "{offset:08x}  {:<4}   ", display(c))?;
+        let (Some(digit), Some(name)) = (digit_value(c), glyph_name(c)) else {
+            ok = false;
+            writeln!(out, "!!     unsupported character")?;
+            continue;
+        };
+
+        value = value
+            .and_then(|v| v.checked_mul(3))
+            .and_then(|v| v.checked_add(u64::from(digit)));
+        match value {
+            Some(value) => writeln!(out, "{digit}      {name:<4}  {value}")?,
+            None => {
+                ok = false;
+                writeln!(out, "{digit}      {name:<4}  !! overflow")?;
+            }
+        }
+    }
+    Ok(ok)
+}
+
+/// Shows a character as is, or escaped if it would be invisible
+fn display(c: char) -> String {
+    match c {
+        ' ' => "' '".to_string(),
+        c if c.is_whitespace() || c.is_control() => c.escape_debug().to_string(),
+        c => c.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(input: &str) -> (bool, String) {
+        let mut out = Vec::new();
+        let ok = dump(input, &mut out).unwrap();
+        (ok, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            run("Hi!"),
+            (
+                true,
+                "offset    char  ascii  dollcode\n\
+                 00000000  H     0x48   ▘▖▘▌\u{200d}\n\
+                 00000001  i     0x69   ▌▘▖▌\u{200d}\n\
+                 00000002  !     0x21   ▌▖▌\u{200d}\n"
+                    .to_string()
+            )
+        );
+
+        let (ok, dump) = run("é");
+        assert!(!ok);
+        assert!(dump.ends_with("00000000  é     !!     unsupported character\n"));
+    }
+
+    #[test]
+    fn test_segments() {
+        let (ok, dump) = run("▘▖▘▌\u{200d}\n▌▘▖▌\u{200d}");
+        assert!(ok);
+        assert_eq!(
+            dump,
+            "offset    dollcode  ascii  char\n\
+             00000000  ▘▖▘▌      0x48   H\n\
+             00000006  ▌▘▖▌      0x69   i\n"
+        );
+
+        // Corrupted segments are marked without stopping the dump
+        let (ok, dump) = run("▘▖x▌\u{200d}▖\u{200d}▘▖▘▌\u{200d}▌▘▖▌");
+        assert!(!ok);
+        let rows: Vec<&str> = dump.lines().skip(1).collect();
+        assert_eq!(
+            rows[0],
+            "00000000  ▘▖x▌      !!     unsupported character x"
+        );
+        assert_eq!(rows[1], "00000005  ▖         !!     not printable ASCII");
+        assert_eq!(rows[2], "00000007  ▘▖▘▌      0x48   H");
+        assert_eq!(rows[3], "0000000c  ▌▘▖▌      0x69   i missing delimiter");
+    }
+
+    #[test]
+    fn test_glyphs() {
+        assert_eq!(
+            run("▖▖ ▖▌"),
+            (
+                true,
+                "offset    glyph  digit  name  value\n\
+                 00000000  ▖      1      low   1\n\
+                 00000001  ▖      1      low   4\n\
+                 00000003  ▖      1      low   13\n\
+                 00000004  ▌      3      tall  42\n"
+                    .to_string()
+            )
+        );
+
+        let (ok, dump) = run(&"▌".repeat(42));
+        assert!(!ok);
+        assert!(dump.ends_with("00000029  ▌      3      tall  !! overflow\n"));
+    }
+}
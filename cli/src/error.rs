@@ -0,0 +1,103 @@
+//! Errors reported by the command-line tool.
+
+use dollcode::DollcodeError;
+use serde::Serialize;
+use std::{fmt, io};
+
+/// Everything that can make a command fail
+#[derive(Debug)]
+pub enum CliError {
+    /// Nothing to convert was given
+    EmptyInput,
+    /// Input to `num` is not a decimal or hex number in the `u64` range
+    InvalidNumber(String),
+    /// Encoding or decoding failed
+    Dollcode(DollcodeError),
+    /// A check digit or checksum of the input does not match, naming which
+    Mismatch(&'static str),
+    /// Reading input or writing output failed
+    Io(io::Error),
+    /// Converting a line of streamed input failed
+    Line(usize, Box<CliError>),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyInput => write!(f, "no input"),
+            Self::InvalidNumber(input) => write!(
+                f,
+                "{input:?} is not a number from 0 to {} (decimal or 0x hex)",
+                u64::MAX
+            ),
+            Self::Dollcode(DollcodeError::InvalidChar(c, position)) => {
+                write!(f, "unsupported character {c:?} at position {position}")
+            }
+            Self::Dollcode(DollcodeError::InvalidInput) => write!(f, "invalid dollcode sequence"),
+            Self::Dollcode(DollcodeError::Overflow) => write!(f, "value does not fit in 64 bits"),
+            Self::Mismatch(check) => write!(f, "{check} does not match"),
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Line(line, e) => write!(f, "line {line}: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Category of a failure, each with its own exit code so scripts can tell
+/// them apart. Usage errors exit with 2, from argument parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorKind {
+    /// The input is not valid for the conversion
+    Validation,
+    /// The input is valid but its value does not fit in 64 bits
+    Capacity,
+    /// Reading input or writing output failed
+    Io,
+}
+
+impl ErrorKind {
+    /// Process exit code for this kind of failure
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Self::Validation => 1,
+            Self::Capacity => 3,
+            Self::Io => 4,
+        }
+    }
+}
+
+impl CliError {
+    /// Category of the failure. Input that is not UTF-8 is a validation
+    /// error, even though it is found while reading.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::EmptyInput
+            | Self::InvalidNumber(_)
+            | Self::Mismatch(_)
+            | Self::Dollcode(DollcodeError::InvalidChar(..) | DollcodeError::InvalidInput) => {
+                ErrorKind::Validation
+            }
+            Self::Dollcode(DollcodeError::Overflow) => ErrorKind::Capacity,
+            Self::Io(e) if e.kind() == io::ErrorKind::InvalidData => ErrorKind::Validation,
+            Self::Io(_) => ErrorKind::Io,
+            Self::Line(_, e) => e.kind(),
+        }
+    }
+}
+
+impl From<DollcodeError> for CliError {
+    fn from(e: DollcodeError) -> Self {
+        Self::Dollcode(e)
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// Result type for command-line operations
+pub type Result<T> = std::result::Result<T, CliError>;
@@ -0,0 +1,276 @@
+//! Input and output files for batch conversion.
+//!
+//! Input in other encodings is converted to UTF-8 before the line handlers
+//! see it, and output files are only replaced once a conversion has
+//! finished.
+
+use crate::{
+    color::GlyphColors,
+    error::{CliError, Result},
+};
+use clap::ValueEnum;
+use std::{
+    fs::{self, File},
+    io::{self, BufRead, BufReader, BufWriter, Cursor, Read, StdoutLock, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+/// Byte order mark, dropped from the start of input
+const BOM: char = '\u{FEFF}';
+
+/// Character encoding of input
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum InputEncoding {
+    /// UTF-8, with or without a byte order mark
+    #[default]
+    #[value(name = "utf-8")]
+    Utf8,
+    /// Little-endian UTF-16, as saved by many Windows tools
+    #[value(name = "utf-16le")]
+    Utf16Le,
+    /// Big-endian UTF-16
+    #[value(name = "utf-16be")]
+    Utf16Be,
+    /// ISO 8859-1, a character per byte
+    Latin1,
+}
+
+/// Opens `path`, or stdin when there is none, as UTF-8.
+///
+/// A leading byte order mark is dropped. UTF-8 is passed through as it is
+/// read; other encodings are read whole and converted first.
+///
+/// # Errors
+///
+/// Fails if the file cannot be opened or read, or for UTF-16 that does not
+/// decode.
+pub fn open(path: Option<&Path>, encoding: InputEncoding) -> Result<Box<dyn BufRead>> {
+    let reader: Box<dyn BufRead> = match path {
+        Some(path) => Box::new(BufReader::new(File::open(path)?)),
+        None => Box::new(io::stdin().lock()),
+    };
+    decode(reader, encoding)
+}
+
+/// Converts `reader` from `encoding` to UTF-8 without a byte order mark
+fn decode<'a>(
+    mut reader: impl BufRead + 'a,
+    encoding: InputEncoding,
+) -> Result<Box<dyn BufRead + 'a>> {
+    if encoding == InputEncoding::Utf8 {
+        let mut start = Vec::new();
+        reader
+            .by_ref()
+            .take(BOM.len_utf8() as u64)
+            .read_to_end(&mut start)?;
+        if start == BOM.to_string().as_bytes() {
+            start.clear();
+        }
+        return Ok(Box::new(Cursor::new(start).chain(reader)));
+    }
+
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    let text: String = match encoding {
+        InputEncoding::Latin1 => bytes.iter().map(|&b| char::from(b)).collect(),
+        _ => {
+            if bytes.len() % 2 != 0 {
+                return Err(invalid_data("UTF-16 input has an odd number of bytes"));
+            }
+            let units = bytes.chunks_exact(2).map(|pair| {
+                let pair = [pair[0], pair[1]];
+                if encoding == InputEncoding::Utf16Le {
+                    u16::from_le_bytes(pair)
+                } else {
+                    u16::from_be_bytes(pair)
+                }
+            });
+            char::decode_utf16(units)
+                .collect::<std::result::Result<_, _>>()
+                .map_err(|_| invalid_data("input is not valid UTF-16"))?
+        }
+    };
+    let text = text.strip_prefix(BOM).unwrap_or(&text);
+    Ok(Box::new(Cursor::new(text.as_bytes().to_vec())))
+}
+
+fn invalid_data(message: &str) -> CliError {
+    io::Error::new(io::ErrorKind::InvalidData, message).into()
+}
+
+/// Where converted output goes
+pub enum Output {
+    /// Buffered stdout, with glyphs colored if enabled
+    Stdout(GlyphColors<BufWriter<StdoutLock<'static>>>),
+    /// A file, replaced on [`Output::finish`]
+    File(AtomicFile),
+}
+
+impl Output {
+    /// Writes to a new file at `path`, or stdout when there is none. Files
+    /// are never colored.
+    pub fn create(path: Option<&Path>, color: bool) -> Result<Self> {
+        Ok(match path {
+            Some(path) => Self::File(AtomicFile::create(path)?),
+            None => Self::Stdout(GlyphColors::new(BufWriter::new(io::stdout().lock()), color)),
+        })
+    }
+
+    /// Flushes stdout, or moves a finished file into place
+    pub fn finish(self) -> Result<()> {
+        match self {
+            Self::Stdout(mut out) => out.flush()?,
+            Self::File(file) => file.commit()?,
+        }
+        Ok(())
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Stdout(out) => out.write(buf),
+            Self::File(file) => file.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Stdout(out) => out.flush(),
+            Self::File(file) => file.flush(),
+        }
+    }
+}
+
+/// A file written under a temporary name in the same directory and renamed
+/// over its destination by [`AtomicFile::commit`].
+///
+/// Readers of the destination see either the old content or all of the new
+/// one. Dropping it without committing removes the temporary file, so a
+/// failed conversion leaves the destination as it was.
+pub struct AtomicFile {
+    /// `None` once closed
+    file: Option<BufWriter<File>>,
+    temp: PathBuf,
+    path: PathBuf,
+    committed: bool,
+}
+
+impl AtomicFile {
+    /// Starts writing a replacement for `path`, keeping the permissions of
+    /// the file it replaces
+    pub fn create(path: &Path) -> Result<Self> {
+        let Some(name) = path.file_name() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} is not a file name", path.display()),
+            )
+            .into());
+        };
+        let temp =
+            path.with_file_name(format!(".{}.{}.tmp", name.to_string_lossy(), process::id()));
+        let file = File::options().write(true).create_new(true).open(&temp)?;
+        let mut atomic = Self {
+            file: Some(BufWriter::new(file)),
+            temp,
+            path: path.to_owned(),
+            committed: false,
+        };
+        if let Ok(metadata) = fs::metadata(path) {
+            atomic
+                .file_mut()
+                .get_ref()
+                .set_permissions(metadata.permissions())?;
+        }
+        Ok(atomic)
+    }
+
+    fn file_mut(&mut self) -> &mut BufWriter<File> {
+        self.file.as_mut().expect("file is open until dropped")
+    }
+
+    /// Writes everything to disk and replaces the destination
+    pub fn commit(mut self) -> Result<()> {
+        let file = self.file.take().expect("file is open until dropped");
+        let file = file.into_inner().map_err(io::IntoInnerError::into_error)?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&self.temp, &self.path)?;
+        self.committed = true;
+        Ok(())
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file_mut().flush()
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        // Closed first, as open files cannot be removed everywhere
+        drop(self.file.take());
+        if !self.committed {
+            let _ = fs::remove_file(&self.temp);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(bytes: &[u8], encoding: InputEncoding) -> Result<String> {
+        let mut text = String::new();
+        decode(bytes, encoding)?.read_to_string(&mut text)?;
+        Ok(text)
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(
+            read(b"\xEF\xBB\xBFHi\r\n", InputEncoding::Utf8).unwrap(),
+            "Hi\r\n"
+        );
+        assert_eq!(read(b"Hi", InputEncoding::Utf8).unwrap(), "Hi");
+        assert_eq!(read(b"", InputEncoding::Utf8).unwrap(), "");
+        assert_eq!(
+            read(b"\xFF\xFEH\0i\0", InputEncoding::Utf16Le).unwrap(),
+            "Hi"
+        );
+        assert_eq!(read(b"\0H\0i", InputEncoding::Utf16Be).unwrap(), "Hi");
+        assert_eq!(read(b"caf\xE9", InputEncoding::Latin1).unwrap(), "café");
+
+        assert!(read(b"H\0i", InputEncoding::Utf16Le).is_err());
+        assert!(read(b"\0\xD8", InputEncoding::Utf16Le).is_err());
+    }
+
+    #[test]
+    fn test_atomic_file() {
+        let dir = std::env::temp_dir().join(format!("dollcode-files-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("out.dc");
+        fs::write(&path, "old").unwrap();
+
+        // Dropped without committing, so the destination stays as it was
+        let mut file = AtomicFile::create(&path).unwrap();
+        file.write_all(b"partial").unwrap();
+        drop(file);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+
+        let mut file = AtomicFile::create(&path).unwrap();
+        file.write_all(b"new").unwrap();
+        file.commit().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+
+        // Nothing left behind but the destination
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,281 @@
+//! Check digits, checksums and armor around converted sequences.
+//!
+//! When encoding, every sequence is converted first and then sealed: a check
+//! digit is appended, then a CRC-8 covering it, and the result is armored.
+//! Decoding undoes the same steps in reverse before converting.
+//!
+//! Sealing needs a whole sequence, so streamed lines are held in memory one
+//! at a time, and armored input, whose sequences span lines, is read whole.
+
+use crate::{
+    error::{CliError, Result},
+    stream::LineHandler,
+};
+use clap::Args;
+use dollcode::{
+    armor::{armor, unarmor, BEGIN},
+    check::{check_digit, checksum, verify_check_digit, verify_checksum},
+    DollcodeError,
+};
+use std::io::{self, Read, Write};
+
+/// Conversion of one input, before sealing or after opening
+pub type Convert<'a> = &'a dyn Fn(&str, &mut dyn Write) -> Result<()>;
+
+/// Framing flags shared by the conversion commands
+#[derive(Debug, Clone, Copy, Default, Args)]
+pub struct Frame {
+    /// Append a check digit glyph, catching a mistyped or swapped glyph;
+    /// decode verifies and removes it
+    #[arg(long)]
+    pub check_digit: bool,
+    /// Append a CRC-8 of the sequence as six glyphs, catching damage such
+    /// as a dropped delimiter; decode verifies and removes it
+    #[arg(long)]
+    pub crc: bool,
+    /// Write each sequence between BEGIN and END lines, 64 glyphs per line;
+    /// decode reads every armored sequence in the input
+    #[arg(long)]
+    pub armor: bool,
+    /// Write numbers as exactly N base-3 digits, with ▖ ▘ ▌ as 0, 1 and 2;
+    /// decode reads numbers of that width
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u8).range(1..=64))]
+    pub fixed_width: Option<u8>,
+}
+
+impl Frame {
+    /// Whether any framing flag is given
+    pub fn is_set(&self) -> bool {
+        self.check_digit || self.crc || self.armor || self.fixed_width.is_some()
+    }
+
+    /// Appends the check digit and checksum to `sequence` as set, and writes
+    /// it to `out`, armored if set
+    fn seal(&self, sequence: &str, out: &mut dyn Write) -> Result<()> {
+        let mut sequence = sequence.to_string();
+        if self.check_digit {
+            let digit = check_digit(&sequence)?;
+            sequence.push(digit);
+        }
+        if self.crc {
+            let sum = checksum(&sequence);
+            sequence.extend(sum);
+        }
+
+        if self.armor {
+            let mut armored = String::new();
+            armor(&sequence, &mut armored)?;
+            write!(out, "{armored}")?;
+        } else {
+            write!(out, "{sequence}")?;
+        }
+        Ok(())
+    }
+
+    /// Verifies and removes the checksum and check digit of `input` as set,
+    /// returning its glyphs without whitespace
+    fn open(&self, input: &str) -> Result<String> {
+        let glyphs: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+        let mut sequence = glyphs.as_str();
+        if self.crc {
+            sequence = verify_checksum(sequence).map_err(mismatch("CRC"))?;
+        }
+        if self.check_digit {
+            sequence = verify_check_digit(sequence).map_err(mismatch("check digit"))?;
+        }
+        Ok(sequence.to_string())
+    }
+}
+
+/// Reports a failed verification as a mismatch of `check`
+fn mismatch(check: &'static str) -> impl Fn(DollcodeError) -> CliError {
+    move |e| match e {
+        DollcodeError::InvalidInput => CliError::Mismatch(check),
+        e => e.into(),
+    }
+}
+
+/// Converts `input` with `convert` and writes it sealed as `frame` sets
+pub fn encode(frame: &Frame, convert: Convert, input: &str, out: &mut dyn Write) -> Result<()> {
+    let mut sequence = Vec::new();
+    convert(input, &mut sequence)?;
+    let sequence =
+        String::from_utf8(sequence).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    frame.seal(&sequence, out)
+}
+
+/// Opens `input` as `frame` sets and converts it with `convert`
+pub fn decode(frame: &Frame, convert: Convert, input: &str, out: &mut dyn Write) -> Result<()> {
+    convert(&frame.open(input)?, out)
+}
+
+/// Decodes every armored sequence in `input` with [`decode`], a line each.
+///
+/// # Errors
+///
+/// Fails if `input` holds no armored sequence, and with the first sequence
+/// that does not decode.
+pub fn decode_armored(
+    frame: &Frame,
+    convert: Convert,
+    input: &str,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let mut rest = input;
+    loop {
+        let mut sequence = String::new();
+        let end = match unarmor(rest, &mut sequence) {
+            Ok(end) => end,
+            // Nothing but text follows the last sequence
+            Err(DollcodeError::InvalidInput)
+                if rest.len() < input.len() && !rest.contains(BEGIN) =>
+            {
+                return Ok(())
+            }
+            Err(e) => return Err(e.into()),
+        };
+        decode(frame, convert, &sequence, out)?;
+        writeln!(out)?;
+        rest = &rest[end..];
+    }
+}
+
+/// Reads all of `reader` for [`decode_armored`]
+pub fn read_all(mut reader: impl Read) -> Result<String> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Ok(input)
+}
+
+/// Encodes or decodes every line whole, sealed or opened as `frame` sets.
+/// Blank lines stay blank.
+pub struct Lines<'a> {
+    frame: Frame,
+    convert: Convert<'a>,
+    /// Whether lines are decoded rather than encoded
+    decode: bool,
+    line: String,
+}
+
+impl<'a> Lines<'a> {
+    /// Creates a handler converting lines with `convert`
+    pub fn new(frame: Frame, convert: Convert<'a>, decode: bool) -> Self {
+        Self {
+            frame,
+            convert,
+            decode,
+            line: String::new(),
+        }
+    }
+}
+
+impl LineHandler for Lines<'_> {
+    fn chunk(&mut self, chunk: &str, _out: &mut dyn Write) -> Result<()> {
+        self.line.push_str(chunk);
+        Ok(())
+    }
+
+    fn end_line(&mut self, out: &mut dyn Write) -> Result<()> {
+        let line = std::mem::take(&mut self.line);
+        if line.trim().is_empty() {
+            Ok(())
+        } else if self.decode {
+            decode(&self.frame, self.convert, &line, out)
+        } else {
+            encode(&self.frame, self.convert, &line, out)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert;
+
+    const ALL: Frame = Frame {
+        check_digit: true,
+        crc: true,
+        armor: false,
+        fixed_width: None,
+    };
+
+    fn encoded(frame: &Frame, input: &str) -> Result<String> {
+        let mut out = Vec::new();
+        encode(
+            frame,
+            &|input, mut out| convert::encode(input, &mut out),
+            input,
+            &mut out,
+        )?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    fn decoded(frame: &Frame, input: &str) -> Result<String> {
+        let mut out = Vec::new();
+        decode(
+            frame,
+            &|input, mut out| convert::decode(input, &mut out),
+            input,
+            &mut out,
+        )?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_seal() {
+        let check_digit = Frame {
+            check_digit: true,
+            ..Frame::default()
+        };
+        assert_eq!(encoded(&check_digit, "42").unwrap(), "▖▖▖▌▘");
+        assert_eq!(decoded(&check_digit, "▖▖▖▌▘").unwrap(), "42");
+
+        for (input, output) in [("42", "42"), ("Hi", "Hi"), ("0xFF", "255")] {
+            let sealed = encoded(&ALL, input).unwrap();
+            assert_eq!(decoded(&ALL, &sealed).unwrap(), output);
+        }
+    }
+
+    #[test]
+    fn test_mismatch() {
+        let sealed = encoded(&ALL, "Hi").unwrap();
+        // A dropped delimiter
+        let damaged = sealed.replacen('\u{200d}', "", 1);
+        assert!(matches!(
+            decoded(&ALL, &damaged),
+            Err(CliError::Mismatch("CRC"))
+        ));
+
+        let check_digit = Frame {
+            check_digit: true,
+            ..Frame::default()
+        };
+        assert!(matches!(
+            decoded(&check_digit, "▖▖▖▌▌"),
+            Err(CliError::Mismatch("check digit"))
+        ));
+        assert!(matches!(
+            decoded(&check_digit, "▖▖x▌▘"),
+            Err(CliError::Dollcode(DollcodeError::InvalidChar('x', 2)))
+        ));
+    }
+
+    #[test]
+    fn test_armor() {
+        let frame = Frame { armor: true, ..ALL };
+        let sealed = encoded(&frame, "Hi").unwrap();
+        assert!(sealed.starts_with("-----BEGIN DOLLCODE-----\n"));
+        assert!(sealed.ends_with("\n-----END DOLLCODE-----"));
+
+        let input = format!(
+            "Two messages:\n\n{sealed}\n\n  {}\n",
+            encoded(&frame, "42").unwrap()
+        );
+        let mut out = Vec::new();
+        let convert = |input: &str, mut out: &mut dyn Write| convert::decode(input, &mut out);
+        decode_armored(&frame, &convert, &input, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "Hi\n42\n");
+
+        assert!(decode_armored(&frame, &convert, "no armor", &mut Vec::new()).is_err());
+    }
+}
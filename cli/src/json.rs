@@ -0,0 +1,274 @@
+//! Structured JSON records for scripts, one object per line of output.
+
+use crate::{
+    convert,
+    error::{CliError, ErrorKind, Result},
+};
+use dollcode::{
+    from_dollcode,
+    text::{TextDecoder, TextIterator, DELIMITER},
+    to_dollcode, DollcodeError, MAX_DOLLCODE_SIZE,
+};
+use serde::Serialize;
+use std::io::{BufRead, Write};
+
+/// Conversion a record is produced for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// Number or text, detected with [`convert::is_number`]
+    Encode,
+    /// Dollcode into a number or text
+    Decode,
+    /// Always text
+    Text,
+    /// Always a number
+    Number,
+}
+
+/// How the value of a record is represented in plain form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    /// A `u64` number
+    Number,
+    /// Printable ASCII text
+    Text,
+}
+
+impl Operation {
+    /// Detects the mode of `input` the same way the plain output does
+    fn mode(self, input: &str) -> Mode {
+        match self {
+            Self::Encode if convert::is_number(input) => Mode::Number,
+            Self::Decode if !input.contains(DELIMITER) => Mode::Number,
+            Self::Number => Mode::Number,
+            _ => Mode::Text,
+        }
+    }
+}
+
+/// Result of converting one input
+#[derive(Debug, Serialize)]
+pub struct Record<'a> {
+    /// Input as given
+    pub input: &'a str,
+    /// Detected or requested mode
+    pub mode: Mode,
+    /// Dollcode form, without whitespace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dollcode: Option<String>,
+    /// Value in decimal, for numbers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decimal: Option<u64>,
+    /// Value in `0x`-prefixed hex, for numbers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hex: Option<String>,
+    /// Plain text, for text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Glyphs in the dollcode, not counting delimiters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub glyphs: Option<usize>,
+    /// Why the conversion failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RecordError>,
+}
+
+/// A failed conversion
+#[derive(Debug, Serialize)]
+pub struct RecordError {
+    /// Same message as printed without `--json`
+    pub message: String,
+    /// Category, matching the exit code
+    pub kind: ErrorKind,
+    /// 1-based line of streamed input that failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Unsupported character, if that is what failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub character: Option<char>,
+    /// 0-based position of the unsupported character
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<usize>,
+}
+
+impl From<CliError> for RecordError {
+    fn from(e: CliError) -> Self {
+        let kind = e.kind();
+        let (line, e) = match e {
+            CliError::Line(line, e) => (Some(line), *e),
+            e => (None, e),
+        };
+        let (character, position) = match e {
+            CliError::Dollcode(DollcodeError::InvalidChar(c, position)) => {
+                (Some(c), Some(position))
+            }
+            _ => (None, None),
+        };
+        Self {
+            message: e.to_string(),
+            kind,
+            line,
+            character,
+            position,
+        }
+    }
+}
+
+impl<'a> Record<'a> {
+    /// Converts `input`, recording a failure instead of returning it
+    pub fn new(operation: Operation, input: &'a str) -> Self {
+        let mut record = Self {
+            input,
+            mode: operation.mode(input),
+            dollcode: None,
+            decimal: None,
+            hex: None,
+            text: None,
+            glyphs: None,
+            error: None,
+        };
+        if let Err(e) = record.convert(operation) {
+            record.error = Some(e.into());
+        }
+        record
+    }
+
+    fn convert(&mut self, operation: Operation) -> Result<()> {
+        let dollcode = if operation == Operation::Decode {
+            let glyphs: String = self.input.chars().filter(|c| !c.is_whitespace()).collect();
+            if glyphs.is_empty() {
+                return Err(CliError::EmptyInput);
+            }
+            match self.mode {
+                Mode::Number => {
+                    let chars: Vec<char> = glyphs.chars().collect();
+                    if chars.len() > MAX_DOLLCODE_SIZE {
+                        return Err(DollcodeError::Overflow.into());
+                    }
+                    self.set_number(from_dollcode(&chars)?);
+                }
+                Mode::Text => {
+                    self.text = Some(TextDecoder::new(&glyphs).collect::<dollcode::Result<_>>()?);
+                }
+            }
+            glyphs
+        } else {
+            match self.mode {
+                Mode::Number => {
+                    let value = convert::parse_number(self.input)?;
+                    self.set_number(value);
+                    to_dollcode(value)?.to_string()
+                }
+                Mode::Text => {
+                    if self.input.is_empty() {
+                        return Err(CliError::EmptyInput);
+                    }
+                    let dollcode = TextIterator::new(self.input)
+                        .map(|segment| segment.map(|s| s.to_string()))
+                        .collect::<dollcode::Result<_>>()?;
+                    self.text = Some(self.input.to_string());
+                    dollcode
+                }
+            }
+        };
+
+        self.glyphs = Some(dollcode.chars().filter(|&c| c != DELIMITER).count());
+        self.dollcode = Some(dollcode);
+        Ok(())
+    }
+
+    fn set_number(&mut self, value: u64) {
+        self.decimal = Some(value);
+        self.hex = Some(format!("0x{value:X}"));
+    }
+
+    /// Whether the conversion succeeded
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Writes the record as a single line of JSON
+    pub fn write(&self, out: &mut impl Write) -> Result<()> {
+        serde_json::to_writer(&mut *out, self).map_err(std::io::Error::from)?;
+        writeln!(out)?;
+        Ok(())
+    }
+}
+
+/// Writes a record for every line of `reader`, returning whether all
+/// conversions succeeded
+pub fn write_lines(
+    operation: Operation,
+    reader: impl BufRead,
+    out: &mut impl Write,
+) -> Result<bool> {
+    let mut ok = true;
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.strip_suffix('\r').unwrap_or(&line);
+        let record = Record::new(operation, line);
+        ok &= record.is_ok();
+        record.write(out)?;
+    }
+    Ok(ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json(operation: Operation, input: &str) -> String {
+        let mut out = Vec::new();
+        Record::new(operation, input).write(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(
+            json(Operation::Encode, "0xFF"),
+            r#"{"input":"0xFF","mode":"number","dollcode":"▘▘▌▌▌","decimal":255,"hex":"0xFF","glyphs":5}"#
+                .to_string()
+                + "\n"
+        );
+        assert_eq!(
+            json(Operation::Decode, "▖▖ ▖▌"),
+            r#"{"input":"▖▖ ▖▌","mode":"number","dollcode":"▖▖▖▌","decimal":42,"hex":"0x2A","glyphs":4}"#
+                .to_string()
+                + "\n"
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        let record = Record::new(Operation::Text, "42");
+        assert_eq!(record.mode, Mode::Text);
+        assert_eq!(record.text.as_deref(), Some("42"));
+        assert_eq!(record.glyphs, Some(8));
+        assert_eq!(record.decimal, None);
+
+        let decoded = Record::new(Operation::Decode, record.dollcode.as_deref().unwrap());
+        assert_eq!(decoded.mode, Mode::Text);
+        assert_eq!(decoded.text.as_deref(), Some("42"));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            json(Operation::Encode, "café"),
+            r#"{"input":"café","mode":"text","error":{"message":"unsupported character 'é' at position 3","kind":"validation","character":"é","position":3}}"#
+                .to_string()
+                + "\n"
+        );
+
+        let record = Record::new(Operation::Number, "Hi");
+        assert!(!record.is_ok());
+        assert_eq!(record.dollcode, None);
+
+        let mut out = Vec::new();
+        let ok = write_lines(Operation::Encode, &b"42\r\n\xE2\x96\x96\n"[..], &mut out).unwrap();
+        assert!(!ok);
+        assert_eq!(String::from_utf8(out).unwrap().lines().count(), 2);
+    }
+}
@@ -0,0 +1,534 @@
+//! `dollcode` command-line tool.
+//!
+//! Converts between numbers, text and dollcode from arguments or stdin.
+//! Stdin is converted line by line as it is read, so input of any size runs
+//! in constant memory:
+//!
+//! ```text
+//! $ dollcode encode 42
+//! ▖▖▖▌
+//! $ echo Hi | dollcode text | dollcode decode
+//! Hi
+//! $ dollcode encode --json 42
+//! {"input":"42","mode":"number","dollcode":"▖▖▖▌","decimal":42,"hex":"0x2A","glyphs":4}
+//! $ dollcode text -i notes.txt -o notes.dc
+//! ```
+
+mod bench;
+mod classify;
+mod color;
+mod convert;
+mod dump;
+mod error;
+mod files;
+mod frame;
+mod generate;
+mod json;
+#[cfg(feature = "image")]
+mod render;
+mod repl;
+mod stream;
+mod table;
+mod watch;
+
+use clap::{
+    error::ErrorKind as ClapErrorKind, Args, ColorChoice, CommandFactory, Parser, Subcommand,
+    ValueEnum,
+};
+use clap_complete::Shell;
+use error::{CliError, ErrorKind, Result};
+use files::{InputEncoding, Output};
+use frame::{Convert, Frame};
+use generate::SampleKind;
+use json::{Operation, Record, RecordError};
+use serde::Serialize;
+use std::{
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+use stream::{
+    AutoEncoder, Decoder, FixedWidthDecoder, FixedWidthEncoder, LineHandler, NumberEncoder,
+    TextEncoder,
+};
+use table::TableFormat;
+
+/// Exit codes, listed in `--help`
+const EXIT_CODES: &str = "\
+Exit codes:
+  0  success
+  1  validation: input is not valid for the conversion, or with --json or
+     dump, some input did not convert
+  2  usage: invalid command-line arguments
+  3  capacity: a value does not fit in 64 bits
+  4  io: reading input or writing output failed";
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "dollcode",
+    version,
+    about = "Encode and decode dollcode (▖▘▌)",
+    after_help = EXIT_CODES
+)]
+struct Cli {
+    /// Print a JSON object per input with every representation, and errors
+    /// with their position, instead of plain output
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// When to color glyphs and errors; `auto` colors terminals unless
+    /// NO_COLOR is set
+    #[arg(long, global = true, value_enum, value_name = "WHEN", default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Print nothing on failure and only set the exit code
+    #[arg(short, long, global = true, conflicts_with = "errors")]
+    quiet: bool,
+
+    /// How to print a failure on stderr
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_name = "FORMAT",
+        default_value_t
+    )]
+    errors: ErrorFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Encode a number or text, detected as on the website
+    Encode(ConvertArgs),
+    /// Decode dollcode into a number or text
+    Decode(TextArgs),
+    /// Encode input as text, even if it looks like a number
+    Text(TextArgs),
+    /// Encode a decimal or 0x-prefixed hex number
+    Num(ConvertArgs),
+    /// Show text or dollcode side by side with its conversion, a row per
+    /// character or segment, marking anything that does not convert
+    Dump(InputArgs),
+    /// Print the detected type and conversion of every line, for triaging
+    /// mixed input such as logs
+    Classify(InputArgs),
+    /// Convert lines as they are entered, detecting dollcode, numbers and
+    /// text as on the website
+    Repl {
+        /// File to keep entered lines in [default: ~/.dollcode_history]
+        #[arg(long, value_name = "FILE")]
+        history: Option<PathBuf>,
+        /// Do not read or write a history file
+        #[arg(long, conflicts_with = "history")]
+        no_history: bool,
+    },
+    /// Draw a number or text as a PNG image, detected as on the website;
+    /// dollcode is drawn as is
+    #[cfg(feature = "image")]
+    Render {
+        #[command(flatten)]
+        input: InputArgs,
+        /// PNG file to write
+        #[arg(short, long, value_name = "FILE")]
+        output: PathBuf,
+        /// Height of the image, in pixels
+        #[arg(
+            long,
+            default_value_t = 24,
+            value_name = "PX",
+            value_parser = clap::value_parser!(u32).range(2..)
+        )]
+        height: u32,
+    },
+    /// Print the dollcode segment of every printable ASCII character
+    Table {
+        /// Only list these characters
+        filter: Option<String>,
+        /// Output layout
+        #[arg(long, value_enum, default_value_t)]
+        format: TableFormat,
+    },
+    /// Print random sample inputs with their dollcode, separated by a tab,
+    /// the same every time for a given seed
+    Generate {
+        /// Number of samples
+        #[arg(long, default_value_t = 10)]
+        count: usize,
+        /// What to sample
+        #[arg(long, value_enum, default_value = "number")]
+        kind: SampleKind,
+        /// Seed for the random samples
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Longest text sample, in characters
+        #[arg(long, default_value_t = 16)]
+        max_len: usize,
+    },
+    /// Measure encode and decode speed on this machine
+    Bench {
+        /// How long to run each benchmark, in milliseconds
+        #[arg(long, default_value_t = 500, value_name = "MS")]
+        time: u64,
+    },
+    /// Convert a file line by line and print it again whenever it changes
+    Watch {
+        /// File to watch
+        file: PathBuf,
+        /// How often to check for changes, in milliseconds
+        #[arg(long, default_value_t = 250, value_name = "MS")]
+        interval: u64,
+    },
+    /// Print a shell completion script, e.g.
+    /// `dollcode completions bash > /etc/bash_completion.d/dollcode`
+    Completions {
+        /// Shell to complete in
+        shell: Shell,
+    },
+}
+
+#[derive(Debug, Args)]
+struct InputArgs {
+    /// Input to convert, joined with spaces; stdin is converted line by line
+    /// when omitted
+    input: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+struct ConvertArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    /// Read input from a file instead
+    #[arg(short = 'i', long, value_name = "FILE", conflicts_with = "input")]
+    input_file: Option<PathBuf>,
+    /// Write output to a file, replaced only once all of it is written
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+    /// Encoding of the input file or stdin; a byte order mark is dropped
+    #[arg(long, value_enum, default_value_t)]
+    input_encoding: InputEncoding,
+    #[command(flatten)]
+    frame: Frame,
+}
+
+#[derive(Debug, Args)]
+struct TextArgs {
+    #[command(flatten)]
+    convert: ConvertArgs,
+    /// Write or read text as fixed groups of six glyphs without delimiters,
+    /// for places that strip zero-width joiners
+    #[arg(long)]
+    no_delimiter: bool,
+}
+
+/// Format of failures printed on stderr
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+enum ErrorFormat {
+    /// A `dollcode: ` prefixed message
+    #[default]
+    Plain,
+    /// A JSON object with the message, kind and exit code, and the line and
+    /// character where known
+    Json,
+}
+
+/// Failure as printed with `--errors json`
+#[derive(Debug, Serialize)]
+struct ErrorReport {
+    #[serde(flatten)]
+    error: RecordError,
+    /// Exit code of the process
+    code: u8,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let (quiet, errors) = (cli.quiet, cli.errors);
+    let color = color::enabled(cli.color, &io::stderr());
+    let e = match run(cli) {
+        Ok(code) => return code,
+        // Output piped into e.g. `head` closing early is not a failure
+        Err(CliError::Io(e)) if e.kind() == io::ErrorKind::BrokenPipe => return ExitCode::SUCCESS,
+        Err(e) => e,
+    };
+
+    let code = e.kind().exit_code();
+    match errors {
+        _ if quiet => {}
+        ErrorFormat::Plain => eprintln!("{} {e}", color::paint("dollcode:", color::ERROR, color)),
+        ErrorFormat::Json => {
+            let report = ErrorReport {
+                error: e.into(),
+                code,
+            };
+            // Only plain fields, so serializing cannot fail
+            eprintln!("{}", serde_json::to_string(&report).unwrap_or_default());
+        }
+    }
+    ExitCode::from(code)
+}
+
+fn run(cli: Cli) -> Result<ExitCode> {
+    // Glyphs inside JSON strings are left alone for parsers
+    let color = !cli.json && color::enabled(cli.color, &io::stdout());
+    let (operation, args, no_delimiter) = match cli.command {
+        Command::Encode(args) => (Operation::Encode, args, false),
+        Command::Decode(args) => (Operation::Decode, args.convert, args.no_delimiter),
+        Command::Text(args) => (Operation::Text, args.convert, args.no_delimiter),
+        Command::Num(args) => (Operation::Number, args, false),
+        Command::Generate {
+            count,
+            kind,
+            seed,
+            max_len,
+        } => {
+            return write_output(None, color, |out| {
+                generate::generate(kind, count, seed, max_len, cli.json, out)?;
+                Ok(true)
+            })
+        }
+        Command::Classify(args) => {
+            return write_output(None, color, |out| {
+                if args.input.is_empty() {
+                    classify::classify(io::stdin().lock(), cli.json, out)?;
+                } else {
+                    classify::classify_line(&args.input.join(" "), cli.json, out)?;
+                }
+                Ok(true)
+            })
+        }
+        _ if cli.json => Cli::command()
+            .error(
+                ClapErrorKind::ArgumentConflict,
+                "--json only applies to conversions",
+            )
+            .exit(),
+        Command::Dump(args) => {
+            return write_output(None, color, |out| dump::dump(&read_input(args)?, out))
+        }
+        Command::Repl {
+            history,
+            no_history,
+        } => {
+            let history = history.or_else(repl::default_history);
+            repl::run(history.filter(|_| !no_history), color)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        #[cfg(feature = "image")]
+        Command::Render {
+            input,
+            output,
+            height,
+        } => {
+            let input = read_input(input)?;
+            return write_output(Some(&output), false, |out| {
+                render::png(&input, height, out)?;
+                Ok(true)
+            });
+        }
+        Command::Table { filter, format } => {
+            return write_output(None, color, |out| {
+                table::table(filter.as_deref().unwrap_or_default(), format, out)?;
+                Ok(true)
+            })
+        }
+        Command::Bench { time } => {
+            return write_output(None, color, |out| {
+                bench::bench(Duration::from_millis(time), out)?;
+                Ok(true)
+            })
+        }
+        Command::Watch { file, interval } => {
+            return write_output(None, color, |out| {
+                watch::watch(&file, Duration::from_millis(interval), out)?;
+                Ok(true)
+            })
+        }
+        Command::Completions { shell } => {
+            return write_output(None, color, |out| {
+                clap_complete::generate(shell, &mut Cli::command(), "dollcode", out);
+                Ok(true)
+            })
+        }
+    };
+    if cli.json && no_delimiter {
+        usage("--json does not support --no-delimiter")
+    }
+    if cli.json && args.frame.is_set() {
+        usage("--json does not support --check-digit, --crc, --armor or --fixed-width")
+    }
+    if args.frame.fixed_width.is_some() && (operation == Operation::Text || no_delimiter) {
+        usage("--fixed-width only applies to numbers")
+    }
+
+    write_output(args.output.as_deref(), color, |out| {
+        if args.frame.is_set() {
+            convert_framed(operation, &args, no_delimiter, out)?;
+        } else if no_delimiter && operation == Operation::Decode {
+            convert(
+                &args,
+                convert::decode_fixed_width,
+                FixedWidthDecoder::default(),
+                out,
+            )?;
+        } else if no_delimiter {
+            convert(
+                &args,
+                convert::encode_fixed_width,
+                FixedWidthEncoder::default(),
+                out,
+            )?;
+        } else {
+            return convert_input(operation, &args, cli.json, out);
+        }
+        Ok(true)
+    })
+}
+
+/// Exits with a usage error for arguments that cannot be combined
+fn usage(message: &str) -> ! {
+    Cli::command()
+        .error(ClapErrorKind::ArgumentConflict, message)
+        .exit()
+}
+
+/// Runs `f` on a file at `path`, or buffered stdout with glyphs colored if
+/// `color`, exiting with a validation failure if it reports that any input
+/// did not convert. The file is only replaced if `f` returns.
+fn write_output(
+    path: Option<&Path>,
+    color: bool,
+    f: impl FnOnce(&mut Output) -> Result<bool>,
+) -> Result<ExitCode> {
+    let mut out = Output::create(path, color)?;
+    let ok = f(&mut out)?;
+    out.finish()?;
+    Ok(if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(ErrorKind::Validation.exit_code())
+    })
+}
+
+/// Runs a conversion, with plain or JSON output
+fn convert_input(
+    operation: Operation,
+    args: &ConvertArgs,
+    json: bool,
+    out: &mut impl Write,
+) -> Result<bool> {
+    if json {
+        return write_json(operation, args, out);
+    }
+
+    match operation {
+        Operation::Encode => convert(args, convert::encode, AutoEncoder::default(), out),
+        Operation::Decode => convert(args, convert::decode, Decoder::default(), out),
+        Operation::Text => convert(args, convert::encode_text, TextEncoder::default(), out),
+        Operation::Number => convert(args, convert::encode_number, NumberEncoder::default(), out),
+    }?;
+    Ok(true)
+}
+
+/// Runs a conversion sealed or opened as the framing flags set, see [`frame`]
+fn convert_framed(
+    operation: Operation,
+    args: &ConvertArgs,
+    no_delimiter: bool,
+    out: &mut impl Write,
+) -> Result<()> {
+    let frame = args.frame;
+    let width = frame.fixed_width.map(usize::from);
+    let convert: Convert = match (operation, width) {
+        (Operation::Encode | Operation::Number, Some(width)) => {
+            &move |input, mut out| convert::encode_fixed_number(input, width, &mut out)
+        }
+        (Operation::Encode, None) => &|input, mut out| convert::encode(input, &mut out),
+        (Operation::Number, None) => &|input, mut out| convert::encode_number(input, &mut out),
+        (Operation::Text, _) if no_delimiter => {
+            &|input, mut out| convert::encode_fixed_width(input, &mut out)
+        }
+        (Operation::Text, _) => &|input, mut out| convert::encode_text(input, &mut out),
+        (Operation::Decode, Some(width)) => {
+            &move |input, mut out| convert::decode_fixed_number(input, width, &mut out)
+        }
+        (Operation::Decode, None) if no_delimiter => {
+            &|input, mut out| convert::decode_fixed_width(input, &mut out)
+        }
+        (Operation::Decode, None) => &|input, mut out| convert::decode(input, &mut out),
+    };
+    let decode = operation == Operation::Decode;
+
+    if args.input.input.is_empty() {
+        let reader = files::open(args.input_file.as_deref(), args.input_encoding)?;
+        if decode && frame.armor {
+            return frame::decode_armored(&frame, convert, &frame::read_all(reader)?, out);
+        }
+        let mut lines = frame::Lines::new(frame, convert, decode);
+        return stream::stream_lines(reader, out, &mut lines);
+    }
+
+    let input = args.input.input.join(" ");
+    if decode && frame.armor {
+        return frame::decode_armored(&frame, convert, &input, out);
+    }
+    if decode {
+        frame::decode(&frame, convert, &input, out)?;
+    } else {
+        frame::encode(&frame, convert, &input, out)?;
+    }
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Converts the joined arguments with `convert`, or streams the input file
+/// or stdin through `handler` when there are none
+fn convert<W: Write>(
+    args: &ConvertArgs,
+    convert: fn(&str, &mut W) -> Result<()>,
+    mut handler: impl LineHandler,
+    out: &mut W,
+) -> Result<()> {
+    if args.input.input.is_empty() {
+        let reader = files::open(args.input_file.as_deref(), args.input_encoding)?;
+        return stream::stream_lines(reader, out, &mut handler);
+    }
+
+    convert(&args.input.input.join(" "), out)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Writes a JSON record for the joined arguments, or for every line of the
+/// input file or stdin when there are none. Failed conversions are reported
+/// in their record, so this only returns whether all of them succeeded.
+fn write_json(operation: Operation, args: &ConvertArgs, out: &mut impl Write) -> Result<bool> {
+    if args.input.input.is_empty() {
+        let reader = files::open(args.input_file.as_deref(), args.input_encoding)?;
+        return json::write_lines(operation, reader, out);
+    }
+
+    let input = args.input.input.join(" ");
+    let record = Record::new(operation, &input);
+    record.write(out)?;
+    Ok(record.is_ok())
+}
+
+/// Joins the arguments, or reads all of stdin without its final line break
+fn read_input(args: InputArgs) -> Result<String> {
+    if !args.input.is_empty() {
+        return Ok(args.input.join(" "));
+    }
+
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    let len = input
+        .strip_suffix('\n')
+        .map(|s| s.strip_suffix('\r').unwrap_or(s))
+        .map_or(input.len(), str::len);
+    input.truncate(len);
+    Ok(input)
+}
@@ -0,0 +1,30 @@
+//! PNG images of dollcode, for printing on labels.
+
+use crate::{convert, error::Result};
+use dollcode::render::{self, Encoded, RasterStyle};
+use std::io::Write;
+
+/// Writes `input` as a PNG image `height` pixels high. Input containing any
+/// glyph or delimiter is drawn as is, ignoring whitespace, and anything else
+/// is encoded with [`convert::encode`] first, as the website does.
+pub fn png(input: &str, height: u32, out: &mut impl Write) -> Result<()> {
+    let dollcode = if convert::is_dollcode(input) {
+        input.chars().filter(|c| !c.is_whitespace()).collect()
+    } else {
+        let mut encoded = Vec::new();
+        convert::encode(input, &mut encoded)?;
+        // Glyphs and delimiters only
+        String::from_utf8(encoded).unwrap_or_default()
+    };
+
+    let style = RasterStyle {
+        cell_width: height / 2,
+        cell_height: height,
+        delimiter_width: height / 4,
+        ..RasterStyle::DEFAULT
+    };
+    // Reports invalid glyphs as such rather than as a failed write
+    render::raster_size(&Encoded(&dollcode), &style)?;
+    render::png(&Encoded(&dollcode), &style, out)?;
+    Ok(())
+}
@@ -0,0 +1,68 @@
+//! Interactive prompt converting each entered line, like the website.
+
+use crate::{
+    color::{self, GlyphColors},
+    convert,
+    error::Result,
+};
+use rustyline::{error::ReadlineError, DefaultEditor};
+use std::{
+    env,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+/// History file used when none is given, in the home directory
+const HISTORY_FILE: &str = ".dollcode_history";
+
+/// `~/.dollcode_history`, if the home directory is known
+pub fn default_history() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(HISTORY_FILE))
+}
+
+/// Reads lines until end of input, printing each line's conversion or error.
+///
+/// Previous lines can be recalled with the arrow keys and are kept in
+/// `history` across sessions. Glyphs and errors are colored if `color`.
+pub fn run(history: Option<PathBuf>, color: bool) -> Result<()> {
+    let mut editor = DefaultEditor::new().map_err(io_error)?;
+    if let Some(path) = &history {
+        // Missing on first use
+        let _ = editor.load_history(path);
+    }
+
+    loop {
+        let line = match editor.readline("dollcode> ") {
+            Ok(line) => line,
+            // Ctrl-C discards the line, Ctrl-D quits
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(io_error(e).into()),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        editor.add_history_entry(&line).map_err(io_error)?;
+
+        let mut out = GlyphColors::new(Vec::new(), color);
+        match convert::auto(&line, &mut out) {
+            Ok(()) => {
+                out.flush()?;
+                println!("{}", String::from_utf8_lossy(out.get_ref()));
+            }
+            Err(e) => eprintln!("{} {e}", color::paint("error:", color::ERROR, color)),
+        }
+    }
+
+    if let Some(path) = &history {
+        editor.save_history(path).map_err(io_error)?;
+    }
+    Ok(())
+}
+
+fn io_error(e: ReadlineError) -> io::Error {
+    match e {
+        ReadlineError::Io(e) => e,
+        e => io::Error::other(e),
+    }
+}
@@ -0,0 +1,426 @@
+//! Line-by-line streaming conversion in constant memory.
+//!
+//! Input is read in fixed-size chunks and every line is converted on its own,
+//! producing one output line per input line. Lines are never held in memory
+//! whole: text is encoded and decoded as it arrives, and only the few
+//! characters needed to tell a number from text are buffered.
+
+use crate::{
+    convert,
+    error::{CliError, Result},
+};
+use dollcode::{
+    bytes::{encode_byte, ByteDecoder, GLYPHS_PER_BYTE},
+    digit_value, from_dollcode,
+    text::{StreamDecoder, TextIterator, DELIMITER},
+    DollcodeError, MAX_DOLLCODE_SIZE,
+};
+use std::io::{self, ErrorKind, Read, Write};
+
+/// Bytes read from the input at a time
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Longest number accepted: 20 decimal digits, or 18 hex chars with `0x`
+const MAX_NUMBER_LEN: usize = 20;
+
+/// Converts one line at a time, receiving each line in pieces
+pub trait LineHandler {
+    /// Converts the next piece of the current line
+    fn chunk(&mut self, chunk: &str, out: &mut dyn Write) -> Result<()>;
+
+    /// Completes the current line; the line break is written by the caller
+    fn end_line(&mut self, out: &mut dyn Write) -> Result<()>;
+}
+
+/// Feeds `reader` to `handler` line by line, writing a line break after each
+/// converted line.
+///
+/// `\r\n` line endings are accepted. A final line without a line break is
+/// still converted.
+///
+/// # Errors
+///
+/// Returns [`CliError::Line`] with the 1-based line number for conversion
+/// errors, and I/O errors, including for input that is not UTF-8, as they
+/// are.
+pub fn stream_lines(
+    mut reader: impl Read,
+    out: &mut impl Write,
+    handler: &mut impl LineHandler,
+) -> Result<()> {
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    let mut carry = 0;
+    let mut line = 1;
+    let mut in_line = false;
+
+    let at_line = |line: usize| move |error: CliError| CliError::Line(line, Box::new(error));
+
+    loop {
+        let read = match reader.read(&mut buf[carry..]) {
+            Ok(read) => read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        let len = carry + read;
+        let mut start = 0;
+
+        while let Some(end) = buf[start..len].iter().position(|&b| b == b'\n') {
+            let content = &buf[start..start + end];
+            let content = content.strip_suffix(b"\r").unwrap_or(content);
+            handler.chunk(utf8(content)?, out).map_err(at_line(line))?;
+            handler.end_line(out).map_err(at_line(line))?;
+            writeln!(out)?;
+
+            start += end + 1;
+            line += 1;
+            in_line = false;
+        }
+
+        let rest = &buf[start..len];
+        if read == 0 {
+            if in_line || !rest.is_empty() {
+                handler.chunk(utf8(rest)?, out).map_err(at_line(line))?;
+                handler.end_line(out).map_err(at_line(line))?;
+                writeln!(out)?;
+            }
+            return Ok(());
+        }
+
+        // Hold back a character split across reads, or a `\r` that may
+        // start a line break
+        carry = match std::str::from_utf8(rest) {
+            Ok(_) => usize::from(rest.last() == Some(&b'\r')),
+            Err(e) if e.error_len().is_none() => rest.len() - e.valid_up_to(),
+            Err(_) => return Err(invalid_utf8().into()),
+        };
+        let complete = &rest[..rest.len() - carry];
+        if !complete.is_empty() {
+            handler.chunk(utf8(complete)?, out).map_err(at_line(line))?;
+            in_line = true;
+        }
+        buf.copy_within(len - carry..len, 0);
+    }
+}
+
+fn utf8(bytes: &[u8]) -> io::Result<&str> {
+    std::str::from_utf8(bytes).map_err(|_| invalid_utf8())
+}
+
+fn invalid_utf8() -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, "input is not valid UTF-8")
+}
+
+/// Encodes every line as text
+#[derive(Debug, Default)]
+pub struct TextEncoder {
+    /// Characters of the current line already encoded
+    position: usize,
+}
+
+impl LineHandler for TextEncoder {
+    fn chunk(&mut self, chunk: &str, out: &mut dyn Write) -> Result<()> {
+        for segment in TextIterator::new(chunk) {
+            let segment = segment.map_err(|e| match e {
+                DollcodeError::InvalidChar(c, position) => {
+                    DollcodeError::InvalidChar(c, self.position + position)
+                }
+                e => e,
+            })?;
+            write!(out, "{segment}")?;
+        }
+        self.position += chunk.chars().count();
+        Ok(())
+    }
+
+    fn end_line(&mut self, _out: &mut dyn Write) -> Result<()> {
+        self.position = 0;
+        Ok(())
+    }
+}
+
+/// Encodes every line as text without delimiters, as for
+/// [`convert::encode_fixed_width`]
+#[derive(Debug, Default)]
+pub struct FixedWidthEncoder {
+    /// Characters of the current line already encoded
+    position: usize,
+}
+
+impl LineHandler for FixedWidthEncoder {
+    fn chunk(&mut self, chunk: &str, out: &mut dyn Write) -> Result<()> {
+        for c in chunk.chars() {
+            if !matches!(c, ' '..='~') {
+                return Err(DollcodeError::InvalidChar(c, self.position).into());
+            }
+            for glyph in encode_byte(c as u8) {
+                write!(out, "{glyph}")?;
+            }
+            self.position += 1;
+        }
+        Ok(())
+    }
+
+    fn end_line(&mut self, _out: &mut dyn Write) -> Result<()> {
+        self.position = 0;
+        Ok(())
+    }
+}
+
+/// Decodes every line written by [`FixedWidthEncoder`], ignoring whitespace
+#[derive(Debug, Default)]
+pub struct FixedWidthDecoder {
+    /// Glyphs of the current, incomplete group
+    group: String,
+    /// Glyphs of the line in completed groups
+    position: usize,
+}
+
+impl LineHandler for FixedWidthDecoder {
+    fn chunk(&mut self, chunk: &str, out: &mut dyn Write) -> Result<()> {
+        for c in chunk.chars().filter(|c| !c.is_whitespace()) {
+            let len = self.group.chars().count();
+            if digit_value(c).is_none() {
+                return Err(DollcodeError::InvalidChar(c, self.position + len).into());
+            }
+            self.group.push(c);
+            if len + 1 < GLYPHS_PER_BYTE {
+                continue;
+            }
+
+            let byte = ByteDecoder::new(&self.group)
+                .next()
+                .unwrap_or(Err(DollcodeError::InvalidInput))?;
+            if !(b' '..=b'~').contains(&byte) {
+                return Err(DollcodeError::InvalidInput.into());
+            }
+            write!(out, "{}", char::from(byte))?;
+            self.group.clear();
+            self.position += GLYPHS_PER_BYTE;
+        }
+        Ok(())
+    }
+
+    fn end_line(&mut self, _out: &mut dyn Write) -> Result<()> {
+        let incomplete = !self.group.is_empty();
+        self.group.clear();
+        self.position = 0;
+        if incomplete {
+            return Err(DollcodeError::InvalidInput.into());
+        }
+        Ok(())
+    }
+}
+
+/// Encodes every line as a number
+#[derive(Debug, Default)]
+pub struct NumberEncoder {
+    line: String,
+}
+
+impl LineHandler for NumberEncoder {
+    fn chunk(&mut self, chunk: &str, _out: &mut dyn Write) -> Result<()> {
+        // Anything longer is rejected, so only keep enough to report it
+        let room = (MAX_NUMBER_LEN + 1).saturating_sub(self.line.chars().count());
+        self.line.extend(chunk.chars().take(room));
+        Ok(())
+    }
+
+    fn end_line(&mut self, mut out: &mut dyn Write) -> Result<()> {
+        let line = std::mem::take(&mut self.line);
+        convert::encode_number(&line, &mut out)
+    }
+}
+
+/// Encodes every line as a number or text, detected as for
+/// [`convert::encode`]
+#[derive(Debug, Default)]
+pub struct AutoEncoder {
+    /// Start of the line, while it could still be a number
+    prefix: String,
+    /// Set once the line is known to be text
+    text: Option<TextEncoder>,
+}
+
+impl LineHandler for AutoEncoder {
+    fn chunk(&mut self, chunk: &str, out: &mut dyn Write) -> Result<()> {
+        if let Some(text) = &mut self.text {
+            return text.chunk(chunk, out);
+        }
+
+        if self.prefix.len() + chunk.len() <= MAX_NUMBER_LEN {
+            self.prefix.push_str(chunk);
+            return Ok(());
+        }
+
+        // Too long for a number
+        let mut text = TextEncoder::default();
+        text.chunk(&std::mem::take(&mut self.prefix), out)?;
+        text.chunk(chunk, out)?;
+        self.text = Some(text);
+        Ok(())
+    }
+
+    fn end_line(&mut self, mut out: &mut dyn Write) -> Result<()> {
+        let prefix = std::mem::take(&mut self.prefix);
+        match self.text.take() {
+            Some(_) => Ok(()),
+            None if prefix.is_empty() => Ok(()),
+            None => convert::encode(&prefix, &mut out),
+        }
+    }
+}
+
+/// Decodes every line into text if it contains delimiters, or a number
+/// otherwise. Whitespace between glyphs is ignored.
+#[derive(Debug, Default)]
+pub struct Decoder {
+    /// Glyphs of the line while no delimiter has been seen
+    glyphs: Vec<char>,
+    /// Set at the first delimiter, once the line is known to be text
+    text: Option<StreamDecoder>,
+}
+
+impl Decoder {
+    fn push_text(decoder: &mut StreamDecoder, c: char, out: &mut dyn Write) -> Result<()> {
+        if let Some(decoded) = decoder.push(c)? {
+            write!(out, "{decoded}")?;
+        }
+        Ok(())
+    }
+}
+
+impl LineHandler for Decoder {
+    fn chunk(&mut self, chunk: &str, out: &mut dyn Write) -> Result<()> {
+        for c in chunk.chars().filter(|c| !c.is_whitespace()) {
+            if let Some(decoder) = &mut self.text {
+                Self::push_text(decoder, c, out)?;
+            } else if c == DELIMITER {
+                let mut decoder = StreamDecoder::new();
+                for glyph in self.glyphs.drain(..) {
+                    Self::push_text(&mut decoder, glyph, out)?;
+                }
+                Self::push_text(&mut decoder, c, out)?;
+                self.text = Some(decoder);
+            } else if self.glyphs.len() < MAX_DOLLCODE_SIZE {
+                self.glyphs.push(c);
+            } else {
+                // Text segments are much shorter, so this can only be an
+                // oversized number
+                return Err(DollcodeError::Overflow.into());
+            }
+        }
+        Ok(())
+    }
+
+    fn end_line(&mut self, out: &mut dyn Write) -> Result<()> {
+        if let Some(mut decoder) = self.text.take() {
+            if let Some(decoded) = decoder.finish()? {
+                write!(out, "{decoded}")?;
+            }
+        } else if !self.glyphs.is_empty() {
+            let value = from_dollcode(&self.glyphs);
+            self.glyphs.clear();
+            write!(out, "{}", value?)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Streams `input` through a reader that returns at most `step` bytes
+    /// per read, so chunk boundaries fall everywhere
+    fn run(handler: &mut impl LineHandler, input: &str, step: usize) -> Result<String> {
+        struct Trickle<'a>(&'a [u8], usize);
+
+        impl Read for Trickle<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let n = self.0.len().min(self.1).min(buf.len());
+                buf[..n].copy_from_slice(&self.0[..n]);
+                self.0 = &self.0[n..];
+                Ok(n)
+            }
+        }
+
+        let mut out = Vec::new();
+        stream_lines(Trickle(input.as_bytes(), step), &mut out, handler)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_text_lines() {
+        for step in [1, 2, 3, 7, CHUNK_SIZE] {
+            let encoded = run(&mut TextEncoder::default(), "Hi\r\nthere\n\n!", step).unwrap();
+            let lines: Vec<&str> = encoded.lines().collect();
+            assert_eq!(lines.len(), 4);
+            assert_eq!(lines[0], "▘▖▘▌\u{200d}▌▘▖▌\u{200d}");
+            assert_eq!(lines[2], "");
+
+            let decoded = run(&mut Decoder::default(), &encoded, step).unwrap();
+            assert_eq!(decoded, "Hi\nthere\n\n!\n");
+        }
+    }
+
+    #[test]
+    fn test_auto_lines() {
+        for step in [1, 5, CHUNK_SIZE] {
+            let input = "42\n0xFF\nHi\n123456789012345678901234\n";
+            let encoded = run(&mut AutoEncoder::default(), input, step).unwrap();
+            let lines: Vec<&str> = encoded.lines().collect();
+            assert_eq!(lines[0], "▖▖▖▌");
+            assert_eq!(lines[1], "▘▘▌▌▌");
+            assert_eq!(lines[2], "▘▖▘▌\u{200d}▌▘▖▌\u{200d}");
+
+            // Too long for a number, so encoded as text
+            let decoded = run(&mut Decoder::default(), &encoded, step).unwrap();
+            assert_eq!(decoded, "42\n255\nHi\n123456789012345678901234\n");
+        }
+    }
+
+    #[test]
+    fn test_fixed_width_lines() {
+        for step in [1, 4, CHUNK_SIZE] {
+            let encoded = run(&mut FixedWidthEncoder::default(), "Hi\n\n!", step).unwrap();
+            assert!(!encoded.contains(DELIMITER));
+            let decoded = run(&mut FixedWidthDecoder::default(), &encoded, step).unwrap();
+            assert_eq!(decoded, "Hi\n\n!\n");
+        }
+
+        let error = run(&mut FixedWidthDecoder::default(), "▖▖▖▖▖▖\n▖▖▘", 1).unwrap_err();
+        assert!(matches!(error, CliError::Line(1, _)));
+        let error = run(&mut FixedWidthDecoder::default(), "▖▖▌▌▖▖▖▖x", 1).unwrap_err();
+        assert!(matches!(
+            error,
+            CliError::Line(1, e) if matches!(*e, CliError::Dollcode(DollcodeError::InvalidChar('x', 8)))
+        ));
+    }
+
+    #[test]
+    fn test_number_lines() {
+        assert_eq!(
+            run(&mut NumberEncoder::default(), "42\n1", 1).unwrap(),
+            "▖▖▖▌\n▖\n"
+        );
+        let error = run(&mut NumberEncoder::default(), "1\n2\nx", 1).unwrap_err();
+        assert!(matches!(error, CliError::Line(3, e) if matches!(*e, CliError::InvalidNumber(_))));
+    }
+
+    #[test]
+    fn test_errors() {
+        // Positions count from the start of the line, across reads
+        let error = run(&mut TextEncoder::default(), "ok\nabcé", 1).unwrap_err();
+        assert!(matches!(
+            error,
+            CliError::Line(2, e) if matches!(*e, CliError::Dollcode(DollcodeError::InvalidChar('é', 3)))
+        ));
+
+        let mut out = Vec::new();
+        let invalid = stream_lines(&b"ab\xFFcd\n"[..], &mut out, &mut TextEncoder::default());
+        assert!(matches!(invalid, Err(CliError::Io(e)) if e.kind() == ErrorKind::InvalidData));
+
+        let error = run(&mut Decoder::default(), &"▌".repeat(42), CHUNK_SIZE).unwrap_err();
+        assert!(matches!(error, CliError::Line(1, _)));
+    }
+}
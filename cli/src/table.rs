@@ -0,0 +1,130 @@
+//! The printable ASCII to dollcode segment table, straight from the core
+//! [`SEGMENT_TABLE`] used by the encoder.
+
+use crate::error::Result;
+use clap::ValueEnum;
+use dollcode::{
+    text::{DELIMITER, SEGMENT_TABLE},
+    DollcodeError,
+};
+use std::io::Write;
+
+/// Layout of the printed table
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TableFormat {
+    /// Aligned columns for reading in a terminal
+    #[default]
+    Plain,
+    /// A GitHub-flavored Markdown table
+    Markdown,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+/// Prints the segment of every printable ASCII character, or only of the
+/// characters in `filter` when it is not empty.
+///
+/// Segments are shown without their invisible delimiter.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidChar`] for filter characters outside
+/// printable ASCII.
+pub fn table(filter: &str, format: TableFormat, out: &mut impl Write) -> Result<()> {
+    if let Some((position, c)) = filter
+        .chars()
+        .enumerate()
+        .find(|&(_, c)| !(' '..='~').contains(&c))
+    {
+        return Err(DollcodeError::InvalidChar(c, position).into());
+    }
+
+    match format {
+        TableFormat::Plain => writeln!(out, "char  dec  hex   dollcode")?,
+        TableFormat::Markdown => {
+            writeln!(out, "| Char | Dec | Hex | Dollcode |")?;
+            writeln!(out, "| ---- | --: | --- | -------- |")?;
+        }
+        TableFormat::Csv => writeln!(out, "char,dec,hex,dollcode")?,
+    }
+
+    let rows = (' '..='~')
+        .zip(SEGMENT_TABLE)
+        .filter(|&(c, _)| filter.is_empty() || filter.contains(c));
+    for (c, segment) in rows {
+        let glyphs: String = segment.chars().filter(|&g| g != DELIMITER).collect();
+        let code = c as u32;
+        match format {
+            TableFormat::Plain => {
+                let shown = if c == ' ' {
+                    "' '".to_string()
+                } else {
+                    c.to_string()
+                };
+                writeln!(out, "{shown:<4}  {code:<3}  0x{code:02X}  {glyphs}")?;
+            }
+            TableFormat::Markdown => {
+                let shown = match c {
+                    ' ' => "space".to_string(),
+                    '|' => "\\|".to_string(),
+                    c => c.to_string(),
+                };
+                writeln!(out, "| {shown} | {code} | 0x{code:02X} | {glyphs} |")?;
+            }
+            TableFormat::Csv => {
+                let shown = match c {
+                    '"' => "\"\"\"\"".to_string(),
+                    ',' => "\",\"".to_string(),
+                    c => c.to_string(),
+                };
+                writeln!(out, "{shown},{code},0x{code:02X},{glyphs}")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::CliError;
+
+    fn run(filter: &str, format: TableFormat) -> Result<String> {
+        let mut out = Vec::new();
+        table(filter, format, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_formats() {
+        let plain = run("", TableFormat::Plain).unwrap();
+        assert_eq!(plain.lines().count(), 96);
+        assert!(plain.contains("\n' '   32   0x20  ▌▖▘\n"));
+        assert!(plain.ends_with("~     126  0x7E  ▖▖▖▘▌\n"));
+
+        assert_eq!(
+            run("H|", TableFormat::Markdown).unwrap(),
+            "| Char | Dec | Hex | Dollcode |\n\
+             | ---- | --: | --- | -------- |\n\
+             | H | 72 | 0x48 | ▘▖▘▌ |\n\
+             | \\| | 124 | 0x7C | ▖▖▖▘▖ |\n"
+        );
+
+        assert_eq!(
+            run(",\"*", TableFormat::Csv).unwrap(),
+            "char,dec,hex,dollcode\n\
+             \"\"\"\",34,0x22,▌▘▖\n\
+             *,42,0x2A,▖▖▖▌\n\
+             \",\",44,0x2C,▖▖▘▘\n"
+        );
+    }
+
+    #[test]
+    fn test_filter() {
+        assert_eq!(run("aa", TableFormat::Plain).unwrap().lines().count(), 2);
+        assert!(matches!(
+            run("aé", TableFormat::Plain),
+            Err(CliError::Dollcode(DollcodeError::InvalidChar('é', 1)))
+        ));
+    }
+}
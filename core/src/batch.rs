@@ -0,0 +1,211 @@
+//! Batch encoding and decoding of many numbers in one call.
+//!
+//! These entry points amortize per-call setup for high-volume callers such as
+//! telemetry pipelines: encoding reuses a single stack buffer for every value,
+//! and decoding validates and decodes a whole whitespace-separated input in a
+//! single pass without copying sequences into intermediate buffers.
+//!
+//! Zero encodes to an empty sequence, which whitespace cannot frame. Writing
+//! a [`TERMINATOR`] after every sequence instead keeps zeros in the batch.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{batch, Result};
+//! # fn main() -> Result<()> {
+//! let mut encoded = heapless::String::<128>::new();
+//! batch::encode_all(&[1, 42, 0, 255], &mut |dollcode| {
+//!     for &c in dollcode.as_chars() {
+//!         encoded.push(c).unwrap();
+//!     }
+//!     encoded.push(batch::TERMINATOR).unwrap();
+//! })?;
+//!
+//! let mut decoded = heapless::Vec::<u64, 8>::new();
+//! batch::decode_all(&encoded, &mut |value| decoded.push(value).unwrap())?;
+//! assert_eq!(decoded, [1, 42, 0, 255]);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{digit_value, encode_into, Dollcode, DollcodeError, Result};
+
+/// Ends a sequence in a batch, so that an empty one decodes as zero
+pub const TERMINATOR: char = ';';
+
+/// Encodes every number in `values`, passing each sequence to `f` in order.
+///
+/// The same buffer is reused for every value, so `f` must copy out anything it
+/// needs before returning. Zero is passed as an empty sequence; follow every
+/// sequence with a [`TERMINATOR`] for [`decode_all`] to read it back.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::Overflow`] if a number is too large to encode.
+/// Sequences for values before the failing one have already been passed to `f`.
+pub fn encode_all(values: &[u64], f: &mut impl FnMut(&Dollcode)) -> Result<()> {
+    let mut dollcode = Dollcode::new();
+
+    for &value in values {
+        encode_into(value, &mut dollcode)?;
+        f(&dollcode);
+    }
+
+    Ok(())
+}
+
+/// Decodes every whitespace-separated dollcode sequence in `input`, passing
+/// each value to `f` in order.
+///
+/// Runs of ASCII whitespace (spaces, tabs, newlines) separate values; leading,
+/// trailing and repeated separators are ignored. A [`TERMINATOR`] ends a
+/// value too, and one with no sequence since the previous terminator decodes
+/// as zero. The input is validated and decoded in the same pass.
+///
+/// # Errors
+///
+/// Returns:
+/// - [`DollcodeError::InvalidInput`] if the input contains characters other than
+///   dollcode glyphs, whitespace and terminators
+/// - [`DollcodeError::Overflow`] if a sequence would overflow u64
+///
+/// Values before the failing sequence have already been passed to `f`.
+pub fn decode_all(input: &str, f: &mut impl FnMut(u64)) -> Result<()> {
+    let mut value = 0u64;
+    let mut digits = 0usize;
+    // Whether no value was passed since the last terminator
+    let mut empty = true;
+
+    for c in input.chars() {
+        if let Some(digit) = digit_value(c) {
+            value = value
+                .checked_mul(3)
+                .and_then(|v| v.checked_add(digit as u64))
+                .ok_or(DollcodeError::Overflow)?;
+            digits += 1;
+        } else if c.is_ascii_whitespace() {
+            if digits > 0 {
+                f(value);
+                value = 0;
+                digits = 0;
+                empty = false;
+            }
+        } else if c == TERMINATOR {
+            if digits > 0 || empty {
+                f(value);
+            }
+            value = 0;
+            digits = 0;
+            empty = true;
+        } else {
+            return Err(DollcodeError::InvalidInput);
+        }
+    }
+
+    if digits > 0 {
+        f(value);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{from_dollcode, to_dollcode};
+    use heapless::{String, Vec};
+
+    #[test]
+    fn test_encode_all_matches_single() {
+        let values = [1, 42, 255, 0xDEADBEEF, u64::MAX];
+        let mut index = 0;
+
+        encode_all(&values, &mut |dollcode| {
+            let expected = to_dollcode(values[index]).unwrap();
+            assert_eq!(dollcode.as_chars(), expected.as_chars());
+            index += 1;
+        })
+        .unwrap();
+
+        assert_eq!(index, values.len());
+    }
+
+    #[test]
+    fn test_batch_roundtrip() {
+        let values = [1, 13, 42, 1000, 440729, u64::MAX];
+        let mut encoded = String::<512>::new();
+
+        encode_all(&values, &mut |dollcode| {
+            for &c in dollcode.as_chars() {
+                encoded.push(c).unwrap();
+            }
+            encoded.push('\n').unwrap();
+        })
+        .unwrap();
+
+        let mut decoded: Vec<u64, 8> = Vec::new();
+        decode_all(&encoded, &mut |value| decoded.push(value).unwrap()).unwrap();
+        assert_eq!(decoded, values);
+
+        for (value, line) in values.iter().zip(encoded.lines()) {
+            let chars: Vec<char, 64> = line.chars().collect();
+            assert_eq!(from_dollcode(&chars).unwrap(), *value);
+        }
+    }
+
+    #[test]
+    fn test_zero_roundtrip() {
+        let values = [0, 5, 0, 0, 7, 0];
+        let mut encoded = String::<64>::new();
+        encode_all(&values, &mut |dollcode| {
+            for &c in dollcode.as_chars() {
+                encoded.push(c).unwrap();
+            }
+            encoded.push(TERMINATOR).unwrap();
+        })
+        .unwrap();
+        assert_eq!(encoded, ";▖▘;;;▘▖;;");
+
+        let mut decoded: Vec<u64, 8> = Vec::new();
+        decode_all(&encoded, &mut |value| decoded.push(value).unwrap()).unwrap();
+        assert_eq!(decoded, values);
+
+        // Whitespace around terminators does not add values
+        decoded.clear();
+        decode_all("▖ ; \n;▘▘\n▌;", &mut |value| {
+            decoded.push(value).unwrap()
+        })
+        .unwrap();
+        assert_eq!(decoded, [1, 0, 8, 3]);
+    }
+
+    #[test]
+    fn test_decode_all_separators() {
+        let mut decoded: Vec<u64, 8> = Vec::new();
+        decode_all("  ▖\t▘▘▌▌▌\n\n▖▖▖▌  ", &mut |value| {
+            decoded.push(value).unwrap()
+        })
+        .unwrap();
+        assert_eq!(decoded, [1, 0xFF, 42]);
+
+        let mut count = 0;
+        decode_all("", &mut |_| count += 1).unwrap();
+        decode_all(" \n ", &mut |_| count += 1).unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_decode_all_errors() {
+        let mut decoded: Vec<u64, 8> = Vec::new();
+        let result = decode_all("▖ ▘ x ▌", &mut |value| decoded.push(value).unwrap());
+        assert!(matches!(result, Err(DollcodeError::InvalidInput)));
+        assert_eq!(decoded, [1, 2]);
+
+        let overflow = [crate::DOLLCODE_CHAR_MAP[2]; crate::MAX_DOLLCODE_SIZE];
+        let overflow: String<128> = overflow.iter().collect();
+        assert!(matches!(
+            decode_all(&overflow, &mut |_| {}),
+            Err(DollcodeError::Overflow)
+        ));
+    }
+}
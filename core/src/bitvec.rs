@@ -0,0 +1,168 @@
+//! Conversions between dollcode and `bitvec` bit slices.
+//!
+//! Every glyph takes two bits holding its digit value, high bit first, as
+//! in [`text::TextSegment`](crate::text::TextSegment). The unused pair 00
+//! stands for [`DELIMITER`], so text round-trips as well as numbers:
+//!
+//! | Bits | Character |
+//! |------|-----------|
+//! | 00 | `\u{200D}` (delimiter) |
+//! | 01 | ▖ |
+//! | 10 | ▘ |
+//! | 11 | ▌ |
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{bitvec::{read_bits, write_bits}, Result};
+//! use bitvec::prelude::*;
+//! # fn main() -> Result<()> {
+//! let mut bits = bitarr![u8, Msb0; 0; 16];
+//! let written = write_bits("▖▖▖▌", &mut bits)?;
+//! assert_eq!(written, 8);
+//! assert_eq!(bits.as_raw_slice()[0], 0b01_01_01_11);
+//!
+//! let glyphs: Result<heapless::String<16>> = read_bits(&bits[..written]).collect();
+//! assert_eq!(glyphs?, "▖▖▖▌");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{digit_char, digit_value, text::DELIMITER, DollcodeError, Result};
+use ::bitvec::{order::BitOrder, slice::BitSlice, store::BitStore};
+
+/// Bits used for each glyph or delimiter
+pub const BITS_PER_TRIT: usize = 2;
+
+/// Returns the two-bit code of a glyph or delimiter
+fn code(c: char) -> Option<u8> {
+    if c == DELIMITER {
+        Some(0)
+    } else {
+        digit_value(c)
+    }
+}
+
+/// Writes the two-bit codes of `glyphs` to the start of `bits`, returning
+/// the number of bits written.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidChar`] for characters other than glyphs
+/// and delimiters, and [`DollcodeError::Overflow`] if `bits` is too short.
+pub fn write_bits<T: BitStore, O: BitOrder>(
+    glyphs: &str,
+    bits: &mut BitSlice<T, O>,
+) -> Result<usize> {
+    let mut written = 0;
+    for (position, c) in glyphs.chars().enumerate() {
+        let code = code(c).ok_or(DollcodeError::InvalidChar(c, position))?;
+        let pair = bits
+            .get_mut(written..written + BITS_PER_TRIT)
+            .ok_or(DollcodeError::Overflow)?;
+        pair.set(0, code & 0b10 != 0);
+        pair.set(1, code & 0b01 != 0);
+        written += BITS_PER_TRIT;
+    }
+    Ok(written)
+}
+
+/// Reads glyphs and delimiters back from their two-bit codes
+pub fn read_bits<T: BitStore, O: BitOrder>(bits: &BitSlice<T, O>) -> BitDecoder<'_, T, O> {
+    BitDecoder {
+        bits,
+        failed: false,
+    }
+}
+
+/// Iterator over the characters of a bit slice, created by [`read_bits`]
+///
+/// # Errors
+///
+/// Yields [`DollcodeError::InvalidInput`] if the slice ends with a single
+/// bit, and stops after it.
+#[derive(Debug)]
+pub struct BitDecoder<'a, T: BitStore, O: BitOrder> {
+    bits: &'a BitSlice<T, O>,
+    failed: bool,
+}
+
+impl<T: BitStore, O: BitOrder> Iterator for BitDecoder<'_, T, O> {
+    type Item = Result<char>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.bits.is_empty() {
+            return None;
+        }
+        if self.bits.len() < BITS_PER_TRIT {
+            self.failed = true;
+            return Some(Err(DollcodeError::InvalidInput));
+        }
+        let (pair, rest) = self.bits.split_at(BITS_PER_TRIT);
+        self.bits = rest;
+        let code = u8::from(pair[0]) << 1 | u8::from(pair[1]);
+        Some(Ok(digit_char(code).unwrap_or(DELIMITER)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.len().div_ceil(BITS_PER_TRIT);
+        (len, Some(len))
+    }
+}
+
+/// Encodes `glyphs` into a new bit vector
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidChar`] for characters other than glyphs
+/// and delimiters.
+#[cfg(feature = "std")]
+pub fn to_bitvec(glyphs: &str) -> Result<::bitvec::vec::BitVec<u8, ::bitvec::order::Msb0>> {
+    let mut bits =
+        ::bitvec::bitvec![u8, ::bitvec::order::Msb0; 0; glyphs.chars().count() * BITS_PER_TRIT];
+    write_bits(glyphs, &mut bits)?;
+    Ok(bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::bitvec::prelude::*;
+    use heapless::String;
+
+    #[test]
+    fn test_round_trip() {
+        let text = "▘▖▘▌\u{200d}▌▘▖▌\u{200d}";
+        let mut bits = bitarr![u16, Lsb0; 0; 32];
+        let written = write_bits(text, &mut bits).unwrap();
+        assert_eq!(written, 20);
+
+        let decoded: Result<String<64>> = read_bits(&bits[..written]).collect();
+        assert_eq!(decoded.unwrap(), text);
+
+        #[cfg(feature = "std")]
+        assert_eq!(to_bitvec(text).unwrap(), bits[..written]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut array = bitarr![u8, Msb0; 0; 8];
+        let bits = &mut array[..4];
+        assert!(matches!(
+            write_bits("▖▖▖", bits),
+            Err(DollcodeError::Overflow)
+        ));
+        assert!(matches!(
+            write_bits("▖x", bits),
+            Err(DollcodeError::InvalidChar('x', 1))
+        ));
+
+        let mut decoder = read_bits(&bits[..3]);
+        assert!(matches!(decoder.next(), Some(Ok('▖'))));
+        assert!(matches!(
+            decoder.next(),
+            Some(Err(DollcodeError::InvalidInput))
+        ));
+        assert!(decoder.next().is_none());
+    }
+}
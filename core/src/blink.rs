@@ -0,0 +1,227 @@
+//! Blinking dollcode on a single LED.
+//!
+//! [`BlinkPattern`] turns a dollcode string into `(on, duration)` steps. Each
+//! glyph is a flash whose length tells the digit apart, followed by a short
+//! gap; a [`DELIMITER`] lengthens the gap before the next segment:
+//!
+//! | Symbol | LED | Default length |
+//! |--------|-----|----------------|
+//! | ▖ | on | 1 unit |
+//! | ▘ | on | 2 units |
+//! | ▌ | on | 3 units |
+//! | between glyphs | off | 1 unit |
+//! | between segments | off | 4 units |
+//!
+//! Consecutive off steps are merged, so the steps alternate between on and
+//! off and a blinker only needs to toggle the LED and wait.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{blink::{BlinkPattern, Timing}, to_dollcode, Result};
+//! # fn main() -> Result<()> {
+//! let encoded = to_dollcode(5)?.to_heapless_string::<8>()?;
+//! assert_eq!(encoded, "▖▘");
+//!
+//! let steps: heapless::Vec<_, 8> = BlinkPattern::new(&encoded, Timing::DEFAULT.scaled(200))
+//!     .collect::<Result<_>>()?;
+//! assert_eq!(steps, [(true, 200), (false, 200), (true, 400), (false, 200)]);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{digit_value, text::DELIMITER, DollcodeError, Result};
+use core::{iter::Peekable, str::Chars};
+
+/// Lengths of the steps of a pattern
+///
+/// The lengths are in any unit the blinker waits in, such as milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    /// Length of the flash for digits 1, 2 and 3
+    pub pulses: [u32; 3],
+    /// Off time after a glyph
+    pub digit_gap: u32,
+    /// Off time after a glyph followed by a delimiter, and for each further
+    /// delimiter
+    pub segment_gap: u32,
+}
+
+impl Timing {
+    /// Default lengths, in units
+    pub const DEFAULT: Self = Self {
+        pulses: [1, 2, 3],
+        digit_gap: 1,
+        segment_gap: 4,
+    };
+
+    /// Multiplies every length by `unit`, saturating at `u32::MAX`
+    pub const fn scaled(self, unit: u32) -> Self {
+        let [one, two, three] = self.pulses;
+        Self {
+            pulses: [
+                one.saturating_mul(unit),
+                two.saturating_mul(unit),
+                three.saturating_mul(unit),
+            ],
+            digit_gap: self.digit_gap.saturating_mul(unit),
+            segment_gap: self.segment_gap.saturating_mul(unit),
+        }
+    }
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Iterator over the `(on, duration)` steps blinking a dollcode string
+///
+/// Yields [`DollcodeError::InvalidChar`] for characters that are neither
+/// glyphs nor delimiters, and then stops.
+#[derive(Debug, Clone)]
+pub struct BlinkPattern<'a> {
+    chars: Peekable<Chars<'a>>,
+    timing: Timing,
+    /// Characters read so far, for error positions
+    position: usize,
+    /// The off step after the last flash, if not yet yielded
+    gap: Option<u32>,
+    failed: bool,
+}
+
+impl<'a> BlinkPattern<'a> {
+    /// Creates a pattern for the glyphs and delimiters of `encoded`
+    pub fn new(encoded: &'a str, timing: Timing) -> Self {
+        Self {
+            chars: encoded.chars().peekable(),
+            timing,
+            position: 0,
+            gap: None,
+            failed: false,
+        }
+    }
+
+    /// Reads the delimiters coming next, returning the off time they add up to
+    fn delimiters(&mut self) -> u32 {
+        let mut gap = 0u32;
+        while self.chars.next_if_eq(&DELIMITER).is_some() {
+            self.position += 1;
+            gap = gap.saturating_add(self.timing.segment_gap);
+        }
+        gap
+    }
+}
+
+impl Iterator for BlinkPattern<'_> {
+    type Item = Result<(bool, u32)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(gap) = self.gap.take() {
+            return Some(Ok((false, gap)));
+        }
+        if self.failed {
+            return None;
+        }
+        // Delimiters before any glyph
+        if self.chars.peek() == Some(&DELIMITER) {
+            return Some(Ok((false, self.delimiters())));
+        }
+
+        let c = self.chars.next()?;
+        let Some(digit) = digit_value(c) else {
+            self.failed = true;
+            return Some(Err(DollcodeError::InvalidChar(c, self.position)));
+        };
+        self.position += 1;
+        self.gap = Some(match self.chars.peek() {
+            Some(&DELIMITER) => self.delimiters(),
+            _ => self.timing.digit_gap,
+        });
+        Some(Ok((true, self.timing.pulses[usize::from(digit - 1)])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::Vec;
+
+    fn steps(encoded: &str) -> Result<Vec<(bool, u32), 32>> {
+        BlinkPattern::new(encoded, Timing::DEFAULT).collect()
+    }
+
+    #[test]
+    fn test_glyphs() {
+        assert_eq!(
+            steps("▖▘▌").unwrap(),
+            [
+                (true, 1),
+                (false, 1),
+                (true, 2),
+                (false, 1),
+                (true, 3),
+                (false, 1)
+            ]
+        );
+        assert!(steps("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_segments() {
+        // "Hi" in text segments
+        assert_eq!(
+            steps("▘▖▘▌\u{200D}▌▘▖▌\u{200D}").unwrap(),
+            [
+                (true, 2),
+                (false, 1),
+                (true, 1),
+                (false, 1),
+                (true, 2),
+                (false, 1),
+                (true, 3),
+                (false, 4),
+                (true, 3),
+                (false, 1),
+                (true, 2),
+                (false, 1),
+                (true, 1),
+                (false, 1),
+                (true, 3),
+                (false, 4),
+            ]
+        );
+        // Empty frames lengthen the gap
+        assert_eq!(
+            steps("\u{200D}▖\u{200D}\u{200D}").unwrap(),
+            [(false, 4), (true, 1), (false, 8)]
+        );
+    }
+
+    #[test]
+    fn test_timing() {
+        let timing = Timing {
+            pulses: [100, 300, 500],
+            digit_gap: 100,
+            segment_gap: 700,
+        };
+        let steps: Vec<_, 4> = BlinkPattern::new("▌\u{200D}", timing)
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(steps, [(true, 500), (false, 700)]);
+        assert_eq!(Timing::DEFAULT.scaled(u32::MAX).pulses[2], u32::MAX);
+    }
+
+    #[test]
+    fn test_invalid_char() {
+        let mut pattern = BlinkPattern::new("▖x▖", Timing::DEFAULT);
+        assert!(matches!(pattern.next(), Some(Ok((true, 1)))));
+        assert!(matches!(pattern.next(), Some(Ok((false, 1)))));
+        assert!(matches!(
+            pattern.next(),
+            Some(Err(DollcodeError::InvalidChar('x', 1)))
+        ));
+        assert!(pattern.next().is_none());
+    }
+}
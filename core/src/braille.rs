@@ -0,0 +1,209 @@
+//! Braille alphabet for dollcode.
+//!
+//! Box-drawing glyphs are missing from many fonts, and the zero-width
+//! delimiter is often dropped when text is copied. Braille patterns are far
+//! more widely supported, so this alphabet writes each glyph as the braille
+//! cell with the same shape, and the delimiter as the blank cell, which is
+//! visible as a gap but is not whitespace:
+//!
+//! | Dollcode | Braille | Dots |
+//! |----------|---------|------|
+//! | ▖ | ⠆ | 2, 3 |
+//! | ▘ | ⠃ | 1, 2 |
+//! | ▌ | ⠇ | 1, 2, 3 |
+//! | [`DELIMITER`](crate::text::DELIMITER) | ⠀ (U+2800) | none |
+//!
+//! [`to_braille`] and [`from_braille`] convert between the alphabets into any
+//! [`Sink`], and [`Braille`] is a sink converting what is pushed into it, so
+//! any [`ToDollcode`](crate::ToDollcode) value can be written in braille
+//! directly. With the `alloc` feature, [`encode`] and [`decode`] return new
+//! strings.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{braille::{from_braille, Braille}, text::TextDecoder, Result, ToDollcode};
+//! # fn main() -> Result<()> {
+//! let mut braille = heapless::String::<64>::new();
+//! "Hi".encode_dollcode(&mut Braille::new(&mut braille))?;
+//! assert_eq!(braille, "⠃⠆⠃⠇⠀⠇⠃⠆⠇⠀");
+//!
+//! let mut glyphs = heapless::String::<64>::new();
+//! from_braille(&braille, &mut glyphs)?;
+//! let text: heapless::String<8> = TextDecoder::new(&glyphs).collect::<Result<_>>()?;
+//! assert_eq!(text, "Hi");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{digit_char, digit_value, text::DELIMITER, DollcodeError, Result, Sink};
+
+/// Braille cells for digits 1, 2 and 3, as ▖, ▘ and ▌
+pub const BRAILLE_CHAR_MAP: [char; 3] = ['⠆', '⠃', '⠇'];
+
+/// Braille cell standing for the delimiter, the blank pattern
+pub const BRAILLE_DELIMITER: char = '\u{2800}';
+
+/// Returns the braille cell for a glyph or the delimiter
+pub const fn braille_char(c: char) -> Option<char> {
+    match c {
+        DELIMITER => Some(BRAILLE_DELIMITER),
+        _ => match digit_value(c) {
+            Some(digit) => Some(BRAILLE_CHAR_MAP[(digit - 1) as usize]),
+            None => None,
+        },
+    }
+}
+
+/// Returns the glyph or delimiter a braille cell stands for
+pub const fn glyph_char(c: char) -> Option<char> {
+    match c {
+        BRAILLE_DELIMITER => Some(DELIMITER),
+        '⠆' => digit_char(1),
+        '⠃' => digit_char(2),
+        '⠇' => digit_char(3),
+        _ => None,
+    }
+}
+
+/// Sink writing the braille cells of the glyphs and delimiters pushed into
+/// it to another sink
+#[derive(Debug)]
+pub struct Braille<'a, S: ?Sized> {
+    sink: &'a mut S,
+    /// Characters pushed so far, for error positions
+    position: usize,
+}
+
+impl<'a, S: Sink + ?Sized> Braille<'a, S> {
+    /// Creates a sink writing braille to `sink`
+    pub fn new(sink: &'a mut S) -> Self {
+        Self { sink, position: 0 }
+    }
+}
+
+impl<S: Sink + ?Sized> Sink for Braille<'_, S> {
+    /// Writes the braille cell of a glyph or delimiter
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DollcodeError::InvalidChar`] for any other character, and
+    /// the errors of the wrapped sink.
+    fn push(&mut self, c: char) -> Result<()> {
+        let cell = braille_char(c).ok_or(DollcodeError::InvalidChar(c, self.position))?;
+        self.sink.push(cell)?;
+        self.position += 1;
+        Ok(())
+    }
+}
+
+/// Writes the braille cells of the dollcode `input` to `sink`.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidChar`] for characters that are neither
+/// glyphs nor delimiters, and the errors of `sink`.
+pub fn to_braille(input: &str, sink: &mut impl Sink) -> Result<()> {
+    for (position, c) in input.chars().enumerate() {
+        sink.push(braille_char(c).ok_or(DollcodeError::InvalidChar(c, position))?)?;
+    }
+    Ok(())
+}
+
+/// Writes the glyphs and delimiters of the braille `input` to `sink`.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidChar`] for characters outside the
+/// alphabet, and the errors of `sink`.
+pub fn from_braille(input: &str, sink: &mut impl Sink) -> Result<()> {
+    for (position, c) in input.chars().enumerate() {
+        sink.push(glyph_char(c).ok_or(DollcodeError::InvalidChar(c, position))?)?;
+    }
+    Ok(())
+}
+
+/// Converts dollcode to braille in a new string.
+///
+/// # Errors
+///
+/// Returns the errors of [`to_braille`].
+#[cfg(feature = "alloc")]
+pub fn encode(input: &str) -> Result<alloc::string::String> {
+    let mut braille = alloc::string::String::new();
+    to_braille(input, &mut braille)?;
+    Ok(braille)
+}
+
+/// Converts braille to dollcode in a new string.
+///
+/// # Errors
+///
+/// Returns the errors of [`from_braille`].
+#[cfg(feature = "alloc")]
+pub fn decode(input: &str) -> Result<alloc::string::String> {
+    let mut glyphs = alloc::string::String::new();
+    from_braille(input, &mut glyphs)?;
+    Ok(glyphs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{from_dollcode, to_dollcode, ToDollcode, DOLLCODE_CHAR_MAP};
+    use heapless::{String, Vec};
+
+    #[test]
+    fn test_char_mapping() {
+        for (glyph, cell) in DOLLCODE_CHAR_MAP.into_iter().zip(BRAILLE_CHAR_MAP) {
+            assert_eq!(braille_char(glyph), Some(cell));
+            assert_eq!(glyph_char(cell), Some(glyph));
+        }
+        assert_eq!(braille_char(DELIMITER), Some(BRAILLE_DELIMITER));
+        assert_eq!(glyph_char(BRAILLE_DELIMITER), Some(DELIMITER));
+        assert_eq!(braille_char('x'), None);
+        assert_eq!(glyph_char('⠉'), None);
+        assert!(!BRAILLE_DELIMITER.is_whitespace());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut braille = String::<256>::new();
+        u64::MAX
+            .encode_dollcode(&mut Braille::new(&mut braille))
+            .unwrap();
+
+        let mut glyphs = Vec::<char, 64>::new();
+        from_braille(&braille, &mut glyphs).unwrap();
+        assert_eq!(from_dollcode(&glyphs).unwrap(), u64::MAX);
+
+        let encoded = to_dollcode(42).unwrap().to_heapless_string::<16>().unwrap();
+        let mut braille = String::<16>::new();
+        to_braille(&encoded, &mut braille).unwrap();
+        assert_eq!(braille, "⠆⠆⠆⠇");
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut output = String::<16>::new();
+        assert!(matches!(
+            to_braille("▖x", &mut output),
+            Err(DollcodeError::InvalidChar('x', 1))
+        ));
+        assert!(matches!(
+            from_braille("⠆⠀ ⠇", &mut output),
+            Err(DollcodeError::InvalidChar(' ', 2))
+        ));
+        let mut braille = Braille::new(&mut output);
+        braille.push('▖').unwrap();
+        assert!(matches!(
+            braille.push('a'),
+            Err(DollcodeError::InvalidChar('a', 1))
+        ));
+        let mut small = String::<4>::new();
+        assert!(matches!(
+            to_braille("▖▘", &mut small),
+            Err(DollcodeError::Overflow)
+        ));
+    }
+}
@@ -0,0 +1,506 @@
+//! Binary data encoding.
+//!
+//! Every byte is written as exactly [`GLYPHS_PER_BYTE`] glyphs holding its six
+//! base-3 digits, most significant first, with ▖, ▘ and ▌ standing for the
+//! digits 0, 1 and 2. Because groups have a fixed width no delimiter is
+//! needed, and arbitrary binary data such as files or hashes round-trips
+//! without being treated as text.
+//!
+//! ```text
+//! 0x00 → ▖▖▖▖▖▖   (000000 in base 3)
+//! 0x2A → ▖▖▘▘▌▖   (001120: 1×27 + 1×9 + 2×3)
+//! 0xFF → ▘▖▖▘▘▖   (100110: 1×243 + 1×9 + 1×3)
+//! ```
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{bytes::{ByteDecoder, ByteEncoder}, Result};
+//! # fn main() -> Result<()> {
+//! let data = [0xDE, 0xAD, 0xBE, 0xEF];
+//!
+//! let encoded: heapless::String<128> = ByteEncoder::new(&data).collect();
+//! assert_eq!(encoded.chars().count(), data.len() * 6);
+//!
+//! let mut decoded = heapless::Vec::<u8, 4>::new();
+//! for byte in ByteDecoder::new(&encoded) {
+//!     decoded.push(byte?).unwrap();
+//! }
+//! assert_eq!(decoded, data);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{digit_char, digit_value, DollcodeError, Result, Sink, DOLLCODE_CHAR_MAP};
+use core::{slice, str::Chars};
+
+/// Number of glyphs used to encode a single byte (`3^6 = 729 ≥ 256`).
+pub const GLYPHS_PER_BYTE: usize = 6;
+
+/// Returns the number of glyphs needed to encode `len` bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dollcode::bytes::encoded_len;
+/// assert_eq!(encoded_len(32), 192);
+/// ```
+#[inline]
+pub const fn encoded_len(len: usize) -> usize {
+    len * GLYPHS_PER_BYTE
+}
+
+/// Encodes a single byte into its fixed-width glyph group.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dollcode::bytes::encode_byte;
+/// assert_eq!(encode_byte(0), ['▖'; 6]);
+/// assert_eq!(encode_byte(42), ['▖', '▖', '▘', '▘', '▌', '▖']);
+/// assert_eq!(encode_byte(255), ['▘', '▖', '▖', '▘', '▘', '▖']);
+/// ```
+#[inline]
+pub fn encode_byte(byte: u8) -> [char; GLYPHS_PER_BYTE] {
+    let mut glyphs = [DOLLCODE_CHAR_MAP[0]; GLYPHS_PER_BYTE];
+    let mut value = byte;
+
+    for slot in glyphs.iter_mut().rev() {
+        *slot = digit_char(value % 3 + 1).unwrap_or(DOLLCODE_CHAR_MAP[0]);
+        value /= 3;
+    }
+
+    glyphs
+}
+
+/// Zero-allocation iterator that encodes bytes into glyphs.
+///
+/// Yields [`GLYPHS_PER_BYTE`] characters for every input byte.
+#[derive(Debug, Clone)]
+pub struct ByteEncoder<'a> {
+    bytes: slice::Iter<'a, u8>,
+    group: [char; GLYPHS_PER_BYTE],
+    index: usize,
+}
+
+impl<'a> ByteEncoder<'a> {
+    /// Creates an encoder over `bytes`.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes: bytes.iter(),
+            group: [DOLLCODE_CHAR_MAP[0]; GLYPHS_PER_BYTE],
+            index: GLYPHS_PER_BYTE,
+        }
+    }
+}
+
+impl Iterator for ByteEncoder<'_> {
+    type Item = char;
+
+    #[inline]
+    fn next(&mut self) -> Option<char> {
+        if self.index >= GLYPHS_PER_BYTE {
+            self.group = encode_byte(*self.bytes.next()?);
+            self.index = 0;
+        }
+
+        let c = self.group.get(self.index).copied();
+        self.index += 1;
+        c
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = encoded_len(self.bytes.len()) + (GLYPHS_PER_BYTE - self.index);
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for ByteEncoder<'_> {}
+
+/// Zero-allocation iterator that decodes glyph groups back into bytes.
+///
+/// # Errors
+///
+/// Each item is an error when:
+/// - a character is not a dollcode glyph ([`DollcodeError::InvalidChar`] with
+///   its position in the input)
+/// - a group encodes a value above 255, or the input ends partway through a
+///   group ([`DollcodeError::InvalidInput`])
+///
+/// Iteration stops after the first error.
+#[derive(Debug, Clone)]
+pub struct ByteDecoder<'a> {
+    chars: Chars<'a>,
+    position: usize,
+    failed: bool,
+}
+
+impl<'a> ByteDecoder<'a> {
+    /// Creates a decoder over an encoded string.
+    pub fn new(encoded: &'a str) -> Self {
+        Self {
+            chars: encoded.chars(),
+            position: 0,
+            failed: false,
+        }
+    }
+
+    fn decode_group(&mut self, first: char) -> Result<u8> {
+        let mut value: u16 = 0;
+        let mut c = first;
+
+        for digit in 0..GLYPHS_PER_BYTE {
+            if digit > 0 {
+                c = self.chars.next().ok_or(DollcodeError::InvalidInput)?;
+            }
+
+            let trit = digit_value(c).ok_or(DollcodeError::InvalidChar(c, self.position))?;
+            value = value * 3 + (trit - 1) as u16;
+            self.position += 1;
+        }
+
+        u8::try_from(value).map_err(|_| DollcodeError::InvalidInput)
+    }
+}
+
+impl Iterator for ByteDecoder<'_> {
+    type Item = Result<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let first = self.chars.next()?;
+        let result = self.decode_group(first);
+        self.failed = result.is_err();
+        Some(result)
+    }
+}
+
+/// Writes `value` as exactly `width` base-3 digits, most significant first,
+/// with the glyphs of [`encode_byte`].
+///
+/// Unlike [`to_dollcode`](crate::to_dollcode), every value of a given width
+/// has the same length, so numbers line up in columns or fill fixed fields.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dollcode::{bytes::{decode_fixed, encode_fixed}, Result};
+/// # fn main() -> Result<()> {
+/// let mut encoded = heapless::String::<64>::new();
+/// encode_fixed(42, 8, &mut encoded)?;
+/// assert_eq!(encoded, "▖▖▖▖▘▘▌▖");
+/// assert_eq!(decode_fixed(&encoded)?, 42);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidInput`] if `width` is 0,
+/// [`DollcodeError::Overflow`] if `value` needs more digits, and the errors of
+/// `sink`.
+pub fn encode_fixed(value: u64, width: usize, sink: &mut impl Sink) -> Result<()> {
+    if width == 0 {
+        return Err(DollcodeError::InvalidInput);
+    }
+    let digits = u32::try_from(width).unwrap_or(u32::MAX);
+    if 3u64.checked_pow(digits).is_some_and(|limit| value >= limit) {
+        return Err(DollcodeError::Overflow);
+    }
+
+    for digit in (0..digits).rev() {
+        // Places beyond the range of u64 are always zero
+        let trit = 3u64.checked_pow(digit).map_or(0, |place| value / place % 3);
+        sink.push(digit_char(trit as u8 + 1).unwrap_or(DOLLCODE_CHAR_MAP[0]))?;
+    }
+    Ok(())
+}
+
+/// Reads a number written by [`encode_fixed`], of any width.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidInput`] for empty input,
+/// [`DollcodeError::InvalidChar`] for characters other than glyphs, and
+/// [`DollcodeError::Overflow`] if the value does not fit in a `u64`.
+pub fn decode_fixed(glyphs: &str) -> Result<u64> {
+    if glyphs.is_empty() {
+        return Err(DollcodeError::InvalidInput);
+    }
+
+    glyphs
+        .chars()
+        .enumerate()
+        .try_fold(0u64, |value, (position, c)| {
+            let trit = digit_value(c).ok_or(DollcodeError::InvalidChar(c, position))?;
+            value
+                .checked_mul(3)
+                .and_then(|value| value.checked_add(u64::from(trit - 1)))
+                .ok_or(DollcodeError::Overflow)
+        })
+}
+
+/// UTF-8 bytes of each glyph.
+const GLYPH_BYTES: usize = 3;
+
+/// The byte encoding as a codec over UTF-8 buffers, with the method names of
+/// `data_encoding::Encoding`, so it can stand in where base32 or base64 is
+/// used. Unlike there, every failure is reported through [`DollcodeError`]
+/// rather than a panic.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dollcode::{bytes::DOLLCODE, Result};
+/// # fn main() -> Result<()> {
+/// let data = [0x2A];
+/// let mut encoded = [0u8; 18];
+/// assert_eq!(DOLLCODE.encode_len(data.len()), encoded.len());
+/// DOLLCODE.encode_mut(&data, &mut encoded)?;
+/// assert_eq!(core::str::from_utf8(&encoded).unwrap(), "▖▖▘▘▌▖");
+///
+/// let mut decoded = [0u8; 1];
+/// let len = DOLLCODE.decode_mut(&encoded, &mut decoded)?;
+/// assert_eq!(&decoded[..len], data);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Encoding;
+
+/// The byte encoding as an [`Encoding`]
+pub const DOLLCODE: Encoding = Encoding;
+
+impl Encoding {
+    /// Returns the UTF-8 length of the encoding of `len` bytes.
+    #[inline]
+    pub const fn encode_len(&self, len: usize) -> usize {
+        encoded_len(len) * GLYPH_BYTES
+    }
+
+    /// Encodes `input` into `output` as UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DollcodeError::Overflow`] if `output` is not exactly
+    /// [`encode_len`](Self::encode_len) bytes long.
+    pub fn encode_mut(&self, input: &[u8], output: &mut [u8]) -> Result<()> {
+        if output.len() != self.encode_len(input.len()) {
+            return Err(DollcodeError::Overflow);
+        }
+
+        let groups = output.chunks_exact_mut(GLYPHS_PER_BYTE * GLYPH_BYTES);
+        for (&byte, group) in input.iter().zip(groups) {
+            for (c, slot) in encode_byte(byte)
+                .into_iter()
+                .zip(group.chunks_exact_mut(GLYPH_BYTES))
+            {
+                c.encode_utf8(slot);
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the encoding of `input` to a formatter or string.
+    pub fn encode_write<W: core::fmt::Write + ?Sized>(
+        &self,
+        input: &[u8],
+        output: &mut W,
+    ) -> core::fmt::Result {
+        ByteEncoder::new(input).try_for_each(|c| output.write_char(c))
+    }
+
+    /// Returns the encoding of `input` as a new string.
+    #[cfg(feature = "std")]
+    pub fn encode(&self, input: &[u8]) -> std::string::String {
+        ByteEncoder::new(input).collect()
+    }
+
+    /// Returns the number of bytes decoded from `len` bytes of UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DollcodeError::InvalidInput`] if `len` is not a whole number
+    /// of glyph groups.
+    pub const fn decode_len(&self, len: usize) -> Result<usize> {
+        let group = GLYPHS_PER_BYTE * GLYPH_BYTES;
+        if !len.is_multiple_of(group) {
+            return Err(DollcodeError::InvalidInput);
+        }
+        Ok(len / group)
+    }
+
+    /// Decodes UTF-8 `input` into `output`, returning the number of bytes
+    /// written.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`decode_len`](Self::decode_len) and
+    /// [`ByteDecoder`], [`DollcodeError::InvalidInput`] if `input` is not
+    /// UTF-8, and [`DollcodeError::Overflow`] if `output` is shorter than
+    /// [`decode_len`](Self::decode_len).
+    pub fn decode_mut(&self, input: &[u8], output: &mut [u8]) -> Result<usize> {
+        let len = self.decode_len(input.len())?;
+        let output = output.get_mut(..len).ok_or(DollcodeError::Overflow)?;
+        let encoded = core::str::from_utf8(input).map_err(|_| DollcodeError::InvalidInput)?;
+
+        let mut written = 0;
+        for (slot, byte) in output.iter_mut().zip(ByteDecoder::new(encoded)) {
+            *slot = byte?;
+            written += 1;
+        }
+        Ok(written)
+    }
+
+    /// Decodes UTF-8 `input` into a new vector.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`decode_mut`](Self::decode_mut).
+    #[cfg(feature = "std")]
+    pub fn decode(&self, input: &[u8]) -> Result<std::vec::Vec<u8>> {
+        let mut output = std::vec![0; self.decode_len(input.len())?];
+        let len = self.decode_mut(input, &mut output)?;
+        output.truncate(len);
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::String;
+
+    #[test]
+    fn test_byte_roundtrip() {
+        let data: [u8; 256] = core::array::from_fn(|i| i as u8);
+
+        let encoded: String<4608> = ByteEncoder::new(&data).collect();
+        assert_eq!(encoded.chars().count(), encoded_len(data.len()));
+
+        for (byte, result) in data.iter().zip(ByteDecoder::new(&encoded)) {
+            assert_eq!(result.unwrap(), *byte);
+        }
+        assert_eq!(ByteDecoder::new(&encoded).count(), data.len());
+    }
+
+    #[test]
+    fn test_encoder_size_hint() {
+        let mut encoder = ByteEncoder::new(&[1, 2]);
+        assert_eq!(encoder.len(), 12);
+        encoder.next();
+        assert_eq!(encoder.len(), 11);
+        assert_eq!(encoder.count(), 11);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        // Invalid character reports its position
+        let mut decoder = ByteDecoder::new("▖▖▖▖▖▖▖▖X▖▖▖");
+        assert_eq!(decoder.next().unwrap().unwrap(), 0);
+        assert!(matches!(
+            decoder.next(),
+            Some(Err(DollcodeError::InvalidChar('X', 8)))
+        ));
+        assert!(decoder.next().is_none());
+
+        // Incomplete trailing group
+        let mut decoder = ByteDecoder::new("▖▖▖");
+        assert!(matches!(
+            decoder.next(),
+            Some(Err(DollcodeError::InvalidInput))
+        ));
+
+        // Value above 255
+        let mut decoder = ByteDecoder::new("▌▌▌▌▌▌");
+        assert!(matches!(
+            decoder.next(),
+            Some(Err(DollcodeError::InvalidInput))
+        ));
+
+        assert!(ByteDecoder::new("").next().is_none());
+    }
+
+    #[test]
+    fn test_fixed() {
+        for (value, width) in [(0, 1), (2, 1), (42, 4), (255, 6), (u64::MAX, 41), (7, 60)] {
+            let mut encoded = String::<256>::new();
+            encode_fixed(value, width, &mut encoded).unwrap();
+            assert_eq!(encoded.chars().count(), width);
+            assert_eq!(decode_fixed(&encoded).unwrap(), value);
+        }
+
+        // Bytes are fixed-width numbers of six glyphs
+        let mut encoded = String::<32>::new();
+        encode_fixed(42, GLYPHS_PER_BYTE, &mut encoded).unwrap();
+        assert_eq!(
+            encoded.chars().collect::<heapless::Vec<_, 6>>(),
+            encode_byte(42)
+        );
+
+        let mut encoded = String::<32>::new();
+        assert!(matches!(
+            encode_fixed(3, 1, &mut encoded),
+            Err(DollcodeError::Overflow)
+        ));
+        assert!(matches!(
+            encode_fixed(0, 0, &mut encoded),
+            Err(DollcodeError::InvalidInput)
+        ));
+        assert!(encoded.is_empty());
+        assert!(matches!(decode_fixed(""), Err(DollcodeError::InvalidInput)));
+        assert!(matches!(
+            decode_fixed("▖x"),
+            Err(DollcodeError::InvalidChar('x', 1))
+        ));
+        let overflow: String<128> = core::iter::repeat_n('▌', 41).collect();
+        assert!(matches!(
+            decode_fixed(&overflow),
+            Err(DollcodeError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_encoding() {
+        let data: [u8; 256] = core::array::from_fn(|i| i as u8);
+        let mut encoded = [0u8; 256 * 18];
+        DOLLCODE.encode_mut(&data, &mut encoded).unwrap();
+
+        let glyphs: String<{ 256 * 18 }> = ByteEncoder::new(&data).collect();
+        assert_eq!(encoded, glyphs.as_bytes());
+
+        let mut decoded = [0u8; 256];
+        assert_eq!(DOLLCODE.decode_mut(&encoded, &mut decoded).unwrap(), 256);
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn test_encoding_errors() {
+        let mut short = [0u8; 17];
+        assert!(matches!(
+            DOLLCODE.encode_mut(&[0], &mut short),
+            Err(DollcodeError::Overflow)
+        ));
+        assert!(matches!(
+            DOLLCODE.decode_len(17),
+            Err(DollcodeError::InvalidInput)
+        ));
+
+        let mut output = [0u8; 1];
+        assert!(matches!(
+            DOLLCODE.decode_mut("▖▖▖▖▖▖▖▖▖▖▖▖".as_bytes(), &mut output),
+            Err(DollcodeError::Overflow)
+        ));
+        assert!(matches!(
+            DOLLCODE.decode_mut("▖▖▖▖▖XYZ".as_bytes(), &mut output),
+            Err(DollcodeError::InvalidChar('X', 5))
+        ));
+        assert!(matches!(
+            DOLLCODE.decode_mut(&[0xFF; 18], &mut output),
+            Err(DollcodeError::InvalidInput)
+        ));
+    }
+}
@@ -0,0 +1,237 @@
+//! Check digits and checksums for dollcode sequences.
+//!
+//! Both are appended to a sequence and verified and removed before decoding
+//! it:
+//!
+//! - A check digit is a single glyph over the glyphs of the sequence. It
+//!   catches any one changed glyph and any swap of two neighbouring glyphs,
+//!   the usual mistakes when copying by hand. Delimiters are not covered.
+//! - A checksum is the CRC-8 of the whole sequence, delimiters included,
+//!   written as a byte in the fixed-width [`bytes`](crate::bytes) encoding.
+//!   It catches damage in transit, such as a dropped zero-width joiner.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{check::{check_digit, checksum, verify_check_digit, verify_checksum}, Result};
+//! # fn main() -> Result<()> {
+//! let mut sequence = heapless::String::<64>::new();
+//! sequence.push_str("▖▖▖▌").unwrap();
+//! sequence.push(check_digit(&sequence)?).unwrap();
+//! assert_eq!(sequence, "▖▖▖▌▘");
+//! assert_eq!(verify_check_digit(&sequence)?, "▖▖▖▌");
+//!
+//! for c in checksum(&sequence) {
+//!     sequence.push(c).unwrap();
+//! }
+//! assert_eq!(verify_checksum(&sequence)?, "▖▖▖▌▘");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    bytes::{encode_byte, ByteDecoder, GLYPHS_PER_BYTE},
+    digit_char, digit_value,
+    text::DELIMITER,
+    DollcodeError, Result,
+};
+
+/// Returns the glyph standing for `sum` modulo 3, with ▖, ▘ and ▌ as 0, 1
+/// and 2
+fn residue_char(sum: u32) -> char {
+    // The residue is below 3, so always a digit
+    digit_char((sum % 3) as u8 + 1).unwrap_or('▖')
+}
+
+/// Returns the check digit of `sequence`.
+///
+/// Glyphs count as the digits 0, 1 and 2, weighted alternately by 1 and 2
+/// from the first, and the check digit is their sum modulo 3.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidChar`] for characters that are neither
+/// glyphs nor delimiters.
+pub fn check_digit(sequence: &str) -> Result<char> {
+    let mut sum = 0u32;
+    let mut glyphs = 0u32;
+    for (position, c) in sequence.chars().enumerate() {
+        if c == DELIMITER {
+            continue;
+        }
+        let digit = digit_value(c).ok_or(DollcodeError::InvalidChar(c, position))?;
+        let weight = if glyphs.is_multiple_of(2) { 1 } else { 2 };
+        sum = (sum + weight * u32::from(digit - 1)) % 3;
+        glyphs += 1;
+    }
+    Ok(residue_char(sum))
+}
+
+/// Checks the final glyph of `sequence` against the [`check_digit`] of the
+/// rest, returning the rest.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidInput`] if `sequence` is empty or the
+/// check digit does not match, and the errors of [`check_digit`].
+pub fn verify_check_digit(sequence: &str) -> Result<&str> {
+    let mut chars = sequence.chars();
+    let last = chars.next_back().ok_or(DollcodeError::InvalidInput)?;
+    let rest = chars.as_str();
+    if check_digit(rest)? == last {
+        Ok(rest)
+    } else {
+        Err(DollcodeError::InvalidInput)
+    }
+}
+
+/// Returns the CRC-8 of `data`, with polynomial 0x07 and no reflection, as
+/// used by SMBus
+pub const fn crc8(data: &[u8]) -> u8 {
+    let mut crc = 0u8;
+    let mut i = 0;
+    while i < data.len() {
+        crc ^= data[i];
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        i += 1;
+    }
+    crc
+}
+
+/// Returns the glyphs of the checksum of `sequence`: the [`crc8`] of its
+/// UTF-8 bytes as a fixed-width byte
+pub fn checksum(sequence: &str) -> [char; GLYPHS_PER_BYTE] {
+    encode_byte(crc8(sequence.as_bytes()))
+}
+
+/// Checks the final [`GLYPHS_PER_BYTE`] glyphs of `sequence` against the
+/// [`checksum`] of the rest, returning the rest.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidInput`] if `sequence` is too short or the
+/// checksum does not match, and [`DollcodeError::InvalidChar`] if it does
+/// not end in glyphs.
+pub fn verify_checksum(sequence: &str) -> Result<&str> {
+    let start = sequence
+        .char_indices()
+        .nth_back(GLYPHS_PER_BYTE - 1)
+        .ok_or(DollcodeError::InvalidInput)?
+        .0;
+    let (rest, sum) = sequence.split_at(start);
+    let crc = ByteDecoder::new(sum)
+        .next()
+        .unwrap_or(Err(DollcodeError::InvalidInput))
+        .map_err(|e| match e {
+            DollcodeError::InvalidChar(c, position) => {
+                DollcodeError::InvalidChar(c, rest.chars().count() + position)
+            }
+            e => e,
+        })?;
+    if crc == crc8(rest.as_bytes()) {
+        Ok(rest)
+    } else {
+        Err(DollcodeError::InvalidInput)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{to_dollcode, DOLLCODE_CHAR_MAP};
+    use heapless::String;
+
+    #[test]
+    fn test_check_digit() {
+        assert_eq!(check_digit("").unwrap(), '▖');
+        // Delimiters are skipped
+        assert_eq!(
+            check_digit("▘▖▘▌\u{200D}▌▘▖▌\u{200D}").unwrap(),
+            check_digit("▘▖▘▌▌▘▖▌").unwrap()
+        );
+
+        let sequence = "▖▘▌▌▘▖▘";
+        let mut checked = String::<64>::new();
+        checked.push_str(sequence).unwrap();
+        checked.push(check_digit(sequence).unwrap()).unwrap();
+        assert_eq!(verify_check_digit(&checked).unwrap(), sequence);
+
+        let glyphs: heapless::Vec<char, 8> = checked.chars().collect();
+        for i in 0..glyphs.len() {
+            // Every changed glyph is caught
+            for glyph in DOLLCODE_CHAR_MAP.into_iter().filter(|&g| g != glyphs[i]) {
+                let mut changed = glyphs.clone();
+                changed[i] = glyph;
+                let changed: String<64> = changed.into_iter().collect();
+                assert!(verify_check_digit(&changed).is_err(), "{changed}");
+            }
+            // As is every swap of different neighbours
+            if i + 1 < glyphs.len() && glyphs[i] != glyphs[i + 1] {
+                let mut swapped = glyphs.clone();
+                swapped.swap(i, i + 1);
+                let swapped: String<64> = swapped.into_iter().collect();
+                assert!(verify_check_digit(&swapped).is_err(), "{swapped}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_checksum() {
+        // CRC-8/SMBUS check value
+        assert_eq!(crc8(b"123456789"), 0xF4);
+        assert_eq!(crc8(b""), 0);
+
+        let sequence = "▘▖▘▌\u{200D}▌▘▖▌\u{200D}";
+        let mut summed = String::<64>::new();
+        summed.push_str(sequence).unwrap();
+        for c in checksum(sequence) {
+            summed.push(c).unwrap();
+        }
+        assert_eq!(verify_checksum(&summed).unwrap(), sequence);
+
+        // A dropped delimiter is caught
+        let dropped: String<64> = summed
+            .chars()
+            .enumerate()
+            .filter(|&(i, _)| i != 4)
+            .map(|(_, c)| c)
+            .collect();
+        assert!(verify_checksum(&dropped).is_err());
+
+        let number = to_dollcode(42).unwrap();
+        let number: String<64> = number.as_chars().iter().collect();
+        let mut summed = number.clone();
+        for c in checksum(&number) {
+            summed.push(c).unwrap();
+        }
+        assert_eq!(verify_checksum(&summed).unwrap(), number);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            check_digit("▖x"),
+            Err(DollcodeError::InvalidChar('x', 1))
+        ));
+        assert!(matches!(
+            verify_check_digit(""),
+            Err(DollcodeError::InvalidInput)
+        ));
+        assert!(matches!(
+            verify_checksum("▖▖▖▖▖"),
+            Err(DollcodeError::InvalidInput)
+        ));
+        assert!(matches!(
+            verify_checksum("▖▖▖▖▖▖x"),
+            Err(DollcodeError::InvalidChar('x', 6))
+        ));
+    }
+}
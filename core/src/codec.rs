@@ -0,0 +1,319 @@
+//! Traits for types that convert to and from dollcode.
+//!
+//! [`ToDollcode`] writes a value's glyphs into a [`Sink`], and
+//! [`FromDollcode`] reads a value back from a string of glyphs. The built-in
+//! implementations use the crate's encodings:
+//!
+//! | Type | Encoding |
+//! |------|----------|
+//! | `u8` to `u64`, `usize`, [`Dollcode`] | a number, as [`to_dollcode`] |
+//! | `str`, `heapless::String<N>`, `String` | text, as [`TextIterator`] |
+//! | `[u8]`, `heapless::Vec<u8, N>`, `Vec<u8>` | binary data, as [`ByteEncoder`] |
+//!
+//! Implementing the traits for other types lets them be passed to anything
+//! generic over them. Sinks are implemented for `heapless::String<N>`,
+//! `heapless::Vec<char, N>` and `heapless::Vec<u8, N>`, which holds UTF-8,
+//! and with the `alloc` feature for `String` and `Vec<char>`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{FromDollcode, Result, Sink, ToDollcode};
+//! # fn main() -> Result<()> {
+//! struct Celsius(u8);
+//!
+//! impl ToDollcode for Celsius {
+//!     fn encode_dollcode(&self, sink: &mut impl Sink) -> Result<()> {
+//!         self.0.encode_dollcode(sink)
+//!     }
+//! }
+//!
+//! let mut encoded = heapless::String::<64>::new();
+//! Celsius(42).encode_dollcode(&mut encoded)?;
+//! assert_eq!(encoded, "▖▖▖▌");
+//! assert_eq!(u8::decode_dollcode(&encoded)?, 42);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    bytes::{ByteDecoder, ByteEncoder},
+    from_dollcode,
+    text::{TextDecoder, TextIterator},
+    to_dollcode, Dollcode, DollcodeError, Result, MAX_DOLLCODE_SIZE,
+};
+
+/// Destination for encoded glyphs
+pub trait Sink {
+    /// Appends one glyph or delimiter
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DollcodeError::Overflow`] if the sink is full.
+    fn push(&mut self, c: char) -> Result<()>;
+
+    /// Appends every character of `chars`, stopping at the first error
+    fn push_all(&mut self, chars: impl IntoIterator<Item = char>) -> Result<()> {
+        chars.into_iter().try_for_each(|c| self.push(c))
+    }
+}
+
+/// A type that can be written as dollcode
+pub trait ToDollcode {
+    /// Writes the glyphs of `self` into `sink`
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of the encoding, such as
+    /// [`DollcodeError::InvalidChar`] for text outside printable ASCII, and
+    /// [`DollcodeError::Overflow`] if the sink is full.
+    fn encode_dollcode(&self, sink: &mut impl Sink) -> Result<()>;
+}
+
+/// A type that can be read back from dollcode
+pub trait FromDollcode: Sized {
+    /// Decodes a value from `input`
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of the decoding, and [`DollcodeError::Overflow`]
+    /// if the value does not fit in `Self`.
+    fn decode_dollcode(input: &str) -> Result<Self>;
+}
+
+impl<const N: usize> Sink for heapless::String<N> {
+    fn push(&mut self, c: char) -> Result<()> {
+        heapless::String::push(self, c).map_err(|_| DollcodeError::Overflow)
+    }
+}
+
+impl<const N: usize> Sink for heapless::Vec<char, N> {
+    fn push(&mut self, c: char) -> Result<()> {
+        heapless::Vec::push(self, c).map_err(|_| DollcodeError::Overflow)
+    }
+}
+
+/// Appends the UTF-8 encoding of each character, so the bytes can be sent to
+/// a display or a serial port as they are. A character that does not fit is
+/// not written in part.
+impl<const N: usize> Sink for heapless::Vec<u8, N> {
+    fn push(&mut self, c: char) -> Result<()> {
+        let mut buf = [0u8; 4];
+        self.extend_from_slice(c.encode_utf8(&mut buf).as_bytes())
+            .map_err(|_| DollcodeError::Overflow)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Sink for alloc::string::String {
+    fn push(&mut self, c: char) -> Result<()> {
+        alloc::string::String::push(self, c);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Sink for alloc::vec::Vec<char> {
+    fn push(&mut self, c: char) -> Result<()> {
+        alloc::vec::Vec::push(self, c);
+        Ok(())
+    }
+}
+
+impl<T: ToDollcode + ?Sized> ToDollcode for &T {
+    fn encode_dollcode(&self, sink: &mut impl Sink) -> Result<()> {
+        (**self).encode_dollcode(sink)
+    }
+}
+
+impl ToDollcode for Dollcode {
+    fn encode_dollcode(&self, sink: &mut impl Sink) -> Result<()> {
+        sink.push_all(self.as_chars().iter().copied())
+    }
+}
+
+impl FromDollcode for Dollcode {
+    fn decode_dollcode(input: &str) -> Result<Self> {
+        to_dollcode(u64::decode_dollcode(input)?)
+    }
+}
+
+impl ToDollcode for u64 {
+    fn encode_dollcode(&self, sink: &mut impl Sink) -> Result<()> {
+        to_dollcode(*self)?.encode_dollcode(sink)
+    }
+}
+
+impl FromDollcode for u64 {
+    fn decode_dollcode(input: &str) -> Result<Self> {
+        let mut chars = heapless::Vec::<char, MAX_DOLLCODE_SIZE>::new();
+        for c in input.chars() {
+            chars.push(c).map_err(|_| DollcodeError::Overflow)?;
+        }
+        from_dollcode(&chars)
+    }
+}
+
+macro_rules! impl_number {
+    ($($t:ty),*) => {$(
+        impl ToDollcode for $t {
+            fn encode_dollcode(&self, sink: &mut impl Sink) -> Result<()> {
+                (*self as u64).encode_dollcode(sink)
+            }
+        }
+
+        impl FromDollcode for $t {
+            fn decode_dollcode(input: &str) -> Result<Self> {
+                Self::try_from(u64::decode_dollcode(input)?).map_err(|_| DollcodeError::Overflow)
+            }
+        }
+    )*};
+}
+
+impl_number!(u8, u16, u32, usize);
+
+impl ToDollcode for str {
+    fn encode_dollcode(&self, sink: &mut impl Sink) -> Result<()> {
+        for segment in TextIterator::new(self) {
+            sink.push_all(segment?.chars())?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> ToDollcode for heapless::String<N> {
+    fn encode_dollcode(&self, sink: &mut impl Sink) -> Result<()> {
+        self.as_str().encode_dollcode(sink)
+    }
+}
+
+impl<const N: usize> FromDollcode for heapless::String<N> {
+    fn decode_dollcode(input: &str) -> Result<Self> {
+        let mut text = Self::new();
+        for c in TextDecoder::new(input) {
+            text.push(c?).map_err(|_| DollcodeError::Overflow)?;
+        }
+        Ok(text)
+    }
+}
+
+impl ToDollcode for [u8] {
+    fn encode_dollcode(&self, sink: &mut impl Sink) -> Result<()> {
+        sink.push_all(ByteEncoder::new(self))
+    }
+}
+
+impl<const N: usize> ToDollcode for heapless::Vec<u8, N> {
+    fn encode_dollcode(&self, sink: &mut impl Sink) -> Result<()> {
+        self.as_slice().encode_dollcode(sink)
+    }
+}
+
+impl<const N: usize> FromDollcode for heapless::Vec<u8, N> {
+    fn decode_dollcode(input: &str) -> Result<Self> {
+        let mut bytes = Self::new();
+        for byte in ByteDecoder::new(input) {
+            bytes.push(byte?).map_err(|_| DollcodeError::Overflow)?;
+        }
+        Ok(bytes)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ToDollcode for std::string::String {
+    fn encode_dollcode(&self, sink: &mut impl Sink) -> Result<()> {
+        self.as_str().encode_dollcode(sink)
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromDollcode for std::string::String {
+    fn decode_dollcode(input: &str) -> Result<Self> {
+        TextDecoder::new(input).collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl ToDollcode for std::vec::Vec<u8> {
+    fn encode_dollcode(&self, sink: &mut impl Sink) -> Result<()> {
+        self.as_slice().encode_dollcode(sink)
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromDollcode for std::vec::Vec<u8> {
+    fn decode_dollcode(input: &str) -> Result<Self> {
+        ByteDecoder::new(input).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::{String, Vec};
+
+    fn encoded<T: ToDollcode + ?Sized>(value: &T) -> String<256> {
+        let mut out = String::new();
+        value.encode_dollcode(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(encoded(&42u8), "▖▖▖▌");
+        assert_eq!(encoded(&0u64), "");
+        assert_eq!(encoded(&to_dollcode(42).unwrap()), "▖▖▖▌");
+        assert_eq!(u64::decode_dollcode(&encoded(&u64::MAX)).unwrap(), u64::MAX);
+        assert_eq!(
+            Dollcode::decode_dollcode("▖▖▖▌").unwrap().as_chars(),
+            ['▖', '▖', '▖', '▌']
+        );
+
+        assert!(matches!(
+            u8::decode_dollcode(&encoded(&256u16)),
+            Err(DollcodeError::Overflow)
+        ));
+        assert!(matches!(
+            u64::decode_dollcode(&['▌'; 42].iter().collect::<String<128>>()),
+            Err(DollcodeError::Overflow)
+        ));
+        assert!(matches!(
+            u64::decode_dollcode("▖x"),
+            Err(DollcodeError::InvalidInput)
+        ));
+    }
+
+    #[test]
+    fn test_text_and_bytes() {
+        assert_eq!(encoded("Hi"), "▘▖▘▌\u{200d}▌▘▖▌\u{200d}");
+        assert_eq!(
+            String::<8>::decode_dollcode("▘▖▘▌\u{200d}▌▘▖▌\u{200d}").unwrap(),
+            "Hi"
+        );
+        assert_eq!(encoded(&[0x2Au8][..]), "▖▖▘▘▌▖");
+        assert_eq!(Vec::<u8, 2>::decode_dollcode("▖▖▘▘▌▖").unwrap(), [0x2A]);
+
+        assert!(matches!(
+            String::<1>::decode_dollcode("▘▖▘▌\u{200d}▌▘▖▌\u{200d}"),
+            Err(DollcodeError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_full_sink() {
+        let mut sink = Vec::<char, 3>::new();
+        assert!(matches!(
+            42u64.encode_dollcode(&mut sink),
+            Err(DollcodeError::Overflow)
+        ));
+        assert_eq!(sink, ['▖', '▖', '▖']);
+
+        // Whole characters only, three bytes each
+        let mut bytes = Vec::<u8, 8>::new();
+        assert!(matches!(
+            42u64.encode_dollcode(&mut bytes),
+            Err(DollcodeError::Overflow)
+        ));
+        assert_eq!(core::str::from_utf8(&bytes).unwrap(), "▖▖");
+    }
+}
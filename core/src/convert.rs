@@ -0,0 +1,303 @@
+//! Conversions between common textual bases and dollcode.
+//!
+//! Payloads in hexadecimal, base32 or base64 are re-encoded into the
+//! fixed-width [`bytes`](crate::bytes) encoding and back, one byte at a time,
+//! without collecting the decoded bytes first. [`from_base`] and [`to_base`]
+//! write into any [`Sink`]; with the `alloc` feature, functions such as
+//! [`from_base64`] and [`to_hex`] return new strings.
+//!
+//! | Base | Alphabet | Padding |
+//! |------|----------|---------|
+//! | [`Base::Hex`] | `0-9 a-f`, either case when decoding | none |
+//! | [`Base::Base32`] | RFC 4648 `A-Z 2-7`, either case when decoding | `=` to 8 characters |
+//! | [`Base::Base64`] | RFC 4648 `A-Z a-z 0-9 + /` | `=` to 4 characters |
+//!
+//! Padding is written when encoding and optional when decoding.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{convert::{from_base, to_base, Base}, Result};
+//! # fn main() -> Result<()> {
+//! let mut encoded = heapless::String::<64>::new();
+//! from_base(Base::Base64, "Kg==", &mut encoded)?;
+//! assert_eq!(encoded, "▖▖▘▘▌▖");
+//!
+//! let mut hex = heapless::String::<8>::new();
+//! to_base(Base::Hex, &encoded, &mut hex)?;
+//! assert_eq!(hex, "2a");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    bytes::{encode_byte, ByteDecoder},
+    DollcodeError, Result, Sink,
+};
+
+/// Character written to pad base32 and base64 output
+const PADDING: char = '=';
+
+/// A textual base
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base {
+    /// Hexadecimal, two lowercase digits per byte
+    Hex,
+    /// RFC 4648 base32
+    Base32,
+    /// RFC 4648 base64 with the standard alphabet
+    Base64,
+}
+
+impl Base {
+    /// Characters for each digit value
+    const fn alphabet(self) -> &'static [u8] {
+        match self {
+            Self::Hex => b"0123456789abcdef",
+            Self::Base32 => b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+            Self::Base64 => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+        }
+    }
+
+    /// Number of bits carried by each character
+    const fn bits(self) -> u32 {
+        match self {
+            Self::Hex => 4,
+            Self::Base32 => 5,
+            Self::Base64 => 6,
+        }
+    }
+
+    /// Number of characters padded output is a multiple of
+    const fn block(self) -> usize {
+        match self {
+            Self::Hex => 1,
+            Self::Base32 => 8,
+            Self::Base64 => 4,
+        }
+    }
+
+    /// Returns the digit value of `c`
+    fn value(self, c: char) -> Option<u32> {
+        match self {
+            Self::Hex => c.to_digit(16),
+            Self::Base32 => match c {
+                'A'..='Z' => Some(c as u32 - 'A' as u32),
+                'a'..='z' => Some(c as u32 - 'a' as u32),
+                '2'..='7' => Some(c as u32 - '2' as u32 + 26),
+                _ => None,
+            },
+            Self::Base64 => match c {
+                'A'..='Z' => Some(c as u32 - 'A' as u32),
+                'a'..='z' => Some(c as u32 - 'a' as u32 + 26),
+                '0'..='9' => Some(c as u32 - '0' as u32 + 52),
+                '+' => Some(62),
+                '/' => Some(63),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Converts `input` in `base` to dollcode bytes, writing the glyphs into
+/// `sink`.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidChar`] for characters outside the base's
+/// alphabet, [`DollcodeError::InvalidInput`] for a truncated final
+/// character, unused bits that are not zero or misplaced padding, and
+/// [`DollcodeError::Overflow`] if the sink is full. Glyphs of the bytes
+/// before the error have already been written.
+pub fn from_base(base: Base, input: &str, sink: &mut impl Sink) -> Result<()> {
+    let bits = base.bits();
+    let (mut acc, mut pending) = (0u32, 0u32);
+    let mut chars = input.chars().enumerate();
+
+    for (position, c) in chars.by_ref() {
+        if c == PADDING && base.block() > 1 {
+            // Only padding may follow, up to the end of the block
+            let mut len = position + 1;
+            for (_, c) in chars.by_ref() {
+                if c != PADDING {
+                    return Err(DollcodeError::InvalidInput);
+                }
+                len += 1;
+            }
+            if len != position.div_ceil(base.block()) * base.block() {
+                return Err(DollcodeError::InvalidInput);
+            }
+            break;
+        }
+        let value = base
+            .value(c)
+            .ok_or(DollcodeError::InvalidChar(c, position))?;
+        acc = (acc << bits | value) & 0xFFFF;
+        pending += bits;
+        if pending >= 8 {
+            pending -= 8;
+            sink.push_all(encode_byte((acc >> pending) as u8))?;
+        }
+    }
+
+    if pending >= bits || acc & ((1 << pending) - 1) != 0 {
+        return Err(DollcodeError::InvalidInput);
+    }
+    Ok(())
+}
+
+/// Converts dollcode bytes in `input` to `base`, writing the characters into
+/// `sink`.
+///
+/// # Errors
+///
+/// Returns the errors of [`ByteDecoder`], and [`DollcodeError::Overflow`] if
+/// the sink is full.
+pub fn to_base(base: Base, input: &str, sink: &mut impl Sink) -> Result<()> {
+    let bits = base.bits();
+    let mask = (1 << bits) - 1;
+    let digit = |value: u32| char::from(base.alphabet()[(value & mask) as usize]);
+    let (mut acc, mut pending, mut written) = (0u32, 0u32, 0usize);
+
+    for byte in ByteDecoder::new(input) {
+        acc = (acc << 8 | u32::from(byte?)) & 0xFFFF;
+        pending += 8;
+        while pending >= bits {
+            pending -= bits;
+            sink.push(digit(acc >> pending))?;
+            written += 1;
+        }
+    }
+
+    if pending > 0 {
+        sink.push(digit(acc << (bits - pending)))?;
+        written += 1;
+    }
+    while written % base.block() != 0 {
+        sink.push(PADDING)?;
+        written += 1;
+    }
+    Ok(())
+}
+
+/// Defines the owned-string conversions of a base
+macro_rules! owned_conversions {
+    ($($base:ident, $name:literal, $from:ident, $to:ident;)*) => {$(
+        #[doc = concat!("Converts ", $name, " to dollcode bytes in a new string.")]
+        ///
+        /// # Errors
+        ///
+        /// Returns the errors of [`from_base`].
+        #[cfg(feature = "alloc")]
+        pub fn $from(input: &str) -> Result<alloc::string::String> {
+            let mut encoded = alloc::string::String::new();
+            from_base(Base::$base, input, &mut encoded)?;
+            Ok(encoded)
+        }
+
+        #[doc = concat!("Converts dollcode bytes to ", $name, " in a new string.")]
+        ///
+        /// # Errors
+        ///
+        /// Returns the errors of [`to_base`].
+        #[cfg(feature = "alloc")]
+        pub fn $to(input: &str) -> Result<alloc::string::String> {
+            let mut converted = alloc::string::String::new();
+            to_base(Base::$base, input, &mut converted)?;
+            Ok(converted)
+        }
+    )*};
+}
+
+owned_conversions! {
+    Hex, "hexadecimal", from_hex, to_hex;
+    Base32, "base32", from_base32, to_base32;
+    Base64, "base64", from_base64, to_base64;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::String;
+
+    fn from(base: Base, input: &str) -> Result<String<512>> {
+        let mut encoded = String::new();
+        from_base(base, input, &mut encoded)?;
+        Ok(encoded)
+    }
+
+    fn to(base: Base, input: &str) -> Result<String<64>> {
+        let mut converted = String::new();
+        to_base(base, input, &mut converted)?;
+        Ok(converted)
+    }
+
+    #[test]
+    fn test_rfc4648_vectors() {
+        // "", "f", "fo", "foo", "foob", "fooba", "foobar"
+        let cases = [
+            ("", "", ""),
+            ("66", "MY======", "Zg=="),
+            ("666f", "MZXQ====", "Zm8="),
+            ("666f6f", "MZXW6===", "Zm9v"),
+            ("666f6f62", "MZXW6YQ=", "Zm9vYg=="),
+            ("666f6f6261", "MZXW6YTB", "Zm9vYmE="),
+            ("666f6f626172", "MZXW6YTBOI======", "Zm9vYmFy"),
+        ];
+        for (hex, base32, base64) in cases {
+            let encoded = from(Base::Hex, hex).unwrap();
+            assert_eq!(from(Base::Base32, base32).unwrap(), encoded);
+            assert_eq!(from(Base::Base64, base64).unwrap(), encoded);
+
+            assert_eq!(to(Base::Hex, &encoded).unwrap(), hex);
+            assert_eq!(to(Base::Base32, &encoded).unwrap(), base32);
+            assert_eq!(to(Base::Base64, &encoded).unwrap(), base64);
+        }
+    }
+
+    #[test]
+    fn test_lenient_decoding() {
+        let encoded = from(Base::Hex, "2a").unwrap();
+        assert_eq!(encoded, "▖▖▘▘▌▖");
+        assert_eq!(from(Base::Hex, "2A").unwrap(), encoded);
+        assert_eq!(from(Base::Base32, "fi").unwrap(), encoded);
+        assert_eq!(from(Base::Base64, "Kg").unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            from(Base::Hex, "2g"),
+            Err(DollcodeError::InvalidChar('g', 1))
+        ));
+        assert!(matches!(
+            from(Base::Hex, "2a=="),
+            Err(DollcodeError::InvalidChar('=', 2))
+        ));
+        for (base, input) in [
+            (Base::Hex, "2"),
+            (Base::Base64, "K"),
+            (Base::Base64, "Kh=="),
+            (Base::Base64, "Kg="),
+            (Base::Base64, "Kg==Kg=="),
+            (Base::Base64, "Kg==x"),
+            (Base::Base64, "Zm9v===="),
+            (Base::Base32, "FI====="),
+        ] {
+            assert!(
+                matches!(from(base, input), Err(DollcodeError::InvalidInput)),
+                "{input}"
+            );
+        }
+
+        assert!(matches!(
+            to(Base::Hex, "▖▖▘"),
+            Err(DollcodeError::InvalidInput)
+        ));
+        let mut small = String::<6>::new();
+        assert!(matches!(
+            from_base(Base::Hex, "2a2a", &mut small),
+            Err(DollcodeError::Overflow)
+        ));
+    }
+}
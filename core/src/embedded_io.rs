@@ -0,0 +1,462 @@
+//! Stream adapters over the `embedded-io` traits.
+//!
+//! [`TextEncoder`] wraps a writer and encodes the printable ASCII written to
+//! it, and [`TextDecoder`] wraps a reader and decodes the dollcode read from
+//! it, so firmware can talk to a UART in dollcode with its usual IO code.
+//! Line breaks are passed through in both directions, keeping commands and
+//! replies on their own lines. Numbers are sent as frames, each followed by
+//! [`DELIMITER`], with [`write_frame`] and [`read_frame`].
+//!
+//! With the `embedded-io-async` feature the adapters also implement the
+//! async traits, and [`write_frame_async`] and [`read_frame_async`] are
+//! available.
+//!
+//! Nothing is allocated; each adapter holds a buffer of [`CHUNK_SIZE`] bytes.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use dollcode::embedded_io::{read_frame, write_frame, TextDecoder, TextEncoder};
+//! use embedded_io::{Read, Write};
+//!
+//! let mut uart = [0u8; 64];
+//! let mut tx = TextEncoder::new(&mut uart[..]);
+//! tx.write_all(b"Hi\n").unwrap();
+//!
+//! let mut rx = TextDecoder::new(&uart[..]);
+//! let mut command = [0u8; 3];
+//! rx.read_exact(&mut command).unwrap();
+//! assert_eq!(&command, b"Hi\n");
+//!
+//! let mut uart = [0u8; 32];
+//! write_frame(&mut &mut uart[..], 42).unwrap();
+//! assert_eq!(read_frame(&mut &uart[..]).unwrap(), Some(42));
+//! ```
+
+use crate::{
+    stream::{encode_text_byte, FrameDecoder, TextBytes},
+    text::DELIMITER,
+    to_dollcode, DollcodeError, Sink,
+};
+use ::embedded_io::{ErrorKind, ErrorType, Read, Write};
+use core::fmt;
+
+/// Size of the buffer each adapter holds
+pub const CHUNK_SIZE: usize = 64;
+
+/// UTF-8 bytes of the longest segment: five glyphs and the delimiter
+const SEGMENT_BYTES: usize = 18;
+
+/// UTF-8 bytes of the longest frame
+const FRAME_BYTES: usize = crate::MAX_DOLLCODE_SIZE * 3 + 3;
+
+/// Failure of an adapter
+#[derive(Debug)]
+pub enum Error<E> {
+    /// The data could not be encoded or decoded
+    Dollcode(DollcodeError),
+    /// The wrapped reader or writer failed
+    Io(E),
+}
+
+impl<E> From<DollcodeError> for Error<E> {
+    fn from(e: DollcodeError) -> Self {
+        Self::Dollcode(e)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Dollcode(e) => e.fmt(f),
+            Self::Io(e) => e.fmt(f),
+        }
+    }
+}
+
+impl<E: ::embedded_io::Error> ::embedded_io::Error for Error<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Dollcode(_) => ErrorKind::InvalidData,
+            Self::Io(e) => e.kind(),
+        }
+    }
+}
+
+/// Wraps a writer, encoding printable ASCII written to it as dollcode text
+#[derive(Debug)]
+pub struct TextEncoder<W> {
+    inner: W,
+    buf: heapless::Vec<u8, CHUNK_SIZE>,
+    /// Bytes encoded so far, for error positions
+    position: usize,
+}
+
+impl<W> TextEncoder<W> {
+    /// Wraps `inner`
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            buf: heapless::Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Returns the wrapped writer
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Encodes a prefix of `input` into the buffer, returning its length
+    fn encode(&mut self, input: &[u8]) -> Result<usize, DollcodeError> {
+        self.buf.clear();
+        let mut encoded = 0;
+        for &byte in input {
+            if self.buf.len() + SEGMENT_BYTES > CHUNK_SIZE {
+                break;
+            }
+            if !encode_text_byte(byte, &mut self.buf)? {
+                // Report the bad byte once what precedes it is written
+                if encoded > 0 {
+                    break;
+                }
+                let c = char::from(byte);
+                return Err(DollcodeError::InvalidChar(c, self.position));
+            }
+            encoded += 1;
+        }
+        self.position += encoded;
+        Ok(encoded)
+    }
+}
+
+impl<W: ErrorType> ErrorType for TextEncoder<W> {
+    type Error = Error<W::Error>;
+}
+
+impl<W: Write> Write for TextEncoder<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        let encoded = self.encode(buf)?;
+        self.inner.write_all(&self.buf).map_err(Error::Io)?;
+        Ok(encoded)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.inner.flush().map_err(Error::Io)
+    }
+}
+
+/// Wraps a reader, decoding the dollcode text read from it
+#[derive(Debug)]
+pub struct TextDecoder<R> {
+    inner: R,
+    input: [u8; CHUNK_SIZE],
+    start: usize,
+    end: usize,
+    decoder: TextBytes,
+}
+
+impl<R> TextDecoder<R> {
+    /// Wraps `inner`
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            input: [0; CHUNK_SIZE],
+            start: 0,
+            end: 0,
+            decoder: TextBytes::default(),
+        }
+    }
+
+    /// Returns the wrapped reader, dropping any input read but not decoded
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Decodes buffered input into `buf`, returning the bytes written
+    fn drain(&mut self, buf: &mut [u8]) -> Result<usize, DollcodeError> {
+        let input = &self.input[self.start..self.end];
+        let (consumed, written) = self.decoder.decode(input, buf)?;
+        self.start += consumed;
+        Ok(written)
+    }
+}
+
+impl<R: ErrorType> ErrorType for TextDecoder<R> {
+    type Error = Error<R::Error>;
+}
+
+impl<R: Read> Read for TextDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        loop {
+            let len = self.drain(buf)?;
+            if len > 0 || buf.is_empty() {
+                return Ok(len);
+            }
+            let read = self.inner.read(&mut self.input).map_err(Error::Io)?;
+            if read == 0 {
+                return Ok(self.decoder.finish(buf)?);
+            }
+            (self.start, self.end) = (0, read);
+        }
+    }
+}
+
+/// Encodes `value` as a frame
+fn frame(value: u64) -> Result<heapless::Vec<u8, FRAME_BYTES>, DollcodeError> {
+    let mut frame = heapless::Vec::new();
+    frame.push_all(to_dollcode(value)?.as_chars().iter().copied())?;
+    Sink::push(&mut frame, DELIMITER)?;
+    Ok(frame)
+}
+
+/// Writes `value` as a frame: its glyphs followed by [`DELIMITER`]
+///
+/// # Errors
+///
+/// Returns [`Error::Io`] if the writer fails.
+pub fn write_frame<W: Write>(writer: &mut W, value: u64) -> Result<(), Error<W::Error>> {
+    writer.write_all(&frame(value)?).map_err(Error::Io)
+}
+
+/// Reads the next frame, or `None` at the end of the stream.
+///
+/// Whitespace between frames is skipped. Bytes are read one at a time, so
+/// the reader is left right after the frame.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidChar`] for characters other than glyphs,
+/// [`DollcodeError::Overflow`] for values past `u64::MAX`,
+/// [`DollcodeError::InvalidInput`] if the stream ends inside a frame, and
+/// [`Error::Io`] if the reader fails.
+pub fn read_frame<R: Read>(reader: &mut R) -> Result<Option<u64>, Error<R::Error>> {
+    let mut decoder = FrameDecoder::default();
+    let mut byte = [0u8];
+    loop {
+        if reader.read(&mut byte).map_err(Error::Io)? == 0 {
+            return Ok(decoder.finish()?);
+        }
+        if let Some(value) = decoder.push(byte[0])? {
+            return Ok(Some(value));
+        }
+    }
+}
+
+#[cfg(feature = "embedded-io-async")]
+mod asynch {
+    use super::*;
+    use ::embedded_io_async::{Read as AsyncRead, Write as AsyncWrite};
+
+    impl<W: AsyncWrite> AsyncWrite for TextEncoder<W> {
+        async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            let encoded = self.encode(buf)?;
+            self.inner.write_all(&self.buf).await.map_err(Error::Io)?;
+            Ok(encoded)
+        }
+
+        async fn flush(&mut self) -> Result<(), Self::Error> {
+            self.inner.flush().await.map_err(Error::Io)
+        }
+    }
+
+    impl<R: AsyncRead> AsyncRead for TextDecoder<R> {
+        async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            loop {
+                let len = self.drain(buf)?;
+                if len > 0 || buf.is_empty() {
+                    return Ok(len);
+                }
+                let read = self.inner.read(&mut self.input).await.map_err(Error::Io)?;
+                if read == 0 {
+                    return Ok(self.decoder.finish(buf)?);
+                }
+                (self.start, self.end) = (0, read);
+            }
+        }
+    }
+
+    /// Async counterpart of [`write_frame`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the writer fails.
+    pub async fn write_frame_async<W: AsyncWrite>(
+        writer: &mut W,
+        value: u64,
+    ) -> Result<(), Error<W::Error>> {
+        writer.write_all(&frame(value)?).await.map_err(Error::Io)
+    }
+
+    /// Async counterpart of [`read_frame`]
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`read_frame`].
+    pub async fn read_frame_async<R: AsyncRead>(
+        reader: &mut R,
+    ) -> Result<Option<u64>, Error<R::Error>> {
+        let mut decoder = FrameDecoder::default();
+        let mut byte = [0u8];
+        loop {
+            if reader.read(&mut byte).await.map_err(Error::Io)? == 0 {
+                return Ok(decoder.finish()?);
+            }
+            if let Some(value) = decoder.push(byte[0])? {
+                return Ok(Some(value));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "embedded-io-async")]
+pub use asynch::{read_frame_async, write_frame_async};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes `input` into `uart`, returning the bytes written
+    fn transmit(input: &[u8], uart: &mut [u8]) -> usize {
+        let capacity = uart.len();
+        let mut remaining = &mut uart[..];
+        let mut tx = TextEncoder::new(&mut remaining);
+        tx.write_all(input).unwrap();
+        capacity - remaining.len()
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let mut uart = [0u8; 512];
+        let len = transmit(b"LED on\r\nreply: ok\n", &mut uart);
+        let encoded = core::str::from_utf8(&uart[..len]).unwrap();
+        assert!(encoded.contains("\r\n"));
+        assert_eq!(encoded.matches(DELIMITER).count(), 15);
+
+        let mut rx = TextDecoder::new(&uart[..len]);
+        let mut decoded = heapless::Vec::<u8, 32>::new();
+        let mut buf = [0u8; 4];
+        loop {
+            match rx.read(&mut buf).unwrap() {
+                0 => break,
+                n => decoded.extend_from_slice(&buf[..n]).unwrap(),
+            }
+        }
+        assert_eq!(&decoded[..], b"LED on\r\nreply: ok\n");
+    }
+
+    #[test]
+    fn test_small_reads() {
+        let mut uart = [0u8; 64];
+        let len = transmit(b"A\nB", &mut uart);
+        let mut rx = TextDecoder::new(&uart[..len]);
+        let mut byte = [0u8];
+        for expected in *b"A\nB" {
+            assert_eq!(rx.read(&mut byte).unwrap(), 1);
+            assert_eq!(byte[0], expected);
+        }
+        assert_eq!(rx.read(&mut byte).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_text_errors() {
+        let mut uart = [0u8; 64];
+        let mut tx = TextEncoder::new(&mut uart[..]);
+        assert_eq!(tx.write(b"a\tb").unwrap(), 1);
+        assert!(matches!(
+            tx.write(b"\tb"),
+            Err(Error::Dollcode(DollcodeError::InvalidChar('\t', 1)))
+        ));
+
+        let mut rx = TextDecoder::new("▖x".as_bytes());
+        let mut buf = [0u8; 4];
+        assert!(matches!(
+            rx.read(&mut buf),
+            Err(Error::Dollcode(DollcodeError::InvalidChar('x', _)))
+        ));
+
+        // A glyph cut short by the end of the stream
+        let mut rx = TextDecoder::new(&"▖".as_bytes()[..2]);
+        assert!(matches!(
+            rx.read(&mut buf),
+            Err(Error::Dollcode(DollcodeError::InvalidInput))
+        ));
+    }
+
+    #[test]
+    fn test_frames() {
+        let mut uart = [0u8; 256];
+        let capacity = uart.len();
+        let mut tx = &mut uart[..];
+        for value in [42, 0, u64::MAX] {
+            write_frame(&mut tx, value).unwrap();
+        }
+        let len = capacity - tx.len();
+
+        let mut rx = &uart[..len];
+        assert_eq!(read_frame(&mut rx).unwrap(), Some(42));
+        assert_eq!(read_frame(&mut rx).unwrap(), Some(0));
+        assert_eq!(read_frame(&mut rx).unwrap(), Some(u64::MAX));
+        assert_eq!(read_frame(&mut rx).unwrap(), None);
+
+        let mut rx = "\r\n▖▖▖▌\u{200D}\n".as_bytes();
+        assert_eq!(read_frame(&mut rx).unwrap(), Some(42));
+        assert_eq!(read_frame(&mut rx).unwrap(), None);
+    }
+
+    #[test]
+    fn test_frame_errors() {
+        assert!(matches!(
+            read_frame(&mut "▖▖".as_bytes()),
+            Err(Error::Dollcode(DollcodeError::InvalidInput))
+        ));
+        assert!(matches!(
+            read_frame(&mut "▖7\u{200D}".as_bytes()),
+            Err(Error::Dollcode(DollcodeError::InvalidChar('7', 1)))
+        ));
+        let mut too_long = heapless::String::<256>::new();
+        for _ in 0..41 {
+            too_long.push('▌').unwrap();
+        }
+        too_long.push(DELIMITER).unwrap();
+        assert!(matches!(
+            read_frame(&mut too_long.as_bytes()),
+            Err(Error::Dollcode(DollcodeError::Overflow))
+        ));
+    }
+
+    #[cfg(feature = "embedded-io-async")]
+    #[test]
+    fn test_async() {
+        use ::embedded_io_async::Read as AsyncRead;
+        use core::{
+            future::Future,
+            pin::pin,
+            task::{Context, Poll, Waker},
+        };
+
+        /// Polls a future that never waits
+        fn block_on<F: Future>(future: F) -> F::Output {
+            let mut context = Context::from_waker(Waker::noop());
+            match pin!(future).poll(&mut context) {
+                Poll::Ready(output) => output,
+                Poll::Pending => unreachable!(),
+            }
+        }
+
+        let mut uart = [0u8; 64];
+        let capacity = uart.len();
+        let mut tx = &mut uart[..];
+        block_on(write_frame_async(&mut tx, 42)).unwrap();
+        let len = capacity - tx.len();
+        assert_eq!(
+            block_on(read_frame_async(&mut &uart[..len])).unwrap(),
+            Some(42)
+        );
+
+        let mut rx = TextDecoder::new("▘▖▘▌\u{200D}▌▘▖▌\u{200D}".as_bytes());
+        let mut buf = [0u8; 4];
+        assert_eq!(block_on(AsyncRead::read(&mut rx, &mut buf)).unwrap(), 2);
+        assert_eq!(&buf[..2], b"Hi");
+    }
+}
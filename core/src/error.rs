@@ -7,17 +7,17 @@
 //! # Examples
 //!
 //! ```rust
-//! #[test]
-//! fn test_invalid_decode() {
-//!    let result = dollcode::from_dollcode(&['A', 'B', 'C']);
-//!    assert!(matches!(result, Err(DollcodeError::InvalidInput)));
-//!}
-//!```
+//! # use dollcode::DollcodeError;
+//! let result = dollcode::from_dollcode(&['A', 'B', 'C']);
+//! assert!(matches!(result, Err(DollcodeError::InvalidInput)));
+//! ```
 
 use core::fmt;
-use owo_colors::OwoColorize;
 
 /// Errors that can occur during dollcode operations
+///
+/// Displayed as plain text, so messages stay readable in logs and pipes;
+/// coloring them is up to the application.
 #[derive(Debug)]
 pub enum DollcodeError {
     /// Input validation failed due to invalid characters or sequence
@@ -47,17 +47,18 @@ pub enum DollcodeError {
 impl fmt::Display for DollcodeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::InvalidInput => write!(f, "{}", "Invalid dollcode sequence".purple()),
+            Self::InvalidInput => write!(f, "Invalid dollcode sequence"),
             Self::InvalidChar(c, pos) => {
-                write!(f, "{}", "Invalid character".purple())?;
-                write!(f, ": '{}'", c)?;
-                write!(f, " at position {}", pos)
+                write!(f, "Invalid character: '{}' at position {}", c, pos)
             }
-            Self::Overflow => write!(f, "{}", "Value overflow".red()),
+            Self::Overflow => write!(f, "Value overflow"),
         }
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for DollcodeError {}
+
 /// Result type specialized for dollcode operations
 pub type Result<T> = core::result::Result<T, DollcodeError>;
 
@@ -95,4 +96,18 @@ mod tests {
         let _ = write!(s, "{}", err);
         assert!(!s.is_empty());
     }
+
+    #[test]
+    fn test_error_display_plain() {
+        let mut s: String<64> = String::new();
+        let _ = write!(s, "{}", DollcodeError::InvalidChar('!', 3));
+        assert_eq!(s, "Invalid character: '!' at position 3");
+
+        // No terminal escape codes
+        for err in [DollcodeError::InvalidInput, DollcodeError::Overflow] {
+            s.clear();
+            let _ = write!(s, "{}", err);
+            assert!(!s.contains('\x1b'));
+        }
+    }
 }
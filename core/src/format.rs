@@ -0,0 +1,317 @@
+//! Pretty-printing dollcode for people to read.
+//!
+//! [`pretty`] writes glyphs in groups separated by spaces, wrapped into
+//! lines of whole groups, optionally each line preceded by a gutter with the
+//! offset of its first glyph, as hex dumps do:
+//!
+//! ```text
+//!  0: ▖▖▖▌ ▘▘▘▘ ▖▖▖▖
+//! 12: ▌▌▌▌ ▘▖
+//! ```
+//!
+//! Delimiters take no space and are written after the glyph they follow.
+//! [`strip`] removes the formatting again, so printed output can be decoded
+//! after being copied back.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{format::{pretty, strip, PrettyOptions}, from_dollcode, Result};
+//! # fn main() -> Result<()> {
+//! let options = PrettyOptions {
+//!     group: 4,
+//!     wrap: 9,
+//!     gutter: true,
+//! };
+//! let mut printed = heapless::String::<256>::new();
+//! pretty(&u64::MAX, &options, &mut printed)?;
+//! assert!(printed.starts_with(" 0: ▖▖▖▖ ▘▘▖▘\n 8: ▌▘▘▖ ▘▘▖▖\n16: "));
+//!
+//! let mut glyphs = heapless::Vec::<char, 64>::new();
+//! strip(&printed, &mut glyphs)?;
+//! assert_eq!(from_dollcode(&glyphs)?, u64::MAX);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{digit_value, text::DELIMITER, DollcodeError, Result, Sink, ToDollcode};
+use core::fmt::{self, Write};
+
+/// Grouping, wrapping and gutter of [`pretty`] output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrettyOptions {
+    /// Glyphs per group, or 0 for no groups
+    pub group: usize,
+    /// Most columns of glyphs and spaces per line, gutter excluded, or 0 for
+    /// one line
+    ///
+    /// Lines hold at least one group, so the width of a group is the
+    /// narrowest wrapping.
+    pub wrap: usize,
+    /// Writes the offset of the first glyph of each line before it
+    pub gutter: bool,
+}
+
+impl PrettyOptions {
+    /// Groups of 4 in lines of 60 columns, without gutter
+    pub const DEFAULT: Self = Self {
+        group: 4,
+        wrap: 60,
+        gutter: false,
+    };
+
+    /// Returns the glyphs per line, or 0 for one line
+    const fn line_glyphs(&self) -> usize {
+        match (self.group, self.wrap) {
+            (_, 0) => 0,
+            (0, wrap) => wrap,
+            (group, wrap) => {
+                let groups = (wrap + 1) / (group + 1);
+                group * if groups == 0 { 1 } else { groups }
+            }
+        }
+    }
+}
+
+impl Default for PrettyOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Counts the glyphs of a sequence
+struct Count(usize);
+
+impl Sink for Count {
+    fn push(&mut self, c: char) -> Result<()> {
+        if c != DELIMITER {
+            self.0 += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Sink writing glyphs with the formatting of [`pretty`]
+struct Pretty<'a, W: ?Sized> {
+    out: &'a mut W,
+    group: usize,
+    line_glyphs: usize,
+    /// Width of the gutter, if written
+    gutter: Option<usize>,
+    glyphs: usize,
+    position: usize,
+}
+
+impl<W: Write + ?Sized> Pretty<'_, W> {
+    /// Writes what goes before the next glyph
+    fn separate(&mut self) -> fmt::Result {
+        let glyphs = self.glyphs;
+        if glyphs > 0 && self.line_glyphs > 0 && glyphs.is_multiple_of(self.line_glyphs) {
+            self.out.write_char('\n')?;
+        } else if glyphs > 0 {
+            if self.group > 0 && glyphs.is_multiple_of(self.group) {
+                self.out.write_char(' ')?;
+            }
+            return Ok(());
+        }
+        match self.gutter {
+            Some(width) => write!(self.out, "{glyphs:>width$}: "),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<W: Write + ?Sized> Sink for Pretty<'_, W> {
+    fn push(&mut self, c: char) -> Result<()> {
+        let position = self.position;
+        self.position += 1;
+        if c != DELIMITER {
+            digit_value(c).ok_or(DollcodeError::InvalidChar(c, position))?;
+            self.separate().map_err(|_| DollcodeError::Overflow)?;
+            self.glyphs += 1;
+        }
+        self.out.write_char(c).map_err(|_| DollcodeError::Overflow)
+    }
+}
+
+/// Writes `sequence` to `out` grouped and wrapped as `options` set.
+///
+/// Lines are separated by newlines, without trailing spaces. With a
+/// gutter, `sequence` is encoded twice: once to size the gutter and once to
+/// write it.
+///
+/// # Errors
+///
+/// Returns the errors of encoding `sequence`, and
+/// [`DollcodeError::Overflow`] if `out` fails.
+pub fn pretty<T, W>(sequence: &T, options: &PrettyOptions, out: &mut W) -> Result<()>
+where
+    T: ToDollcode + ?Sized,
+    W: Write + ?Sized,
+{
+    let line_glyphs = options.line_glyphs();
+    let gutter = if options.gutter {
+        let mut count = Count(0);
+        sequence.encode_dollcode(&mut count)?;
+        let last = match line_glyphs {
+            0 => 0,
+            line_glyphs => count.0.saturating_sub(1) / line_glyphs * line_glyphs,
+        };
+        Some(
+            last.checked_ilog10()
+                .map_or(1, |digits| digits as usize + 1),
+        )
+    } else {
+        None
+    };
+    sequence.encode_dollcode(&mut Pretty {
+        out,
+        group: options.group,
+        line_glyphs,
+        gutter,
+        glyphs: 0,
+        position: 0,
+    })
+}
+
+/// Writes the glyphs and delimiters of formatted `input` to `sink`,
+/// skipping whitespace and the gutter at the start of each line.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidChar`] for other characters, with their
+/// position in `input`, and the errors of `sink`.
+pub fn strip(input: &str, sink: &mut impl Sink) -> Result<()> {
+    let mut position = 0;
+    for line in input.split_inclusive('\n') {
+        // A gutter is digits and a colon after any indentation
+        let body = line.trim_start();
+        let digits = body.len() - body.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let gutter = if digits > 0 && body[digits..].starts_with(':') {
+            line.len() - body.len() + digits + 1
+        } else {
+            0
+        };
+        position += line[..gutter].chars().count();
+        for c in line[gutter..].chars() {
+            if !c.is_whitespace() {
+                if c != DELIMITER && digit_value(c).is_none() {
+                    return Err(DollcodeError::InvalidChar(c, position));
+                }
+                sink.push(c)?;
+            }
+            position += 1;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::Encoded;
+    use heapless::String;
+
+    fn printed<T: ToDollcode + ?Sized>(sequence: &T, options: &PrettyOptions) -> String<512> {
+        let mut out = String::new();
+        pretty(sequence, options, &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_grouping() {
+        let glyphs = Encoded("▖▘▌▖▘▌▖▘▌▖");
+        let options = PrettyOptions {
+            group: 3,
+            wrap: 0,
+            gutter: false,
+        };
+        assert_eq!(printed(&glyphs, &options), "▖▘▌ ▖▘▌ ▖▘▌ ▖");
+
+        let options = PrettyOptions { wrap: 8, ..options };
+        assert_eq!(printed(&glyphs, &options), "▖▘▌ ▖▘▌\n▖▘▌ ▖");
+
+        // Narrower than a group, and without groups
+        let options = PrettyOptions { wrap: 2, ..options };
+        assert_eq!(printed(&glyphs, &options), "▖▘▌\n▖▘▌\n▖▘▌\n▖");
+        let options = PrettyOptions {
+            group: 0,
+            ..options
+        };
+        assert_eq!(printed(&glyphs, &options), "▖▘\n▌▖\n▘▌\n▖▘\n▌▖");
+
+        // Delimiters follow their glyph
+        let options = PrettyOptions {
+            group: 2,
+            ..PrettyOptions::DEFAULT
+        };
+        assert_eq!(
+            printed(&Encoded("▖▘\u{200D}▌\u{200D}"), &options),
+            "▖▘\u{200D} ▌\u{200D}"
+        );
+        assert_eq!(printed(&Encoded(""), &options), "");
+    }
+
+    #[test]
+    fn test_gutter() {
+        let options = PrettyOptions {
+            group: 2,
+            wrap: 5,
+            gutter: true,
+        };
+        let glyphs = Encoded("▖▖▘▘▌▌▖▖▘▘▌▌▖▖▘▘▌▌▖▖▘");
+        assert_eq!(
+            printed(&glyphs, &options),
+            " 0: ▖▖ ▘▘\n 4: ▌▌ ▖▖\n 8: ▘▘ ▌▌\n12: ▖▖ ▘▘\n16: ▌▌ ▖▖\n20: ▘"
+        );
+
+        let options = PrettyOptions { wrap: 0, ..options };
+        assert_eq!(printed(&Encoded("▖▘▌"), &options), "0: ▖▘ ▌");
+        // Exactly full lines
+        let options = PrettyOptions { wrap: 5, ..options };
+        assert_eq!(
+            printed(&Encoded("▖▘▌▖▘▌▖▘▌▖▘▌▖▘▌▖▘▌▖▘"), &options),
+            " 0: ▖▘ ▌▖\n 4: ▘▌ ▖▘\n 8: ▌▖ ▘▌\n12: ▖▘ ▌▖\n16: ▘▌ ▖▘"
+        );
+    }
+
+    #[test]
+    fn test_strip() {
+        let options = PrettyOptions {
+            group: 3,
+            wrap: 7,
+            gutter: true,
+        };
+        let glyphs = Encoded("▖▘▌\u{200D}▖▘▌▖▘▌▖▘▌▖\u{200D}");
+        let mut stripped = String::<64>::new();
+        strip(&printed(&glyphs, &options), &mut stripped).unwrap();
+        assert_eq!(stripped, glyphs.0);
+
+        stripped.clear();
+        strip("  4: ▖▘\r\n\t12:▌ ▖", &mut stripped).unwrap();
+        assert_eq!(stripped, "▖▘▌▖");
+
+        // Digits are a gutter only at the start of a line
+        assert!(matches!(
+            strip("▖ 12: ▘", &mut stripped),
+            Err(DollcodeError::InvalidChar('1', 2))
+        ));
+        assert!(matches!(
+            strip("▖\n4 ▘", &mut stripped),
+            Err(DollcodeError::InvalidChar('4', 2))
+        ));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut out = String::<8>::new();
+        assert!(matches!(
+            pretty(&Encoded("▖▘x"), &PrettyOptions::DEFAULT, &mut out),
+            Err(DollcodeError::InvalidChar('x', 2))
+        ));
+        assert!(matches!(
+            pretty(&u64::MAX, &PrettyOptions::DEFAULT, &mut out),
+            Err(DollcodeError::Overflow)
+        ));
+    }
+}
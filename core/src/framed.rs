@@ -0,0 +1,330 @@
+//! Async message streams as newline-delimited dollcode.
+//!
+//! Each item is a [`message`] on its own line: its frames, then `\n`.
+//! Incoming bytes are buffered until a line is complete, so a message may be
+//! split anywhere, even inside a glyph, across reads and await points. Blank
+//! lines between messages are skipped.
+//!
+//! - With the `futures` feature, [`MessageStream`] reads items from a
+//!   `futures_io::AsyncRead` and [`MessageSink`] writes them to an
+//!   `AsyncWrite`.
+//! - With the `tokio` feature, [`MessageCodec`] is a `tokio_util` codec, so
+//!   `FramedRead` and `FramedWrite` give a `Stream` and a `Sink` over tokio
+//!   IO.
+//!
+//! Messages that cannot be read fail with [`io::ErrorKind::InvalidData`],
+//! carrying the [`DollcodeError`], as do lines longer than [`MAX_LINE`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! # #[cfg(feature = "futures")]
+//! # futures::executor::block_on(async {
+//! use dollcode::framed::{MessageSink, MessageStream};
+//! use futures::{SinkExt, StreamExt};
+//!
+//! let mut sink = MessageSink::new(Vec::new());
+//! sink.send(42u64).await?;
+//! sink.send(7).await?;
+//! let sent = sink.into_inner();
+//! assert_eq!(String::from_utf8(sent.clone()).unwrap(), "▖▖▖▌\u{200d}\n▘▖\u{200d}\n");
+//!
+//! let received: Vec<u64> = MessageStream::<_, u64>::new(&sent[..])
+//!     .map(Result::unwrap)
+//!     .collect()
+//!     .await;
+//! assert_eq!(received, [42, 7]);
+//! # Ok::<(), std::io::Error>(())
+//! # }).unwrap();
+//! ```
+
+use crate::{
+    io::invalid_data,
+    message::{self, DollcodeEncode},
+    DollcodeError,
+};
+use core::{fmt, marker::PhantomData};
+use std::io;
+
+/// Longest line accepted when reading, in bytes with the line break
+pub const MAX_LINE: usize = 64 * 1024;
+
+/// Writes `item` and its line break to `out`
+fn encode_line<T: DollcodeEncode>(item: &T, out: &mut impl fmt::Write) -> io::Result<()> {
+    item.encode(out)
+        .and_then(|()| out.write_char('\n'))
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, DollcodeError::InvalidInput))
+}
+
+/// Returns the length of the first complete line in `buf`, with its break
+fn line_end(buf: &[u8]) -> io::Result<Option<usize>> {
+    match buf.iter().take(MAX_LINE).position(|&b| b == b'\n') {
+        Some(i) => Ok(Some(i + 1)),
+        None if buf.len() >= MAX_LINE => Err(invalid_data(DollcodeError::Overflow)),
+        None => Ok(None),
+    }
+}
+
+/// Reads the message on `line`
+fn decode_line<T: DollcodeEncode>(line: &[u8]) -> io::Result<T> {
+    let line = core::str::from_utf8(line).map_err(|_| invalid_data(DollcodeError::InvalidInput))?;
+    message::from_str(line).map_err(invalid_data)
+}
+
+/// Returns true if `line` holds no message
+fn is_blank(line: &[u8]) -> bool {
+    line.iter().all(u8::is_ascii_whitespace)
+}
+
+#[cfg(feature = "futures")]
+pub use self::futures::{MessageSink, MessageStream};
+
+#[cfg(feature = "futures")]
+mod futures {
+    use super::*;
+    use core::{
+        pin::Pin,
+        task::{ready, Context, Poll},
+    };
+    use futures_core::Stream;
+    use futures_io::{AsyncRead, AsyncWrite};
+    use futures_sink::Sink;
+    use std::{string::String, vec::Vec};
+
+    /// Bytes requested from the reader at a time
+    const READ_SIZE: usize = 1024;
+
+    /// Buffered bytes after which [`MessageSink`] writes before accepting more
+    const WRITE_SIZE: usize = 8 * 1024;
+
+    /// Reads messages of type `T` from an `AsyncRead`, one per line
+    #[derive(Debug)]
+    pub struct MessageStream<R, T> {
+        inner: R,
+        buf: Vec<u8>,
+        eof: bool,
+        item: PhantomData<fn() -> T>,
+    }
+
+    impl<R, T> MessageStream<R, T> {
+        /// Wraps `inner`
+        pub fn new(inner: R) -> Self {
+            Self {
+                inner,
+                buf: Vec::new(),
+                eof: false,
+                item: PhantomData,
+            }
+        }
+
+        /// Returns a reference to the wrapped reader
+        pub fn get_ref(&self) -> &R {
+            &self.inner
+        }
+
+        /// Returns the wrapped reader, dropping any input read but not decoded
+        pub fn into_inner(self) -> R {
+            self.inner
+        }
+    }
+
+    impl<R: AsyncRead + Unpin, T: DollcodeEncode> Stream for MessageStream<R, T> {
+        type Item = io::Result<T>;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            let this = self.get_mut();
+            loop {
+                match line_end(&this.buf) {
+                    Ok(Some(len)) => {
+                        let line = this.buf.drain(..len);
+                        if is_blank(line.as_slice()) {
+                            continue;
+                        }
+                        return Poll::Ready(Some(decode_line(line.as_slice())));
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        this.buf.clear();
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                }
+                if this.eof {
+                    // A last message may end without a line break
+                    let item = (!is_blank(&this.buf)).then(|| decode_line(&this.buf));
+                    this.buf.clear();
+                    return Poll::Ready(item);
+                }
+
+                let len = this.buf.len();
+                this.buf.resize(len + READ_SIZE, 0);
+                let read = Pin::new(&mut this.inner).poll_read(cx, &mut this.buf[len..]);
+                let read = match read {
+                    Poll::Ready(Ok(read)) => read,
+                    Poll::Ready(Err(e)) => {
+                        this.buf.truncate(len);
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                    Poll::Pending => {
+                        this.buf.truncate(len);
+                        return Poll::Pending;
+                    }
+                };
+                this.buf.truncate(len + read);
+                this.eof = read == 0;
+            }
+        }
+    }
+
+    /// Writes messages of type `T` to an `AsyncWrite`, one per line
+    #[derive(Debug)]
+    pub struct MessageSink<W, T> {
+        inner: W,
+        buf: Vec<u8>,
+        line: String,
+        item: PhantomData<fn(T)>,
+    }
+
+    impl<W, T> MessageSink<W, T> {
+        /// Wraps `inner`
+        pub fn new(inner: W) -> Self {
+            Self {
+                inner,
+                buf: Vec::new(),
+                line: String::new(),
+                item: PhantomData,
+            }
+        }
+
+        /// Returns a reference to the wrapped writer
+        pub fn get_ref(&self) -> &W {
+            &self.inner
+        }
+
+        /// Returns the wrapped writer, dropping any messages not yet written
+        pub fn into_inner(self) -> W {
+            self.inner
+        }
+    }
+
+    impl<W: AsyncWrite + Unpin, T> MessageSink<W, T> {
+        /// Writes out the buffered messages
+        fn poll_write_buf(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            while !self.buf.is_empty() {
+                let written = ready!(Pin::new(&mut self.inner).poll_write(cx, &self.buf))?;
+                if written == 0 {
+                    return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+                }
+                self.buf.drain(..written);
+            }
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl<W: AsyncWrite + Unpin, T: DollcodeEncode> Sink<T> for MessageSink<W, T> {
+        type Error = io::Error;
+
+        fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            let this = self.get_mut();
+            if this.buf.len() < WRITE_SIZE {
+                return Poll::Ready(Ok(()));
+            }
+            this.poll_write_buf(cx)
+        }
+
+        /// Buffers `item`, failing with [`io::ErrorKind::InvalidInput`] if it
+        /// cannot be encoded.
+        fn start_send(self: Pin<&mut Self>, item: T) -> io::Result<()> {
+            let this = self.get_mut();
+            this.line.clear();
+            encode_line(&item, &mut this.line)?;
+            this.buf.extend_from_slice(this.line.as_bytes());
+            Ok(())
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            let this = self.get_mut();
+            ready!(this.poll_write_buf(cx))?;
+            Pin::new(&mut this.inner).poll_flush(cx)
+        }
+
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            let this = self.get_mut();
+            ready!(this.poll_write_buf(cx))?;
+            Pin::new(&mut this.inner).poll_close(cx)
+        }
+    }
+}
+
+/// A `tokio_util` codec for messages of type `T`, one per line
+///
+/// ```rust
+/// # futures::executor::block_on(async {
+/// use dollcode::framed::MessageCodec;
+/// use futures::StreamExt;
+/// use tokio_util::codec::FramedRead;
+///
+/// let input = "▖▖▖▌\u{200d}\n▘▖\u{200d}\n".as_bytes();
+/// let mut frames = FramedRead::new(input, MessageCodec::<u64>::new());
+/// assert_eq!(frames.next().await.unwrap()?, 42);
+/// # Ok::<(), std::io::Error>(())
+/// # }).unwrap();
+/// ```
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+pub struct MessageCodec<T> {
+    item: PhantomData<fn(T) -> T>,
+}
+
+#[cfg(feature = "tokio")]
+impl<T> MessageCodec<T> {
+    /// Creates a codec
+    pub fn new() -> Self {
+        Self { item: PhantomData }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T> Default for MessageCodec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T: DollcodeEncode> tokio_util::codec::Decoder for MessageCodec<T> {
+    type Item = T;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut bytes::BytesMut) -> io::Result<Option<T>> {
+        while let Some(len) = line_end(src)? {
+            let line = src.split_to(len);
+            if !is_blank(&line) {
+                return decode_line(&line).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, src: &mut bytes::BytesMut) -> io::Result<Option<T>> {
+        if let Some(item) = self.decode(src)? {
+            return Ok(Some(item));
+        }
+        // A last message may end without a line break
+        let rest = src.split();
+        match is_blank(&rest) {
+            true => Ok(None),
+            false => decode_line(&rest).map(Some),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T: DollcodeEncode> tokio_util::codec::Encoder<T> for MessageCodec<T> {
+    type Error = io::Error;
+
+    /// Writes `item`, failing with [`io::ErrorKind::InvalidInput`] if it
+    /// cannot be encoded
+    fn encode(&mut self, item: T, dst: &mut bytes::BytesMut) -> io::Result<()> {
+        encode_line(&item, dst)
+    }
+}
@@ -0,0 +1,278 @@
+//! Signaling dollcode over two GPIO pins.
+//!
+//! Both pins, A and B, idle low. Every glyph and delimiter is a pulse on one
+//! or both pins, followed by both pins low for one unit of time:
+//!
+//! | Symbol | Pins high | Pulse length |
+//! |--------|-----------|--------------|
+//! | ▖ | A | 1 unit |
+//! | ▘ | B | 1 unit |
+//! | ▌ | A and B | 1 unit |
+//! | [`DELIMITER`] | A and B | [`DELIMITER_UNITS`] units |
+//!
+//! Returning to idle after every pulse keeps repeated glyphs apart, so the
+//! receiver only needs the unit length to tell ▌ from the delimiter.
+//!
+//! [`PinEncoder`] is a [`Sink`] that drives the pins through closures and
+//! waits through a timing hook, so any [`ToDollcode`](crate::ToDollcode)
+//! value can be sent with its `encode_dollcode`. [`PinDecoder`] turns the
+//! states seen on the receive side back into glyphs and delimiters.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{gpio::{Pin, PinDecoder, PinEncoder}, Result, ToDollcode};
+//! # fn set_high(_: Pin) {}
+//! # fn set_low(_: Pin) {}
+//! # fn delay_us(_: u32) {}
+//! # fn main() -> Result<()> {
+//! const UNIT_US: u32 = 500;
+//! let mut encoder = PinEncoder::new(set_high, set_low, |units| delay_us(units * UNIT_US));
+//! 42u64.encode_dollcode(&mut encoder)?;
+//!
+//! // States of the pins seen by the receiver, as (a, b, units)
+//! let line = [(true, false, 1), (false, false, 1), (true, true, 3), (false, false, 5)];
+//! let mut decoder = PinDecoder::new();
+//! let mut received = heapless::String::<8>::new();
+//! for (a, b, units) in line {
+//!     if let Some(c) = decoder.push(a, b, units)? {
+//!         received.push(c).unwrap();
+//!     }
+//! }
+//! assert_eq!(received, "▖\u{200D}");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{digit_value, text::DELIMITER, DollcodeError, Result, Sink, DOLLCODE_CHAR_MAP};
+
+/// Length of the delimiter pulse, in units
+pub const DELIMITER_UNITS: u32 = 3;
+
+/// One of the two signal pins
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pin {
+    /// Pin A, high for ▖ and ▌
+    A,
+    /// Pin B, high for ▘ and ▌
+    B,
+}
+
+/// Returns the pins raised for `digit`
+const fn digit_pins(digit: u8) -> &'static [Pin] {
+    match digit {
+        1 => &[Pin::A],
+        2 => &[Pin::B],
+        _ => &[Pin::A, Pin::B],
+    }
+}
+
+/// Drives two pins to send glyphs and delimiters
+///
+/// `set` raises a pin, `clear` lowers it and `wait` holds the line for the
+/// given number of units, typically by calling a delay.
+#[derive(Debug)]
+pub struct PinEncoder<S, C, W> {
+    set: S,
+    clear: C,
+    wait: W,
+    /// Characters sent so far, for error positions
+    position: usize,
+}
+
+impl<S, C, W> PinEncoder<S, C, W>
+where
+    S: FnMut(Pin),
+    C: FnMut(Pin),
+    W: FnMut(u32),
+{
+    /// Creates an encoder driving the pins through the given closures
+    pub fn new(set: S, clear: C, wait: W) -> Self {
+        Self {
+            set,
+            clear,
+            wait,
+            position: 0,
+        }
+    }
+
+    /// Sends a pulse on `pins` lasting `units`, then one unit of idle
+    fn pulse(&mut self, pins: &[Pin], units: u32) {
+        pins.iter().for_each(|&pin| (self.set)(pin));
+        (self.wait)(units);
+        pins.iter().for_each(|&pin| (self.clear)(pin));
+        (self.wait)(1);
+    }
+}
+
+impl<S, C, W> Sink for PinEncoder<S, C, W>
+where
+    S: FnMut(Pin),
+    C: FnMut(Pin),
+    W: FnMut(u32),
+{
+    /// Sends one glyph or delimiter
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DollcodeError::InvalidChar`] for any other character.
+    fn push(&mut self, c: char) -> Result<()> {
+        if c == DELIMITER {
+            self.pulse(&[Pin::A, Pin::B], DELIMITER_UNITS);
+        } else {
+            let digit = digit_value(c).ok_or(DollcodeError::InvalidChar(c, self.position))?;
+            self.pulse(digit_pins(digit), 1);
+        }
+        self.position += 1;
+        Ok(())
+    }
+}
+
+/// Receive side of the pin protocol
+///
+/// The caller reports each state of the pins once it ends, with its length
+/// rounded to whole units. States shorter than half a unit, such as one pin
+/// rising a moment before the other, round to zero and are ignored.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PinDecoder {
+    /// Whether a pulse was read and the line has not been idle since
+    in_pulse: bool,
+}
+
+impl PinDecoder {
+    /// Creates a decoder waiting for the first pulse
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reports that pin A was at `a` and pin B at `b` for `units` units.
+    ///
+    /// Returns the glyph or [`DELIMITER`] a pulse stands for, and `None` for
+    /// idle and ignored states.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DollcodeError::InvalidInput`] for two pulses without idle
+    /// between them, and for a pulse on a single pin longer than a glyph.
+    pub fn push(&mut self, a: bool, b: bool, units: u32) -> Result<Option<char>> {
+        if units == 0 {
+            return Ok(None);
+        }
+        if !a && !b {
+            self.in_pulse = false;
+            return Ok(None);
+        }
+        if core::mem::replace(&mut self.in_pulse, true) {
+            return Err(DollcodeError::InvalidInput);
+        }
+        let [one, two, three] = DOLLCODE_CHAR_MAP;
+        let c = match (a, b, units) {
+            // Closer to the delimiter's length than to a glyph's
+            (true, true, units) if units.saturating_mul(2) > 1 + DELIMITER_UNITS => DELIMITER,
+            (_, _, 2..) => return Err(DollcodeError::InvalidInput),
+            (true, false, _) => one,
+            (false, true, _) => two,
+            _ => three,
+        };
+        Ok(Some(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::to_dollcode;
+    use core::cell::RefCell;
+    use heapless::Vec;
+
+    /// Line states as (a, b, units)
+    type Line = Vec<(bool, bool, u32), 128>;
+
+    /// Sends `chars` and records the line states
+    fn send(chars: impl IntoIterator<Item = char>) -> Result<Line> {
+        let pins = RefCell::new((false, false));
+        let line = RefCell::new(Line::new());
+        let mut encoder = PinEncoder::new(
+            |pin| match pin {
+                Pin::A => pins.borrow_mut().0 = true,
+                Pin::B => pins.borrow_mut().1 = true,
+            },
+            |pin| match pin {
+                Pin::A => pins.borrow_mut().0 = false,
+                Pin::B => pins.borrow_mut().1 = false,
+            },
+            |units| {
+                let (a, b) = *pins.borrow();
+                line.borrow_mut().push((a, b, units)).unwrap();
+            },
+        );
+        encoder.push_all(chars)?;
+        Ok(line.into_inner())
+    }
+
+    fn receive(line: &[(bool, bool, u32)]) -> Result<heapless::String<256>> {
+        let mut decoder = PinDecoder::new();
+        let mut received = heapless::String::new();
+        for &(a, b, units) in line {
+            if let Some(c) = decoder.push(a, b, units)? {
+                received.push(c).unwrap();
+            }
+        }
+        Ok(received)
+    }
+
+    #[test]
+    fn test_line_states() {
+        let line = send("▖▘▌\u{200D}".chars()).unwrap();
+        assert_eq!(
+            line,
+            [
+                (true, false, 1),
+                (false, false, 1),
+                (false, true, 1),
+                (false, false, 1),
+                (true, true, 1),
+                (false, false, 1),
+                (true, true, DELIMITER_UNITS),
+                (false, false, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let glyphs = to_dollcode(u64::MAX).unwrap();
+        let line = send(glyphs.as_chars().iter().copied().chain([DELIMITER])).unwrap();
+        let received = receive(&line).unwrap();
+        let glyphs = glyphs.to_heapless_string::<128>().unwrap();
+        assert_eq!(received.strip_suffix(DELIMITER), Some(glyphs.as_str()));
+    }
+
+    #[test]
+    fn test_skew_and_jitter() {
+        // B rises after A, a long idle, and a delimiter read as 4 units
+        let line = [
+            (true, false, 0),
+            (true, true, 1),
+            (false, true, 0),
+            (false, false, 9),
+            (true, true, 4),
+            (false, false, 1),
+        ];
+        assert_eq!(receive(&line).unwrap(), "▌\u{200D}");
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            send("▖x".chars()),
+            Err(DollcodeError::InvalidChar('x', 1))
+        ));
+        for line in [
+            [(true, false, 1), (true, true, 1)],
+            [(true, false, 3), (false, false, 1)],
+        ] {
+            assert!(matches!(receive(&line), Err(DollcodeError::InvalidInput)));
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Drawing dollcode with `embedded-graphics`.
+//!
+//! [`Glyphs`] draws a dollcode sequence as filled rectangles, so a display
+//! can show it without a font containing ▖, ▘ and ▌. Each glyph takes a cell
+//! of [`GlyphStyle::cell`] and fills the left half of it, as the block
+//! characters do:
+//!
+//! | Symbol | Filled |
+//! |--------|--------|
+//! | ▖ | lower left quarter |
+//! | ▘ | upper left quarter |
+//! | ▌ | left half |
+//! | [`DELIMITER`] | nothing, [`GlyphStyle::delimiter_width`] wide |
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{graphics::{GlyphStyle, Glyphs}, to_dollcode, Result};
+//! # use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::*};
+//! # fn main() -> Result<()> {
+//! let mut display = MockDisplay::new();
+//! let style = GlyphStyle::new(BinaryColor::On, Size::new(4, 4));
+//!
+//! let encoded = to_dollcode(5)?;
+//! let next = Glyphs::from_dollcode(&encoded, Point::zero(), style)
+//!     .draw(&mut display)
+//!     .unwrap();
+//! assert_eq!(next, Point::new(8, 0));
+//! display.assert_pattern(&[
+//!     "    ##",
+//!     "    ##",
+//!     "##    ",
+//!     "##    ",
+//! ]);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{digit_value, text::DELIMITER, Dollcode, DollcodeError, Result};
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, Point, Size},
+    pixelcolor::PixelColor,
+    primitives::Rectangle,
+    Drawable,
+};
+
+/// Color and size of drawn glyphs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlyphStyle<C> {
+    /// Color of the filled parts
+    pub color: C,
+    /// Size of the cell each glyph takes
+    pub cell: Size,
+    /// Space left for each delimiter
+    pub delimiter_width: u32,
+}
+
+impl<C> GlyphStyle<C> {
+    /// Creates a style with cells of `cell` and delimiters half a cell wide
+    pub const fn new(color: C, cell: Size) -> Self {
+        Self {
+            color,
+            cell,
+            delimiter_width: cell.width / 2,
+        }
+    }
+}
+
+/// A dollcode sequence drawn at a position
+///
+/// The position is the top left corner of the first cell. Drawing returns
+/// the position following the last cell, to draw more from.
+#[derive(Debug, Clone, Copy)]
+pub struct Glyphs<'a, C> {
+    /// Only one of these is non-empty
+    encoded: &'a str,
+    chars: &'a [char],
+    position: Point,
+    style: GlyphStyle<C>,
+}
+
+impl<'a, C> Glyphs<'a, C> {
+    /// Creates a drawable for the glyphs and delimiters of `encoded`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DollcodeError::InvalidChar`] for any other character.
+    pub fn new(encoded: &'a str, position: Point, style: GlyphStyle<C>) -> Result<Self> {
+        match encoded
+            .chars()
+            .enumerate()
+            .find(|&(_, c)| c != DELIMITER && digit_value(c).is_none())
+        {
+            Some((position, c)) => Err(DollcodeError::InvalidChar(c, position)),
+            None => Ok(Self {
+                encoded,
+                chars: &[],
+                position,
+                style,
+            }),
+        }
+    }
+
+    /// Creates a drawable for an encoded number
+    pub fn from_dollcode(dollcode: &'a Dollcode, position: Point, style: GlyphStyle<C>) -> Self {
+        Self {
+            encoded: "",
+            chars: dollcode.as_chars(),
+            position,
+            style,
+        }
+    }
+
+    fn symbols(&self) -> impl Iterator<Item = char> + 'a {
+        self.encoded.chars().chain(self.chars.iter().copied())
+    }
+
+    /// Returns the width a symbol takes
+    fn advance(&self, c: char) -> u32 {
+        if c == DELIMITER {
+            self.style.delimiter_width
+        } else {
+            self.style.cell.width
+        }
+    }
+
+    /// Returns the filled part of the cell at `origin` for `digit`
+    fn fill(&self, origin: Point, digit: u8) -> Rectangle {
+        let Size { width, height } = self.style.cell;
+        let upper = height / 2;
+        match digit {
+            1 => Rectangle::new(
+                origin + Size::new(0, upper),
+                Size::new(width / 2, height - upper),
+            ),
+            2 => Rectangle::new(origin, Size::new(width / 2, upper)),
+            _ => Rectangle::new(origin, Size::new(width / 2, height)),
+        }
+    }
+}
+
+impl<C: PixelColor> Drawable for Glyphs<'_, C> {
+    type Color = C;
+    type Output = Point;
+
+    fn draw<D>(&self, target: &mut D) -> core::result::Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let mut origin = self.position;
+        for c in self.symbols() {
+            if let Some(digit) = digit_value(c) {
+                target.fill_solid(&self.fill(origin, digit), self.style.color)?;
+            }
+            origin += Size::new(self.advance(c), 0);
+        }
+        Ok(origin)
+    }
+}
+
+impl<C> Dimensions for Glyphs<'_, C> {
+    fn bounding_box(&self) -> Rectangle {
+        let width = self
+            .symbols()
+            .fold(0u32, |width, c| width.saturating_add(self.advance(c)));
+        Rectangle::new(self.position, Size::new(width, self.style.cell.height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    const STYLE: GlyphStyle<BinaryColor> = GlyphStyle::new(BinaryColor::On, Size::new(4, 4));
+
+    #[test]
+    fn test_glyph_geometry() {
+        let mut display = MockDisplay::new();
+        let glyphs = Glyphs::new("▘▖▌", Point::new(1, 2), STYLE).unwrap();
+        assert_eq!(glyphs.draw(&mut display).unwrap(), Point::new(13, 2));
+        assert_eq!(
+            glyphs.bounding_box(),
+            Rectangle::new(Point::new(1, 2), Size::new(12, 4))
+        );
+        display.assert_pattern(&[
+            "           ",
+            "           ",
+            " ##      ##",
+            " ##      ##",
+            "     ##  ##",
+            "     ##  ##",
+        ]);
+    }
+
+    #[test]
+    fn test_delimiters() {
+        let mut display = MockDisplay::new();
+        let style = GlyphStyle {
+            delimiter_width: 1,
+            ..STYLE
+        };
+        let glyphs = Glyphs::new("▖\u{200D}▖\u{200D}", Point::zero(), style).unwrap();
+        assert_eq!(glyphs.draw(&mut display).unwrap(), Point::new(10, 0));
+        display.assert_pattern(&["       ", "       ", "##   ##", "##   ##"]);
+    }
+
+    #[test]
+    fn test_odd_cell() {
+        // The lower quarter takes the middle row
+        let mut display = MockDisplay::new();
+        let style = GlyphStyle::new(BinaryColor::On, Size::new(3, 3));
+        Glyphs::new("▘▖", Point::zero(), style)
+            .unwrap()
+            .draw(&mut display)
+            .unwrap();
+        display.assert_pattern(&["#   ", "   #", "   #"]);
+    }
+
+    #[test]
+    fn test_invalid_char() {
+        assert!(matches!(
+            Glyphs::new("▖▘x", Point::zero(), STYLE),
+            Err(DollcodeError::InvalidChar('x', 2))
+        ));
+    }
+}
@@ -0,0 +1,363 @@
+//! Detection of what user input holds, as the website's converter does it.
+//!
+//! [`classify`] decides whether input is dollcode, a number or text, and
+//! [`parse_decimal`] and [`parse_hex`] read the numbers it accepts. The
+//! WebAssembly and Node.js packages both convert through these, so the same
+//! input is read the same way everywhere. Size limits are left to callers.
+//!
+//! Input is classified in this order:
+//!
+//! 1. Percent-encoded dollcode, made only of `%XX` escapes of glyphs and
+//!    delimiters, as shared in links
+//! 2. Dollcode, if it contains any glyph or delimiter, ignoring whitespace
+//! 3. Decimal numbers, all digits
+//! 4. Hex numbers, `0x` followed by hex digits
+//! 5. Printable ASCII text
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{input::{classify, parse_decimal, InputKind}, Result};
+//! # fn main() -> Result<()> {
+//! assert_eq!(classify("42")?, InputKind::Decimal);
+//! assert_eq!(classify("▖▖ ▖▌")?, InputKind::DollcodeNumber);
+//! assert_eq!(classify("%E2%96%96")?, InputKind::DollcodeUrl);
+//! assert_eq!(classify("+42")?, InputKind::Text);
+//! assert_eq!(parse_decimal("+42"), None);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{digit_value, text::DELIMITER, DollcodeError, Result};
+
+/// What an input holds, as decided by [`classify`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputKind {
+    /// Decimal digits
+    Decimal,
+    /// `0x` followed by hex digits
+    Hex,
+    /// Printable ASCII text
+    Text,
+    /// Glyphs without delimiters, a number
+    DollcodeNumber,
+    /// Glyphs separated by delimiters, text
+    DollcodeText,
+    /// Percent-encoded glyphs and delimiters
+    DollcodeUrl,
+}
+
+impl InputKind {
+    /// Identifier of the kind, such as `"dollcode-number"`
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Decimal => "decimal",
+            Self::Hex => "hex",
+            Self::Text => "text",
+            Self::DollcodeNumber => "dollcode-number",
+            Self::DollcodeText => "dollcode-text",
+            Self::DollcodeUrl => "dollcode-url",
+        }
+    }
+
+    /// Why input is of this kind, as a sentence for users
+    pub const fn reason(self) -> &'static str {
+        match self {
+            Self::Decimal => "All characters are decimal digits",
+            Self::Hex => "0x prefix followed by hexadecimal digits",
+            Self::Text => "Printable ASCII text",
+            Self::DollcodeNumber => "Dollcode glyphs without delimiters",
+            Self::DollcodeText => "Dollcode glyphs separated by zero-width joiners",
+            Self::DollcodeUrl => "Percent-encoded dollcode glyphs",
+        }
+    }
+
+    /// Whether the input is also valid text, as numbers are
+    pub const fn is_number(self) -> bool {
+        matches!(self, Self::Decimal | Self::Hex)
+    }
+}
+
+/// Whether `c` is a glyph or the delimiter
+pub fn is_dollcode_char(c: char) -> bool {
+    c == DELIMITER || digit_value(c).is_some()
+}
+
+/// Determines what `input` holds, without converting it.
+///
+/// Only the shape of the input is checked, so numbers and dollcode may still
+/// be out of range.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidChar`] for dollcode with anything but
+/// whitespace between the glyphs, and for other input with characters
+/// outside printable ASCII, and [`DollcodeError::InvalidInput`] for empty
+/// input.
+pub fn classify(input: &str) -> Result<InputKind> {
+    if input.is_empty() {
+        return Err(DollcodeError::InvalidInput);
+    }
+    if is_url_encoded(input) {
+        return Ok(InputKind::DollcodeUrl);
+    }
+
+    // Whitespace from line wrapping or spacing is allowed between glyphs
+    if input.chars().any(is_dollcode_char) {
+        if let Some((position, c)) = input
+            .chars()
+            .enumerate()
+            .find(|&(_, c)| !is_dollcode_char(c) && !c.is_whitespace())
+        {
+            return Err(DollcodeError::InvalidChar(c, position));
+        }
+        return Ok(if input.contains(DELIMITER) {
+            InputKind::DollcodeText
+        } else {
+            InputKind::DollcodeNumber
+        });
+    }
+
+    if let Some((position, c)) = input
+        .chars()
+        .enumerate()
+        .find(|&(_, c)| !matches!(c, ' '..='~'))
+    {
+        return Err(DollcodeError::InvalidChar(c, position));
+    }
+    if is_digits(input, 10) {
+        Ok(InputKind::Decimal)
+    } else if input
+        .strip_prefix("0x")
+        .is_some_and(|hex| is_digits(hex, 16))
+    {
+        Ok(InputKind::Hex)
+    } else {
+        Ok(InputKind::Text)
+    }
+}
+
+/// Whether `input` is one or more digits in `radix`, without a sign
+fn is_digits(input: &str, radix: u32) -> bool {
+    !input.is_empty() && input.chars().all(|c| c.is_digit(radix))
+}
+
+/// Parses decimal digits, without a sign or spaces.
+///
+/// Returns `None` for anything else, or values over [`u64::MAX`].
+pub fn parse_decimal(input: &str) -> Option<u64> {
+    is_digits(input, 10).then(|| input.parse().ok()).flatten()
+}
+
+/// Parses hex digits of either case, after an optional `0x` prefix, without
+/// a sign or spaces.
+///
+/// Returns `None` for anything else, or values over [`u64::MAX`].
+pub fn parse_hex(input: &str) -> Option<u64> {
+    let hex = input.strip_prefix("0x").unwrap_or(input);
+    is_digits(hex, 16)
+        .then(|| u64::from_str_radix(hex, 16).ok())
+        .flatten()
+}
+
+/// Characters of percent-encoded input, paired with the byte index of the
+/// `%` that starts each one.
+///
+/// Yields an error with the position of the first malformed escape or
+/// invalid UTF-8 sequence, then stops.
+///
+/// ```rust
+/// # use dollcode::input::UrlDecoder;
+/// let mut decoder = UrlDecoder::new("%E2%96%96%41%zz");
+/// assert_eq!(decoder.next(), Some(Ok((0, '▖'))));
+/// assert_eq!(decoder.next(), Some(Ok((9, 'A'))));
+/// assert_eq!(decoder.next(), Some(Err(12)));
+/// assert_eq!(decoder.next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct UrlDecoder<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+
+impl<'a> UrlDecoder<'a> {
+    /// Decodes `input`, which should consist only of `%XX` escapes
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input: input.as_bytes(),
+            position: 0,
+        }
+    }
+
+    /// Consumes a single `%XX` escape
+    fn byte(&mut self) -> Option<u8> {
+        let [b'%', hi, lo, rest @ ..] = self.input else {
+            return None;
+        };
+        let hi = char::from(*hi).to_digit(16)?;
+        let lo = char::from(*lo).to_digit(16)?;
+
+        self.input = rest;
+        self.position += 3;
+        u8::try_from(hi << 4 | lo).ok()
+    }
+
+    fn decode_char(&mut self) -> Option<char> {
+        let mut buf = [0u8; 4];
+        buf[0] = self.byte()?;
+
+        let len = match buf[0] {
+            0x00..=0x7F => 1,
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => return None,
+        };
+        for slot in &mut buf[1..len] {
+            *slot = self.byte()?;
+        }
+
+        core::str::from_utf8(&buf[..len]).ok()?.chars().next()
+    }
+}
+
+impl Iterator for UrlDecoder<'_> {
+    type Item = core::result::Result<(usize, char), usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.input.is_empty() {
+            return None;
+        }
+
+        let position = self.position;
+        match self.decode_char() {
+            Some(c) => Some(Ok((position, c))),
+            None => {
+                self.input = &[];
+                Some(Err(position))
+            }
+        }
+    }
+}
+
+/// Whether `input` consists only of percent-encoded glyphs and delimiters
+pub fn is_url_encoded(input: &str) -> bool {
+    input.starts_with('%')
+        && UrlDecoder::new(input).all(|result| result.is_ok_and(|(_, c)| is_dollcode_char(c)))
+}
+
+/// Maps characters commonly pasted in place of a glyph to the glyph itself.
+///
+/// Covers the mirrored quadrants and half block produced by right-to-left
+/// rendering and some fonts, and the neighbouring left block widths that
+/// font fallback substitutes for ▌. Other characters are returned unchanged.
+pub fn canonical_glyph(c: char) -> char {
+    match c {
+        // ▗ QUADRANT LOWER RIGHT
+        '\u{2597}' => '▖',
+        // ▝ QUADRANT UPPER RIGHT
+        '\u{259D}' => '▘',
+        // ▐ RIGHT HALF BLOCK, ▋ LEFT FIVE EIGHTHS, ▍ LEFT THREE EIGHTHS
+        '\u{2590}' | '\u{258B}' | '\u{258D}' => '▌',
+        c => c,
+    }
+}
+
+/// Whether `c` carries no dollcode content when pasted: whitespace,
+/// variation selectors, zero-width spaces and byte order marks
+pub fn is_ignorable(c: char) -> bool {
+    c.is_whitespace()
+        || matches!(c, '\u{FE00}'..='\u{FE0F}' | '\u{E0100}'..='\u{E01EF}')
+        || matches!(c, '\u{200B}' | '\u{FEFF}')
+}
+
+/// Cleans pasted dollcode into its canonical form, writing it to `sink`.
+///
+/// Lookalike glyphs are mapped with [`canonical_glyph`] and
+/// [`is_ignorable`] characters removed. Delimiters are kept.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidChar`] for any other character, with its
+/// position in `input`, and [`DollcodeError::Overflow`] if `sink` is full.
+pub fn normalize(input: &str, sink: &mut impl crate::Sink) -> Result<()> {
+    for (position, c) in input.chars().enumerate() {
+        if is_ignorable(c) {
+            continue;
+        }
+        let c = canonical_glyph(c);
+        if !is_dollcode_char(c) {
+            return Err(DollcodeError::InvalidChar(c, position));
+        }
+        sink.push(c)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::String;
+
+    #[test]
+    fn test_classify() {
+        let cases = [
+            ("42", InputKind::Decimal),
+            ("0042", InputKind::Decimal),
+            ("0x2A", InputKind::Hex),
+            ("0x", InputKind::Text),
+            ("0x-1", InputKind::Text),
+            ("+42", InputKind::Text),
+            (" 42", InputKind::Text),
+            ("Hi", InputKind::Text),
+            ("▖▖\n▖▌", InputKind::DollcodeNumber),
+            ("▘▖▘▌\u{200d}▌▘▖▌", InputKind::DollcodeText),
+            ("%E2%96%96%E2%80%8D", InputKind::DollcodeUrl),
+        ];
+        for (input, kind) in cases {
+            assert_eq!(classify(input).unwrap(), kind, "{input:?}");
+        }
+
+        assert!(matches!(classify(""), Err(DollcodeError::InvalidInput)));
+        assert!(matches!(
+            classify("id=▖▖▖▌"),
+            Err(DollcodeError::InvalidChar('i', 0))
+        ));
+        assert!(matches!(
+            classify("café"),
+            Err(DollcodeError::InvalidChar('é', 3))
+        ));
+        // Escapes of anything else are text
+        assert_eq!(classify("%41").unwrap(), InputKind::Text);
+        assert_eq!(classify("%E2%96").unwrap(), InputKind::Text);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse_decimal("42"), Some(42));
+        assert_eq!(parse_decimal("18446744073709551615"), Some(u64::MAX));
+        assert_eq!(parse_decimal("18446744073709551616"), None);
+        for input in ["", "+42", "-1", " 42", "4 2", "0x2A"] {
+            assert_eq!(parse_decimal(input), None, "{input:?}");
+        }
+
+        assert_eq!(parse_hex("0x2a"), Some(42));
+        assert_eq!(parse_hex("2A"), Some(42));
+        assert_eq!(parse_hex("0xffffffffffffffff"), Some(u64::MAX));
+        for input in ["", "0x", "0x0x2A", "+2A", "0x+2A", "0x1_0", "0xg"] {
+            assert_eq!(parse_hex(input), None, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut output = String::<64>::new();
+        normalize("\u{feff}▗▝ ▐\u{fe0f}\u{200d}", &mut output).unwrap();
+        assert_eq!(output, "▖▘▌\u{200d}");
+
+        assert!(matches!(
+            normalize("▖ x", &mut String::<64>::new()),
+            Err(DollcodeError::InvalidChar('x', 2))
+        ));
+    }
+}
@@ -0,0 +1,215 @@
+//! Adapters for `std::io` readers and writers.
+//!
+//! [`DollcodeWriter`] encodes the printable ASCII written to it and
+//! [`DollcodeReader`] decodes the dollcode read from it, so the codec fits
+//! into ordinary IO pipelines such as `io::copy`. Line breaks are passed
+//! through in both directions. Numbers are written with
+//! [`DollcodeWriter::write_number`] as frames, each followed by
+//! [`DELIMITER`], and read back with [`DollcodeReader::read_number`].
+//!
+//! Data that cannot be encoded or decoded fails with
+//! [`io::ErrorKind::InvalidData`], carrying the [`DollcodeError`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use dollcode::io::{DollcodeReader, DollcodeWriter};
+//! use std::io::{self, Read, Write};
+//!
+//! let mut writer = DollcodeWriter::new(Vec::new());
+//! writer.write_all(b"Hi\n")?;
+//! writer.write_number(42)?;
+//! let encoded = writer.into_inner();
+//! assert_eq!(
+//!     String::from_utf8(encoded.clone()).unwrap(),
+//!     "▘▖▘▌\u{200d}▌▘▖▌\u{200d}\n▖▖▖▌\u{200d}"
+//! );
+//!
+//! let mut reader = DollcodeReader::new(&encoded[..]);
+//! let mut line = [0u8; 3];
+//! reader.read_exact(&mut line)?;
+//! assert_eq!(&line, b"Hi\n");
+//! assert_eq!(reader.read_number()?, Some(42));
+//! # Ok::<(), io::Error>(())
+//! ```
+
+use crate::{
+    stream::{encode_text_byte, FrameDecoder, TextBytes},
+    text::DELIMITER,
+    to_dollcode, DollcodeError,
+};
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    string::String,
+};
+
+/// Wraps `e` in an [`io::ErrorKind::InvalidData`] error
+pub(crate) fn invalid_data(e: DollcodeError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+/// Wraps a writer, encoding printable ASCII written to it as dollcode text
+///
+/// Each call to [`Write::write`] encodes as much of its input as it can
+/// and writes the glyphs to the inner writer in one `write_all`. Wrap an
+/// unbuffered inner writer such as a file in an [`io::BufWriter`].
+#[derive(Debug)]
+pub struct DollcodeWriter<W> {
+    inner: W,
+    buf: String,
+    /// Bytes encoded so far, for error positions
+    position: usize,
+}
+
+impl<W: Write> DollcodeWriter<W> {
+    /// Wraps `inner`
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            buf: String::new(),
+            position: 0,
+        }
+    }
+
+    /// Returns a reference to the wrapped writer
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns the wrapped writer
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes `value` as a frame: its glyphs followed by [`DELIMITER`]
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of the inner writer.
+    pub fn write_number(&mut self, value: u64) -> io::Result<()> {
+        self.buf.clear();
+        let encoded = to_dollcode(value).map_err(invalid_data)?;
+        self.buf.extend(encoded.as_chars());
+        self.buf.push(DELIMITER);
+        self.inner.write_all(self.buf.as_bytes())
+    }
+}
+
+impl<W: Write> Write for DollcodeWriter<W> {
+    /// Encodes `buf`, stopping before the first byte that is neither a line
+    /// break nor printable ASCII.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] if the first byte cannot be
+    /// encoded, and with the errors of the inner writer.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.clear();
+        let mut encoded = 0;
+        for &byte in buf {
+            if !encode_text_byte(byte, &mut self.buf).map_err(invalid_data)? {
+                // Report the bad byte once what precedes it is written
+                if encoded > 0 {
+                    break;
+                }
+                let c = char::from(byte);
+                return Err(invalid_data(DollcodeError::InvalidChar(c, self.position)));
+            }
+            encoded += 1;
+        }
+        self.inner.write_all(self.buf.as_bytes())?;
+        self.position += encoded;
+        Ok(encoded)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Wraps a reader, decoding the dollcode text read from it
+///
+/// The inner reader is buffered, so it need not be.
+#[derive(Debug)]
+pub struct DollcodeReader<R> {
+    inner: BufReader<R>,
+    decoder: TextBytes,
+}
+
+impl<R: Read> DollcodeReader<R> {
+    /// Wraps `inner`
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner: BufReader::new(inner),
+            decoder: TextBytes::default(),
+        }
+    }
+
+    /// Returns a reference to the wrapped reader
+    pub fn get_ref(&self) -> &R {
+        self.inner.get_ref()
+    }
+
+    /// Returns the wrapped reader, dropping any input read but not decoded
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+
+    /// Reads the next frame, or `None` at the end of the stream.
+    ///
+    /// Whitespace before the frame is skipped. Reading continues after the
+    /// text already decoded, so frames and text may follow each other on
+    /// segment boundaries.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] for characters other than
+    /// glyphs, values past `u64::MAX` and a stream ending inside a frame, and
+    /// with the errors of the inner reader.
+    pub fn read_number(&mut self) -> io::Result<Option<u64>> {
+        let mut frame = FrameDecoder::default();
+        loop {
+            let input = self.inner.fill_buf()?;
+            if input.is_empty() {
+                return frame.finish().map_err(invalid_data);
+            }
+            let mut used = input.len();
+            let mut result = None;
+            for (i, &byte) in input.iter().enumerate() {
+                match frame.push(byte) {
+                    Ok(None) => continue,
+                    Ok(Some(value)) => result = Some(Ok(Some(value))),
+                    Err(e) => result = Some(Err(invalid_data(e))),
+                }
+                used = i + 1;
+                break;
+            }
+            self.inner.consume(used);
+            if let Some(result) = result {
+                return result;
+            }
+        }
+    }
+}
+
+impl<R: Read> Read for DollcodeReader<R> {
+    /// Decodes into `buf`, waiting for at least one complete segment.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] if the input is not valid
+    /// dollcode text, and with the errors of the inner reader.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let input = self.inner.fill_buf()?;
+            if input.is_empty() {
+                return self.decoder.finish(buf).map_err(invalid_data);
+            }
+            let (consumed, written) = self.decoder.decode(input, buf).map_err(invalid_data)?;
+            self.inner.consume(consumed);
+            if written > 0 || buf.is_empty() {
+                return Ok(written);
+            }
+        }
+    }
+}
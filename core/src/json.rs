@@ -0,0 +1,232 @@
+//! JSON documents as framed dollcode.
+//!
+//! A [`serde_json::Value`] is written as [`message`](crate::message) frames.
+//! Unlike a message, the document describes itself: every value starts with
+//! a frame naming its kind, so it is read back without knowing its shape.
+//!
+//! | Value | Frames |
+//! |-------|--------|
+//! | `null` | 0 |
+//! | `false`, `true` | 1, 2 |
+//! | non-negative integer | 3, then the number |
+//! | negative integer | 4, then the zigzag mapped number |
+//! | other number | 5, then the bits of the `f64` |
+//! | string | 6, then the string as in a message |
+//! | array | 7, the number of elements, then each element |
+//! | object | 8, the number of entries, then each key string and value |
+//!
+//! # Examples
+//!
+//! ```rust
+//! # fn main() -> dollcode::Result<()> {
+//! let encoded = dollcode::json::encode_json(r#"{"retries": 3}"#)?;
+//! assert_eq!(dollcode::json::decode_json(&encoded)?, r#"{"retries":3}"#);
+//!
+//! let value = dollcode::json::from_str(&encoded)?;
+//! assert_eq!(value["retries"], 3);
+//! assert_eq!(dollcode::json::to_string(&value), encoded);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    message::{self, DollcodeEncode, Fields},
+    DollcodeError, Result,
+};
+use core::fmt;
+use serde_json::{Map, Number, Value};
+use std::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Deepest nesting of arrays and objects accepted when reading, as in
+/// `serde_json`
+const MAX_DEPTH: usize = 128;
+
+const NULL: u64 = 0;
+const FALSE: u64 = 1;
+const TRUE: u64 = 2;
+const UNSIGNED: u64 = 3;
+const NEGATIVE: u64 = 4;
+const FLOAT: u64 = 5;
+const STRING: u64 = 6;
+const ARRAY: u64 = 7;
+const OBJECT: u64 = 8;
+
+impl DollcodeEncode for Value {
+    fn encode<W: fmt::Write + ?Sized>(&self, out: &mut W) -> fmt::Result {
+        match self {
+            Self::Null => NULL.encode(out),
+            Self::Bool(false) => FALSE.encode(out),
+            Self::Bool(true) => TRUE.encode(out),
+            Self::Number(n) => {
+                if let Some(n) = n.as_u64() {
+                    UNSIGNED.encode(out)?;
+                    n.encode(out)
+                } else if let Some(n) = n.as_i64() {
+                    NEGATIVE.encode(out)?;
+                    n.encode(out)
+                } else {
+                    FLOAT.encode(out)?;
+                    n.as_f64().ok_or(fmt::Error)?.to_bits().encode(out)
+                }
+            }
+            Self::String(s) => {
+                STRING.encode(out)?;
+                s.encode(out)
+            }
+            Self::Array(values) => {
+                ARRAY.encode(out)?;
+                values.len().encode(out)?;
+                values.iter().try_for_each(|value| value.encode(out))
+            }
+            Self::Object(entries) => {
+                OBJECT.encode(out)?;
+                entries.len().encode(out)?;
+                entries.iter().try_for_each(|(key, value)| {
+                    key.encode(out)?;
+                    value.encode(out)
+                })
+            }
+        }
+    }
+
+    fn decode(fields: &mut Fields<'_>) -> Result<Self> {
+        decode_value(fields, 0)
+    }
+}
+
+/// Reads a value nested `depth` arrays and objects deep
+fn decode_value(fields: &mut Fields<'_>, depth: usize) -> Result<Value> {
+    let value = match fields.word()? {
+        NULL => Value::Null,
+        FALSE => Value::Bool(false),
+        TRUE => Value::Bool(true),
+        UNSIGNED => Value::from(fields.word()?),
+        NEGATIVE => Value::from(i64::decode(fields)?),
+        FLOAT => Number::from_f64(f64::from_bits(fields.word()?))
+            .map(Value::Number)
+            .ok_or(DollcodeError::InvalidInput)?,
+        STRING => Value::String(String::decode(fields)?),
+        ARRAY if depth < MAX_DEPTH => {
+            // The length is not trusted for preallocation
+            let len = fields.word()?;
+            let mut values = Vec::new();
+            for _ in 0..len {
+                values.push(decode_value(fields, depth + 1)?);
+            }
+            Value::Array(values)
+        }
+        OBJECT if depth < MAX_DEPTH => {
+            let len = fields.word()?;
+            let mut entries = Map::new();
+            for _ in 0..len {
+                let key = String::decode(fields)?;
+                entries.insert(key, decode_value(fields, depth + 1)?);
+            }
+            Value::Object(entries)
+        }
+        _ => return Err(DollcodeError::InvalidInput),
+    };
+    Ok(value)
+}
+
+/// Writes `value` as a framed document
+pub fn to_string(value: &Value) -> String {
+    let mut encoded = String::new();
+    // Writing to a String cannot fail, and every number of a Value has an
+    // f64 form
+    let _ = value.encode(&mut encoded);
+    encoded
+}
+
+/// Reads a framed document
+///
+/// # Errors
+///
+/// Returns the errors of [`message::from_str`], and
+/// [`DollcodeError::InvalidInput`] for unknown kinds, infinite or NaN
+/// numbers, and nesting deeper than 128 levels.
+pub fn from_str(input: &str) -> Result<Value> {
+    message::from_str(input)
+}
+
+/// Parses JSON text and writes it as a framed document
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidInput`] if `json` is not valid JSON.
+pub fn encode_json(json: &str) -> Result<String> {
+    let value = serde_json::from_str(json).map_err(|_| DollcodeError::InvalidInput)?;
+    Ok(to_string(&value))
+}
+
+/// Reads a framed document and returns it as compact JSON text
+///
+/// # Errors
+///
+/// Returns the errors of [`from_str`].
+pub fn decode_json(input: &str) -> Result<String> {
+    Ok(from_str(input)?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn frame(n: u64) -> String {
+        crate::to_dollcode(n).unwrap().to_string() + "\u{200D}"
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let document = json!({
+            "name": "sensor",
+            "enabled": true,
+            "offset": -2,
+            "scale": 0.5,
+            "limits": [0, 18446744073709551615u64, null],
+            "nested": { "empty": {}, "list": [] },
+        });
+        let encoded = to_string(&document);
+        assert_eq!(from_str(&encoded).unwrap(), document);
+        assert_eq!(
+            decode_json(&encode_json(&document.to_string()).unwrap()).unwrap(),
+            document.to_string()
+        );
+    }
+
+    #[test]
+    fn test_frames() {
+        assert_eq!(to_string(&Value::Null), frame(NULL));
+        assert_eq!(to_string(&json!(42)), frame(UNSIGNED) + &frame(42));
+        assert_eq!(to_string(&json!(-1)), frame(NEGATIVE) + &frame(1));
+        assert_eq!(
+            to_string(&json!([true])),
+            frame(ARRAY) + &frame(1) + &frame(TRUE)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(encode_json("{"), Err(DollcodeError::InvalidInput)));
+        // Unknown kind, and an array missing its element
+        for input in ["▌▌\u{200D}", "▘▖\u{200D}▖\u{200D}"] {
+            assert!(matches!(from_str(input), Err(DollcodeError::InvalidInput)));
+        }
+        // Infinity is not a JSON number
+        let infinity = frame(FLOAT) + &frame(f64::INFINITY.to_bits());
+        assert!(matches!(
+            from_str(&infinity),
+            Err(DollcodeError::InvalidInput)
+        ));
+
+        // Arrays of one element each, around a null
+        const NESTED: &str = "▘▖\u{200D}▖\u{200D}";
+        let deep = NESTED.repeat(MAX_DEPTH + 1) + "\u{200D}";
+        assert!(matches!(from_str(&deep), Err(DollcodeError::InvalidInput)));
+        assert!(from_str(&deep[NESTED.len()..]).is_ok());
+    }
+}
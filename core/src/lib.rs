@@ -36,20 +36,19 @@
 //! For completely allocation-free operation, use `heapless` collections:
 //!
 //! ```rust
-//! # use dollcode::{text::TextIterator, Result};
+//! # use dollcode::{text, to_dollcode, Result};
 //! # fn main() -> Result<()> {
-//! let text = "Hi!";
-//! // Fixed stack buffer, no heap allocation
-//! let mut encoded = heapless::Vec::<char, 128>::new();
-//!
-//! for segment in TextIterator::new(text) {
-//!     let segment = segment?;
-//!     encoded.extend_from_slice(segment.as_chars()).unwrap();
-//! }
+//! // Fixed stack buffers, no heap allocation
+//! let number = to_dollcode(42)?.to_heapless_string::<16>()?;
+//! let text = text::encode_to_heapless::<128>("Hi!")?;
 //! # Ok(())
 //! # }
 //! ```
 //!
+//! Any [`ToDollcode`] value can be written into a [`Sink`], which
+//! `heapless::String<N>`, `heapless::Vec<char, N>` and `heapless::Vec<u8, N>`
+//! all are. A full sink returns [`DollcodeError::Overflow`].
+//!
 //! For String conversion, be aware that standard String operations will allocate:
 //!
 //! ```rust
@@ -88,23 +87,13 @@
 //! ## Text Encoding Example
 //!
 //! ```rust
-//! # use dollcode::text::{TextIterator, TextDecoder};
+//! # use dollcode::{text::encode_to_heapless, FromDollcode};
 //! # fn main() -> dollcode::Result<()> {
-//! let text = "Hi!";
-//! let mut encoded = heapless::Vec::<char, 128>::new();
-//!
-//! for segment in TextIterator::new(text) {
-//!     let segment = segment?;
-//!     encoded.extend_from_slice(segment.as_chars()).unwrap(); // Use unwrap instead of ? for Vec result
-//! }
-//!
-//! // Define the expected encoded string
-//! let expected = "▘▖▘▌\u{200d}▌▘▖▌\u{200d}▌▖▌\u{200d}";
+//! let encoded = encode_to_heapless::<128>("Hi!")?;
+//! assert_eq!(encoded, "▘▖▘▌\u{200d}▌▘▖▌\u{200d}▌▖▌\u{200d}");
 //!
-//! // Convert the encoded Vec to a String for comparison
-//! let encoded_str: String = encoded.iter().collect();
-//!
-//! assert_eq!(encoded_str, expected, "Encoded string does not match expected value");
+//! let decoded = heapless::String::<8>::decode_dollcode(&encoded)?;
+//! assert_eq!(decoded, "Hi!");
 //! # Ok(())
 //! # }
 //! ```
@@ -145,15 +134,133 @@
 //! - No heap allocations or system calls
 //! - Constant memory usage regardless of input size
 //!
+//! ## Feature Flags
+//!
+//! - `simd`: Classifies glyph bytes 16 at a time with SSE2 when decoding long text
+//!   sequences. Used on x86 targets with SSE2; other targets keep the scalar path.
+//! - `alloc`: Adds [`encode_to_string`], [`encode_text_to_string`],
+//!   [`decode_text_to_string`] and the string conversions in [`convert`], which
+//!   return owned strings.
+//! - `std`: Links the standard library for APIs that need it, such as the
+//!   `std::io` adapters in [`io`], and implements `std::error::Error` for
+//!   [`DollcodeError`]. Implies `alloc`.
+//! - `parallel`: Order-preserving parallel iterators over number slices and large
+//!   documents in the [`parallel`] module, built on `rayon`. Implies `std`.
+//! - `ufmt`: Implements `ufmt::uDisplay` for [`Dollcode`] and [`text::TextSegment`] and
+//!   adds [`text::write_text`] for encoding straight into a `uWrite` sink, avoiding
+//!   `core::fmt` on size-constrained firmware.
+//! - `serde`: Implements `Serialize` and `Deserialize` for [`Dollcode`], as the
+//!   glyph string in human-readable formats and as packed trits in binary ones,
+//!   and adds [`serde_u64`] and, with `std`, `serde_text` and `serde_bytes` for
+//!   storing single fields as dollcode strings with `#[serde(with = "...")]`.
+//! - `embedded-graphics`: Draws dollcode on displays as filled rectangles with
+//!   [`graphics::Glyphs`], for screens without a font containing the glyphs.
+//! - `embedded-hal`: [`serial::transmit`] for writing dollcode to an
+//!   `embedded-hal` 0.2 serial port.
+//! - `embedded-io`, `embedded-io-async`: Text adapters and number frames over the
+//!   `embedded-io` traits in [`embedded_io`], for piping dollcode through a UART.
+//! - `futures`, `tokio`: Async streams and sinks of newline-delimited messages in
+//!   [`framed`], over `futures` IO traits or as a `tokio_util` codec. Imply `std`.
+//! - `json`: Reads and writes `serde_json` documents as self-describing frames in
+//!   [`json`], for shipping configuration as dollcode. Implies `std` and `serde`.
+//! - `postcard`: Flavors in [`postcard`] for serializing with `postcard` straight to
+//!   glyphs and back, allocation-free. Implies `serde`.
+//! - `nom`, `winnow`: Parsers in [`nom`] and [`winnow`] for numbers, text segments
+//!   and frames, for embedding dollcode fields in protocol and log grammars.
+//! - `image`: [`render::image`] and [`render::png`] for rendering dollcode as an
+//!   `image` crate buffer or a PNG file, for shareable images and printable
+//!   labels. Implies `std`.
+//! - `bitvec`: Two bits per glyph conversions to and from `bitvec` bit slices in
+//!   [`bitvec`], for signal processing and error correction experiments.
+//! - `derive`: Re-exports the `DollcodeEncode` derive macro from [`message`], for
+//!   structs written as field-delimited frames.
+//! - `no-panic`: Fails the link step of optimized builds if any of the core
+//!   encode/decode entry points can still reach a panic.
+//!
+//! ## Verification
+//!
+//! Kani proof harnesses for bounds and overflow safety are compiled only under
+//! the verifier: `cargo kani -p dollcode`.
+//!
+//! [`to_dollcode`], [`from_dollcode`], the text iterators and [`text::StreamDecoder`]
+//! report every failure through [`DollcodeError`] and never panic. The
+//! `no-panic` feature proves this at link time for release builds:
+//! `cargo test -p dollcode --release --features no-panic --lib`.
+//!
 //! ## Examples
 //!
 //! More examples can be found in the documentation for individual functions.
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod armor;
+pub mod batch;
+#[cfg(feature = "bitvec")]
+pub mod bitvec;
+pub mod blink;
+pub mod braille;
+pub mod bytes;
+pub mod check;
+pub mod codec;
+pub mod convert;
+#[cfg(feature = "embedded-io")]
+pub mod embedded_io;
 pub mod error;
+pub mod format;
+#[cfg(any(feature = "futures", feature = "tokio"))]
+pub mod framed;
+pub mod gpio;
+#[cfg(feature = "embedded-graphics")]
+pub mod graphics;
+pub mod input;
+#[cfg(feature = "std")]
+pub mod io;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod matrix;
+pub mod message;
+pub mod multibase;
+pub mod ndef;
+#[cfg(feature = "nom")]
+pub mod nom;
+pub mod ocr;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+#[cfg(feature = "postcard")]
+pub mod postcard;
+pub mod render;
+#[cfg(feature = "serde")]
+mod serde_fields;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "embedded-hal")]
+pub mod serial;
+#[cfg(all(
+    feature = "simd",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+))]
+mod simd;
+pub mod stego;
+#[cfg(any(feature = "std", feature = "embedded-io"))]
+mod stream;
 /// Module for text encoding and decoding
 pub mod text;
+pub mod tone;
+#[cfg(kani)]
+mod verification;
+#[cfg(feature = "winnow")]
+pub mod winnow;
 
+pub use codec::{FromDollcode, Sink, ToDollcode};
 pub use error::{DollcodeError, Result};
+#[cfg(feature = "serde")]
+pub use serde_fields::serde_u64;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub use serde_fields::{serde_bytes, serde_text};
 
 /// Maximum length of a dollcode sequence
 pub const MAX_DOLLCODE_SIZE: usize = 41;
@@ -162,6 +269,75 @@ pub const MAX_DOLLCODE_SIZE: usize = 41;
 /// Maps 1->▖, 2->▘, 3->▌
 pub const DOLLCODE_CHAR_MAP: [char; 3] = ['▖', '▘', '▌'];
 
+/// Maps a dollcode character to its digit value (1-3).
+///
+/// This is the single lookup used by every encoder and decoder in the crate,
+/// backed by [`DOLLCODE_CHAR_MAP`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use dollcode::digit_value;
+/// assert_eq!(digit_value('▖'), Some(1));
+/// assert_eq!(digit_value('▌'), Some(3));
+/// assert_eq!(digit_value('A'), None);
+/// ```
+#[inline]
+pub const fn digit_value(c: char) -> Option<u8> {
+    let mut i = 0;
+    while i < DOLLCODE_CHAR_MAP.len() {
+        if DOLLCODE_CHAR_MAP[i] == c {
+            return Some(i as u8 + 1);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Maps a digit value (1-3) to its dollcode character.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dollcode::digit_char;
+/// assert_eq!(digit_char(2), Some('▘'));
+/// assert_eq!(digit_char(0), None);
+/// assert_eq!(digit_char(4), None);
+/// ```
+#[inline]
+pub const fn digit_char(digit: u8) -> Option<char> {
+    match digit {
+        1 => Some(DOLLCODE_CHAR_MAP[0]),
+        2 => Some(DOLLCODE_CHAR_MAP[1]),
+        3 => Some(DOLLCODE_CHAR_MAP[2]),
+        _ => None,
+    }
+}
+
+/// Spelled-out glyph names in value order, describing each glyph's shape:
+/// ▖ is a low block, ▘ a high block and ▌ a tall bar.
+///
+/// Used for screen-reader descriptions and other text-only renderings.
+pub const GLYPH_NAMES: [&str; 3] = ["low", "high", "tall"];
+
+/// Maps a dollcode character to its spelled-out name from [`GLYPH_NAMES`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use dollcode::glyph_name;
+/// assert_eq!(glyph_name('▖'), Some("low"));
+/// assert_eq!(glyph_name('▌'), Some("tall"));
+/// assert_eq!(glyph_name('\u{200D}'), None);
+/// ```
+#[inline]
+pub const fn glyph_name(c: char) -> Option<&'static str> {
+    match digit_value(c) {
+        Some(digit) => Some(GLYPH_NAMES[digit as usize - 1]),
+        None => None,
+    }
+}
+
 /// A fixed-size dollcode sequence with zero heap allocation
 #[derive(Debug, Clone, Copy)]
 pub struct Dollcode {
@@ -207,8 +383,9 @@ impl Dollcode {
     /// # }
     /// ```
     #[inline]
+    #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
     pub fn as_chars(&self) -> &[char] {
-        &self.chars[..self.len]
+        self.chars.get(..self.len).unwrap_or(&[])
     }
 
     /// Returns the number of characters in this sequence
@@ -247,8 +424,43 @@ impl Dollcode {
     pub fn is_empty(&self) -> bool {
         self.len == 0
     }
+
+    /// Copies the glyphs into a fixed-capacity string of `N` bytes.
+    ///
+    /// Each glyph takes 3 bytes of UTF-8, so `N` of `3 * MAX_DOLLCODE_SIZE`
+    /// holds any sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dollcode::{to_dollcode, Result};
+    /// # fn main() -> Result<()> {
+    /// let encoded = to_dollcode(42)?.to_heapless_string::<16>()?;
+    /// assert_eq!(encoded, "▖▖▖▌");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DollcodeError::Overflow`] if the glyphs do not fit.
+    pub fn to_heapless_string<const N: usize>(&self) -> Result<heapless::String<N>> {
+        let mut encoded = heapless::String::new();
+        self.encode_dollcode(&mut encoded)?;
+        Ok(encoded)
+    }
+}
+
+/// Sequences are equal when their glyphs are, whatever is left in the unused
+/// part of the buffer
+impl PartialEq for Dollcode {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_chars() == other.as_chars()
+    }
 }
 
+impl Eq for Dollcode {}
+
 /// Display implementation for Dollcode that renders the sequence as a string of box-drawing characters.
 ///
 /// # Examples
@@ -258,6 +470,11 @@ impl Dollcode {
 /// # fn main() -> Result<()> {
 /// let dollcode = to_dollcode(42)?;
 /// assert_eq!(dollcode.to_string(), "▖▖▖▌");
+/// assert_eq!(format!("[{dollcode:>6}]"), "[  ▖▖▖▌]");
+/// assert_eq!(format!("[{dollcode:*^8}]"), "[**▖▖▖▌**]");
+///
+/// let dollcode = to_dollcode(u32::MAX.into())?;
+/// assert_eq!(format!("{dollcode:#}"), "▌▖▘▌ ▖▌▘▘ ▖▌▖▘ ▘▖▖▖ ▖▖▌▌");
 /// # Ok(())
 /// # }
 /// ```
@@ -266,16 +483,126 @@ impl Dollcode {
 ///
 /// - Only includes the valid characters in the sequence
 /// - Empty sequences display as an empty string
-/// - No separators or additional formatting are added
+/// - No separators are added, except with the alternate flag `{:#}`, which
+///   writes the glyphs in groups of four separated by spaces as
+///   [`format::pretty`] does
+/// - Width, fill and alignment pad the output as for strings, counting
+///   glyphs and group separators, left-aligned by default
+/// - Glyphs are copied from a precomputed UTF-8 table into a stack buffer and
+///   written in chunks, avoiding a formatting call per character
 impl core::fmt::Display for Dollcode {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use core::fmt::Write;
+
+        let grouped = f.alternate();
+        let Some(width) = f.width() else {
+            return self.write_glyphs(f, grouped);
+        };
+
+        let groups = self.len.saturating_sub(1) / DISPLAY_GROUP_GLYPHS;
+        let shown = if grouped { self.len + groups } else { self.len };
+        let padding = width.saturating_sub(shown);
+        let (before, after) = match f.align() {
+            Some(core::fmt::Alignment::Right) => (padding, 0),
+            Some(core::fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            _ => (0, padding),
+        };
+
+        let fill = f.fill();
+        for _ in 0..before {
+            f.write_char(fill)?;
+        }
+        self.write_glyphs(f, grouped)?;
+        for _ in 0..after {
+            f.write_char(fill)?;
+        }
+        Ok(())
+    }
+}
+
+impl Dollcode {
+    /// Writes the glyphs of `Display`, in groups if `grouped`
+    fn write_glyphs(&self, f: &mut core::fmt::Formatter<'_>, grouped: bool) -> core::fmt::Result {
+        if grouped {
+            return format::pretty(self, &DISPLAY_GROUPS, f).map_err(|_| core::fmt::Error);
+        }
+
+        let mut buf = [[0u8; 3]; DISPLAY_CHUNK_GLYPHS];
+
+        for chunk in self.as_chars().chunks(DISPLAY_CHUNK_GLYPHS) {
+            for (dst, &c) in buf.iter_mut().zip(chunk) {
+                *dst = glyph_utf8(c).ok_or(core::fmt::Error)?;
+            }
+
+            let bytes = buf.get(..chunk.len()).unwrap_or(&[]).as_flattened();
+            f.write_str(core::str::from_utf8(bytes).map_err(|_| core::fmt::Error)?)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// `ufmt` counterpart of the `Display` implementation, producing the same output.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dollcode::{to_dollcode, Result};
+/// # fn main() -> Result<()> {
+/// let mut out = heapless::String::<16>::new();
+/// ufmt::uwrite!(out, "{}", to_dollcode(42)?).unwrap();
+/// assert_eq!(out, "▖▖▖▌");
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for Dollcode {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> core::result::Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
         for &c in self.as_chars() {
-            write!(f, "{}", c)?;
+            f.write_char(c)?;
         }
         Ok(())
     }
 }
 
+/// Number of glyphs batched into each `write_str` call by `Display`.
+const DISPLAY_CHUNK_GLYPHS: usize = 21;
+
+/// Glyphs per group of the alternate `Display` form.
+const DISPLAY_GROUP_GLYPHS: usize = 4;
+
+/// Formatting of the alternate `Display` form.
+const DISPLAY_GROUPS: format::PrettyOptions = format::PrettyOptions {
+    group: DISPLAY_GROUP_GLYPHS,
+    wrap: 0,
+    gutter: false,
+};
+
+/// UTF-8 encodings of [`DOLLCODE_CHAR_MAP`], in value order.
+const GLYPH_UTF8: [[u8; 3]; 3] = {
+    let mut table = [[0u8; 3]; 3];
+    let mut i = 0;
+    while i < DOLLCODE_CHAR_MAP.len() {
+        DOLLCODE_CHAR_MAP[i].encode_utf8(&mut table[i]);
+        i += 1;
+    }
+    table
+};
+
+/// Returns the UTF-8 encoding of a dollcode glyph.
+#[inline]
+fn glyph_utf8(c: char) -> Option<[u8; 3]> {
+    match digit_value(c)? {
+        1 => Some(GLYPH_UTF8[0]),
+        2 => Some(GLYPH_UTF8[1]),
+        3 => Some(GLYPH_UTF8[2]),
+        _ => None,
+    }
+}
+
 /// Encodes a number into dollcode using base-3.
 /// Each digit represents a value 1-3, mapped to ▖,▘,▌ respectively.
 ///
@@ -293,38 +620,43 @@ impl core::fmt::Display for Dollcode {
 /// # Errors
 ///
 /// Returns [`DollcodeError::Overflow`] if the number is too large to encode.
-pub fn to_dollcode(mut num: u64) -> Result<Dollcode> {
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn to_dollcode(num: u64) -> Result<Dollcode> {
+    let mut dollcode = Dollcode::new();
+    encode_into(num, &mut dollcode)?;
+    Ok(dollcode)
+}
+
+/// Encodes a number into an existing sequence, replacing its contents.
+///
+/// Lets batch callers reuse one buffer instead of zeroing a fresh one per value.
+pub(crate) fn encode_into(mut num: u64, dollcode: &mut Dollcode) -> Result<()> {
+    dollcode.len = 0;
     if num == 0 {
-        return Ok(Dollcode::new());
+        return Ok(());
     }
 
-    let mut dollcode = Dollcode::new();
     let mut output = [0u8; MAX_DOLLCODE_SIZE]; // Stack-allocated buffer
     let mut digits = 0;
 
     // Convert to base-3 with digits representing values 1-3
     while num > 0 {
-        if digits >= MAX_DOLLCODE_SIZE {
-            return Err(DollcodeError::Overflow);
-        }
+        let slot = output.get_mut(digits).ok_or(DollcodeError::Overflow)?;
 
         let rem = (num - 1) % 3; // Get 0-2 remainder
-        output[digits] = rem as u8 + 1; // Store remainder directly
-        num = (num - 1 - rem) / 3; // Reduce number
+        *slot = rem as u8 + 1; // Store remainder directly
+        num = (num - 1) / 3; // Reduce number
         digits += 1;
     }
 
-    // Map remainders to characters in reverse order with correct indexing
-    dollcode.len = digits;
-    for i in 0..digits {
-        let rem = output[digits - 1 - i];
-        if rem == 0 || rem > 3 {
-            return Err(DollcodeError::InvalidInput);
-        }
-        dollcode.chars[i] = DOLLCODE_CHAR_MAP[(rem - 1) as usize]; // Adjust index by subtracting 1
+    // Map remainders to characters in reverse order
+    let remainders = output.get(..digits).ok_or(DollcodeError::Overflow)?;
+    for (slot, &rem) in dollcode.chars.iter_mut().zip(remainders.iter().rev()) {
+        *slot = digit_char(rem).ok_or(DollcodeError::InvalidInput)?;
     }
+    dollcode.len = digits;
 
-    Ok(dollcode)
+    Ok(())
 }
 
 /// Decodes dollcode back to a number.
@@ -348,6 +680,7 @@ pub fn to_dollcode(mut num: u64) -> Result<Dollcode> {
 /// Returns:
 /// - [`DollcodeError::InvalidInput`] if the sequence contains invalid characters
 /// - [`DollcodeError::Overflow`] if the decoded value would overflow u64
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn from_dollcode(chars: &[char]) -> Result<u64> {
     if chars.is_empty() {
         return Ok(0);
@@ -361,19 +694,81 @@ pub fn from_dollcode(chars: &[char]) -> Result<u64> {
         result = result.checked_mul(3).ok_or(DollcodeError::Overflow)?;
 
         // Map character to value and add
-        let val = match c {
-            '▖' => 1, // Maps to 1
-            '▘' => 2, // Maps to 2
-            '▌' => 3, // Maps to 3
-            _ => return Err(DollcodeError::InvalidInput),
-        };
+        let val = digit_value(c).ok_or(DollcodeError::InvalidInput)?;
 
-        result = result.checked_add(val).ok_or(DollcodeError::Overflow)?;
+        result = result
+            .checked_add(val as u64)
+            .ok_or(DollcodeError::Overflow)?;
     }
 
     Ok(result)
 }
 
+/// Encodes a number into a new string.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dollcode::{encode_to_string, Result};
+/// # fn main() -> Result<()> {
+/// assert_eq!(encode_to_string(42)?, "▖▖▖▌");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns the errors of [`to_dollcode`].
+#[cfg(feature = "alloc")]
+pub fn encode_to_string(num: u64) -> Result<alloc::string::String> {
+    Ok(to_dollcode(num)?.as_chars().iter().collect())
+}
+
+/// Encodes text into a new string of delimited segments.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dollcode::{encode_text_to_string, Result};
+/// # fn main() -> Result<()> {
+/// assert_eq!(encode_text_to_string("Hi")?, "▘▖▘▌\u{200d}▌▘▖▌\u{200d}");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidChar`] for characters outside printable
+/// ASCII.
+#[cfg(feature = "alloc")]
+pub fn encode_text_to_string(input: &str) -> Result<alloc::string::String> {
+    let mut encoded = alloc::string::String::new();
+    for segment in text::TextIterator::new(input) {
+        encoded.extend(segment?.chars());
+    }
+    Ok(encoded)
+}
+
+/// Decodes delimited segments into a new string.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dollcode::{decode_text_to_string, Result};
+/// # fn main() -> Result<()> {
+/// assert_eq!(decode_text_to_string("▘▖▘▌\u{200d}▌▘▖▌\u{200d}")?, "Hi");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns the errors of [`text::TextDecoder`].
+#[cfg(feature = "alloc")]
+pub fn decode_text_to_string(input: &str) -> Result<alloc::string::String> {
+    text::TextDecoder::new(input).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -455,6 +850,85 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_digit_mapping() {
+        for (i, &c) in DOLLCODE_CHAR_MAP.iter().enumerate() {
+            let digit = i as u8 + 1;
+            assert_eq!(digit_value(c), Some(digit));
+            assert_eq!(digit_char(digit), Some(c));
+            assert_eq!(glyph_name(c), Some(GLYPH_NAMES[i]));
+        }
+
+        assert_eq!(digit_value('\u{200D}'), None);
+        assert_eq!(digit_value('a'), None);
+        assert_eq!(digit_char(0), None);
+        assert_eq!(digit_char(4), None);
+    }
+
+    #[test]
+    fn test_display_chunking() {
+        use core::fmt::Write;
+
+        for &num in &[0, 1, 42, 0xDEADBEEF, u64::MAX] {
+            let encoded = to_dollcode(num).unwrap();
+            let mut displayed: String<256> = String::new();
+            write!(displayed, "{}", encoded).unwrap();
+
+            let expected: String<256> = encoded.as_chars().iter().collect();
+            assert_eq!(displayed, expected, "Display mismatch for {}", num);
+        }
+    }
+
+    #[test]
+    fn test_display_padding() {
+        use core::fmt::Write;
+
+        let encoded = to_dollcode(42).unwrap();
+        let mut displayed: String<256> = String::new();
+        write!(
+            displayed,
+            "[{encoded:6}][{encoded:>6}][{encoded:^7}][{encoded:-<5}][{encoded:2}]"
+        )
+        .unwrap();
+        assert_eq!(displayed, "[▖▖▖▌  ][  ▖▖▖▌][ ▖▖▖▌  ][▖▖▖▌-][▖▖▖▌]");
+
+        let empty = to_dollcode(0).unwrap();
+        displayed.clear();
+        write!(displayed, "[{empty:>3}][{empty:#}]").unwrap();
+        assert_eq!(displayed, "[   ][]");
+    }
+
+    #[test]
+    fn test_display_grouped() {
+        use core::fmt::Write;
+
+        let mut displayed: String<256> = String::new();
+        for (num, expected) in [(42, "▖▖▖▌"), (5, "▖▘"), (121, "▖▖▖▖ ▖")] {
+            displayed.clear();
+            write!(displayed, "{:#}", to_dollcode(num).unwrap()).unwrap();
+            assert_eq!(displayed, expected);
+        }
+
+        // Separators count towards the width
+        displayed.clear();
+        write!(displayed, "[{:>#8}]", to_dollcode(121).unwrap()).unwrap();
+        assert_eq!(displayed, "[  ▖▖▖▖ ▖]");
+    }
+
+    #[test]
+    fn test_to_heapless_string() {
+        let encoded = to_dollcode(u64::MAX).unwrap();
+        let full = encoded
+            .to_heapless_string::<{ 3 * MAX_DOLLCODE_SIZE }>()
+            .unwrap();
+        assert_eq!(full.chars().count(), encoded.len());
+        assert!(matches!(
+            to_dollcode(42).unwrap().to_heapless_string::<11>(),
+            Err(DollcodeError::Overflow)
+        ));
+        assert_eq!(Dollcode::new().to_heapless_string::<0>().unwrap(), "");
+    }
+
     #[test]
     fn test_edge_cases() {
         // Test overflow handling
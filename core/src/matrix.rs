@@ -0,0 +1,580 @@
+//! Two-dimensional dollcode grids.
+//!
+//! A matrix lays out binary data as rows of glyphs of a fixed width, for
+//! payloads too long to read as one line. The data is prefixed with its
+//! length as two bytes, big-endian, written in the fixed-width
+//! [`bytes`](crate::bytes) encoding and flowed into [`Layout::columns`]
+//! glyphs per row, the last row padded with ▖.
+//!
+//! Each data row is framed by orientation marks, ▌ at its start and ▖ at its
+//! end, and the grid by a top row of ▌ and a bottom row of ▖. A grid read
+//! upside down, mirrored or both is turned back before decoding.
+//!
+//! With [`Layout::ecc`], each data row ends with a check glyph and a parity
+//! row follows the data rows, so every row and every column sums to zero in
+//! base 3. One wrong glyph anywhere in the data, check glyphs or parity row
+//! is corrected when decoding; more are detected in most cases. The inside
+//! of the top row is ▘ instead of ▌ to mark it.
+//!
+//! ```text
+//! ▌▌▌▌▌▌▌▌▌▌     top row
+//! ▌▖▖▖▖▖▖▖▖▖     ▌, 8 data glyphs, ▖
+//! ▌▖▖▖▌▖▖▌▌▖     the length 2, then "Hi", 6 glyphs a byte
+//! ▌▖▖▖▘▖▌▌▖▖
+//! ▖▖▖▖▖▖▖▖▖▖     bottom row
+//! ```
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{matrix::{decode, encode, size, Layout}, Result};
+//! # fn main() -> Result<()> {
+//! let layout = Layout { columns: 8, ecc: true };
+//! let mut grid = heapless::String::<256>::new();
+//! encode(b"Hi", &layout, &mut grid)?;
+//! assert_eq!(size(2, &layout)?, (11, 6));
+//!
+//! let rows: heapless::Vec<&str, 8> = grid.lines().collect();
+//! let mut data = [0u8; 8];
+//! let len = decode(&rows, &mut data)?;
+//! assert_eq!(&data[..len], b"Hi");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    bytes::{encode_byte, GLYPHS_PER_BYTE},
+    digit_value, DollcodeError, Result, DOLLCODE_CHAR_MAP,
+};
+use core::{fmt, str::Chars};
+
+/// Most data glyphs a row can hold
+pub const MAX_COLUMNS: usize = 128;
+
+/// Bytes of the length prefix
+const HEADER: usize = 2;
+/// Mark starting data rows, and the glyph of the top row
+const START: char = '▌';
+/// Mark ending data rows, and the glyph of the bottom row
+const END: char = '▖';
+/// Inside of the top row of grids with error correction
+const ECC: char = '▘';
+
+/// Width of grid rows and whether they carry check glyphs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    /// Data glyphs per row, from 1 to [`MAX_COLUMNS`]
+    pub columns: usize,
+    /// Adds check glyphs and a parity row correcting one wrong glyph
+    pub ecc: bool,
+}
+
+impl Layout {
+    /// 24 columns with error correction
+    pub const DEFAULT: Self = Self {
+        columns: 24,
+        ecc: true,
+    };
+
+    /// Glyphs per row, marks included
+    const fn width(&self) -> usize {
+        self.columns + 2 + self.ecc as usize
+    }
+
+    /// Rows holding the length prefix and `len` bytes
+    const fn data_rows(&self, len: usize) -> usize {
+        ((HEADER + len) * GLYPHS_PER_BYTE).div_ceil(self.columns)
+    }
+
+    fn check(&self, len: usize) -> Result<()> {
+        if self.columns == 0 || self.columns > MAX_COLUMNS {
+            return Err(DollcodeError::InvalidInput);
+        }
+        if len > usize::from(u16::MAX) {
+            return Err(DollcodeError::Overflow);
+        }
+        Ok(())
+    }
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Returns the width and height in glyphs of the grid [`encode`] writes for
+/// `len` bytes.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidInput`] if the layout has no columns or
+/// more than [`MAX_COLUMNS`], and [`DollcodeError::Overflow`] if `len` is
+/// over `u16::MAX`.
+pub fn size(len: usize, layout: &Layout) -> Result<(usize, usize)> {
+    layout.check(len)?;
+    let height = layout.data_rows(len) + 2 + layout.ecc as usize;
+    Ok((layout.width(), height))
+}
+
+/// Writes `data` as a grid to `out`, each row followed by a newline.
+///
+/// # Errors
+///
+/// Returns the errors of [`size`], and [`DollcodeError::Overflow`] if
+/// writing to `out` fails.
+pub fn encode<W: fmt::Write + ?Sized>(data: &[u8], layout: &Layout, out: &mut W) -> Result<()> {
+    let (width, _) = size(data.len(), layout)?;
+    let header = (data.len() as u16).to_be_bytes();
+    let mut stream = header
+        .iter()
+        .chain(data)
+        .flat_map(|&byte| encode_byte(byte))
+        .map(trit);
+
+    let fill = if layout.ecc { ECC } else { START };
+    put(out, START)?;
+    for _ in 2..width {
+        put(out, fill)?;
+    }
+    put(out, START)?;
+    put(out, '\n')?;
+
+    let mut parity = [0u8; MAX_COLUMNS + 1];
+    for _ in 0..layout.data_rows(data.len()) {
+        put(out, START)?;
+        let mut sum = 0;
+        for column in parity.iter_mut().take(layout.columns) {
+            let t = stream.next().unwrap_or(0);
+            sum = (sum + t) % 3;
+            *column = (*column + t) % 3;
+            put(out, glyph(t))?;
+        }
+        if layout.ecc {
+            let check = (3 - sum) % 3;
+            parity[layout.columns] = (parity[layout.columns] + check) % 3;
+            put(out, glyph(check))?;
+        }
+        put(out, END)?;
+        put(out, '\n')?;
+    }
+
+    if layout.ecc {
+        put(out, START)?;
+        for &column in &parity[..=layout.columns] {
+            put(out, glyph((3 - column) % 3))?;
+        }
+        put(out, END)?;
+        put(out, '\n')?;
+    }
+
+    for _ in 0..width {
+        put(out, END)?;
+    }
+    put(out, '\n')
+}
+
+/// Decodes the grid `rows` into `out`, returning the length of the data.
+///
+/// Rows are trimmed of surrounding whitespace, and may be given upside down
+/// or mirrored.
+///
+/// # Errors
+///
+/// Returns:
+/// - [`DollcodeError::InvalidChar`] for a character that is not a glyph,
+///   with its position counting the trimmed rows as given one after another
+/// - [`DollcodeError::InvalidInput`] if the rows are not a grid, or hold more
+///   errors than can be corrected
+/// - [`DollcodeError::Overflow`] if the data does not fit in `out`
+pub fn decode(rows: &[&str], out: &mut [u8]) -> Result<usize> {
+    let grid = &Grid::new(rows)?;
+    let data_rows = rows.len() - 2 - grid.layout.ecc as usize;
+    let mut trits = (1..=data_rows).flat_map(|i| {
+        let mut row = grid.row(i);
+        row.next();
+        row.take(grid.layout.columns)
+            .enumerate()
+            .map(move |(column, c)| grid.corrected(i, column, trit(c)))
+    });
+    let mut next_byte = || {
+        let mut value: u16 = 0;
+        for _ in 0..GLYPHS_PER_BYTE {
+            value = value * 3 + u16::from(trits.next().ok_or(DollcodeError::InvalidInput)?);
+        }
+        u8::try_from(value).map_err(|_| DollcodeError::InvalidInput)
+    };
+
+    let len = usize::from(u16::from_be_bytes([next_byte()?, next_byte()?]));
+    if data_rows != grid.layout.data_rows(len) {
+        return Err(DollcodeError::InvalidInput);
+    }
+    for byte in out.get_mut(..len).ok_or(DollcodeError::Overflow)? {
+        *byte = next_byte()?;
+    }
+    if trits.any(|t| t != 0) {
+        return Err(DollcodeError::InvalidInput);
+    }
+    Ok(len)
+}
+
+/// Writes one character of a grid
+fn put<W: fmt::Write + ?Sized>(out: &mut W, c: char) -> Result<()> {
+    out.write_char(c).map_err(|_| DollcodeError::Overflow)
+}
+
+/// Returns the base 3 digit of a glyph, as in the byte encoding
+fn trit(c: char) -> u8 {
+    digit_value(c).map_or(0, |digit| digit - 1)
+}
+
+fn glyph(trit: u8) -> char {
+    DOLLCODE_CHAR_MAP[usize::from(trit)]
+}
+
+/// Validated rows of a grid, in reading order
+struct Grid<'a> {
+    rows: &'a [&'a str],
+    layout: Layout,
+    /// Whether the rows were given bottom row first
+    flipped: bool,
+    /// Whether the rows were given right to left
+    mirrored: bool,
+    /// Row, column and amount to subtract of a wrong glyph
+    correction: Option<(usize, usize, u8)>,
+}
+
+impl<'a> Grid<'a> {
+    fn new(rows: &'a [&'a str]) -> Result<Self> {
+        let mut position = 0;
+        for row in rows {
+            for c in row.trim().chars() {
+                digit_value(c).ok_or(DollcodeError::InvalidChar(c, position))?;
+                position += 1;
+            }
+        }
+
+        let [first, .., last] = rows else {
+            return Err(DollcodeError::InvalidInput);
+        };
+        let is_bottom = |row: &str| row.trim().chars().all(|c| c == END);
+        let flipped = is_bottom(first) && !is_bottom(last);
+        let top = if flipped { last } else { first }.trim();
+
+        let mut inside = top.chars();
+        let (Some(START), Some(START)) = (inside.next(), inside.next_back()) else {
+            return Err(DollcodeError::InvalidInput);
+        };
+        let ecc = match inside.clone().next() {
+            Some(START) => false,
+            Some(ECC) => true,
+            _ => return Err(DollcodeError::InvalidInput),
+        };
+        let fill = if ecc { ECC } else { START };
+        let width = top.chars().count();
+        let columns = width - 2 - ecc as usize;
+        if !inside.all(|c| c == fill) || columns > MAX_COLUMNS || rows.len() < 3 + ecc as usize {
+            return Err(DollcodeError::InvalidInput);
+        }
+
+        let second = rows[if flipped { rows.len() - 2 } else { 1 }].trim();
+        let mirrored = match second.chars().next() {
+            Some(START) => false,
+            Some(END) => true,
+            _ => return Err(DollcodeError::InvalidInput),
+        };
+
+        let mut grid = Self {
+            rows,
+            layout: Layout { columns, ecc },
+            flipped,
+            mirrored,
+            correction: None,
+        };
+        grid.correction = grid.check(width)?;
+        Ok(grid)
+    }
+
+    /// Returns the glyphs of row `i` in reading order
+    fn row(&self, i: usize) -> Oriented<'a> {
+        let index = if self.flipped {
+            self.rows.len() - 1 - i
+        } else {
+            i
+        };
+        Oriented {
+            chars: self.rows[index].trim().chars(),
+            reversed: self.mirrored,
+        }
+    }
+
+    /// Checks the shape of the rows and, with error correction, finds the
+    /// wrong glyph
+    fn check(&self, width: usize) -> Result<Option<(usize, usize, u8)>> {
+        let last = self.rows.len() - 1;
+        let mut columns = [0u8; MAX_COLUMNS + 1];
+        let mut wrong_row = None;
+        for i in 0..=last {
+            let mut row = self.row(i);
+            if row.chars.as_str().chars().count() != width {
+                return Err(DollcodeError::InvalidInput);
+            }
+            if i == last && !row.all(|c| c == END) {
+                return Err(DollcodeError::InvalidInput);
+            }
+            if i == 0 || i == last {
+                continue;
+            }
+            if (row.next(), row.next_back()) != (Some(START), Some(END)) {
+                return Err(DollcodeError::InvalidInput);
+            }
+            let sum = row.zip(&mut columns).fold(0, |sum, (c, column)| {
+                *column = (*column + trit(c)) % 3;
+                (sum + trit(c)) % 3
+            });
+            if self.layout.ecc && sum != 0 {
+                if wrong_row.is_some() {
+                    return Err(DollcodeError::InvalidInput);
+                }
+                wrong_row = Some((i, sum));
+            }
+        }
+        if !self.layout.ecc {
+            return Ok(None);
+        }
+
+        let mut wrong_columns = columns.iter().enumerate().filter(|&(_, &sum)| sum != 0);
+        match (wrong_row, wrong_columns.next(), wrong_columns.next()) {
+            (None, None, _) => Ok(None),
+            (Some((row, sum)), Some((column, &column_sum)), None) if sum == column_sum => {
+                Ok(Some((row, column, sum)))
+            }
+            _ => Err(DollcodeError::InvalidInput),
+        }
+    }
+
+    /// Returns the digit at row `i` and `column`, corrected if wrong
+    fn corrected(&self, i: usize, column: usize, trit: u8) -> u8 {
+        match self.correction {
+            Some((row, wrong, sum)) if (row, wrong) == (i, column) => (trit + 3 - sum) % 3,
+            _ => trit,
+        }
+    }
+}
+
+/// Characters of a row, right to left if mirrored
+struct Oriented<'a> {
+    chars: Chars<'a>,
+    reversed: bool,
+}
+
+impl Iterator for Oriented<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.reversed {
+            self.chars.next_back()
+        } else {
+            self.chars.next()
+        }
+    }
+}
+
+impl DoubleEndedIterator for Oriented<'_> {
+    fn next_back(&mut self) -> Option<char> {
+        if self.reversed {
+            self.chars.next()
+        } else {
+            self.chars.next_back()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::{String, Vec};
+
+    const PLAIN: Layout = Layout {
+        columns: 8,
+        ecc: false,
+    };
+    const CHECKED: Layout = Layout {
+        columns: 8,
+        ecc: true,
+    };
+
+    fn grid(data: &[u8], layout: &Layout) -> String<8192> {
+        let mut out = String::new();
+        encode(data, layout, &mut out).unwrap();
+        out
+    }
+
+    fn decoded(rows: &[&str]) -> Result<Vec<u8, 64>> {
+        let mut out = [0u8; 64];
+        let len = decode(rows, &mut out)?;
+        Ok(Vec::from_slice(&out[..len]).unwrap())
+    }
+
+    #[test]
+    fn test_layout() {
+        let out = grid(b"Hi", &PLAIN);
+        let rows: Vec<&str, 8> = out.lines().collect();
+        assert_eq!(
+            rows,
+            [
+                "▌▌▌▌▌▌▌▌▌▌",
+                "▌▖▖▖▖▖▖▖▖▖",
+                "▌▖▖▖▌▖▖▌▌▖",
+                "▌▖▖▖▘▖▌▌▖▖",
+                "▖▖▖▖▖▖▖▖▖▖",
+            ]
+        );
+        assert_eq!(size(2, &PLAIN).unwrap(), (10, 5));
+        assert_eq!(decoded(&rows).unwrap(), b"Hi");
+
+        let out = grid(b"Hi", &CHECKED);
+        let rows: Vec<&str, 8> = out.lines().collect();
+        assert_eq!(rows[0], "▌▘▘▘▘▘▘▘▘▘▌");
+        assert_eq!(rows.len(), size(2, &CHECKED).unwrap().1);
+        assert!(rows.iter().all(|row| row.chars().count() == 11));
+        assert_eq!(decoded(&rows).unwrap(), b"Hi");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let data: Vec<u8, 64> = (0..=63).map(|i| i * 4).collect();
+        for columns in [1, 5, 24, MAX_COLUMNS] {
+            for ecc in [false, true] {
+                let layout = Layout { columns, ecc };
+                for len in [0, 1, 17, 64] {
+                    let out = grid(&data[..len], &layout);
+                    let rows: Vec<&str, 1024> = out.lines().collect();
+                    assert_eq!(size(len, &layout).unwrap().1, rows.len());
+                    assert_eq!(decoded(&rows).unwrap(), data[..len]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_orientation() {
+        let out = grid(b"dollcode", &CHECKED);
+        let rows: Vec<&str, 16> = out.lines().collect();
+
+        let mut flipped = rows.clone();
+        flipped.reverse();
+        assert_eq!(decoded(&flipped).unwrap(), b"dollcode");
+
+        let mirrored: Vec<String<64>, 16> =
+            rows.iter().map(|row| row.chars().rev().collect()).collect();
+        let mut mirrored: Vec<&str, 16> = mirrored.iter().map(|row| row.as_str()).collect();
+        assert_eq!(decoded(&mirrored).unwrap(), b"dollcode");
+        mirrored.reverse();
+        assert_eq!(decoded(&mirrored).unwrap(), b"dollcode");
+
+        let indented: Vec<String<64>, 16> = rows
+            .iter()
+            .map(|row| {
+                let mut indented = String::try_from("  ").unwrap();
+                indented.push_str(row).unwrap();
+                indented.push('\r').unwrap();
+                indented
+            })
+            .collect();
+        let indented: Vec<&str, 16> = indented.iter().map(|row| row.as_str()).collect();
+        assert_eq!(decoded(&indented).unwrap(), b"dollcode");
+    }
+
+    /// Replaces the glyph at `row` and `column` with the next one
+    fn corrupt(rows: &mut Vec<String<64>, 16>, row: usize, column: usize) {
+        let next = |c| glyph((trit(c) + 1) % 3);
+        rows[row] = rows[row]
+            .chars()
+            .enumerate()
+            .map(|(i, c)| if i == column { next(c) } else { c })
+            .collect();
+    }
+
+    #[test]
+    fn test_error_correction() {
+        let out = grid(b"dollcode", &CHECKED);
+        let rows: Vec<String<64>, 16> = out.lines().map(|row| row.try_into().unwrap()).collect();
+        let height = rows.len();
+
+        // Data, check glyphs and the parity row
+        for (row, column) in [(1, 1), (3, 8), (4, 9), (height - 2, 5)] {
+            let mut damaged = rows.clone();
+            corrupt(&mut damaged, row, column);
+            let damaged: Vec<&str, 16> = damaged.iter().map(|row| row.as_str()).collect();
+            assert_eq!(decoded(&damaged).unwrap(), b"dollcode");
+        }
+
+        let mut damaged = rows.clone();
+        corrupt(&mut damaged, 2, 3);
+        corrupt(&mut damaged, 4, 6);
+        let damaged: Vec<&str, 16> = damaged.iter().map(|row| row.as_str()).collect();
+        assert!(matches!(
+            decoded(&damaged),
+            Err(DollcodeError::InvalidInput)
+        ));
+
+        // Without error correction, a wrong glyph is not caught by marks
+        let out = grid(b"dollcode", &PLAIN);
+        let mut rows: Vec<String<64>, 16> =
+            out.lines().map(|row| row.try_into().unwrap()).collect();
+        corrupt(&mut rows, 3, 1);
+        let rows: Vec<&str, 16> = rows.iter().map(|row| row.as_str()).collect();
+        assert_ne!(decoded(&rows).ok().as_deref(), Some(&b"dollcode"[..]));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut out = String::<16>::new();
+        for layout in [
+            Layout {
+                columns: 0,
+                ecc: false,
+            },
+            Layout {
+                columns: MAX_COLUMNS + 1,
+                ecc: false,
+            },
+        ] {
+            assert!(matches!(
+                encode(b"", &layout, &mut out),
+                Err(DollcodeError::InvalidInput)
+            ));
+        }
+        assert!(matches!(
+            size(usize::from(u16::MAX) + 1, &PLAIN),
+            Err(DollcodeError::Overflow)
+        ));
+        assert!(matches!(
+            encode(b"Hi", &PLAIN, &mut out),
+            Err(DollcodeError::Overflow)
+        ));
+
+        let out = grid(b"Hi", &PLAIN);
+        let rows: Vec<&str, 8> = out.lines().collect();
+        assert!(matches!(
+            decode(&rows, &mut [0u8; 1]),
+            Err(DollcodeError::Overflow)
+        ));
+        assert!(matches!(
+            decoded(&["▌▌▌▌▌▌▌▌▌▌", "▌▖▖x"]),
+            Err(DollcodeError::InvalidChar('x', 13))
+        ));
+        // Missing rows, a short row, a missing mark and an extra row
+        for damaged in [
+            &rows[..0],
+            &rows[..2],
+            &rows[1..],
+            &[rows[0], rows[1], "▌▖▖▖▌▖▖▌▌", rows[3], rows[4]],
+            &[rows[0], rows[1], "▘▖▖▖▌▖▖▌▌▖", rows[3], rows[4]],
+            &[rows[0], rows[1], rows[2], rows[3], rows[1], rows[4]],
+        ] {
+            assert!(matches!(decoded(damaged), Err(DollcodeError::InvalidInput)));
+        }
+    }
+}
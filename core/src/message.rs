@@ -0,0 +1,341 @@
+//! Typed messages as field-delimited dollcode.
+//!
+//! A message is its fields in declaration order, each written as a frame:
+//! a dollcode number followed by [`DELIMITER`]. Types implementing
+//! [`DollcodeEncode`] write and read their frames; with the `derive` feature
+//! the trait can be derived for structs whose fields implement it.
+//!
+//! | Field | Frames |
+//! |-------|--------|
+//! | `u8` to `u64`, `usize` | the number |
+//! | `i8` to `i64`, `isize` | the zigzag mapped number, so -1 is 1 and 1 is 2 |
+//! | `bool` | 0 or 1 |
+//! | `char` | the code point |
+//! | `heapless::String<N>`, `String` | the length in bytes, then the UTF-8 bytes in the fixed-width [`bytes`](crate::bytes) encoding |
+//!
+//! Nothing describes the fields, so a message is read back only as the type
+//! that wrote it. Whitespace between glyphs is ignored when reading.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # #[cfg(feature = "derive")]
+//! # fn main() -> dollcode::Result<()> {
+//! use dollcode::message::{self, DollcodeEncode};
+//!
+//! #[derive(Debug, PartialEq, DollcodeEncode)]
+//! struct Reading {
+//!     sensor: u8,
+//!     delta: i16,
+//! }
+//!
+//! let reading = Reading { sensor: 3, delta: -2 };
+//! let mut encoded = heapless::String::<32>::new();
+//! reading.encode(&mut encoded).unwrap();
+//! assert_eq!(encoded, "▌\u{200D}▌\u{200D}");
+//!
+//! assert_eq!(message::from_str::<Reading>(&encoded)?, reading);
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "derive"))]
+//! # fn main() {}
+//! ```
+
+use crate::{
+    bytes::{encode_byte, GLYPHS_PER_BYTE},
+    digit_value,
+    text::DELIMITER,
+    to_dollcode, DollcodeError, Result,
+};
+use core::{fmt, str::Chars};
+
+/// Derives [`DollcodeEncode`] for structs whose fields all implement it
+#[cfg(feature = "derive")]
+pub use dollcode_derive::DollcodeEncode;
+
+/// A type written as one or more frames
+pub trait DollcodeEncode: Sized {
+    /// Writes the frames of `self` to `out`
+    fn encode<W: fmt::Write + ?Sized>(&self, out: &mut W) -> fmt::Result;
+
+    /// Reads a value from the next frames of `fields`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DollcodeError::InvalidChar`] for characters other than
+    /// glyphs, [`DollcodeError::Overflow`] for numbers that do not fit the
+    /// field, and [`DollcodeError::InvalidInput`] for missing frames or
+    /// values the field cannot hold.
+    fn decode(fields: &mut Fields<'_>) -> Result<Self>;
+}
+
+/// Reads a whole message from `input`
+///
+/// # Errors
+///
+/// Returns the errors of [`DollcodeEncode::decode`], and
+/// [`DollcodeError::InvalidInput`] if anything but whitespace follows the
+/// message.
+pub fn from_str<T: DollcodeEncode>(input: &str) -> Result<T> {
+    let mut fields = Fields::new(input);
+    let value = T::decode(&mut fields)?;
+    fields.end()?;
+    Ok(value)
+}
+
+/// Reader over the frames of a message
+#[derive(Debug, Clone)]
+pub struct Fields<'a> {
+    chars: Chars<'a>,
+    /// Characters read so far, for error positions
+    position: usize,
+}
+
+impl<'a> Fields<'a> {
+    /// Creates a reader at the start of `input`
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            chars: input.chars(),
+            position: 0,
+        }
+    }
+
+    /// Checks that nothing but whitespace is left
+    pub fn end(&mut self) -> Result<()> {
+        match self.next() {
+            Some(_) => Err(DollcodeError::InvalidInput),
+            None => Ok(()),
+        }
+    }
+
+    /// Reads a number frame
+    pub fn word(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        loop {
+            let c = self.next().ok_or(DollcodeError::InvalidInput)?;
+            if c == DELIMITER {
+                return Ok(value);
+            }
+            let digit = self.digit(c)?;
+            value = value
+                .checked_mul(3)
+                .and_then(|v| v.checked_add(digit.into()))
+                .ok_or(DollcodeError::Overflow)?;
+        }
+    }
+
+    /// Reads one byte in the fixed-width encoding
+    pub fn byte(&mut self) -> Result<u8> {
+        let mut value = 0u16;
+        for _ in 0..GLYPHS_PER_BYTE {
+            let c = self.next().ok_or(DollcodeError::InvalidInput)?;
+            value = value * 3 + u16::from(self.digit(c)? - 1);
+        }
+        u8::try_from(value).map_err(|_| DollcodeError::InvalidInput)
+    }
+
+    /// Next character that is not whitespace
+    fn next(&mut self) -> Option<char> {
+        self.chars.by_ref().find(|c| {
+            self.position += 1;
+            !c.is_whitespace()
+        })
+    }
+
+    fn digit(&self, c: char) -> Result<u8> {
+        digit_value(c).ok_or(DollcodeError::InvalidChar(c, self.position - 1))
+    }
+}
+
+/// Writes a number frame
+fn write_word<W: fmt::Write + ?Sized>(out: &mut W, value: u64) -> fmt::Result {
+    let dollcode = to_dollcode(value).map_err(|_| fmt::Error)?;
+    for &c in dollcode.as_chars() {
+        out.write_char(c)?;
+    }
+    out.write_char(DELIMITER)
+}
+
+/// Writes a length frame followed by the bytes of `s`
+fn write_str<W: fmt::Write + ?Sized>(out: &mut W, s: &str) -> fmt::Result {
+    write_word(out, s.len() as u64)?;
+    for &byte in s.as_bytes() {
+        for c in encode_byte(byte) {
+            out.write_char(c)?;
+        }
+    }
+    Ok(())
+}
+
+/// Zigzag maps small negative and positive numbers to small words
+fn zigzag(v: i64) -> u64 {
+    ((v << 1) ^ (v >> 63)) as u64
+}
+
+fn unzigzag(n: u64) -> i64 {
+    (n >> 1) as i64 ^ -((n & 1) as i64)
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {$(
+        impl DollcodeEncode for $t {
+            fn encode<W: fmt::Write + ?Sized>(&self, out: &mut W) -> fmt::Result {
+                write_word(out, *self as u64)
+            }
+
+            fn decode(fields: &mut Fields<'_>) -> Result<Self> {
+                Self::try_from(fields.word()?).map_err(|_| DollcodeError::Overflow)
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {$(
+        impl DollcodeEncode for $t {
+            fn encode<W: fmt::Write + ?Sized>(&self, out: &mut W) -> fmt::Result {
+                write_word(out, zigzag(*self as i64))
+            }
+
+            fn decode(fields: &mut Fields<'_>) -> Result<Self> {
+                Self::try_from(unzigzag(fields.word()?)).map_err(|_| DollcodeError::Overflow)
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64, usize);
+impl_signed!(i8, i16, i32, i64, isize);
+
+impl DollcodeEncode for bool {
+    fn encode<W: fmt::Write + ?Sized>(&self, out: &mut W) -> fmt::Result {
+        write_word(out, (*self).into())
+    }
+
+    fn decode(fields: &mut Fields<'_>) -> Result<Self> {
+        match fields.word()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(DollcodeError::InvalidInput),
+        }
+    }
+}
+
+impl DollcodeEncode for char {
+    fn encode<W: fmt::Write + ?Sized>(&self, out: &mut W) -> fmt::Result {
+        write_word(out, u32::from(*self).into())
+    }
+
+    fn decode(fields: &mut Fields<'_>) -> Result<Self> {
+        u32::try_from(fields.word()?)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or(DollcodeError::InvalidInput)
+    }
+}
+
+impl<const N: usize> DollcodeEncode for heapless::String<N> {
+    fn encode<W: fmt::Write + ?Sized>(&self, out: &mut W) -> fmt::Result {
+        write_str(out, self)
+    }
+
+    fn decode(fields: &mut Fields<'_>) -> Result<Self> {
+        let len = usize::try_from(fields.word()?).map_err(|_| DollcodeError::Overflow)?;
+        if len > N {
+            return Err(DollcodeError::Overflow);
+        }
+        let mut bytes = heapless::Vec::<u8, N>::new();
+        for _ in 0..len {
+            bytes
+                .push(fields.byte()?)
+                .map_err(|_| DollcodeError::Overflow)?;
+        }
+        Self::from_utf8(bytes).map_err(|_| DollcodeError::InvalidInput)
+    }
+}
+
+#[cfg(feature = "std")]
+impl DollcodeEncode for std::string::String {
+    fn encode<W: fmt::Write + ?Sized>(&self, out: &mut W) -> fmt::Result {
+        write_str(out, self)
+    }
+
+    fn decode(fields: &mut Fields<'_>) -> Result<Self> {
+        let len = usize::try_from(fields.word()?).map_err(|_| DollcodeError::Overflow)?;
+        let bytes = (0..len)
+            .map(|_| fields.byte())
+            .collect::<Result<std::vec::Vec<u8>>>()?;
+        Self::from_utf8(bytes).map_err(|_| DollcodeError::InvalidInput)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::String;
+
+    fn encoded<T: DollcodeEncode>(value: &T) -> String<256> {
+        let mut out = String::new();
+        value.encode(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_fields() {
+        assert_eq!(encoded(&0u8), "\u{200D}");
+        assert_eq!(encoded(&42u64), "▖▖▖▌\u{200D}");
+        assert_eq!(encoded(&-1i32), "▖\u{200D}");
+        assert_eq!(encoded(&true), "▖\u{200D}");
+        // A length of 1, then the byte 0x2A
+        assert_eq!(
+            encoded(&String::<4>::try_from("*").unwrap()),
+            "▖\u{200D}▖▖▘▘▌▖"
+        );
+
+        assert_eq!(from_str::<i64>(&encoded(&i64::MIN)).unwrap(), i64::MIN);
+        assert_eq!(from_str::<u64>(&encoded(&u64::MAX)).unwrap(), u64::MAX);
+        assert_eq!(from_str::<char>(&encoded(&'é')).unwrap(), 'é');
+        let text = String::<16>::try_from("dollcode ▖").unwrap();
+        assert_eq!(from_str::<String<16>>(&encoded(&text)).unwrap(), text);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            from_str::<u8>(""),
+            Err(DollcodeError::InvalidInput)
+        ));
+        assert!(matches!(
+            from_str::<u8>("▖▖"),
+            Err(DollcodeError::InvalidInput)
+        ));
+        assert!(matches!(
+            from_str::<u8>("▖\u{200D} ▖\u{200D}"),
+            Err(DollcodeError::InvalidInput)
+        ));
+        assert!(matches!(
+            from_str::<u8>("▖x\u{200D}"),
+            Err(DollcodeError::InvalidChar('x', 1))
+        ));
+        assert!(matches!(
+            from_str::<u8>(&encoded(&256u16)),
+            Err(DollcodeError::Overflow)
+        ));
+        assert!(matches!(
+            from_str::<bool>("▘\u{200D}"),
+            Err(DollcodeError::InvalidInput)
+        ));
+
+        // Longer than the string's capacity
+        let text = String::<8>::try_from("dollcode").unwrap();
+        assert!(matches!(
+            from_str::<String<4>>(&encoded(&text)),
+            Err(DollcodeError::Overflow)
+        ));
+        // 0xFF 0xFF is not UTF-8
+        assert!(matches!(
+            from_str::<String<4>>("▘\u{200D}▘▖▖▘▘▖▘▖▖▘▘▖"),
+            Err(DollcodeError::InvalidInput)
+        ));
+    }
+}
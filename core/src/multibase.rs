@@ -0,0 +1,109 @@
+//! Self-describing binary data with a multibase-style prefix.
+//!
+//! [Multibase](https://github.com/multiformats/multibase) strings start with
+//! one character naming their base, such as `z` for base58btc or `b` for
+//! base32. Dollcode has no registered code, so [`PREFIX`] is a provisional
+//! one: `▞`, a block element that is neither a glyph nor any registered
+//! prefix. A prefixed string can sit next to base58 or base32 identifiers and
+//! still be told apart from them.
+//!
+//! The data after the prefix is in the fixed-width [`bytes`](crate::bytes)
+//! encoding.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{multibase, Result};
+//! # fn main() -> Result<()> {
+//! let mut encoded = heapless::String::<64>::new();
+//! multibase::encode_write(&[0x2A], &mut encoded).unwrap();
+//! assert_eq!(encoded, "▞▖▖▘▘▌▖");
+//! assert!(multibase::is_dollcode(&encoded));
+//! assert!(!multibase::is_dollcode("zQmYwAPJzv5CZsnA"));
+//!
+//! let mut decoded = [0u8; 4];
+//! let len = multibase::decode_mut(&encoded, &mut decoded)?;
+//! assert_eq!(&decoded[..len], [0x2A]);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    bytes::{ByteEncoder, DOLLCODE},
+    DollcodeError, Result,
+};
+use core::fmt;
+
+/// Provisional multibase prefix of dollcode strings
+pub const PREFIX: char = '▞';
+
+/// Returns the encoded data after the prefix, or `None` if `input` is not a
+/// prefixed dollcode string.
+pub fn strip_prefix(input: &str) -> Option<&str> {
+    input.strip_prefix(PREFIX)
+}
+
+/// Returns true if `input` starts with the dollcode prefix.
+pub fn is_dollcode(input: &str) -> bool {
+    strip_prefix(input).is_some()
+}
+
+/// Writes the prefix and the encoding of `data`.
+pub fn encode_write<W: fmt::Write + ?Sized>(data: &[u8], output: &mut W) -> fmt::Result {
+    output.write_char(PREFIX)?;
+    ByteEncoder::new(data).try_for_each(|c| output.write_char(c))
+}
+
+/// Returns the prefixed encoding of `data` as a new string.
+#[cfg(feature = "std")]
+pub fn encode(data: &[u8]) -> std::string::String {
+    core::iter::once(PREFIX)
+        .chain(ByteEncoder::new(data))
+        .collect()
+}
+
+/// Decodes a prefixed string into `output`, returning the number of bytes
+/// written.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidInput`] if the prefix is missing, and the
+/// errors of [`Encoding::decode_mut`](crate::bytes::Encoding::decode_mut)
+/// otherwise.
+pub fn decode_mut(input: &str, output: &mut [u8]) -> Result<usize> {
+    let encoded = strip_prefix(input).ok_or(DollcodeError::InvalidInput)?;
+    DOLLCODE.decode_mut(encoded.as_bytes(), output)
+}
+
+/// Decodes a prefixed string into a new vector.
+///
+/// # Errors
+///
+/// Returns the errors of [`decode_mut`].
+#[cfg(feature = "std")]
+pub fn decode(input: &str) -> Result<std::vec::Vec<u8>> {
+    let encoded = strip_prefix(input).ok_or(DollcodeError::InvalidInput)?;
+    DOLLCODE.decode(encoded.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix() {
+        assert!(crate::digit_value(PREFIX).is_none());
+        assert!(!PREFIX.is_ascii());
+        assert_eq!(strip_prefix("▞▖▖▘▘▌▖"), Some("▖▖▘▘▌▖"));
+        assert_eq!(strip_prefix("▖▖▘▘▌▖"), None);
+        assert!(!is_dollcode(
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+        ));
+
+        let mut output = [0u8; 1];
+        assert!(matches!(
+            decode_mut("▖▖▘▘▌▖", &mut output),
+            Err(DollcodeError::InvalidInput)
+        ));
+    }
+}
@@ -0,0 +1,250 @@
+//! NFC Data Exchange Format (NDEF) records carrying dollcode.
+//!
+//! Dollcode is stored on NFC tags as an NFC Forum external type record of
+//! type [`RECORD_TYPE`], whose payload is the UTF-8 of framed dollcode such
+//! as a [`message`](crate::message). [`encode_record`] writes a message of
+//! one such record, and [`parse_record`] finds the record in a message read
+//! from a tag, skipping others such as an Android Application Record.
+//!
+//! | Byte | Value |
+//! |------|-------|
+//! | 0 | `0xD4`: first and last record, short, external type; `0xC4` for payloads over 255 bytes |
+//! | 1 | length of the type, 20 |
+//! | 2 | length of the payload, or 4 bytes big-endian for payloads over 255 bytes |
+//! | 3.. | the type, then the payload |
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{ndef::{encode_record, parse_record, record_len}, Result};
+//! # fn main() -> Result<()> {
+//! let frames = "▖▖▖▌\u{200D}▘\u{200D}";
+//! let mut tag = [0u8; 64];
+//! let len = encode_record(frames, &mut tag)?;
+//! assert_eq!(len, record_len(frames));
+//! assert_eq!(&tag[..3], [0xD4, 20, 21]);
+//!
+//! assert_eq!(parse_record(&tag[..len])?, frames);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{digit_value, text::DELIMITER, DollcodeError, Result};
+
+/// Type of dollcode records, an NFC Forum external type name
+pub const RECORD_TYPE: &[u8] = b"v01dlabs.sh:dollcode";
+
+/// Flag of the first record of a message
+const MB: u8 = 0x80;
+/// Flag of the last record of a message
+const ME: u8 = 0x40;
+/// Flag of a record chunk other than the last
+const CF: u8 = 0x20;
+/// Flag of a record with a one-byte payload length
+const SR: u8 = 0x10;
+/// Flag of a record with an ID
+const IL: u8 = 0x08;
+/// Type name format of NFC Forum external types
+const TNF_EXTERNAL: u8 = 0x04;
+
+/// Returns the length of the record [`encode_record`] writes for `encoded`
+pub const fn record_len(encoded: &str) -> usize {
+    let length_bytes = if encoded.len() <= 0xFF { 1 } else { 4 };
+    2 + length_bytes + RECORD_TYPE.len() + encoded.len()
+}
+
+/// Writes an NDEF message of one dollcode record with `encoded` as its
+/// payload into `buf`, returning the length written.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidChar`] if `encoded` holds anything but
+/// glyphs and delimiters, and [`DollcodeError::Overflow`] if the record does
+/// not fit in `buf` or its payload is over `u32::MAX` bytes.
+pub fn encode_record(encoded: &str, buf: &mut [u8]) -> Result<usize> {
+    validate(encoded)?;
+    let payload = encoded.as_bytes();
+    let len = record_len(encoded);
+    let buf = buf.get_mut(..len).ok_or(DollcodeError::Overflow)?;
+
+    let (header, rest) = buf.split_at_mut(2);
+    header[1] = RECORD_TYPE.len() as u8;
+    let rest = match u8::try_from(payload.len()) {
+        Ok(short) => {
+            header[0] = MB | ME | SR | TNF_EXTERNAL;
+            rest[0] = short;
+            &mut rest[1..]
+        }
+        Err(_) => {
+            header[0] = MB | ME | TNF_EXTERNAL;
+            let long = u32::try_from(payload.len()).map_err(|_| DollcodeError::Overflow)?;
+            rest[..4].copy_from_slice(&long.to_be_bytes());
+            &mut rest[4..]
+        }
+    };
+    let (record_type, rest) = rest.split_at_mut(RECORD_TYPE.len());
+    record_type.copy_from_slice(RECORD_TYPE);
+    rest.copy_from_slice(payload);
+    Ok(len)
+}
+
+/// Returns the payload of the first dollcode record in the NDEF message
+/// `message`.
+///
+/// Records of other types are skipped, and the type is compared ignoring
+/// case as NFC Forum external types are.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidInput`] if the message is malformed or
+/// chunked, or has no dollcode record, and [`DollcodeError::InvalidChar`] if
+/// the payload holds anything but glyphs and delimiters.
+pub fn parse_record(message: &[u8]) -> Result<&str> {
+    let mut rest = message;
+    loop {
+        let record;
+        (record, rest) = Record::parse(rest).ok_or(DollcodeError::InvalidInput)?;
+        if record.header & CF != 0 {
+            return Err(DollcodeError::InvalidInput);
+        }
+        if record.header & 0x07 == TNF_EXTERNAL
+            && record.record_type.eq_ignore_ascii_case(RECORD_TYPE)
+        {
+            let payload =
+                core::str::from_utf8(record.payload).map_err(|_| DollcodeError::InvalidInput)?;
+            validate(payload)?;
+            return Ok(payload);
+        }
+        if record.header & ME != 0 {
+            return Err(DollcodeError::InvalidInput);
+        }
+    }
+}
+
+/// Checks that `encoded` holds only glyphs and delimiters
+fn validate(encoded: &str) -> Result<()> {
+    match encoded
+        .chars()
+        .enumerate()
+        .find(|&(_, c)| c != DELIMITER && digit_value(c).is_none())
+    {
+        Some((position, c)) => Err(DollcodeError::InvalidChar(c, position)),
+        None => Ok(()),
+    }
+}
+
+/// The fields of a record that matter here
+struct Record<'a> {
+    header: u8,
+    record_type: &'a [u8],
+    payload: &'a [u8],
+}
+
+impl<'a> Record<'a> {
+    /// Splits the first record off `message`
+    fn parse(message: &'a [u8]) -> Option<(Self, &'a [u8])> {
+        let (&header, rest) = message.split_first()?;
+        let (&type_len, rest) = rest.split_first()?;
+        let (payload_len, rest) = if header & SR != 0 {
+            let (&len, rest) = rest.split_first()?;
+            (usize::from(len), rest)
+        } else {
+            let (len, rest) = rest.split_first_chunk::<4>()?;
+            (usize::try_from(u32::from_be_bytes(*len)).ok()?, rest)
+        };
+        let (id_len, rest) = if header & IL != 0 {
+            let (&len, rest) = rest.split_first()?;
+            (usize::from(len), rest)
+        } else {
+            (0, rest)
+        };
+        let (record_type, rest) = rest.split_at_checked(usize::from(type_len))?;
+        let (_id, rest) = rest.split_at_checked(id_len)?;
+        let (payload, rest) = rest.split_at_checked(payload_len)?;
+        let record = Self {
+            header,
+            record_type,
+            payload,
+        };
+        Some((record, rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_record() {
+        let mut buf = [0u8; 64];
+        let len = encode_record("▖\u{200D}", &mut buf).unwrap();
+        assert_eq!(len, 29);
+        assert_eq!(buf[..3], [0xD4, 20, 6]);
+        assert_eq!(&buf[3..23], RECORD_TYPE);
+        assert_eq!(&buf[23..len], "▖\u{200D}".as_bytes());
+        assert_eq!(parse_record(&buf[..len]).unwrap(), "▖\u{200D}");
+
+        let len = encode_record("", &mut buf).unwrap();
+        assert_eq!(parse_record(&buf[..len]).unwrap(), "");
+    }
+
+    #[test]
+    fn test_long_record() {
+        let mut encoded = heapless::String::<300>::new();
+        for _ in 0..100 {
+            encoded.push('▌').unwrap();
+        }
+        let mut buf = [0u8; 400];
+        let len = encode_record(&encoded, &mut buf).unwrap();
+        assert_eq!(len, 2 + 4 + 20 + 300);
+        assert_eq!(buf[..6], [0xC4, 20, 0, 0, 1, 44]);
+        assert_eq!(parse_record(&buf[..len]).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_other_records() {
+        // An Android Application Record with an ID, then the dollcode
+        // record with its type in upper case
+        let mut message = heapless::Vec::<u8, 128>::new();
+        message.extend_from_slice(&[0x9C, 15, 3, 1, b'i']).unwrap();
+        message.extend_from_slice(b"android.com:pkg").unwrap();
+        message.extend_from_slice(b"org").unwrap();
+        message.extend_from_slice(&[0x54, 20, 3]).unwrap();
+        message.extend_from_slice(b"V01DLABS.SH:DOLLCODE").unwrap();
+        message.extend_from_slice("▘".as_bytes()).unwrap();
+        assert_eq!(parse_record(&message).unwrap(), "▘");
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut buf = [0u8; 64];
+        assert!(matches!(
+            encode_record("▖x", &mut buf),
+            Err(DollcodeError::InvalidChar('x', 1))
+        ));
+        assert!(matches!(
+            encode_record("▖", &mut buf[..25]),
+            Err(DollcodeError::Overflow)
+        ));
+
+        let len = encode_record("▖▘", &mut buf).unwrap();
+        // Truncated, chunked, and of another type only
+        let mut chunked = buf;
+        chunked[0] |= CF;
+        let mut other = buf;
+        other[3] = b'w';
+        for message in [&buf[..len - 1], &chunked[..len], &other[..len], &[]] {
+            assert!(matches!(
+                parse_record(message),
+                Err(DollcodeError::InvalidInput)
+            ));
+        }
+
+        let mut invalid = buf;
+        invalid[len - 3..len].copy_from_slice(b"abc");
+        assert!(matches!(
+            parse_record(&invalid[..len]),
+            Err(DollcodeError::InvalidChar('a', 1))
+        ));
+    }
+}
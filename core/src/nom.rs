@@ -0,0 +1,106 @@
+//! Parsers for embedding dollcode in `nom` grammars.
+//!
+//! Each parser is generic over the grammar's error type and reads from
+//! complete `&str` input:
+//!
+//! - [`dollcode_number`]: a run of glyphs, as a number
+//! - [`dollcode_text_segment`]: up to five glyphs and a delimiter, as a
+//!   printable ASCII character
+//! - [`dollcode_frame`]: glyphs and a delimiter, as a number, the framing used
+//!   by [`message`](crate::message)
+//!
+//! # Examples
+//!
+//! ```rust
+//! use dollcode::nom::dollcode_number;
+//! use nom::{bytes::complete::tag, sequence::preceded, IResult, Parser};
+//!
+//! fn request_id(input: &str) -> IResult<&str, u64> {
+//!     preceded(tag("id="), dollcode_number).parse(input)
+//! }
+//!
+//! assert_eq!(request_id("id=▖▖▖▌ ok"), Ok((" ok", 42)));
+//! ```
+
+use crate::{digit_value, text::TextDecoder, text::DELIMITER, FromDollcode};
+use ::nom::{
+    bytes::complete::{take_while, take_while1, take_while_m_n},
+    character::complete::char,
+    combinator::map_opt,
+    error::ParseError,
+    sequence::terminated,
+    IResult, Parser,
+};
+
+fn is_glyph(c: char) -> bool {
+    digit_value(c).is_some()
+}
+
+/// Parses a run of glyphs as a number. Fails without consuming input if
+/// there are no glyphs or the number does not fit in a `u64`.
+pub fn dollcode_number<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, u64, E> {
+    map_opt(take_while1(is_glyph), |glyphs| {
+        u64::decode_dollcode(glyphs).ok()
+    })
+    .parse(input)
+}
+
+/// Parses one delimited text segment as the character it encodes
+pub fn dollcode_text_segment<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, char, E> {
+    map_opt(
+        terminated(take_while_m_n(1, 5, is_glyph), char(DELIMITER)),
+        |glyphs| TextDecoder::new(glyphs).next().and_then(Result::ok),
+    )
+    .parse(input)
+}
+
+/// Parses a frame, glyphs followed by a delimiter, as a number. A lone
+/// delimiter is the frame of 0.
+pub fn dollcode_frame<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, u64, E> {
+    map_opt(
+        terminated(take_while(is_glyph), char(DELIMITER)),
+        |glyphs| u64::decode_dollcode(glyphs).ok(),
+    )
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::nom::{error::Error, multi::many1_count, Err};
+
+    #[test]
+    fn test_parsers() {
+        assert_eq!(dollcode_number::<()>("▖▖▖▌,"), Ok((",", 42)));
+        assert_eq!(dollcode_text_segment::<()>("▘▖▘▌\u{200d}▌"), Ok(("▌", 'H')));
+        assert_eq!(
+            dollcode_frame::<()>("▖▖▖▌\u{200d}\u{200d}"),
+            Ok(("\u{200d}", 42))
+        );
+        assert_eq!(dollcode_frame::<()>("\u{200d}"), Ok(("", 0)));
+
+        let text = "▘▖▘▌\u{200d}▌▘▖▌\u{200d}!";
+        assert_eq!(
+            many1_count(dollcode_text_segment::<()>).parse(text),
+            Ok(("!", 2))
+        );
+    }
+
+    #[test]
+    fn test_failures() {
+        assert!(matches!(
+            dollcode_number::<Error<&str>>("x"),
+            Err(Err::Error(_))
+        ));
+        let overflow: heapless::String<128> = ['▌'; 41].iter().collect();
+        assert!(matches!(
+            dollcode_number::<Error<&str>>(&overflow),
+            Err(Err::Error(e)) if e.input == overflow.as_str()
+        ));
+        // No delimiter, and too many glyphs for a segment
+        assert!(dollcode_frame::<()>("▖▖").is_err());
+        assert!(dollcode_text_segment::<()>("▖▖▖▖▖▖\u{200d}").is_err());
+    }
+}
@@ -0,0 +1,214 @@
+//! High-contrast alphabet for printing and scanning.
+//!
+//! At small sizes OCR reads the quarter blocks ▖ and ▘ as noise or as each
+//! other, and cannot see the zero-width delimiter at all. This profile uses
+//! block characters that differ in more than a corner, and a visible
+//! delimiter:
+//!
+//! | Dollcode | OCR | |
+//! |----------|-----|-|
+//! | ▖ | █ | full block |
+//! | ▘ | ▀ | upper half block |
+//! | ▌ | ▌ | left half block, unchanged |
+//! | [`DELIMITER`](crate::text::DELIMITER) | · | middle dot |
+//!
+//! [`from_ocr`] normalizes what OCR engines return for scans of these
+//! characters: similar blocks such as ▇ or ■, bars, dots and bullets are
+//! read as the closest character, and whitespace, which scans add at line
+//! breaks and between blocks, is skipped.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{ocr::{from_ocr, Ocr}, text::TextDecoder, Result, ToDollcode};
+//! # fn main() -> Result<()> {
+//! let mut printed = heapless::String::<64>::new();
+//! "Hi".encode_dollcode(&mut Ocr::new(&mut printed))?;
+//! assert_eq!(printed, "▀█▀▌·▌▀█▌·");
+//!
+//! // As scanned, with a line break and look-alikes
+//! let mut glyphs = heapless::String::<64>::new();
+//! from_ocr("▀■▀▌•\n▌▀▇| .", &mut glyphs)?;
+//! let text: heapless::String<8> = TextDecoder::new(&glyphs).collect::<Result<_>>()?;
+//! assert_eq!(text, "Hi");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{digit_char, digit_value, text::DELIMITER, DollcodeError, Result, Sink};
+
+/// Characters for digits 1, 2 and 3, as ▖, ▘ and ▌
+pub const OCR_CHAR_MAP: [char; 3] = ['█', '▀', '▌'];
+
+/// Character standing for the delimiter
+pub const OCR_DELIMITER: char = '·';
+
+/// Returns the OCR character for a glyph or the delimiter
+pub const fn ocr_char(c: char) -> Option<char> {
+    match c {
+        DELIMITER => Some(OCR_DELIMITER),
+        _ => match digit_value(c) {
+            Some(digit) => Some(OCR_CHAR_MAP[(digit - 1) as usize]),
+            None => None,
+        },
+    }
+}
+
+/// Returns the glyph or delimiter a scanned character is read as
+///
+/// Besides the characters of [`OCR_CHAR_MAP`] and [`OCR_DELIMITER`], this
+/// accepts what OCR commonly returns for them.
+pub const fn glyph_char(c: char) -> Option<char> {
+    match c {
+        '█' | '▇' | '▉' | '■' | '◼' => digit_char(1),
+        '▀' | '▔' => digit_char(2),
+        '▌' | '▋' | '▍' | '|' | '│' => digit_char(3),
+        '·' | '.' | '•' | '∙' | '⋅' => Some(DELIMITER),
+        _ => None,
+    }
+}
+
+/// Sink writing the OCR characters of the glyphs and delimiters pushed into
+/// it to another sink
+#[derive(Debug)]
+pub struct Ocr<'a, S: ?Sized> {
+    sink: &'a mut S,
+    /// Characters pushed so far, for error positions
+    position: usize,
+}
+
+impl<'a, S: Sink + ?Sized> Ocr<'a, S> {
+    /// Creates a sink writing OCR characters to `sink`
+    pub fn new(sink: &'a mut S) -> Self {
+        Self { sink, position: 0 }
+    }
+}
+
+impl<S: Sink + ?Sized> Sink for Ocr<'_, S> {
+    /// Writes the OCR character of a glyph or delimiter
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DollcodeError::InvalidChar`] for any other character, and
+    /// the errors of the wrapped sink.
+    fn push(&mut self, c: char) -> Result<()> {
+        let printed = ocr_char(c).ok_or(DollcodeError::InvalidChar(c, self.position))?;
+        self.sink.push(printed)?;
+        self.position += 1;
+        Ok(())
+    }
+}
+
+/// Writes the OCR characters of the dollcode `input` to `sink`.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidChar`] for characters that are neither
+/// glyphs nor delimiters, and the errors of `sink`.
+pub fn to_ocr(input: &str, sink: &mut impl Sink) -> Result<()> {
+    for (position, c) in input.chars().enumerate() {
+        sink.push(ocr_char(c).ok_or(DollcodeError::InvalidChar(c, position))?)?;
+    }
+    Ok(())
+}
+
+/// Writes the glyphs and delimiters of the scanned `input` to `sink`,
+/// skipping whitespace.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidChar`] for characters [`glyph_char`] does
+/// not read, and the errors of `sink`.
+pub fn from_ocr(input: &str, sink: &mut impl Sink) -> Result<()> {
+    for (position, c) in input.chars().enumerate() {
+        if c.is_whitespace() {
+            continue;
+        }
+        sink.push(glyph_char(c).ok_or(DollcodeError::InvalidChar(c, position))?)?;
+    }
+    Ok(())
+}
+
+/// Converts dollcode to OCR characters in a new string.
+///
+/// # Errors
+///
+/// Returns the errors of [`to_ocr`].
+#[cfg(feature = "alloc")]
+pub fn encode(input: &str) -> Result<alloc::string::String> {
+    let mut printed = alloc::string::String::new();
+    to_ocr(input, &mut printed)?;
+    Ok(printed)
+}
+
+/// Converts scanned OCR characters to dollcode in a new string.
+///
+/// # Errors
+///
+/// Returns the errors of [`from_ocr`].
+#[cfg(feature = "alloc")]
+pub fn decode(input: &str) -> Result<alloc::string::String> {
+    let mut glyphs = alloc::string::String::new();
+    from_ocr(input, &mut glyphs)?;
+    Ok(glyphs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{from_dollcode, ToDollcode, DOLLCODE_CHAR_MAP};
+    use heapless::{String, Vec};
+
+    #[test]
+    fn test_char_mapping() {
+        for (glyph, printed) in DOLLCODE_CHAR_MAP.into_iter().zip(OCR_CHAR_MAP) {
+            assert_eq!(ocr_char(glyph), Some(printed));
+            assert_eq!(glyph_char(printed), Some(glyph));
+        }
+        assert_eq!(ocr_char(DELIMITER), Some(OCR_DELIMITER));
+        assert_eq!(glyph_char(OCR_DELIMITER), Some(DELIMITER));
+        assert_eq!(ocr_char(' '), None);
+        // The quarter blocks are not read back
+        assert_eq!(glyph_char('▖'), None);
+        assert_eq!(glyph_char('▘'), None);
+    }
+
+    #[test]
+    fn test_scan_normalization() {
+        let mut printed = String::<256>::new();
+        u64::MAX
+            .encode_dollcode(&mut Ocr::new(&mut printed))
+            .unwrap();
+        let mut glyphs = Vec::<char, 64>::new();
+        from_ocr(&printed, &mut glyphs).unwrap();
+        assert_eq!(from_dollcode(&glyphs).unwrap(), u64::MAX);
+
+        let mut glyphs = String::<64>::new();
+        from_ocr(" ▇▉■ ◼\r\n▔▀\t▋▍|│ .•∙⋅", &mut glyphs).unwrap();
+        assert_eq!(glyphs, "▖▖▖▖▘▘▌▌▌▌\u{200D}\u{200D}\u{200D}\u{200D}");
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut output = String::<16>::new();
+        assert!(matches!(
+            to_ocr("▖x", &mut output),
+            Err(DollcodeError::InvalidChar('x', 1))
+        ));
+        assert!(matches!(
+            from_ocr("█ ▖", &mut output),
+            Err(DollcodeError::InvalidChar('▖', 2))
+        ));
+        let mut ocr = Ocr::new(&mut output);
+        ocr.push('▖').unwrap();
+        assert!(matches!(
+            ocr.push(' '),
+            Err(DollcodeError::InvalidChar(' ', 1))
+        ));
+        let mut small = String::<4>::new();
+        assert!(matches!(
+            to_ocr("▖▘", &mut small),
+            Err(DollcodeError::Overflow)
+        ));
+    }
+}
@@ -0,0 +1,215 @@
+//! Parallel batch encoding and decoding built on [`rayon`].
+//!
+//! Number slices map one-to-one onto indexed parallel iterators. Text inputs
+//! are split into chunks of roughly [`CHUNK_SIZE`] bytes on segment
+//! boundaries, processed in parallel, and flattened back in input order, so
+//! collecting any of these iterators yields the same sequence as the
+//! sequential APIs.
+//!
+//! Requires the `parallel` feature, which pulls in `std`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{parallel, Result};
+//! # use rayon::prelude::*;
+//! # fn main() -> Result<()> {
+//! let values: Vec<u64> = (0..10_000).collect();
+//! let encoded = parallel::par_encode_all(&values).collect::<Result<Vec<_>>>()?;
+//! assert_eq!(encoded[42].to_string(), "▖▖▖▌");
+//!
+//! let text: String = parallel::par_encode_text("Hello, World!")
+//!     .map(|segment| segment.map(|s| s.as_chars().iter().collect::<String>()))
+//!     .collect::<Result<String>>()?;
+//! let decoded = parallel::par_decode_text(&text).collect::<Result<String>>()?;
+//! assert_eq!(decoded, "Hello, World!");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    digit_value, from_dollcode,
+    text::{decode_segment, TextIterator, TextSegment, DELIMITER},
+    to_dollcode, Dollcode, Result,
+};
+use rayon::prelude::*;
+use std::vec::Vec;
+
+/// Target size in bytes of each chunk of a text input processed in parallel.
+pub const CHUNK_SIZE: usize = 16 * 1024;
+
+/// Encodes every number in `values` in parallel, preserving order.
+pub fn par_encode_all(
+    values: &[u64],
+) -> impl IndexedParallelIterator<Item = Result<Dollcode>> + '_ {
+    values.par_iter().map(|&value| to_dollcode(value))
+}
+
+/// Decodes every sequence in `sequences` in parallel, preserving order.
+pub fn par_decode_all<S>(sequences: &[S]) -> impl IndexedParallelIterator<Item = Result<u64>> + '_
+where
+    S: AsRef<[char]> + Sync,
+{
+    sequences
+        .par_iter()
+        .map(|chars| from_dollcode(chars.as_ref()))
+}
+
+/// Encodes a large text document in parallel, yielding segments in input order.
+///
+/// Error positions are character offsets into the whole document, matching
+/// [`TextIterator`].
+pub fn par_encode_text(text: &str) -> impl ParallelIterator<Item = Result<TextSegment>> + '_ {
+    let chunks = split_chunks(text, |rest| {
+        let mut end = CHUNK_SIZE.min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end += 1;
+        }
+        end
+    });
+    let offsets = prefix_offsets(&chunks, |chunk| chunk.chars().count());
+
+    chunks
+        .into_par_iter()
+        .zip(offsets)
+        .flat_map_iter(|(chunk, offset)| TextIterator::with_position(chunk, offset))
+}
+
+/// Decodes a large delimited dollcode document in parallel, yielding
+/// characters in input order.
+///
+/// Unlike [`TextDecoder`](crate::text::TextDecoder), which stops at the first
+/// empty segment, empty segments are skipped so every chunk decodes
+/// independently. Error positions count glyphs from the start of the document.
+pub fn par_decode_text(encoded: &str) -> impl ParallelIterator<Item = Result<char>> + '_ {
+    let chunks = split_chunks(encoded, |rest| {
+        if rest.len() <= CHUNK_SIZE {
+            return rest.len();
+        }
+        let mut start = CHUNK_SIZE;
+        while !rest.is_char_boundary(start) {
+            start += 1;
+        }
+        rest[start..]
+            .find(DELIMITER)
+            .map_or(rest.len(), |idx| start + idx + DELIMITER.len_utf8())
+    });
+    let offsets = prefix_offsets(&chunks, |chunk| {
+        chunk.chars().filter(|&c| digit_value(c).is_some()).count()
+    });
+
+    chunks
+        .into_par_iter()
+        .zip(offsets)
+        .flat_map_iter(|(chunk, offset)| {
+            let mut position = offset;
+            chunk
+                .split(DELIMITER)
+                .filter(|segment| !segment.is_empty())
+                .map(move |segment| decode_segment(segment, &mut position))
+        })
+}
+
+/// Splits `input` into consecutive chunks, `chunk_end` returning the length of
+/// the next chunk from the remaining input.
+fn split_chunks(mut input: &str, chunk_end: impl Fn(&str) -> usize) -> Vec<&str> {
+    let mut chunks = Vec::with_capacity(input.len() / CHUNK_SIZE + 1);
+    while !input.is_empty() {
+        let (chunk, rest) = input.split_at(chunk_end(input));
+        chunks.push(chunk);
+        input = rest;
+    }
+    chunks
+}
+
+/// Computes each chunk's starting offset from per-chunk counts taken in parallel.
+fn prefix_offsets(chunks: &[&str], count: impl Fn(&str) -> usize + Sync) -> Vec<usize> {
+    let counts: Vec<usize> = chunks.par_iter().map(|chunk| count(chunk)).collect();
+    counts
+        .iter()
+        .scan(0, |offset, &n| {
+            let start = *offset;
+            *offset += n;
+            Some(start)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{text::TextDecoder, DollcodeError};
+    use std::string::String;
+
+    fn encode_sequential(text: &str) -> String {
+        TextIterator::new(text)
+            .flat_map(|segment| segment.unwrap().as_chars().to_vec())
+            .collect()
+    }
+
+    fn long_text() -> String {
+        "The quick brown fox jumps over the lazy dog. 0123456789 ~!@#$%^&*() ".repeat(1000)
+    }
+
+    #[test]
+    fn test_par_encode_all_order() {
+        let values: Vec<u64> = (0..10_000).chain([u64::MAX]).collect();
+        let encoded: Vec<Dollcode> = par_encode_all(&values).map(Result::unwrap).collect();
+
+        for (&value, dollcode) in values.iter().zip(&encoded) {
+            assert_eq!(dollcode.as_chars(), to_dollcode(value).unwrap().as_chars());
+        }
+    }
+
+    #[test]
+    fn test_par_decode_all_order() {
+        let values: Vec<u64> = (0..10_000).collect();
+        let sequences: Vec<Vec<char>> = values
+            .iter()
+            .map(|&v| to_dollcode(v).unwrap().as_chars().to_vec())
+            .collect();
+
+        let decoded: Vec<u64> = par_decode_all(&sequences).map(Result::unwrap).collect();
+        assert_eq!(decoded, values);
+
+        let invalid = [['x'], ['▖']];
+        let results: Vec<_> = par_decode_all(&invalid).collect();
+        assert!(matches!(results[0], Err(DollcodeError::InvalidInput)));
+        assert_eq!(results[1].as_ref().unwrap(), &1);
+    }
+
+    #[test]
+    fn test_par_text_roundtrip() {
+        let text = long_text();
+        assert!(text.len() > CHUNK_SIZE * 2);
+
+        let encoded: String = par_encode_text(&text)
+            .map(|segment| segment.unwrap().as_chars().iter().collect::<String>())
+            .collect();
+        assert_eq!(encoded, encode_sequential(&text));
+
+        let decoded: String = par_decode_text(&encoded).map(Result::unwrap).collect();
+        let sequential: String = TextDecoder::new(&encoded).map(Result::unwrap).collect();
+        assert_eq!(decoded, text);
+        assert_eq!(decoded, sequential);
+    }
+
+    #[test]
+    fn test_par_error_positions() {
+        let mut text = long_text();
+        text.push('☺');
+        let position = text.chars().count() - 1;
+
+        let error = par_encode_text(&text).find_first(|r| r.is_err()).unwrap();
+        assert!(matches!(error, Err(DollcodeError::InvalidChar('☺', pos)) if pos == position));
+
+        let mut encoded = encode_sequential(&long_text());
+        let glyphs = encoded.chars().filter(|&c| c != DELIMITER).count();
+        encoded.push('X');
+
+        let error = par_decode_text(&encoded)
+            .find_first(|r| r.is_err())
+            .unwrap();
+        assert!(matches!(error, Err(DollcodeError::InvalidChar('X', pos)) if pos == glyphs));
+    }
+}
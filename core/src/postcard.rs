@@ -0,0 +1,195 @@
+//! `postcard` flavors that write and read its bytes as dollcode.
+//!
+//! [`Encoder`] is a serialization flavor that writes every byte postcard
+//! produces as a fixed-width glyph group from the [`bytes`](crate::bytes)
+//! module into a [`Sink`]. [`Decoder`] reads the groups back, decoding into a
+//! caller-provided buffer so borrowed fields such as `&str` keep working.
+//! Neither allocates, so structs go to dollcode and back on `no_std` targets.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Reading<'a> {
+//!     sensor: &'a str,
+//!     value: u16,
+//! }
+//!
+//! let reading = Reading { sensor: "t0", value: 300 };
+//! let encoded: heapless::String<128> =
+//!     dollcode::postcard::to_sink(&reading, heapless::String::new()).unwrap();
+//!
+//! let mut buf = [0u8; 16];
+//! let decoded: Reading = dollcode::postcard::from_str(&encoded, &mut buf).unwrap();
+//! assert_eq!(decoded, reading);
+//! ```
+
+use crate::{
+    bytes::{encode_byte, GLYPHS_PER_BYTE},
+    digit_value, Sink,
+};
+use ::postcard::{de_flavors, ser_flavors, Error, Result};
+use serde::{Deserialize, Serialize};
+
+/// Serialization flavor writing glyph groups into a [`Sink`]
+#[derive(Debug)]
+pub struct Encoder<S> {
+    sink: S,
+}
+
+impl<S: Sink> Encoder<S> {
+    /// Creates a flavor appending to `sink`
+    pub fn new(sink: S) -> Self {
+        Self { sink }
+    }
+}
+
+impl<S: Sink> ser_flavors::Flavor for Encoder<S> {
+    type Output = S;
+
+    fn try_push(&mut self, data: u8) -> Result<()> {
+        self.sink
+            .push_all(encode_byte(data))
+            .map_err(|_| Error::SerializeBufferFull)
+    }
+
+    fn finalize(self) -> Result<S> {
+        Ok(self.sink)
+    }
+}
+
+/// Deserialization flavor reading glyph groups, with a buffer for the bytes
+/// postcard borrows
+#[derive(Debug)]
+pub struct Decoder<'de> {
+    input: &'de str,
+    buf: &'de mut [u8],
+}
+
+impl<'de> Decoder<'de> {
+    /// Creates a flavor over `input`. Borrowed strings and byte slices are
+    /// decoded into `buf`, which must be large enough to hold them.
+    pub fn new(input: &'de str, buf: &'de mut [u8]) -> Self {
+        Self { input, buf }
+    }
+
+    /// Decodes the next glyph group
+    fn next_byte(&mut self) -> Result<u8> {
+        let mut chars = self.input.chars();
+        let mut value = 0u16;
+        for _ in 0..GLYPHS_PER_BYTE {
+            let c = chars.next().ok_or(Error::DeserializeUnexpectedEnd)?;
+            let digit = digit_value(c).ok_or(Error::DeserializeBadEncoding)?;
+            value = value * 3 + u16::from(digit - 1);
+        }
+        self.input = chars.as_str();
+        u8::try_from(value).map_err(|_| Error::DeserializeBadEncoding)
+    }
+}
+
+impl<'de> de_flavors::Flavor<'de> for Decoder<'de> {
+    type Remainder = &'de str;
+    type Source = &'de str;
+
+    fn pop(&mut self) -> Result<u8> {
+        self.next_byte()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.input.chars().count() / GLYPHS_PER_BYTE)
+    }
+
+    fn try_take_n(&mut self, ct: usize) -> Result<&'de [u8]> {
+        if ct > self.buf.len() {
+            return Err(Error::DeserializeUnexpectedEnd);
+        }
+        let (taken, rest) = core::mem::take(&mut self.buf).split_at_mut(ct);
+        for byte in taken.iter_mut() {
+            *byte = self.next_byte()?;
+        }
+        self.buf = rest;
+        Ok(taken)
+    }
+
+    fn finalize(self) -> Result<&'de str> {
+        Ok(self.input)
+    }
+}
+
+/// Serializes `value` with postcard into `sink` as dollcode
+///
+/// # Errors
+///
+/// Returns postcard's errors, and [`Error::SerializeBufferFull`] if the sink
+/// is full.
+pub fn to_sink<T: Serialize + ?Sized, S: Sink>(value: &T, sink: S) -> Result<S> {
+    ::postcard::serialize_with_flavor(value, Encoder::new(sink))
+}
+
+/// Deserializes a value written by [`to_sink`], ignoring anything after it
+///
+/// # Errors
+///
+/// Returns postcard's errors, [`Error::DeserializeBadEncoding`] for
+/// characters that are not glyphs, and [`Error::DeserializeUnexpectedEnd`]
+/// if the input ends early or `buf` is too small for borrowed fields.
+pub fn from_str<'de, T: Deserialize<'de>>(input: &'de str, buf: &'de mut [u8]) -> Result<T> {
+    take_from_str(input, buf).map(|(value, _)| value)
+}
+
+/// Deserializes a value written by [`to_sink`], returning it with the
+/// glyphs that follow it
+///
+/// # Errors
+///
+/// Returns the same errors as [`from_str`].
+pub fn take_from_str<'de, T: Deserialize<'de>>(
+    input: &'de str,
+    buf: &'de mut [u8],
+) -> Result<(T, &'de str)> {
+    let mut deserializer = ::postcard::Deserializer::from_flavor(Decoder::new(input, buf));
+    let value = T::deserialize(&mut deserializer)?;
+    Ok((value, deserializer.finalize()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::String;
+
+    #[test]
+    fn test_bytes_as_glyphs() {
+        // postcard writes 42u8 as the single byte 0x2A
+        let encoded: String<64> = to_sink(&42u8, String::new()).unwrap();
+        assert_eq!(encoded, "▖▖▘▘▌▖");
+
+        let mut buf = [];
+        let (value, rest) = take_from_str::<u8>("▖▖▘▘▌▖▘", &mut buf).unwrap();
+        assert_eq!((value, rest), (42, "▘"));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            to_sink(&1000u32, String::<6>::new()),
+            Err(Error::SerializeBufferFull)
+        ));
+
+        let mut buf = [0u8; 1];
+        assert!(matches!(
+            from_str::<u8>("▖▖", &mut buf),
+            Err(Error::DeserializeUnexpectedEnd)
+        ));
+        assert!(matches!(
+            from_str::<u8>("▖▖x▘▌▖", &mut buf),
+            Err(Error::DeserializeBadEncoding)
+        ));
+        // A string of two bytes does not fit a one byte buffer
+        let encoded: String<64> = to_sink("hi", String::new()).unwrap();
+        assert!(matches!(
+            from_str::<&str>(&encoded, &mut buf),
+            Err(Error::DeserializeUnexpectedEnd)
+        ));
+    }
+}
@@ -0,0 +1,894 @@
+//! Rendering dollcode as images.
+//!
+//! Glyphs are drawn as shapes rather than text, so the result looks the same
+//! everywhere regardless of how fonts render ▖, ▘ and ▌. Each glyph takes a
+//! cell and fills the left half of it, as the block characters do: ▖ the
+//! lower left quarter, ▘ the upper left quarter and ▌ the whole left half.
+//! Cells of odd height give the extra row to the lower quarter.
+//!
+//! Renderers take any [`ToDollcode`] value, encoding it twice: once to
+//! measure the image and once to draw it. Strings already holding glyphs
+//! and delimiters are drawn as is through [`Encoded`].
+//!
+//! [`svg`] writes a standalone SVG document to any [`fmt::Write`], and
+//! [`raster`] fills an RGBA pixel buffer, sized with [`raster_size`]. With
+//! the `image` feature, `image` returns the pixels as an `image::RgbaImage`
+//! and `png` encodes them as a PNG file. [`ansi`] writes the glyphs
+//! themselves for terminals, colored by digit, and [`banner`] draws them
+//! larger in block characters.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{render::{svg, Encoded, SvgStyle}, Result};
+//! # fn main() -> Result<()> {
+//! let style = SvgStyle {
+//!     cell_width: 4,
+//!     cell_height: 8,
+//!     ..SvgStyle::DEFAULT
+//! };
+//! let mut output = heapless::String::<256>::new();
+//! svg(&5u64, &style, &mut output)?;
+//! assert_eq!(
+//!     output,
+//!     "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"8\" height=\"8\" \
+//!      viewBox=\"0 0 8 8\" shape-rendering=\"crispEdges\">\
+//!      <path fill=\"currentColor\" d=\"M0,4h2v4h-2zM4,0h2v4h-2z\"/></svg>"
+//! );
+//!
+//! let mut drawn = heapless::String::<256>::new();
+//! svg(&Encoded("▖▘"), &style, &mut drawn)?;
+//! assert_eq!(drawn, output);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{digit_value, text::DELIMITER, DollcodeError, Result, Sink, ToDollcode};
+use core::fmt::{self, Write};
+
+/// A string of glyphs and delimiters, drawn as is rather than encoded as
+/// text
+///
+/// Encoding it yields [`DollcodeError::InvalidChar`] for any other
+/// character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Encoded<'a>(pub &'a str);
+
+impl ToDollcode for Encoded<'_> {
+    fn encode_dollcode(&self, sink: &mut impl Sink) -> Result<()> {
+        for (position, c) in self.0.chars().enumerate() {
+            if c != DELIMITER && digit_value(c).is_none() {
+                return Err(DollcodeError::InvalidChar(c, position));
+            }
+            sink.push(c)?;
+        }
+        Ok(())
+    }
+}
+
+/// Size, spacing and colors of an [`svg`] rendering
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SvgStyle<'a> {
+    /// Width of the cell each glyph takes, in pixels
+    pub cell_width: u32,
+    /// Height of the cell each glyph takes, and of the image, in pixels
+    pub cell_height: u32,
+    /// Space after each glyph, in pixels
+    pub spacing: u32,
+    /// Space for each delimiter, in pixels
+    pub delimiter_width: u32,
+    /// Glyph fill, any SVG paint
+    pub color: &'a str,
+    /// Fill behind the glyphs, or `None` for transparent
+    pub background: Option<&'a str>,
+}
+
+impl SvgStyle<'static> {
+    /// 24 pixels high glyphs in `currentColor`, with delimiters taking no
+    /// space as in text
+    pub const DEFAULT: Self = Self {
+        cell_width: 12,
+        cell_height: 24,
+        spacing: 0,
+        delimiter_width: 0,
+        color: "currentColor",
+        background: None,
+    };
+}
+
+impl Default for SvgStyle<'static> {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl SvgStyle<'_> {
+    fn layout(&self) -> Layout {
+        Layout {
+            cell_width: self.cell_width,
+            spacing: self.spacing,
+            delimiter_width: self.delimiter_width,
+        }
+    }
+}
+
+/// Horizontal layout shared by the renderers
+#[derive(Debug, Clone, Copy)]
+struct Layout {
+    cell_width: u32,
+    spacing: u32,
+    delimiter_width: u32,
+}
+
+impl Layout {
+    /// Returns the digit of `c` and the width it takes, or `None` for the
+    /// digit of the delimiter
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DollcodeError::InvalidChar`] for any other character.
+    fn place(&self, c: char, position: usize) -> Result<(Option<u8>, u32)> {
+        match c {
+            DELIMITER => Ok((None, self.delimiter_width)),
+            _ => {
+                let digit = digit_value(c).ok_or(DollcodeError::InvalidChar(c, position))?;
+                Ok((Some(digit), self.cell_width.saturating_add(self.spacing)))
+            }
+        }
+    }
+
+    /// Returns the width of `sequence`
+    fn measure<T: ToDollcode + ?Sized>(&self, sequence: &T) -> Result<u32> {
+        let mut walk = Walk::new(*self, |_, _| Ok(()));
+        sequence.encode_dollcode(&mut walk)?;
+        Ok(walk.x)
+    }
+}
+
+/// Sink placing each glyph and passing its digit and position on
+struct Walk<F> {
+    layout: Layout,
+    draw: F,
+    x: u32,
+    position: usize,
+}
+
+impl<F: FnMut(u8, u32) -> Result<()>> Walk<F> {
+    fn new(layout: Layout, draw: F) -> Self {
+        Self {
+            layout,
+            draw,
+            x: 0,
+            position: 0,
+        }
+    }
+}
+
+impl<F: FnMut(u8, u32) -> Result<()>> Sink for Walk<F> {
+    fn push(&mut self, c: char) -> Result<()> {
+        let (digit, advance) = self.layout.place(c, self.position)?;
+        if let Some(digit) = digit {
+            (self.draw)(digit, self.x)?;
+        }
+        self.x = self.x.saturating_add(advance);
+        self.position += 1;
+        Ok(())
+    }
+}
+
+/// Returns the top and height of the filled part of a cell `height` high
+/// for `digit`
+const fn fill_rows(digit: u8, height: u32) -> (u32, u32) {
+    let upper = height / 2;
+    match digit {
+        1 => (upper, height - upper),
+        2 => (0, upper),
+        _ => (0, height),
+    }
+}
+
+/// Writes a standalone SVG document drawing `sequence` to `out`.
+///
+/// The document is as high as a cell and as wide as the glyphs, spacing
+/// and delimiters of `sequence`, with the glyphs in a single path. Colors
+/// are escaped for the attributes they are written into.
+///
+/// # Errors
+///
+/// Returns the errors of encoding `sequence`, and
+/// [`DollcodeError::Overflow`] if `out` fails.
+pub fn svg<T, W>(sequence: &T, style: &SvgStyle<'_>, out: &mut W) -> Result<()>
+where
+    T: ToDollcode + ?Sized,
+    W: Write + ?Sized,
+{
+    let width = style.layout().measure(sequence)?;
+    let height = style.cell_height;
+    let overflow = |_| DollcodeError::Overflow;
+
+    write!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" shape-rendering=\"crispEdges\">"
+    )
+    .map_err(overflow)?;
+    if let Some(background) = style.background {
+        write!(
+            out,
+            "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+            Escaped(background)
+        )
+        .map_err(overflow)?;
+    }
+    write!(out, "<path fill=\"{}\" d=\"", Escaped(style.color)).map_err(overflow)?;
+
+    let fill_width = style.cell_width / 2;
+    sequence.encode_dollcode(&mut Walk::new(style.layout(), |digit, x| {
+        let (y, h) = fill_rows(digit, height);
+        write!(out, "M{x},{y}h{fill_width}v{h}h-{fill_width}z").map_err(overflow)
+    }))?;
+    out.write_str("\"/></svg>").map_err(overflow)
+}
+
+/// Size, spacing and colors of a [`raster`] rendering
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RasterStyle {
+    /// Width of the cell each glyph takes, in pixels
+    pub cell_width: u32,
+    /// Height of the cell each glyph takes, and of the image, in pixels
+    pub cell_height: u32,
+    /// Space after each glyph, in pixels
+    pub spacing: u32,
+    /// Space for each delimiter, in pixels
+    pub delimiter_width: u32,
+    /// RGBA color of the glyphs
+    pub color: [u8; 4],
+    /// RGBA color behind the glyphs
+    pub background: [u8; 4],
+}
+
+impl RasterStyle {
+    /// 24 pixels high black glyphs on white, with delimiters half a cell
+    /// wide so segments stay apart on labels
+    pub const DEFAULT: Self = Self {
+        cell_width: 12,
+        cell_height: 24,
+        spacing: 0,
+        delimiter_width: 6,
+        color: [0, 0, 0, 0xFF],
+        background: [0xFF; 4],
+    };
+
+    fn layout(&self) -> Layout {
+        Layout {
+            cell_width: self.cell_width,
+            spacing: self.spacing,
+            delimiter_width: self.delimiter_width,
+        }
+    }
+}
+
+impl Default for RasterStyle {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Returns the width and height in pixels of `sequence` rendered with
+/// `style`.
+///
+/// # Errors
+///
+/// Returns the errors of encoding `sequence`.
+pub fn raster_size<T: ToDollcode + ?Sized>(
+    sequence: &T,
+    style: &RasterStyle,
+) -> Result<(u32, u32)> {
+    Ok((style.layout().measure(sequence)?, style.cell_height))
+}
+
+/// Draws `sequence` into `pixels`, four RGBA bytes per pixel in rows from
+/// the top, returning the width and height of the image.
+///
+/// # Errors
+///
+/// Returns the errors of encoding `sequence`, and
+/// [`DollcodeError::Overflow`] if `pixels` is not exactly as long as the
+/// image needs.
+pub fn raster<T: ToDollcode + ?Sized>(
+    sequence: &T,
+    style: &RasterStyle,
+    pixels: &mut [u8],
+) -> Result<(u32, u32)> {
+    let (width, height) = raster_size(sequence, style)?;
+    let row_len = usize::try_from(width)
+        .ok()
+        .and_then(|width| width.checked_mul(4))
+        .ok_or(DollcodeError::Overflow)?;
+    let len = usize::try_from(height)
+        .ok()
+        .and_then(|height| height.checked_mul(row_len))
+        .ok_or(DollcodeError::Overflow)?;
+    if pixels.len() != len {
+        return Err(DollcodeError::Overflow);
+    }
+
+    for pixel in pixels.chunks_exact_mut(4) {
+        pixel.copy_from_slice(&style.background);
+    }
+    let fill_width = (style.cell_width / 2) as usize;
+    sequence.encode_dollcode(&mut Walk::new(style.layout(), |digit, x| {
+        let (top, h) = fill_rows(digit, height);
+        let x = x as usize * 4;
+        for row in pixels
+            .chunks_exact_mut(row_len)
+            .skip(top as usize)
+            .take(h as usize)
+        {
+            for pixel in row[x..x + fill_width * 4].chunks_exact_mut(4) {
+                pixel.copy_from_slice(&style.color);
+            }
+        }
+        Ok(())
+    }))?;
+    Ok((width, height))
+}
+
+/// Returns `sequence` rendered with `style` as an image.
+///
+/// # Errors
+///
+/// Returns the errors of encoding `sequence`, and
+/// [`DollcodeError::Overflow`] if the image is too large to allocate.
+#[cfg(feature = "image")]
+pub fn image<T: ToDollcode + ?Sized>(
+    sequence: &T,
+    style: &RasterStyle,
+) -> Result<image::RgbaImage> {
+    let (width, height) = raster_size(sequence, style)?;
+    let len = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|len| len.checked_mul(4))
+        .ok_or(DollcodeError::Overflow)?;
+    let mut pixels = std::vec![0; len];
+    raster(sequence, style, &mut pixels)?;
+    image::RgbaImage::from_raw(width, height, pixels).ok_or(DollcodeError::Overflow)
+}
+
+/// Writes `sequence` rendered with `style` to `out` as a PNG file.
+///
+/// # Errors
+///
+/// Returns errors encoding `sequence` as [`std::io::ErrorKind::InvalidData`],
+/// and the errors of writing to `out`. Empty images cannot be encoded.
+#[cfg(feature = "image")]
+pub fn png<T, W>(sequence: &T, style: &RasterStyle, out: W) -> std::io::Result<()>
+where
+    T: ToDollcode + ?Sized,
+    W: std::io::Write,
+{
+    use image::{codecs::png::PngEncoder, ExtendedColorType, ImageEncoder, ImageError};
+
+    let image = image(sequence, style).map_err(crate::io::invalid_data)?;
+    PngEncoder::new(out)
+        .write_image(
+            &image,
+            image.width(),
+            image.height(),
+            ExtendedColorType::Rgba8,
+        )
+        .map_err(|e| match e {
+            ImageError::IoError(e) => e,
+            e => std::io::Error::other(e),
+        })
+}
+
+/// A terminal foreground color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnsiColor {
+    /// One of the 16 standard colors: 0 to 7, then their bright versions
+    Basic(u8),
+    /// One of the 256 indexed colors
+    Indexed(u8),
+    /// A 24-bit color
+    Rgb(u8, u8, u8),
+}
+
+impl fmt::Display for AnsiColor {
+    /// Writes the escape sequence selecting the color
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Basic(n) if n & 0x0F < 8 => write!(f, "\x1b[{}m", 30 + (n & 0x0F)),
+            Self::Basic(n) => write!(f, "\x1b[{}m", 90 + (n & 0x0F) - 8),
+            Self::Indexed(n) => write!(f, "\x1b[38;5;{n}m"),
+            Self::Rgb(r, g, b) => write!(f, "\x1b[38;2;{r};{g};{b}m"),
+        }
+    }
+}
+
+/// Colors and shape of an [`ansi`] rendering
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnsiStyle {
+    /// Foreground for digits 1, 2 and 3, or `None` to leave them uncolored
+    pub colors: [Option<AnsiColor>; 3],
+    /// Draws each glyph two lines tall, its quarters as half blocks
+    pub double: bool,
+    /// Whether to write colors at all; `false` for no-color mode
+    pub color: bool,
+}
+
+impl AnsiStyle {
+    /// ▖ in cyan, ▘ in magenta and ▌ in yellow, one line tall
+    pub const DEFAULT: Self = Self {
+        colors: [
+            Some(AnsiColor::Basic(6)),
+            Some(AnsiColor::Basic(5)),
+            Some(AnsiColor::Basic(3)),
+        ],
+        double: false,
+        color: true,
+    };
+}
+
+impl Default for AnsiStyle {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Line of an [`ansi`] rendering being written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Row {
+    Single,
+    Top,
+    Bottom,
+}
+
+/// Sink writing one line of an [`ansi`] rendering
+struct AnsiSink<'a, W: ?Sized> {
+    out: &'a mut W,
+    style: &'a AnsiStyle,
+    row: Row,
+    /// Color last selected
+    current: Option<AnsiColor>,
+    position: usize,
+}
+
+impl<W: Write + ?Sized> AnsiSink<'_, W> {
+    fn write(&mut self, c: char, color: Option<AnsiColor>) -> fmt::Result {
+        if self.style.color && color != self.current {
+            match color {
+                Some(color) => write!(self.out, "{color}")?,
+                None => self.out.write_str(RESET)?,
+            }
+            self.current = color;
+        }
+        self.out.write_char(c)
+    }
+
+    /// Resets the color at the end of the line
+    fn finish(&mut self) -> fmt::Result {
+        if self.current.take().is_some() {
+            self.out.write_str(RESET)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write + ?Sized> Sink for AnsiSink<'_, W> {
+    fn push(&mut self, c: char) -> Result<()> {
+        let position = self.position;
+        self.position += 1;
+        let Some(digit) = digit_value(c) else {
+            if c != DELIMITER {
+                return Err(DollcodeError::InvalidChar(c, position));
+            }
+            // Segments stay apart on doubled lines, which are not meant to
+            // be copied
+            let shown = if self.row == Row::Single { c } else { ' ' };
+            return self
+                .out
+                .write_char(shown)
+                .map_err(|_| DollcodeError::Overflow);
+        };
+        let shown = match (self.row, digit) {
+            (Row::Single, _) => c,
+            (Row::Top, 1) | (Row::Bottom, 2) => ' ',
+            _ => '▌',
+        };
+        let color = if shown == ' ' {
+            self.current
+        } else {
+            self.style.colors[usize::from(digit - 1)]
+        };
+        self.write(shown, color)
+            .map_err(|_| DollcodeError::Overflow)
+    }
+}
+
+/// Resets terminal colors
+const RESET: &str = "\x1b[0m";
+
+/// Writes `sequence` to `out` for a terminal.
+///
+/// Glyphs are written as themselves, each digit in its color, and the
+/// color is reset at the end. Without colors the output is plain dollcode.
+/// Doubled output is two lines, the upper and lower halves of the glyphs,
+/// separated by a newline and with a space for each delimiter.
+///
+/// Terminals and environments such as `NO_COLOR` are left to the caller,
+/// which turns colors off with [`AnsiStyle::color`].
+///
+/// # Errors
+///
+/// Returns the errors of encoding `sequence`, and
+/// [`DollcodeError::Overflow`] if `out` fails.
+pub fn ansi<T, W>(sequence: &T, style: &AnsiStyle, out: &mut W) -> Result<()>
+where
+    T: ToDollcode + ?Sized,
+    W: Write + ?Sized,
+{
+    let rows: &[Row] = if style.double {
+        &[Row::Top, Row::Bottom]
+    } else {
+        &[Row::Single]
+    };
+    for (i, &row) in rows.iter().enumerate() {
+        if i > 0 {
+            out.write_char('\n').map_err(|_| DollcodeError::Overflow)?;
+        }
+        let mut sink = AnsiSink {
+            out: &mut *out,
+            style,
+            row,
+            current: None,
+            position: 0,
+        };
+        sequence.encode_dollcode(&mut sink)?;
+        sink.finish().map_err(|_| DollcodeError::Overflow)?;
+    }
+    Ok(())
+}
+
+/// Size and color of a [`banner`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BannerStyle {
+    /// Columns each glyph takes, the left half of them filled
+    pub cell_width: u32,
+    /// Lines each glyph takes
+    pub cell_height: u32,
+    /// Columns after each glyph
+    pub spacing: u32,
+    /// Columns for each delimiter
+    pub delimiter_width: u32,
+    /// Character filling the glyphs
+    pub fill: char,
+    /// Color of the whole banner, or `None` for plain text
+    pub color: Option<AnsiColor>,
+}
+
+impl BannerStyle {
+    /// Glyphs two columns wide and two lines tall in full blocks, with a
+    /// column for each delimiter
+    pub const DEFAULT: Self = Self {
+        cell_width: 2,
+        cell_height: 2,
+        spacing: 0,
+        delimiter_width: 1,
+        fill: '█',
+        color: None,
+    };
+
+    fn layout(&self) -> Layout {
+        Layout {
+            cell_width: self.cell_width,
+            spacing: self.spacing,
+            delimiter_width: self.delimiter_width,
+        }
+    }
+}
+
+impl Default for BannerStyle {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Writes `sequence` to `out` as block art [`BannerStyle::cell_height`]
+/// lines tall, for MOTDs and splash screens.
+///
+/// Lines are separated by newlines, without trailing spaces. Each line is
+/// wrapped in the color, if any, and its reset.
+///
+/// # Errors
+///
+/// Returns the errors of encoding `sequence`, and
+/// [`DollcodeError::Overflow`] if `out` fails.
+pub fn banner<T, W>(sequence: &T, style: &BannerStyle, out: &mut W) -> Result<()>
+where
+    T: ToDollcode + ?Sized,
+    W: Write + ?Sized,
+{
+    let fill_width = style.cell_width / 2;
+    for line in 0..style.cell_height {
+        if line > 0 {
+            out.write_char('\n').map_err(|_| DollcodeError::Overflow)?;
+        }
+        if let Some(color) = style.color {
+            write!(out, "{color}").map_err(|_| DollcodeError::Overflow)?;
+        }
+        let mut column = 0;
+        let mut walk = Walk::new(style.layout(), |digit, x| {
+            let (top, height) = fill_rows(digit, style.cell_height);
+            if !(top..top + height).contains(&line) {
+                return Ok(());
+            }
+            for _ in column..x {
+                out.write_char(' ').map_err(|_| DollcodeError::Overflow)?;
+            }
+            for _ in 0..fill_width {
+                out.write_char(style.fill)
+                    .map_err(|_| DollcodeError::Overflow)?;
+            }
+            column = x + fill_width;
+            Ok(())
+        });
+        sequence.encode_dollcode(&mut walk)?;
+        if style.color.is_some() {
+            out.write_str(RESET).map_err(|_| DollcodeError::Overflow)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes a string with the characters special in XML attributes escaped
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                _ => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::{String, Vec};
+
+    fn render<T: ToDollcode + ?Sized>(sequence: &T, style: &SvgStyle<'_>) -> Result<String<512>> {
+        let mut output = String::new();
+        svg(sequence, style, &mut output)?;
+        Ok(output)
+    }
+
+    #[test]
+    fn test_default_style() {
+        assert_eq!(
+            render(&42u64, &SvgStyle::DEFAULT).unwrap(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"48\" height=\"24\" \
+             viewBox=\"0 0 48 24\" shape-rendering=\"crispEdges\">\
+             <path fill=\"currentColor\" d=\"M0,12h6v12h-6zM12,12h6v12h-6z\
+             M24,12h6v12h-6zM36,0h6v24h-6z\"/></svg>"
+        );
+        assert!(render(&Encoded(""), &SvgStyle::DEFAULT)
+            .unwrap()
+            .contains("width=\"0\" height=\"24\""));
+    }
+
+    #[test]
+    fn test_text() {
+        // "Hi" is ▘▖▘▌ and ▌▘▖▌, each followed by a delimiter
+        let style = SvgStyle {
+            delimiter_width: 6,
+            ..SvgStyle::DEFAULT
+        };
+        let output = render("Hi", &style).unwrap();
+        assert!(output.contains("width=\"108\""));
+        assert!(output.contains("M36,0h6v24h-6zM54,0h6v24h-6z"));
+    }
+
+    #[test]
+    fn test_spacing_and_colors() {
+        let style = SvgStyle {
+            cell_width: 2,
+            cell_height: 5,
+            spacing: 1,
+            delimiter_width: 4,
+            color: "#c0ffee",
+            background: Some("black"),
+        };
+        let output = render(&Encoded("▘\u{200D}▖"), &style).unwrap();
+        assert!(output.contains("width=\"10\" height=\"5\" viewBox=\"0 0 10 5\""));
+        assert!(output.contains("<rect width=\"100%\" height=\"100%\" fill=\"black\"/>"));
+        assert!(output.contains("fill=\"#c0ffee\" d=\"M0,0h1v2h-1zM7,2h1v3h-1z\""));
+    }
+
+    #[test]
+    fn test_escaped_colors() {
+        let style = SvgStyle {
+            color: "\"/><script>&",
+            ..SvgStyle::DEFAULT
+        };
+        let output = render(&3u64, &style).unwrap();
+        assert!(output.contains("fill=\"&quot;/&gt;&lt;script&gt;&amp;\""));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            render(&Encoded("▖x"), &SvgStyle::DEFAULT),
+            Err(DollcodeError::InvalidChar('x', 1))
+        ));
+        assert!(matches!(
+            render("\u{e9}", &SvgStyle::DEFAULT),
+            Err(DollcodeError::InvalidChar('\u{e9}', 0))
+        ));
+        let mut small = String::<16>::new();
+        assert!(matches!(
+            svg(&1u64, &SvgStyle::DEFAULT, &mut small),
+            Err(DollcodeError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_raster() {
+        // 5 is ▖▘, drawn with 2 pixel wide glyphs in 4 pixel cells
+        let style = RasterStyle {
+            cell_width: 4,
+            cell_height: 4,
+            color: [1; 4],
+            background: [0; 4],
+            ..RasterStyle::DEFAULT
+        };
+        assert_eq!(raster_size(&5u64, &style).unwrap(), (8, 4));
+        let mut pixels = [0u8; 8 * 4 * 4];
+        assert_eq!(raster(&5u64, &style, &mut pixels).unwrap(), (8, 4));
+        let rows: Vec<Vec<u8, 8>, 4> = pixels
+            .chunks_exact(8 * 4)
+            .map(|row| row.chunks_exact(4).map(|pixel| pixel[0]).collect())
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                [0, 0, 0, 0, 1, 1, 0, 0],
+                [0, 0, 0, 0, 1, 1, 0, 0],
+                [1, 1, 0, 0, 0, 0, 0, 0],
+                [1, 1, 0, 0, 0, 0, 0, 0],
+            ]
+        );
+
+        // Delimiters and spacing
+        let style = RasterStyle {
+            spacing: 1,
+            ..style
+        };
+        assert_eq!(
+            raster_size(&Encoded("▌\u{200D}▌"), &style).unwrap(),
+            (16, 4)
+        );
+        assert!(matches!(
+            raster(&5u64, &style, &mut pixels),
+            Err(DollcodeError::Overflow)
+        ));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_png() {
+        let image = image(&Encoded("▌"), &RasterStyle::DEFAULT).unwrap();
+        assert_eq!(image.dimensions(), (12, 24));
+        assert_eq!(image.get_pixel(0, 23).0, [0, 0, 0, 0xFF]);
+        assert_eq!(image.get_pixel(6, 0).0, [0xFF; 4]);
+
+        let mut file = std::vec::Vec::new();
+        png(&42u64, &RasterStyle::DEFAULT, &mut file).unwrap();
+        assert!(file.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        let error = png("\u{e9}", &RasterStyle::DEFAULT, &mut file).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    fn terminal<T: ToDollcode + ?Sized>(sequence: &T, style: &AnsiStyle) -> Result<String<256>> {
+        let mut output = String::new();
+        ansi(sequence, style, &mut output)?;
+        Ok(output)
+    }
+
+    #[test]
+    fn test_ansi() {
+        // Color changes only between different digits
+        assert_eq!(
+            terminal(&Encoded("▖▖▌\u{200D}"), &AnsiStyle::DEFAULT).unwrap(),
+            "\x1b[36m▖▖\x1b[33m▌\u{200D}\x1b[0m"
+        );
+
+        let style = AnsiStyle {
+            color: false,
+            ..AnsiStyle::DEFAULT
+        };
+        assert_eq!(terminal(&42u64, &style).unwrap(), "▖▖▖▌");
+
+        let style = AnsiStyle {
+            colors: [
+                None,
+                Some(AnsiColor::Indexed(208)),
+                Some(AnsiColor::Rgb(1, 2, 3)),
+            ],
+            ..AnsiStyle::DEFAULT
+        };
+        assert_eq!(
+            terminal(&Encoded("▘▖▌"), &style).unwrap(),
+            "\x1b[38;5;208m▘\x1b[0m▖\x1b[38;2;1;2;3m▌\x1b[0m"
+        );
+
+        let mut output = String::<8>::new();
+        write!(output, "{}", AnsiColor::Basic(9)).unwrap();
+        assert_eq!(output, "\x1b[91m");
+    }
+
+    #[test]
+    fn test_banner() {
+        let mut output = String::<256>::new();
+        banner(&Encoded("▘▖▌\u{200D}▌"), &BannerStyle::DEFAULT, &mut output).unwrap();
+        assert_eq!(output, "█   █  █\n  █ █  █");
+
+        let style = BannerStyle {
+            cell_width: 4,
+            cell_height: 3,
+            spacing: 1,
+            fill: '#',
+            color: Some(AnsiColor::Basic(2)),
+            ..BannerStyle::DEFAULT
+        };
+        output.clear();
+        banner(&5u64, &style, &mut output).unwrap();
+        assert_eq!(
+            output,
+            "\x1b[32m     ##\x1b[0m\n\x1b[32m##\x1b[0m\n\x1b[32m##\x1b[0m"
+        );
+
+        assert!(matches!(
+            banner(&Encoded("▖x"), &BannerStyle::DEFAULT, &mut output),
+            Err(DollcodeError::InvalidChar('x', 1))
+        ));
+        let mut small = String::<4>::new();
+        assert!(matches!(
+            banner(&42u64, &BannerStyle::DEFAULT, &mut small),
+            Err(DollcodeError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_ansi_double() {
+        let style = AnsiStyle {
+            double: true,
+            ..AnsiStyle::DEFAULT
+        };
+        assert_eq!(
+            terminal(&Encoded("▘▖"), &style).unwrap(),
+            "\x1b[35m▌ \x1b[0m\n \x1b[36m▌\x1b[0m"
+        );
+
+        let style = AnsiStyle {
+            color: false,
+            ..style
+        };
+        assert_eq!(
+            terminal(&Encoded("▖▘▌\u{200D}▌"), &style).unwrap(),
+            " ▌▌ ▌\n▌ ▌ ▌"
+        );
+        assert!(matches!(
+            terminal(&Encoded("▖x"), &style),
+            Err(DollcodeError::InvalidChar('x', 1))
+        ));
+    }
+}
@@ -0,0 +1,189 @@
+//! Modules for `#[serde(with = "...")]` that store individual fields as
+//! dollcode strings, leaving the rest of the struct as it is.
+
+use crate::{DollcodeError, FromDollcode};
+use core::{fmt, marker::PhantomData};
+use serde::de::{self, Unexpected};
+
+/// Visits a string and decodes it as `T`
+struct DecodeVisitor<T>(&'static str, PhantomData<T>);
+
+impl<T: FromDollcode> de::Visitor<'_> for DecodeVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        T::decode_dollcode(v).map_err(|e| match e {
+            DollcodeError::InvalidChar(c, _) => E::invalid_value(Unexpected::Char(c), &self),
+            _ => E::invalid_value(Unexpected::Str(v), &self),
+        })
+    }
+}
+
+/// Stores a `u64` field as its dollcode sequence.
+///
+/// ```rust
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize)]
+/// struct Order {
+///     #[serde(with = "dollcode::serde_u64")]
+///     id: u64,
+/// }
+///
+/// let json = serde_json::to_string(&Order { id: 42 }).unwrap();
+/// assert_eq!(json, r#"{"id":"▖▖▖▌"}"#);
+/// ```
+pub mod serde_u64 {
+    use super::DecodeVisitor;
+    use crate::to_dollcode;
+    use core::marker::PhantomData;
+    use serde::{ser, Deserializer, Serializer};
+
+    const EXPECTING: &str = "a dollcode number";
+
+    /// Serializes `value` as a dollcode string
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        let dollcode = to_dollcode(*value).map_err(ser::Error::custom)?;
+        serializer.collect_str(&dollcode)
+    }
+
+    /// Deserializes a dollcode string into a `u64`
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        deserializer.deserialize_str(DecodeVisitor(EXPECTING, PhantomData))
+    }
+
+    /// Stores an `Option<u64>` field as a dollcode string or null
+    pub mod option {
+        use core::fmt;
+        use serde::{de, Deserializer, Serialize, Serializer};
+
+        /// A present value, serialized through the parent module
+        struct Present(u64);
+
+        impl Serialize for Present {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                super::serialize(&self.0, serializer)
+            }
+        }
+
+        struct OptionVisitor;
+
+        impl<'de> de::Visitor<'de> for OptionVisitor {
+            type Value = Option<u64>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} or null", super::EXPECTING)
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Option<u64>, E> {
+                Ok(None)
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Option<u64>, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D: Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<Option<u64>, D::Error> {
+                super::deserialize(deserializer).map(Some)
+            }
+        }
+
+        /// Serializes `value` as a dollcode string, or `None` as null
+        pub fn serialize<S: Serializer>(
+            value: &Option<u64>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match *value {
+                Some(value) => serializer.serialize_some(&Present(value)),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        /// Deserializes a dollcode string or null into an `Option<u64>`
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<u64>, D::Error> {
+            deserializer.deserialize_option(OptionVisitor)
+        }
+    }
+}
+
+/// Stores a `String` field as delimited dollcode text. Only printable ASCII
+/// can be serialized.
+///
+/// ```rust
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize)]
+/// struct Greeting {
+///     #[serde(with = "dollcode::serde_text")]
+///     text: String,
+/// }
+///
+/// let json = serde_json::to_string(&Greeting { text: "Hi".into() }).unwrap();
+/// assert_eq!(json, "{\"text\":\"▘▖▘▌\u{200d}▌▘▖▌\u{200d}\"}");
+/// ```
+#[cfg(feature = "std")]
+pub mod serde_text {
+    use super::DecodeVisitor;
+    use crate::ToDollcode;
+    use core::marker::PhantomData;
+    use serde::{ser, Deserializer, Serializer};
+    use std::string::String;
+
+    const EXPECTING: &str = "delimited dollcode text";
+
+    /// Serializes `value` as dollcode text
+    pub fn serialize<S: Serializer>(value: &str, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut encoded = String::new();
+        value
+            .encode_dollcode(&mut encoded)
+            .map_err(ser::Error::custom)?;
+        serializer.serialize_str(&encoded)
+    }
+
+    /// Deserializes dollcode text into a `String`
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        deserializer.deserialize_str(DecodeVisitor(EXPECTING, PhantomData))
+    }
+}
+
+/// Stores a `Vec<u8>` field in the fixed-width [`bytes`](crate::bytes)
+/// encoding, six glyphs per byte.
+///
+/// ```rust
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize)]
+/// struct Blob {
+///     #[serde(with = "dollcode::serde_bytes")]
+///     data: Vec<u8>,
+/// }
+///
+/// let json = serde_json::to_string(&Blob { data: vec![0x2A] }).unwrap();
+/// assert_eq!(json, r#"{"data":"▖▖▘▘▌▖"}"#);
+/// ```
+#[cfg(feature = "std")]
+pub mod serde_bytes {
+    use super::DecodeVisitor;
+    use crate::bytes::ByteEncoder;
+    use core::marker::PhantomData;
+    use serde::{Deserializer, Serializer};
+    use std::{string::String, vec::Vec};
+
+    const EXPECTING: &str = "dollcode bytes";
+
+    /// Serializes `value` as glyph groups
+    pub fn serialize<S: Serializer>(value: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&ByteEncoder::new(value).collect::<String>())
+    }
+
+    /// Deserializes glyph groups into a `Vec<u8>`
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        deserializer.deserialize_str(DecodeVisitor(EXPECTING, PhantomData))
+    }
+}
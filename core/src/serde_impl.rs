@@ -0,0 +1,179 @@
+//! `serde` support for [`Dollcode`].
+//!
+//! Human-readable formats such as JSON store a sequence as its glyph string,
+//! `"▖▖▖▌"`. Binary formats store a length byte followed by the glyphs as
+//! trits packed five to a byte, at most 10 bytes for any `u64`.
+//!
+//! Deserializing checks that the sequence decodes to a `u64`, so every
+//! deserialized [`Dollcode`] is one [`to_dollcode`] could have produced.
+
+use crate::{digit_char, digit_value, from_dollcode, to_dollcode, Dollcode, MAX_DOLLCODE_SIZE};
+use core::fmt;
+use serde::{
+    de::{self, Unexpected},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// Trits packed into each byte, as 3^5 = 243 fits in a byte
+const TRITS_PER_BYTE: usize = 5;
+
+/// Length byte and packed trits of the longest sequence
+const PACKED_SIZE: usize = 1 + MAX_DOLLCODE_SIZE.div_ceil(TRITS_PER_BYTE);
+
+impl Serialize for Dollcode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return serializer.collect_str(self);
+        }
+
+        let mut packed = [0u8; PACKED_SIZE];
+        packed[0] = self.len as u8;
+        for (byte, trits) in packed[1..]
+            .iter_mut()
+            .zip(self.as_chars().chunks(TRITS_PER_BYTE))
+        {
+            *byte = trits.iter().fold(0, |acc, &c| {
+                acc * 3 + digit_value(c).map_or(0, |digit| digit - 1)
+            });
+        }
+        let len = 1 + self.len.div_ceil(TRITS_PER_BYTE);
+        serializer.serialize_bytes(&packed[..len])
+    }
+}
+
+impl<'de> Deserialize<'de> for Dollcode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(DollcodeVisitor)
+        } else {
+            deserializer.deserialize_bytes(DollcodeVisitor)
+        }
+    }
+}
+
+struct DollcodeVisitor;
+
+impl DollcodeVisitor {
+    /// Checks that `chars` decode to a `u64` and builds the sequence for it
+    fn finish<E: de::Error>(chars: &[char]) -> Result<Dollcode, E> {
+        let value = from_dollcode(chars)
+            .map_err(|_| E::invalid_value(Unexpected::Other("sequence beyond u64"), &Self))?;
+        to_dollcode(value).map_err(E::custom)
+    }
+}
+
+impl<'de> de::Visitor<'de> for DollcodeVisitor {
+    type Value = Dollcode;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a dollcode sequence of up to {MAX_DOLLCODE_SIZE} glyphs")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Dollcode, E> {
+        let mut chars = ['\0'; MAX_DOLLCODE_SIZE];
+        let mut len = 0;
+        for c in v.chars() {
+            if digit_value(c).is_none() {
+                return Err(E::invalid_value(Unexpected::Char(c), &self));
+            }
+            let slot = chars
+                .get_mut(len)
+                .ok_or_else(|| E::invalid_length(v.chars().count(), &self))?;
+            *slot = c;
+            len += 1;
+        }
+        Self::finish(&chars[..len])
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Dollcode, E> {
+        let (&len, packed) = v.split_first().ok_or_else(|| E::invalid_length(0, &self))?;
+        let len = usize::from(len);
+        if len > MAX_DOLLCODE_SIZE {
+            return Err(E::invalid_length(len, &self));
+        }
+        if packed.len() != len.div_ceil(TRITS_PER_BYTE) {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+
+        let mut chars = ['\0'; MAX_DOLLCODE_SIZE];
+        for (slots, &byte) in chars[..len].chunks_mut(TRITS_PER_BYTE).zip(packed) {
+            // Only the trits of the group may be set, so every sequence has
+            // exactly one packed form
+            if u32::from(byte) >= 3u32.pow(slots.len() as u32) {
+                return Err(E::invalid_value(
+                    Unexpected::Unsigned(byte.into()),
+                    &"a group of packed trits",
+                ));
+            }
+            let mut rest = byte;
+            for slot in slots.iter_mut().rev() {
+                *slot = digit_char(rest % 3 + 1).unwrap_or('\0');
+                rest /= 3;
+            }
+        }
+        Self::finish(&chars[..len])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+
+    #[test]
+    fn test_readable() {
+        assert_tokens(&to_dollcode(42).unwrap().readable(), &[Token::Str("▖▖▖▌")]);
+        assert_tokens(&Dollcode::new().readable(), &[Token::Str("")]);
+
+        let json = serde_json::to_string(&to_dollcode(42).unwrap()).unwrap();
+        assert_eq!(json, r#""▖▖▖▌""#);
+        assert_eq!(
+            serde_json::from_str::<Dollcode>(&json).unwrap(),
+            to_dollcode(42).unwrap()
+        );
+
+        type Readable = serde_test::Readable<Dollcode>;
+        assert_de_tokens_error::<Readable>(
+            &[Token::Str("▖x")],
+            "invalid value: character `x`, expected a dollcode sequence of up to 41 glyphs",
+        );
+        assert_de_tokens_error::<Readable>(
+            &[Token::Str("▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌")],
+            "invalid value: sequence beyond u64, expected a dollcode sequence of up to 41 glyphs",
+        );
+    }
+
+    #[test]
+    fn test_compact() {
+        // ▖▖▖▌ is the trits 0 0 0 2
+        assert_tokens(
+            &to_dollcode(42).unwrap().compact(),
+            &[Token::Bytes(&[4, 2])],
+        );
+        assert_tokens(&Dollcode::new().compact(), &[Token::Bytes(&[0])]);
+        assert_tokens(
+            &to_dollcode(u64::MAX).unwrap().compact(),
+            &[Token::Bytes(&[41, 1, 97, 93, 51, 78, 168, 224, 84, 2])],
+        );
+
+        type Compact = serde_test::Compact<Dollcode>;
+        assert_de_tokens_error::<Compact>(
+            &[Token::Bytes(&[])],
+            "invalid length 0, expected a dollcode sequence of up to 41 glyphs",
+        );
+        assert_de_tokens_error::<Compact>(
+            &[Token::Bytes(&[6, 0])],
+            "invalid length 2, expected a dollcode sequence of up to 41 glyphs",
+        );
+        assert_de_tokens_error::<Compact>(
+            &[Token::Bytes(&[4, 81])],
+            "invalid value: integer `81`, expected a group of packed trits",
+        );
+        assert_de_tokens_error::<Compact>(
+            &[Token::Bytes(&[
+                41, 242, 242, 242, 242, 242, 242, 242, 242, 2,
+            ])],
+            "invalid value: sequence beyond u64, expected a dollcode sequence of up to 41 glyphs",
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! Transmitting dollcode over an `embedded-hal` serial port.
+//!
+//! [`transmit`] writes the glyphs of any [`ToDollcode`] value as UTF-8 bytes
+//! to an `embedded_hal::serial::Write<u8>`, followed by an optional
+//! terminator, and flushes the port. Each byte is retried while the port
+//! reports `WouldBlock`, so the call returns once everything is sent.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # struct Uart(Vec<u8>);
+//! # impl embedded_hal::serial::Write<u8> for Uart {
+//! #     type Error = core::convert::Infallible;
+//! #     fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+//! #         self.0.push(word);
+//! #         Ok(())
+//! #     }
+//! #     fn flush(&mut self) -> nb::Result<(), Self::Error> {
+//! #         Ok(())
+//! #     }
+//! # }
+//! # let mut uart_tx = Uart(Vec::new());
+//! use dollcode::serial::transmit;
+//!
+//! let reading: u16 = 42;
+//! transmit(&mut uart_tx, &reading, Some(b"\r\n")).unwrap();
+//! assert_eq!(uart_tx.0, "▖▖▖▌\r\n".as_bytes());
+//! ```
+
+use crate::{DollcodeError, Sink, ToDollcode};
+use core::fmt;
+use embedded_hal::serial::Write;
+
+/// Failure of [`transmit`]
+#[derive(Debug)]
+pub enum Error<E> {
+    /// The value could not be encoded
+    Dollcode(DollcodeError),
+    /// The serial port failed
+    Serial(E),
+}
+
+impl<E: fmt::Debug> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Dollcode(e) => e.fmt(f),
+            Self::Serial(e) => write!(f, "Serial error: {:?}", e),
+        }
+    }
+}
+
+/// Sink writing glyphs straight to a serial port
+struct SerialSink<'a, S: Write<u8>> {
+    serial: &'a mut S,
+    /// The port error that stopped the encoding
+    error: Option<S::Error>,
+}
+
+impl<S: Write<u8>> SerialSink<'_, S> {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), S::Error> {
+        bytes
+            .iter()
+            .try_for_each(|&byte| nb::block!(self.serial.write(byte)))
+    }
+}
+
+impl<S: Write<u8>> Sink for SerialSink<'_, S> {
+    fn push(&mut self, c: char) -> crate::Result<()> {
+        let mut buf = [0u8; 4];
+        self.write_bytes(c.encode_utf8(&mut buf).as_bytes())
+            .map_err(|e| {
+                self.error = Some(e);
+                // Replaced by the port error in `transmit`
+                DollcodeError::Overflow
+            })
+    }
+}
+
+/// Writes the glyphs of `value` to `serial` as UTF-8, then `terminator`,
+/// and flushes the port.
+///
+/// Values are encoded as by [`ToDollcode`]: numbers as a single sequence,
+/// text as delimited segments.
+///
+/// # Errors
+///
+/// Returns [`Error::Dollcode`] if `value` cannot be encoded and
+/// [`Error::Serial`] if the port fails. Text is sent segment by segment, so
+/// glyphs before the failure have already been written.
+pub fn transmit<S, T>(
+    serial: &mut S,
+    value: &T,
+    terminator: Option<&[u8]>,
+) -> Result<(), Error<S::Error>>
+where
+    S: Write<u8>,
+    T: ToDollcode + ?Sized,
+{
+    let mut sink = SerialSink {
+        serial,
+        error: None,
+    };
+    value
+        .encode_dollcode(&mut sink)
+        .map_err(|e| sink.error.take().map_or(Error::Dollcode(e), Error::Serial))?;
+    if let Some(terminator) = terminator {
+        sink.write_bytes(terminator).map_err(Error::Serial)?;
+    }
+    nb::block!(sink.serial.flush()).map_err(Error::Serial)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A port that is busy before every byte, failing once `fail_after`
+    /// bytes are written
+    #[derive(Default)]
+    struct Uart {
+        sent: heapless::Vec<u8, 64>,
+        busy: bool,
+        fail_after: Option<usize>,
+        flushed: bool,
+    }
+
+    impl Write<u8> for Uart {
+        type Error = ();
+
+        fn write(&mut self, word: u8) -> nb::Result<(), ()> {
+            self.busy = !self.busy;
+            if self.busy {
+                return Err(nb::Error::WouldBlock);
+            }
+            if self.fail_after == Some(self.sent.len()) {
+                return Err(nb::Error::Other(()));
+            }
+            self.sent.push(word).map_err(|_| nb::Error::Other(()))
+        }
+
+        fn flush(&mut self) -> nb::Result<(), ()> {
+            self.flushed = true;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_transmit() {
+        let mut uart = Uart::default();
+        transmit(&mut uart, &42u64, Some(b"\r\n")).unwrap();
+        assert_eq!(uart.sent, "▖▖▖▌\r\n".as_bytes());
+        assert!(uart.flushed);
+
+        let mut uart = Uart::default();
+        transmit(&mut uart, "Hi", None).unwrap();
+        assert_eq!(uart.sent, "▘▖▘▌\u{200d}▌▘▖▌\u{200d}".as_bytes());
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut uart = Uart::default();
+        assert!(matches!(
+            transmit(&mut uart, "H\u{e9}", None),
+            Err(Error::Dollcode(DollcodeError::InvalidChar('\u{e9}', 1)))
+        ));
+        assert_eq!(uart.sent, "▘▖▘▌\u{200d}".as_bytes());
+        assert!(!uart.flushed);
+
+        let mut uart = Uart {
+            fail_after: Some(4),
+            ..Uart::default()
+        };
+        assert!(matches!(
+            transmit(&mut uart, &42u64, None),
+            Err(Error::Serial(()))
+        ));
+        assert_eq!(uart.sent.len(), 4);
+    }
+}
@@ -0,0 +1,286 @@
+//! SSE2 glyph classification for long text decodes.
+//!
+//! Every dollcode glyph and the delimiter encode to three UTF-8 bytes sharing
+//! a lead byte, so validation and digit mapping can work on raw bytes:
+//!
+//! ```text
+//! ▖   E2 96 96
+//! ▘   E2 96 98
+//! ▌   E2 96 8C
+//! ZWJ E2 80 8D
+//! ```
+//!
+//! A block of 48 bytes, sixteen of these units, is loaded into three SSE2
+//! registers and compared against each byte value at once. The comparison
+//! masks are gathered into one bit per byte, and shifting them onto the lead
+//! byte of each unit classifies all sixteen units with a few integer
+//! operations. The intrinsics come from `safe_arch`, which exposes them
+//! safely when the target has SSE2, as every x86-64 target does; other
+//! targets, including those with NEON, keep the scalar decoder.
+
+use safe_arch::{
+    cmp_eq_mask_i8_m128i, load_unaligned_m128i, m128i, move_mask_i8_m128i, set_splat_i8_m128i,
+};
+
+/// Bytes classified per kernel invocation: sixteen three-byte units.
+pub(crate) const BLOCK: usize = 48;
+
+/// Bit of the lead byte of every unit in a block.
+const LEADS: u64 = 0x2492_4924_9249;
+
+/// Most glyphs in a segment, matching the scalar decoder.
+const SEGMENT_GLYPHS: u32 = 5;
+
+/// Returns a mask with bit `i` set where byte `i` of the block equals `byte`.
+#[inline(always)]
+fn eq_mask(lanes: &[m128i; 3], byte: u8) -> u64 {
+    let splat = set_splat_i8_m128i(byte as i8);
+    let mut mask = 0;
+    for (i, &lane) in lanes.iter().enumerate() {
+        let lane_mask = move_mask_i8_m128i(cmp_eq_mask_i8_m128i(lane, splat)) as u16;
+        mask |= u64::from(lane_mask) << (16 * i);
+    }
+    mask
+}
+
+/// Units of a block, one bit per unit at the position of its lead byte.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Units {
+    /// Glyphs of digit value 2 (▘)
+    twos: u64,
+    /// Glyphs of digit value 3 (▌)
+    threes: u64,
+    /// Well-formed glyphs of any value
+    glyphs: u64,
+    /// Well-formed delimiters
+    delimiters: u64,
+}
+
+/// Classifies the sixteen units of a block.
+#[inline]
+pub(crate) fn classify(block: &[u8; BLOCK]) -> Units {
+    let (lanes, _) = block.as_chunks::<16>();
+    let lanes = [
+        load_unaligned_m128i(&lanes[0]),
+        load_unaligned_m128i(&lanes[1]),
+        load_unaligned_m128i(&lanes[2]),
+    ];
+
+    let lead = eq_mask(&lanes, 0xE2) & LEADS;
+    let x96 = eq_mask(&lanes, 0x96);
+    let x98 = eq_mask(&lanes, 0x98) >> 2;
+    let x8c = eq_mask(&lanes, 0x8C) >> 2;
+
+    let glyphs = lead & (x96 >> 1) & ((x96 >> 2) | x98 | x8c);
+    let delimiters = lead & (eq_mask(&lanes, 0x80) >> 1) & (eq_mask(&lanes, 0x8D) >> 2);
+    Units {
+        twos: glyphs & x98,
+        threes: glyphs & x8c,
+        glyphs,
+        delimiters,
+    }
+}
+
+/// Classified units of a block not yet decoded, the first at bit 0.
+///
+/// Decoding a segment shifts its units out, so one classification serves
+/// every complete segment in the block.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Block {
+    units: Units,
+}
+
+impl Block {
+    /// Classifies the block at the start of `bytes`, if it is long enough.
+    #[inline]
+    pub(crate) fn load(bytes: &[u8]) -> Option<Self> {
+        let (block, _) = bytes.split_first_chunk::<BLOCK>()?;
+        Some(Self {
+            units: classify(block),
+        })
+    }
+
+    /// Decodes the leading text segment and its trailing delimiter.
+    ///
+    /// Returns the decoded character and the number of glyphs consumed; the
+    /// segment spans three bytes more per glyph, for the delimiter. Returns
+    /// `None` without consuming anything when the segment does not end
+    /// within the block or anything unexpected is seen (invalid bytes, empty
+    /// or overlong segments, values outside printable ASCII), leaving the
+    /// scalar path to produce the precise result or error.
+    #[inline]
+    pub(crate) fn next_segment(&mut self) -> Option<(char, usize)> {
+        let Units {
+            twos,
+            threes,
+            glyphs,
+            delimiters,
+        } = self.units;
+
+        // Every unit before the first delimiter must be a glyph
+        let end = delimiters.trailing_zeros();
+        let digits = end / 3;
+        if !(1..=SEGMENT_GLYPHS).contains(&digits) {
+            return None;
+        }
+        let before = (1u64 << end) - 1;
+        if glyphs & before != LEADS & before {
+            return None;
+        }
+
+        let mut value = 0u32;
+        for unit in 0..digits {
+            let bit = 3 * unit;
+            let digit = 1 + ((twos >> bit) & 1) + 2 * ((threes >> bit) & 1);
+            value = value * 3 + digit as u32;
+        }
+        if !(32..=126).contains(&value) {
+            return None;
+        }
+
+        let consumed = end + 3;
+        self.units = Units {
+            twos: twos >> consumed,
+            threes: threes >> consumed,
+            glyphs: glyphs >> consumed,
+            delimiters: delimiters >> consumed,
+        };
+        Some((value as u8 as char, digits as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::{TextDecoder, TextIterator, DELIMITER};
+    use heapless::String;
+
+    /// Classifies a block one unit at a time
+    fn classify_scalar(block: &[u8; BLOCK]) -> Units {
+        let mut units = Units {
+            twos: 0,
+            threes: 0,
+            glyphs: 0,
+            delimiters: 0,
+        };
+        for (i, unit) in block.chunks_exact(3).enumerate() {
+            let bit = 1 << (3 * i);
+            match unit {
+                [0xE2, 0x96, 0x96] => units.glyphs |= bit,
+                [0xE2, 0x96, 0x98] => {
+                    units.glyphs |= bit;
+                    units.twos |= bit;
+                }
+                [0xE2, 0x96, 0x8C] => {
+                    units.glyphs |= bit;
+                    units.threes |= bit;
+                }
+                [0xE2, 0x80, 0x8D] => units.delimiters |= bit,
+                _ => {}
+            }
+        }
+        units
+    }
+
+    #[test]
+    fn test_classify_matches_scalar() {
+        let mut block = [0u8; BLOCK];
+        let units = ["▖", "▘", "▌", "\u{200d}", "A\u{80}\u{0}"];
+        // Every unit kind in every position, with neighbours of each kind
+        for (i, unit) in units.iter().cycle().take(5 * 16 + 7).enumerate() {
+            let start = 3 * (i % 16);
+            block[start..start + 3].copy_from_slice(&unit.as_bytes()[..3]);
+            assert_eq!(classify(&block), classify_scalar(&block), "{block:x?}");
+        }
+
+        // Bytes of the alphabet out of place
+        for shift in 1..3 {
+            let mut shifted = [0u8; BLOCK];
+            shifted[shift..].copy_from_slice(&block[..BLOCK - shift]);
+            assert_eq!(classify(&shifted), classify_scalar(&shifted));
+            assert_eq!(classify(&shifted).glyphs, 0);
+        }
+    }
+
+    /// Decodes the first segment of `input` from a fresh block
+    fn decode_segment(input: &str) -> Option<(char, usize)> {
+        Block::load(input.as_bytes())?.next_segment()
+    }
+
+    #[test]
+    fn test_next_segment() {
+        // "Hi" followed by padding so a full block is available
+        let input = "▘▖▘▌\u{200d}▌▘▖▌\u{200d}▌▖▌\u{200d}▌▖▌\u{200d}";
+        let mut block = Block::load(input.as_bytes()).unwrap();
+        assert_eq!(block.next_segment(), Some(('H', 4)));
+        assert_eq!(block.next_segment(), Some(('i', 4)));
+        assert_eq!(block.next_segment(), Some(('!', 3)));
+        // The last segment ends past the block
+        assert_eq!(block.next_segment(), None);
+
+        // Too short for the vectorized path
+        assert!(Block::load("▘▖▘▌\u{200d}▌▘▖▌\u{200d}".as_bytes()).is_none());
+
+        // Invalid glyph defers to the scalar path
+        let invalid = "▘▖X▌\u{200d}▌▘▖▌\u{200d}▌▖▌\u{200d}▌▖▌\u{200d}";
+        assert!(decode_segment(invalid).is_none());
+
+        // Empty segment defers to the scalar path
+        let empty = "\u{200d}▌▘▖▌\u{200d}▌▖▌\u{200d}▌▖▌\u{200d}▌▖▌\u{200d}";
+        assert!(decode_segment(empty).is_none());
+
+        // As do overlong segments and values outside printable ASCII
+        let overlong = "▖▖▖▖▖▖\u{200d}▌▖▌\u{200d}▌▖▌\u{200d}▌▖▌\u{200d}";
+        assert!(decode_segment(overlong).is_none());
+        let control = "▖\u{200d}▌▖▌\u{200d}▌▖▌\u{200d}▌▖▌\u{200d}▌▖▌\u{200d}";
+        assert!(decode_segment(control).is_none());
+
+        // No delimiter within the block
+        let mut long = String::<64>::new();
+        for _ in 0..16 {
+            long.push('▖').unwrap();
+        }
+        long.push(DELIMITER).unwrap();
+        assert!(decode_segment(&long).is_none());
+    }
+
+    #[test]
+    fn test_long_text_roundtrip() {
+        let original = "The quick brown fox jumps over the lazy dog! ~{|}~ 0123456789";
+        let mut encoded = String::<1024>::new();
+
+        for segment in TextIterator::new(original) {
+            for &c in segment.unwrap().as_chars() {
+                encoded.push(c).unwrap();
+            }
+        }
+
+        let mut decoded = String::<128>::new();
+        for result in TextDecoder::new(&encoded) {
+            decoded.push(result.unwrap()).unwrap();
+        }
+
+        assert_eq!(decoded, original, "Vectorized roundtrip failed");
+    }
+
+    #[test]
+    fn test_long_text_error_position() {
+        let mut encoded = String::<256>::new();
+        encoded.push_str("▘▖▘▌\u{200d}▌▘▖▌\u{200d}").unwrap();
+        encoded.push_str("▌▖X\u{200d}").unwrap();
+        encoded
+            .push_str("▌▖▌\u{200d}▌▖▌\u{200d}▌▖▌\u{200d}▌▖▌\u{200d}▌▖▌\u{200d}")
+            .unwrap();
+
+        let mut decoder = TextDecoder::new(&encoded);
+        assert_eq!(decoder.next().unwrap().unwrap(), 'H');
+        assert_eq!(decoder.next().unwrap().unwrap(), 'i');
+        match decoder.next() {
+            Some(Err(crate::DollcodeError::InvalidChar(c, pos))) => {
+                assert_eq!(c, 'X');
+                assert_eq!(pos, 10);
+            }
+            other => panic!("Expected InvalidChar error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,358 @@
+//! Hiding dollcode in text with zero-width characters.
+//!
+//! A payload is written in the fixed-width [`bytes`](crate::bytes) encoding,
+//! prefixed with its length as two bytes, big-endian, and each glyph is
+//! replaced by an invisible carrier:
+//!
+//! | Glyph | Carrier |
+//! |-------|---------|
+//! | ▖ | zero width space, U+200B |
+//! | ▘ | zero width non-joiner, U+200C |
+//! | ▌ | zero width joiner, U+200D |
+//!
+//! [`embed`] inserts the carriers as one run at the end of the first word of
+//! a cover text, where they do not split a joined emoji, and [`extract`]
+//! finds the run again. The cover text reads and renders as before, so this
+//! watermarks a document without changing it visibly.
+//!
+//! Sanitizers and editors often drop zero-width characters from part of a
+//! text. [`embed_every`] repeats the payload through the cover, and
+//! [`detect`] skips damaged copies to locate the first whole one, scanning
+//! the UTF-8 bytes of large documents without decoding them.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{stego::{embed, extract}, Result};
+//! # fn main() -> Result<()> {
+//! let mut text = heapless::String::<256>::new();
+//! embed("Dear reader", b"id:7", &mut text)?;
+//! assert!(text.starts_with("Dear\u{200B}"));
+//! assert!(text.ends_with(" reader"));
+//!
+//! let mut payload = [0u8; 16];
+//! let len = extract(&text, &mut payload)?;
+//! assert_eq!(&payload[..len], b"id:7");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    bytes::{encode_byte, GLYPHS_PER_BYTE},
+    digit_value, DollcodeError, Result,
+};
+use core::{fmt, iter};
+
+/// Carriers of the digits 0, 1 and 2, as ▖, ▘ and ▌ in the byte encoding
+pub const CARRIERS: [char; 3] = ['\u{200B}', '\u{200C}', '\u{200D}'];
+
+/// Bytes of the length prefix
+const HEADER: usize = 2;
+/// UTF-8 of a carrier, which differ in the last byte only
+const CARRIER_BYTES: usize = 3;
+
+/// Returns the number of carriers [`embed`] inserts for `len` bytes
+pub const fn hidden_len(len: usize) -> usize {
+    (HEADER + len) * GLYPHS_PER_BYTE
+}
+
+/// Location of a hidden payload in a text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    /// Byte offset of the first carrier
+    pub start: usize,
+    /// Byte offset following the last carrier
+    pub end: usize,
+    /// Length of the payload in bytes
+    pub len: usize,
+}
+
+/// Writes `cover` with `payload` hidden in it to `out`.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::Overflow`] if `payload` is over `u16::MAX` bytes
+/// or writing to `out` fails.
+pub fn embed<W: fmt::Write + ?Sized>(cover: &str, payload: &[u8], out: &mut W) -> Result<()> {
+    // No word count reaches past the first word
+    hide(cover, payload, usize::MAX, out)
+}
+
+/// Writes `cover` with `payload` hidden after the first word and every
+/// `words` words following it to `out`.
+///
+/// Each copy is found on its own, so the payload survives as long as one
+/// copy is left whole when the text is cut or partly stripped.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidInput`] if `words` is zero, and the errors
+/// of [`embed`].
+pub fn embed_every<W: fmt::Write + ?Sized>(
+    cover: &str,
+    payload: &[u8],
+    words: usize,
+    out: &mut W,
+) -> Result<()> {
+    if words == 0 {
+        return Err(DollcodeError::InvalidInput);
+    }
+    hide(cover, payload, words, out)
+}
+
+fn hide<W: fmt::Write + ?Sized>(
+    cover: &str,
+    payload: &[u8],
+    every: usize,
+    out: &mut W,
+) -> Result<()> {
+    let len = u16::try_from(payload.len()).map_err(|_| DollcodeError::Overflow)?;
+    let header = len.to_be_bytes();
+    let write_run = |out: &mut W| {
+        for byte in header.iter().chain(payload) {
+            for glyph in encode_byte(*byte) {
+                let digit = digit_value(glyph).map_or(0, |digit| digit - 1);
+                out.write_char(CARRIERS[usize::from(digit)])
+                    .map_err(|_| DollcodeError::Overflow)?;
+            }
+        }
+        Ok(())
+    };
+
+    let mut words = 0usize;
+    let mut written = 0;
+    let mut in_word = false;
+    for (i, c) in cover.char_indices() {
+        let space = c.is_whitespace();
+        if in_word && space {
+            if words.is_multiple_of(every) {
+                out.write_str(&cover[written..i])
+                    .map_err(|_| DollcodeError::Overflow)?;
+                write_run(out)?;
+                written = i;
+            }
+            words += 1;
+        }
+        in_word = !space;
+    }
+    out.write_str(&cover[written..])
+        .map_err(|_| DollcodeError::Overflow)?;
+    // After a last word, or anywhere in a text without words
+    if (in_word && words.is_multiple_of(every)) || (words == 0 && !in_word) {
+        write_run(out)?;
+    }
+    Ok(())
+}
+
+/// Returns the location of the first payload hidden in `text`.
+///
+/// Runs of carriers that do not hold a whole payload, such as a joiner inside
+/// an emoji or a copy with carriers stripped, are skipped.
+pub fn detect(text: &str) -> Option<Span> {
+    runs(text).find_map(|(start, run)| {
+        Some(Span {
+            start,
+            end: start + run.len(),
+            len: payload_len(run)?,
+        })
+    })
+}
+
+/// Finds the payload hidden in `text` and writes it to `out`, returning its
+/// length.
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidInput`] if [`detect`] finds no payload,
+/// and [`DollcodeError::Overflow`] if it does not fit in `out`.
+pub fn extract(text: &str, out: &mut [u8]) -> Result<usize> {
+    let span = detect(text).ok_or(DollcodeError::InvalidInput)?;
+    let out = out.get_mut(..span.len).ok_or(DollcodeError::Overflow)?;
+    for (byte, value) in out
+        .iter_mut()
+        .zip(groups(&text[span.start..span.end]).skip(HEADER))
+    {
+        // Checked by `payload_len`
+        *byte = value as u8;
+    }
+    Ok(span.len)
+}
+
+/// Returns the runs of carriers in `text` with their byte offsets
+fn runs(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let bytes = text.as_bytes();
+    let mut offset = 0;
+    iter::from_fn(move || {
+        let start = (offset..bytes.len()).find(|&i| is_carrier(&bytes[i..]))?;
+        let mut end = start;
+        while is_carrier(&bytes[end..]) {
+            end += CARRIER_BYTES;
+        }
+        offset = end;
+        Some((start, &text[start..end]))
+    })
+}
+
+fn is_carrier(bytes: &[u8]) -> bool {
+    matches!(bytes, [0xE2, 0x80, 0x8B..=0x8D, ..])
+}
+
+/// Returns the values of the groups of a run, which are bytes if valid
+fn groups(run: &str) -> impl Iterator<Item = u16> + '_ {
+    run.as_bytes()
+        .chunks_exact(CARRIER_BYTES * GLYPHS_PER_BYTE)
+        .map(|group| {
+            group
+                .chunks_exact(CARRIER_BYTES)
+                .fold(0, |value, carrier| value * 3 + u16::from(carrier[2] - 0x8B))
+        })
+}
+
+/// Returns the length of the payload a run holds, if it is one
+fn payload_len(run: &str) -> Option<usize> {
+    let count = run.len() / CARRIER_BYTES;
+    if count < hidden_len(0) {
+        return None;
+    }
+    let mut groups = groups(run);
+    let (high, low) = (groups.next()?, groups.next()?);
+    let len = usize::from(u16::from_be_bytes([
+        u8::try_from(high).ok()?,
+        u8::try_from(low).ok()?,
+    ]));
+    (count == hidden_len(len) && groups.all(|value| value <= 0xFF)).then_some(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::String;
+
+    fn embedded(cover: &str, payload: &[u8]) -> String<1024> {
+        let mut out = String::new();
+        embed(cover, payload, &mut out).unwrap();
+        out
+    }
+
+    fn embedded_every(cover: &str, payload: &[u8], words: usize) -> String<1024> {
+        let mut out = String::new();
+        embed_every(cover, payload, words, &mut out).unwrap();
+        out
+    }
+
+    fn extracted(text: &str) -> Result<heapless::Vec<u8, 64>> {
+        let mut out = [0u8; 64];
+        let len = extract(text, &mut out)?;
+        Ok(heapless::Vec::from_slice(&out[..len]).unwrap())
+    }
+
+    #[test]
+    fn test_embed() {
+        let text = embedded("Hi there", b"*");
+        let hidden: String<64> = text.chars().filter(|c| CARRIERS.contains(c)).collect();
+        assert_eq!(hidden.chars().count(), hidden_len(1));
+        // 0x00 0x01 0x2A, as ▖▖▖▖▖▖ ▖▖▖▖▖▘ ▖▖▘▘▌▖
+        assert!(hidden.starts_with("\u{200B}\u{200B}\u{200B}\u{200B}\u{200B}\u{200B}"));
+        assert!(hidden.ends_with("\u{200C}\u{200C}\u{200D}\u{200B}"));
+        let visible: String<16> = text.chars().filter(|c| !CARRIERS.contains(c)).collect();
+        assert_eq!(visible, "Hi there");
+        assert_eq!(text.find(' '), Some(2 + hidden.len()));
+
+        for cover in ["", "word", " leading", "👩\u{200D}💻 and text"] {
+            let text = embedded(cover, b"cover");
+            assert_eq!(extracted(&text).unwrap(), b"cover");
+        }
+        assert_eq!(extracted(&embedded("empty", b"")).unwrap(), b"");
+    }
+
+    #[test]
+    fn test_extract() {
+        // Other runs before the payload are skipped
+        let mut text = String::<1024>::new();
+        text.push_str("👩\u{200D}💻 \u{200B}\u{200B} ").unwrap();
+        text.push_str(&embedded("find me", b"payload")).unwrap();
+        assert_eq!(extracted(&text).unwrap(), b"payload");
+
+        assert!(matches!(
+            extracted("no payload \u{200D}"),
+            Err(DollcodeError::InvalidInput)
+        ));
+        // A run with a carrier stripped
+        let text = embedded("Hi", b"payload");
+        let stripped: String<1024> = text
+            .chars()
+            .enumerate()
+            .filter(|&(i, _)| i != 5)
+            .map(|(_, c)| c)
+            .collect();
+        assert!(matches!(
+            extracted(&stripped),
+            Err(DollcodeError::InvalidInput)
+        ));
+        assert!(matches!(
+            extract(&text, &mut [0u8; 6]),
+            Err(DollcodeError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_detect() {
+        let mut text = String::<1024>::new();
+        text.push_str("👩\u{200D}💻 ").unwrap();
+        text.push_str(&embedded("find me", b"payload")).unwrap();
+        let span = detect(&text).unwrap();
+        assert_eq!(span.start, "👩\u{200D}💻 find".len());
+        assert_eq!(span.end - span.start, hidden_len(7) * 3);
+        assert_eq!(span.len, 7);
+        assert_eq!(&text[span.end..], " me");
+
+        assert_eq!(detect("plain text"), None);
+        assert_eq!(detect("\u{200D}\u{200D}"), None);
+        // Groups over 255
+        let mut invalid = String::<128>::new();
+        for _ in 0..hidden_len(0) {
+            invalid.push('\u{200D}').unwrap();
+        }
+        assert_eq!(detect(&invalid), None);
+    }
+
+    #[test]
+    fn test_embed_every() {
+        let text = embedded_every("one two three four five", b"id", 2);
+        let spans: heapless::Vec<usize, 8> = runs(&text).map(|(start, _)| start).collect();
+        let visible: String<32> = text.chars().filter(|c| !CARRIERS.contains(c)).collect();
+        assert_eq!(visible, "one two three four five");
+        assert_eq!(spans.len(), 3);
+        for (start, word) in spans.iter().zip(["one", "three", "five"]) {
+            assert!(text[..*start].ends_with(word));
+        }
+
+        // The first copy is damaged, and the second found
+        let damaged: String<1024> = text
+            .chars()
+            .enumerate()
+            .filter(|&(i, _)| i != 5)
+            .map(|(_, c)| c)
+            .collect();
+        let span = detect(&damaged).unwrap();
+        assert!(damaged[..span.start].ends_with("three"));
+        assert_eq!(extracted(&damaged).unwrap(), b"id");
+
+        assert_eq!(runs(&embedded_every("  ", b"", 1)).count(), 1);
+        assert_eq!(runs(&embedded_every("a b ", b"", 1)).count(), 2);
+        let mut out = String::<8>::new();
+        assert!(matches!(
+            embed_every("a", b"", 0, &mut out),
+            Err(DollcodeError::InvalidInput)
+        ));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut out = String::<8>::new();
+        assert!(matches!(
+            embed("Hi", b"payload", &mut out),
+            Err(DollcodeError::Overflow)
+        ));
+    }
+}
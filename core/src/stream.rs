@@ -0,0 +1,205 @@
+//! Byte-level encoding and decoding shared by the IO adapters.
+//!
+//! Text arrives as UTF-8 bytes in chunks of any size, so characters and
+//! segments may be split between reads. Line breaks are passed through in
+//! both directions.
+
+use crate::{
+    digit_value,
+    text::{StreamDecoder, DELIMITER, SEGMENT_TABLE},
+    DollcodeError, Result, Sink,
+};
+
+/// Writes the text segment of `byte` into `sink`, or the byte itself for a
+/// line break.
+///
+/// Returns `false`, writing nothing, if `byte` is neither a line break nor
+/// printable ASCII.
+pub(crate) fn encode_text_byte(byte: u8, sink: &mut impl Sink) -> Result<bool> {
+    if matches!(byte, b'\n' | b'\r') {
+        sink.push(char::from(byte))?;
+        return Ok(true);
+    }
+    match byte
+        .checked_sub(b' ')
+        .and_then(|i| SEGMENT_TABLE.get(usize::from(i)))
+    {
+        Some(segment) => {
+            sink.push_all(segment.chars())?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Assembles characters from UTF-8 bytes
+#[derive(Debug, Default, Clone, Copy)]
+struct Utf8 {
+    buf: [u8; 4],
+    len: usize,
+}
+
+impl Utf8 {
+    /// Adds a byte, returning the character it completes
+    fn push(&mut self, byte: u8) -> Result<Option<char>> {
+        let lead = if self.len == 0 { byte } else { self.buf[0] };
+        let width = match lead {
+            0x00..=0x7F => 1,
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => return Err(DollcodeError::InvalidInput),
+        };
+        self.buf[self.len] = byte;
+        self.len += 1;
+        if self.len < width {
+            return Ok(None);
+        }
+        self.len = 0;
+        core::str::from_utf8(&self.buf[..width])
+            .ok()
+            .and_then(|s| s.chars().next())
+            .map(Some)
+            .ok_or(DollcodeError::InvalidInput)
+    }
+
+    /// Checks that no character was left incomplete
+    fn finish(&mut self) -> Result<()> {
+        match core::mem::take(&mut self.len) {
+            0 => Ok(()),
+            _ => Err(DollcodeError::InvalidInput),
+        }
+    }
+}
+
+/// Decoded bytes going into a read buffer
+struct Output<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+    /// A byte that did not fit, returned by the next read
+    pending: Option<u8>,
+}
+
+impl Output<'_> {
+    fn emit(&mut self, byte: u8) {
+        match self.buf.get_mut(self.len) {
+            Some(slot) => {
+                *slot = byte;
+                self.len += 1;
+            }
+            None => self.pending = Some(byte),
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.pending.is_some() || self.len == self.buf.len()
+    }
+}
+
+/// Decodes dollcode text arriving as UTF-8 bytes into ASCII bytes
+#[derive(Debug, Default)]
+pub(crate) struct TextBytes {
+    utf8: Utf8,
+    decoder: StreamDecoder,
+    /// A line break read while a segment was still being output
+    pending: Option<u8>,
+}
+
+impl TextBytes {
+    /// Decodes a prefix of `input` into `buf`, returning the bytes consumed
+    /// and written.
+    pub(crate) fn decode(&mut self, input: &[u8], buf: &mut [u8]) -> Result<(usize, usize)> {
+        let mut out = Output {
+            buf,
+            len: 0,
+            pending: None,
+        };
+        if let Some(byte) = self.pending.take() {
+            out.emit(byte);
+        }
+        let mut consumed = 0;
+        for &byte in input {
+            if out.is_full() {
+                break;
+            }
+            consumed += 1;
+            let Some(c) = self.utf8.push(byte)? else {
+                continue;
+            };
+            if matches!(c, '\n' | '\r') {
+                if let Some(decoded) = self.decoder.finish()? {
+                    out.emit(decoded as u8);
+                }
+                out.emit(c as u8);
+            } else if let Some(decoded) = self.decoder.push(c)? {
+                out.emit(decoded as u8);
+            }
+        }
+        self.pending = out.pending;
+        Ok((consumed, out.len))
+    }
+
+    /// Completes the input at the end of the stream, returning the bytes
+    /// written.
+    pub(crate) fn finish(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let (_, written) = self.decode(&[], buf)?;
+        if written > 0 {
+            return Ok(written);
+        }
+        self.utf8.finish()?;
+        match (self.decoder.finish()?, buf.first_mut()) {
+            (Some(decoded), Some(slot)) => {
+                *slot = decoded as u8;
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
+/// Decodes a number frame one byte at a time, skipping whitespace before it
+#[derive(Debug, Default)]
+pub(crate) struct FrameDecoder {
+    utf8: Utf8,
+    value: u64,
+    started: bool,
+    /// Characters read so far, for error positions
+    position: usize,
+}
+
+impl FrameDecoder {
+    /// Adds a byte, returning the value of the frame it completes
+    pub(crate) fn push(&mut self, byte: u8) -> Result<Option<u64>> {
+        let c = self.utf8.push(byte)?;
+        self.position += usize::from(c.is_some());
+        match c {
+            Some(DELIMITER) => Ok(Some(self.value)),
+            // Line breaks and spaces between frames are skipped
+            Some(c) if c.is_whitespace() => Ok(None),
+            Some(c) => {
+                let digit =
+                    digit_value(c).ok_or(DollcodeError::InvalidChar(c, self.position - 1))?;
+                self.started = true;
+                self.value = self
+                    .value
+                    .checked_mul(3)
+                    .and_then(|v| v.checked_add(digit.into()))
+                    .ok_or(DollcodeError::Overflow)?;
+                Ok(None)
+            }
+            None => {
+                self.started = true;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Handles the end of the stream, which must fall between frames
+    pub(crate) fn finish(&mut self) -> Result<Option<u64>> {
+        self.utf8.finish()?;
+        match self.started {
+            true => Err(DollcodeError::InvalidInput),
+            false => Ok(None),
+        }
+    }
+}
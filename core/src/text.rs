@@ -1,11 +1,13 @@
-use crate::{DollcodeError, Result};
+use crate::{digit_char, digit_value, DollcodeError, Result, ToDollcode};
 use core::result::Result as CoreResult;
 use core::{iter::Peekable, str::Chars};
 
-/// A fixed-size text segment representing encoded dollcode characters.
+/// A compact text segment representing encoded dollcode characters.
 ///
-/// Each segment contains the dollcode representation of a single ASCII character,
-/// using a fixed-size internal buffer to maintain zero-allocation guarantees.
+/// Each segment contains the dollcode representation of a single ASCII character.
+/// Digit values are packed two bits apiece alongside a delimiter flag, so a
+/// segment is four bytes; its glyphs are borrowed from a table shared by all
+/// segments when they are read.
 ///
 /// # Examples
 ///
@@ -17,21 +19,28 @@ use core::{iter::Peekable, str::Chars};
 /// ```
 #[derive(Debug, Copy, Clone)]
 pub struct TextSegment {
-    chars: [char; 6],
-    len: usize,
+    /// Digit values `1..=3`, two bits each, first digit in the lowest bits.
+    digits: u16,
+    /// Number of packed digits.
+    count: u8,
+    /// Whether the segment ends with [`DELIMITER`].
+    delimited: bool,
 }
 
+/// Maximum number of glyphs in a segment, excluding the delimiter.
+const SEGMENT_DIGITS: u8 = 5;
+
 impl TextSegment {
     /// Returns the number of valid characters in this segment.
     #[inline]
     pub fn len(&self) -> usize {
-        self.len
+        self.count as usize + self.delimited as usize
     }
 
     /// Returns true if this segment contains no characters.
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.len == 0
+        self.len() == 0
     }
 }
 
@@ -44,17 +53,17 @@ impl Default for TextSegment {
 impl TextSegment {
     /// Creates a new empty text segment.
     ///
-    /// The segment is initialized with a zeroed buffer and can hold
-    /// up to 6 characters (5 dollcode characters + delimiter).
+    /// The segment can hold up to 6 characters (5 dollcode characters + delimiter).
     #[inline]
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self {
-            chars: ['\0'; 6],
-            len: 0,
+            digits: 0,
+            count: 0,
+            delimited: false,
         }
     }
 
-    /// Returns a slice of the valid characters in this segment.
+    /// Returns the characters of this segment.
     ///
     /// # Examples
     ///
@@ -68,22 +77,84 @@ impl TextSegment {
     /// # }
     /// ```
     #[inline]
+    #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
     pub fn as_chars(&self) -> &[char] {
-        &self.chars[..self.len]
+        // Segments only come from the table, so their value indexes it
+        let mut code = 0;
+        let mut i = 0;
+        while i < self.count {
+            code = code * 3 + usize::from((self.digits >> (2 * i)) & 0b11);
+            i += 1;
+        }
+
+        code.checked_sub(32)
+            .and_then(|index| SEGMENT_CHARS.get(index))
+            .and_then(|chars| chars.get(..self.len()))
+            .unwrap_or(&[])
     }
 
-    /// Pushes a character onto this segment.
+    /// Returns an iterator over the characters of this segment, ending with
+    /// the delimiter.
     ///
-    /// # Errors
+    /// # Examples
     ///
-    /// Returns [`DollcodeError::Overflow`] if the segment is full.
+    /// ```rust
+    /// # use dollcode::{error::Result, text::{TextIterator, DELIMITER}};
+    /// # fn main() -> Result<()> {
+    /// let segment = TextIterator::new("*").next().unwrap()?;
+    /// assert!(segment.chars().eq(['▖', '▖', '▖', '▌', DELIMITER]));
+    /// # Ok(())
+    /// # }
+    /// ```
     #[inline]
-    fn push(&mut self, c: char) -> Result<()> {
-        if self.len >= self.chars.len() {
-            return Err(DollcodeError::Overflow);
+    pub fn chars(&self) -> impl Iterator<Item = char> {
+        let Self {
+            digits,
+            count,
+            delimited,
+        } = *self;
+
+        (0..count)
+            .filter_map(move |i| digit_char(((digits >> (2 * i)) & 0b11) as u8))
+            .chain(delimited.then_some(DELIMITER))
+    }
+}
+
+/// Writes the segment glyphs followed by its delimiter.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dollcode::{error::Result, text::TextIterator};
+/// # fn main() -> Result<()> {
+/// let segment = TextIterator::new("*").next().unwrap()?;
+/// assert_eq!(segment.to_string(), "▖▖▖▌\u{200d}");
+/// # Ok(())
+/// # }
+/// ```
+impl core::fmt::Display for TextSegment {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use core::fmt::Write;
+
+        for c in self.chars() {
+            f.write_char(c)?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders the segment glyphs followed by its delimiter.
+///
+/// Available with the `ufmt` feature for targets that avoid `core::fmt`.
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for TextSegment {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> CoreResult<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        for c in self.chars() {
+            f.write_char(c)?;
         }
-        self.chars[self.len] = c;
-        self.len += 1;
         Ok(())
     }
 }
@@ -125,19 +196,20 @@ impl<'a> TextIterator<'a> {
     /// let iter = TextIterator::new("Hello");
     /// ```
     pub fn new(input: &'a str) -> Self {
+        Self::with_position(input, 0)
+    }
+
+    /// Creates a text iterator whose error positions start at `position`.
+    ///
+    /// Used when encoding a slice of a larger input.
+    pub(crate) fn with_position(input: &'a str, position: usize) -> Self {
         Self {
             chars: input.chars().peekable(),
-            position: 0,
+            position,
         }
     }
 
-    /// Processes a single character into a dollcode segment.
-    ///
-    /// This function converts an ASCII character into its dollcode representation by:
-    /// 1. Validating the character is in the ASCII printable range (32-126)
-    /// 2. Converting to base-3 digits
-    /// 3. Mapping digits to dollcode characters
-    /// 4. Padding to a consistent length
+    /// Looks up the dollcode segment of a single character.
     ///
     /// # Errors
     ///
@@ -147,43 +219,92 @@ impl<'a> TextIterator<'a> {
         let pos = self.position;
         self.position += 1;
 
-        // Only accept ASCII
-        let code = c as u32;
-        if !(32..=126).contains(&code) {
-            return Err(DollcodeError::InvalidChar(c, pos));
-        }
+        // Only accept printable ASCII, which is exactly what the table covers
+        (c as u32)
+            .checked_sub(32)
+            .and_then(|index| SEGMENT_TABLE.get(index as usize))
+            .copied()
+            .ok_or(DollcodeError::InvalidChar(c, pos))
+    }
+}
 
-        let mut segment = TextSegment::new();
-        let mut num = code;
-        let mut digits = [0u8; 8];
-        let mut idx = 0;
-
-        // Convert to bijective base-3
-        while num > 0 && idx < 8 {
-            let rem = num % 3;
-            let digit = if rem == 0 { 3 } else { rem as u8 };
-            num = if rem == 0 { num / 3 - 1 } else { num / 3 };
-            digits[idx] = digit;
-            idx += 1;
+/// Delimited segments of every printable ASCII character, indexed by the
+/// character code minus 32.
+///
+/// Built at compile time and used by [`TextIterator`] for every character, so
+/// listings generated from it always match the encoder.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dollcode::text::SEGMENT_TABLE;
+/// assert_eq!(SEGMENT_TABLE.len(), 95);
+/// assert_eq!(SEGMENT_TABLE[usize::from(b'*' - 32)].to_string(), "▖▖▖▌\u{200d}");
+///
+/// for (c, segment) in (' '..='~').zip(SEGMENT_TABLE) {
+///     println!("{c} {segment}");
+/// }
+/// ```
+pub const SEGMENT_TABLE: [TextSegment; 95] = segment_table();
+
+/// Glyphs and delimiter of each entry of [`SEGMENT_TABLE`], which
+/// [`TextSegment::as_chars`] borrows from.
+static SEGMENT_CHARS: [[char; SEGMENT_DIGITS as usize + 1]; 95] = segment_chars();
+
+/// Spells out the segments of [`SEGMENT_TABLE`], padded with delimiters.
+const fn segment_chars() -> [[char; SEGMENT_DIGITS as usize + 1]; 95] {
+    let mut table = [[DELIMITER; SEGMENT_DIGITS as usize + 1]; 95];
+    let mut i = 0;
+    while i < table.len() {
+        let TextSegment { digits, count, .. } = SEGMENT_TABLE[i];
+        let mut j = 0;
+        while j < count {
+            table[i][j as usize] = match digit_char(((digits >> (2 * j)) & 0b11) as u8) {
+                Some(c) => c,
+                None => DELIMITER,
+            };
+            j += 1;
         }
+        i += 1;
+    }
+    table
+}
 
-        // Reverse digits and map to dollcode characters
-        for &digit in digits[..idx].iter().rev() {
-            segment.push(match digit {
-                1 => '▖',
-                2 => '▘',
-                3 => '▌',
-                _ => return Err(DollcodeError::InvalidInput),
-            })?;
+/// Encodes each printable ASCII code in bijective base-3, most significant
+/// digit first.
+///
+/// Every code from 32 to 126 takes 3 to 5 digits.
+const fn segment_table() -> [TextSegment; 95] {
+    let mut table = [TextSegment::new(); 95];
+    let mut i = 0;
+    while i < table.len() {
+        let mut code = i as u32 + 32;
+
+        // Least significant digit first
+        let mut digits = [0u8; SEGMENT_DIGITS as usize];
+        let mut count = 0;
+        while code > 0 {
+            digits[count] = ((code - 1) % 3) as u8 + 1;
+            code = (code - 1) / 3;
+            count += 1;
         }
 
-        // Pad to minimum length for consistent decoding
-        while segment.len() < 3 {
-            segment.push('▖')?;
+        // Packed with the most significant digit in the lowest bits
+        let mut packed = 0;
+        let mut j = 0;
+        while j < count {
+            packed |= (digits[count - 1 - j] as u16) << (2 * j);
+            j += 1;
         }
 
-        Ok(segment)
+        table[i] = TextSegment {
+            digits: packed,
+            count: count as u8,
+            delimited: true,
+        };
+        i += 1;
     }
+    table
 }
 
 /// Zero-width joiner character used as a delimiter between dollcode segments.
@@ -192,14 +313,86 @@ pub const DELIMITER: char = '\u{200D}';
 impl<'a> Iterator for TextIterator<'a> {
     type Item = Result<TextSegment>;
 
+    #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
     fn next(&mut self) -> Option<Self::Item> {
-        self.chars.next().map(|c| {
-            let mut segment = self.process_char(c)?;
-            segment.push(DELIMITER)?;
+        self.chars.next().map(|c| self.process_char(c))
+    }
+}
 
-            Ok(segment)
-        })
+/// Error returned by [`write_text`].
+///
+/// Separates encoding failures from failures of the underlying writer.
+#[cfg(feature = "ufmt")]
+#[derive(Debug)]
+pub enum WriteError<E> {
+    /// The input could not be encoded
+    Encode(DollcodeError),
+    /// The writer rejected the output
+    Write(E),
+}
+
+/// Encodes `input` segment by segment directly into a `ufmt` writer.
+///
+/// Output is identical to concatenating the segments of a [`TextIterator`],
+/// but nothing is buffered beyond a single segment. Intended for firmware
+/// where pulling in `core::fmt` is too costly.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dollcode::text::{write_text, WriteError};
+/// let mut out = heapless::String::<64>::new();
+/// write_text("Hi", &mut out).unwrap();
+/// assert_eq!(out, "▘▖▘▌\u{200d}▌▘▖▌\u{200d}");
+///
+/// let mut small = heapless::String::<4>::new();
+/// assert!(matches!(write_text("Hi", &mut small), Err(WriteError::Write(()))));
+/// ```
+///
+/// # Errors
+///
+/// Returns [`WriteError::Encode`] for characters outside printable ASCII and
+/// [`WriteError::Write`] if the writer fails. Segments before the failing one
+/// have already been written.
+#[cfg(feature = "ufmt")]
+pub fn write_text<W>(input: &str, writer: &mut W) -> CoreResult<(), WriteError<W::Error>>
+where
+    W: ufmt::uWrite + ?Sized,
+{
+    for segment in TextIterator::new(input) {
+        let segment = segment.map_err(WriteError::Encode)?;
+        for c in segment.chars() {
+            writer.write_char(c).map_err(WriteError::Write)?;
+        }
     }
+
+    Ok(())
+}
+
+/// Encodes `input` into a fixed-capacity string of `N` bytes.
+///
+/// Each glyph takes 3 bytes of UTF-8 and each delimiter 3 more, so a
+/// character needs up to 21 bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dollcode::{text::encode_to_heapless, Result};
+/// # fn main() -> Result<()> {
+/// let encoded = encode_to_heapless::<64>("Hi")?;
+/// assert_eq!(encoded, "▘▖▘▌\u{200d}▌▘▖▌\u{200d}");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`DollcodeError::InvalidChar`] for characters outside printable
+/// ASCII and [`DollcodeError::Overflow`] if the encoding does not fit.
+pub fn encode_to_heapless<const N: usize>(input: &str) -> Result<heapless::String<N>> {
+    let mut encoded = heapless::String::new();
+    input.encode_dollcode(&mut encoded)?;
+    Ok(encoded)
 }
 
 /// Zero-allocation iterator that converts dollcode back into ASCII text.
@@ -224,8 +417,15 @@ impl<'a> Iterator for TextIterator<'a> {
 /// ```
 #[derive(Debug)]
 pub struct TextDecoder<'a> {
-    segments: Peekable<core::str::Split<'a, char>>,
+    rest: Option<&'a str>,
     position: usize,
+    /// Classified units at the start of `rest` for the vectorized kernel
+    #[cfg(all(
+        feature = "simd",
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    ))]
+    block: crate::simd::Block,
 }
 
 impl<'a> TextDecoder<'a> {
@@ -239,51 +439,212 @@ impl<'a> TextDecoder<'a> {
     /// ```
     pub fn new(encoded: &'a str) -> Self {
         Self {
-            segments: encoded.split(DELIMITER).peekable(),
+            rest: Some(encoded),
             position: 0,
+            #[cfg(all(
+                feature = "simd",
+                any(target_arch = "x86", target_arch = "x86_64"),
+                target_feature = "sse2"
+            ))]
+            block: crate::simd::Block::default(),
         }
     }
+
+    /// Decodes the next segment with the vectorized kernel, if possible.
+    #[cfg(all(
+        feature = "simd",
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    ))]
+    #[inline]
+    fn next_simd(&mut self) -> Option<char> {
+        use crate::simd::Block;
+
+        let rest = self.rest?;
+        let (c, digits) = match self.block.next_segment() {
+            Some(segment) => segment,
+            None => {
+                // The scalar path takes over until a block decodes again
+                self.block = Block::load(rest.as_bytes()).unwrap_or_default();
+                let segment = self.block.next_segment();
+                if segment.is_none() {
+                    self.block = Block::default();
+                }
+                segment?
+            }
+        };
+        self.rest = rest.get((digits + 1) * 3..);
+        self.position += digits;
+        Some(c)
+    }
 }
 
 impl<'a> Iterator for TextDecoder<'a> {
     type Item = CoreResult<char, DollcodeError>;
 
+    #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
     fn next(&mut self) -> Option<Self::Item> {
-        let segment = match self.segments.next() {
-            Some(seg) if !seg.is_empty() => seg,
-            _ => return None, // Skip empty segments
+        #[cfg(all(
+            feature = "simd",
+            any(target_arch = "x86", target_arch = "x86_64"),
+            target_feature = "sse2"
+        ))]
+        if let Some(c) = self.next_simd() {
+            return Some(Ok(c));
+        }
+
+        let rest = self.rest?;
+        let segment = match rest.char_indices().find(|&(_, c)| c == DELIMITER) {
+            Some((idx, _)) => {
+                self.rest = rest.get(idx + DELIMITER.len_utf8()..);
+                rest.get(..idx)?
+            }
+            None => {
+                self.rest = None;
+                rest
+            }
         };
 
-        let mut value: u32 = 0;
+        if segment.is_empty() {
+            return None; // Skip empty segments
+        }
 
-        for c in segment.chars() {
-            let digit = match c {
-                '▖' => 1,
-                '▘' => 2,
-                '▌' => 3,
-                _ => return Some(Err(DollcodeError::InvalidChar(c, self.position))),
-            };
+        Some(decode_segment(segment, &mut self.position))
+    }
+}
 
-            value = match value
-                .checked_mul(3)
-                .and_then(|v| v.checked_add(digit as u32))
-            {
-                Some(val) => val,
-                None => return Some(Err(DollcodeError::InvalidInput)),
-            };
+/// Push-based decoder that converts dollcode back into ASCII text one
+/// character at a time.
+///
+/// Unlike [`TextDecoder`], which borrows the complete input, this decoder keeps
+/// the partial segment in a few bytes of state, so input can be fed from any
+/// character source (filtered iterators, chunked reads) without first being
+/// collected into a buffer.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dollcode::{error::Result, text::StreamDecoder};
+/// # fn main() -> Result<()> {
+/// let mut decoder = StreamDecoder::new();
+/// let mut decoded = heapless::String::<16>::new();
+///
+/// for c in "▘▖▘▌\u{200d}▌▘▖▌".chars() {
+///     if let Some(c) = decoder.push(c)? {
+///         decoded.push(c).unwrap();
+///     }
+/// }
+/// if let Some(c) = decoder.finish()? {
+///     decoded.push(c).unwrap();
+/// }
+///
+/// assert_eq!(decoded, "Hi");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StreamDecoder {
+    value: u32,
+    digits: usize,
+    position: usize,
+}
 
-            if value > 126 {
-                return Some(Err(DollcodeError::InvalidInput));
-            }
+impl StreamDecoder {
+    /// Creates a decoder with no pending segment.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-            self.position += 1;
+    /// Feeds a single character into the decoder.
+    ///
+    /// Returns the decoded character when `c` is a [`DELIMITER`] completing a
+    /// segment, and `None` otherwise. Delimiters without a pending segment are
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns:
+    /// - [`DollcodeError::InvalidChar`] if `c` is neither a glyph nor the delimiter
+    /// - [`DollcodeError::InvalidInput`] if the segment value leaves the printable ASCII range
+    #[inline]
+    #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+    pub fn push(&mut self, c: char) -> Result<Option<char>> {
+        if c == DELIMITER {
+            return self.finish();
         }
 
-        if (32..=126).contains(&value) {
-            Some(Ok(value as u8 as char))
-        } else {
-            Some(Err(DollcodeError::InvalidInput))
+        self.value = push_glyph(self.value, c, self.position)?;
+        self.digits += 1;
+        self.position += 1;
+        Ok(None)
+    }
+
+    /// Completes a trailing segment that was not followed by a delimiter.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DollcodeError::InvalidInput`] if the pending segment does not
+    /// decode to printable ASCII.
+    #[inline]
+    #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+    pub fn finish(&mut self) -> Result<Option<char>> {
+        if self.digits == 0 {
+            return Ok(None);
         }
+
+        let value = self.value;
+        self.value = 0;
+        self.digits = 0;
+        segment_char(value).map(Some)
+    }
+
+    /// Returns true if glyphs have been pushed since the last completed segment.
+    #[inline]
+    pub fn is_pending(&self) -> bool {
+        self.digits > 0
+    }
+}
+
+/// Decodes a single delimiter-free segment back into its ASCII character.
+///
+/// `position` counts the glyphs decoded so far and is advanced for every valid
+/// glyph, so errors report their position within the overall input.
+pub(crate) fn decode_segment(segment: &str, position: &mut usize) -> Result<char> {
+    let mut value: u32 = 0;
+
+    for c in segment.chars() {
+        value = push_glyph(value, c, *position)?;
+        *position += 1;
+    }
+
+    segment_char(value)
+}
+
+/// Accumulates one glyph into a partial segment value.
+#[inline]
+fn push_glyph(value: u32, c: char, position: usize) -> Result<u32> {
+    let digit = digit_value(c).ok_or(DollcodeError::InvalidChar(c, position))?;
+
+    let value = value
+        .checked_mul(3)
+        .and_then(|v| v.checked_add(digit as u32))
+        .ok_or(DollcodeError::InvalidInput)?;
+
+    if value > 126 {
+        return Err(DollcodeError::InvalidInput);
+    }
+
+    Ok(value)
+}
+
+/// Converts a completed segment value into its ASCII character.
+#[inline]
+fn segment_char(value: u32) -> Result<char> {
+    if (32..=126).contains(&value) {
+        Ok(value as u8 as char)
+    } else {
+        Err(DollcodeError::InvalidInput)
     }
 }
 
@@ -292,6 +653,22 @@ mod tests {
     use super::*;
     use heapless::String;
 
+    #[test]
+    fn test_encode_to_heapless() {
+        assert_eq!(
+            encode_to_heapless::<64>("Hi!").unwrap(),
+            "▘▖▘▌\u{200d}▌▘▖▌\u{200d}▌▖▌\u{200d}"
+        );
+        assert!(matches!(
+            encode_to_heapless::<8>("Hi"),
+            Err(DollcodeError::Overflow)
+        ));
+        assert!(matches!(
+            encode_to_heapless::<64>("é"),
+            Err(DollcodeError::InvalidChar('é', 0))
+        ));
+    }
+
     #[test]
     fn test_ascii_roundtrip() {
         let test_cases = [
@@ -470,4 +847,112 @@ mod tests {
 
         assert_eq!(decoded, original, "Roundtrip encoding/decoding failed");
     }
+
+    #[test]
+    fn test_stream_decoder_matches_text_decoder() {
+        let original = "Hello, World! ~";
+        let mut encoded = String::<256>::new();
+        for segment in TextIterator::new(original) {
+            for c in segment.unwrap().chars() {
+                encoded.push(c).unwrap();
+            }
+        }
+
+        let mut decoder = StreamDecoder::new();
+        let mut decoded = String::<64>::new();
+        for c in encoded.chars() {
+            if let Some(c) = decoder.push(c).unwrap() {
+                decoded.push(c).unwrap();
+            }
+        }
+        assert!(!decoder.is_pending());
+        assert_eq!(decoder.finish().unwrap(), None);
+        assert_eq!(decoded, original);
+    }
+
+    #[test]
+    fn test_stream_decoder_errors() {
+        let mut decoder = StreamDecoder::new();
+        decoder.push('▖').unwrap();
+        decoder.push('▌').unwrap();
+        assert!(matches!(
+            decoder.push('X'),
+            Err(DollcodeError::InvalidChar('X', 2))
+        ));
+
+        // Incomplete segment below the printable range
+        let mut decoder = StreamDecoder::new();
+        decoder.push('▖').unwrap();
+        assert!(decoder.is_pending());
+        assert!(matches!(decoder.finish(), Err(DollcodeError::InvalidInput)));
+        assert!(!decoder.is_pending());
+
+        // Value exceeding ASCII range
+        let mut decoder = StreamDecoder::new();
+        for &c in &['▖', '▖', '▖', '▌'] {
+            decoder.push(c).unwrap();
+        }
+        assert!(matches!(
+            decoder.push('▘'),
+            Err(DollcodeError::InvalidInput)
+        ));
+    }
+
+    #[test]
+    fn test_packed_segment() {
+        assert_eq!(core::mem::size_of::<TextSegment>(), 4);
+
+        for segment in TextIterator::new("~ Az") {
+            let segment = segment.unwrap();
+            let chars = segment.as_chars();
+            assert_eq!(chars.len(), segment.len());
+            assert!(segment.chars().eq(chars.iter().copied()));
+            assert_eq!(chars.last(), Some(&DELIMITER));
+
+            let mut displayed = String::<32>::new();
+            core::fmt::write(&mut displayed, format_args!("{}", segment)).unwrap();
+            assert!(displayed.chars().eq(segment.chars()));
+        }
+    }
+
+    #[test]
+    fn test_segment_table() {
+        for (c, segment) in (' '..='~').zip(SEGMENT_TABLE) {
+            let mut decoder = StreamDecoder::new();
+            let mut decoded = None;
+            for glyph in segment.chars() {
+                decoded = decoder.push(glyph).unwrap();
+            }
+            assert_eq!(decoded, Some(c));
+            assert!((4..=SEGMENT_DIGITS as usize + 1).contains(&segment.len()));
+        }
+    }
+
+    #[cfg(feature = "ufmt")]
+    #[test]
+    fn test_ufmt_output_matches_iterator() {
+        let text = "Hello, World!";
+        let mut expected = String::<512>::new();
+        let mut rendered = String::<512>::new();
+
+        for segment in TextIterator::new(text) {
+            let segment = segment.unwrap();
+            for c in segment.chars() {
+                expected.push(c).unwrap();
+            }
+            ufmt::uwrite!(rendered, "{}", segment).unwrap();
+        }
+
+        let mut written = String::<512>::new();
+        write_text(text, &mut written).unwrap();
+
+        assert_eq!(rendered, expected);
+        assert_eq!(written, expected);
+
+        let mut out = String::<512>::new();
+        assert!(matches!(
+            write_text("a\u{7f}", &mut out),
+            Err(WriteError::Encode(DollcodeError::InvalidChar('\u{7f}', 1)))
+        ));
+    }
 }
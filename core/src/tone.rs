@@ -0,0 +1,295 @@
+//! Sending dollcode as audio tones.
+//!
+//! Each glyph and [`DELIMITER`] is a tone of its own frequency, followed by
+//! a short silence so repeated glyphs stay apart. [`Tones`] turns a dollcode
+//! string into `(freq_hz, ms)` pairs for a buzzer or sound card, with a
+//! frequency of 0 for silence. On the receiving side, [`ToneDecoder`] turns
+//! the tones a detector reports back into glyphs and delimiters.
+//!
+//! | Symbol | Default tone |
+//! |--------|--------------|
+//! | ▖ | 1200 Hz |
+//! | ▘ | 1600 Hz |
+//! | ▌ | 2000 Hz |
+//! | [`DELIMITER`] | 2400 Hz |
+//! | between symbols | silence |
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use dollcode::{tone::{ToneDecoder, ToneMap, Tones}, Result};
+//! # fn main() -> Result<()> {
+//! let tones: heapless::Vec<_, 8> = Tones::new("▖▖\u{200D}", ToneMap::DEFAULT)
+//!     .collect::<Result<_>>()?;
+//! assert_eq!(
+//!     tones,
+//!     [(1200, 60), (0, 20), (1200, 60), (0, 20), (2400, 60), (0, 20)]
+//! );
+//!
+//! // A detector reporting slightly off frequencies
+//! let mut decoder = ToneDecoder::new(ToneMap::DEFAULT);
+//! let mut received = heapless::String::<16>::new();
+//! for (freq_hz, ms) in [(1190, 58), (0, 22), (1215, 61), (0, 20), (2390, 60)] {
+//!     if let Some(c) = decoder.push(freq_hz, ms)? {
+//!         received.push(c).unwrap();
+//!     }
+//! }
+//! assert_eq!(received, "▖▖\u{200D}");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{digit_value, text::DELIMITER, DollcodeError, Result, DOLLCODE_CHAR_MAP};
+use core::str::Chars;
+
+/// Frequencies and lengths of the tones
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToneMap {
+    /// Frequency for digits 1, 2 and 3, in Hz
+    pub tones: [u32; 3],
+    /// Frequency for the delimiter, in Hz
+    pub delimiter: u32,
+    /// Length of each tone, in milliseconds
+    pub tone_ms: u32,
+    /// Silence after each tone, in milliseconds
+    pub gap_ms: u32,
+}
+
+impl ToneMap {
+    /// Tones between 1200 and 2400 Hz, 60 ms long with 20 ms of silence
+    pub const DEFAULT: Self = Self {
+        tones: [1200, 1600, 2000],
+        delimiter: 2400,
+        tone_ms: 60,
+        gap_ms: 20,
+    };
+
+    /// Returns the frequencies of the glyphs and the delimiter, with their
+    /// characters
+    fn symbols(&self) -> [(u32, char); 4] {
+        let [one, two, three] = DOLLCODE_CHAR_MAP;
+        [
+            (self.tones[0], one),
+            (self.tones[1], two),
+            (self.tones[2], three),
+            (self.delimiter, DELIMITER),
+        ]
+    }
+
+    /// Returns the frequency of `c`, or `None` if it is not a glyph or the
+    /// delimiter
+    pub fn frequency(&self, c: char) -> Option<u32> {
+        match c {
+            DELIMITER => Some(self.delimiter),
+            _ => digit_value(c).map(|digit| self.tones[usize::from(digit - 1)]),
+        }
+    }
+
+    /// Returns the symbol closest to `freq_hz`, if it is within half the
+    /// smallest spacing between two tones
+    pub fn symbol(&self, freq_hz: u32) -> Option<char> {
+        let symbols = self.symbols();
+        let mut spacing = u32::MAX;
+        for (i, &(a, _)) in symbols.iter().enumerate() {
+            for &(b, _) in &symbols[i + 1..] {
+                spacing = spacing.min(a.abs_diff(b));
+            }
+        }
+        symbols
+            .iter()
+            .min_by_key(|&&(tone, _)| tone.abs_diff(freq_hz))
+            .filter(|&&(tone, _)| tone.abs_diff(freq_hz) < spacing.div_ceil(2))
+            .map(|&(_, c)| c)
+    }
+}
+
+impl Default for ToneMap {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Iterator over the `(freq_hz, ms)` tones of a dollcode string
+///
+/// Silence is a frequency of 0 and is left out when the map's gap is 0.
+/// Yields [`DollcodeError::InvalidChar`] for characters that are neither
+/// glyphs nor delimiters, and then stops.
+#[derive(Debug, Clone)]
+pub struct Tones<'a> {
+    chars: Chars<'a>,
+    map: ToneMap,
+    /// Characters read so far, for error positions
+    position: usize,
+    /// Whether the silence after the last tone is still to come
+    gap: bool,
+    failed: bool,
+}
+
+impl<'a> Tones<'a> {
+    /// Creates an iterator over the tones of `encoded`
+    pub fn new(encoded: &'a str, map: ToneMap) -> Self {
+        Self {
+            chars: encoded.chars(),
+            map,
+            position: 0,
+            gap: false,
+            failed: false,
+        }
+    }
+}
+
+impl Iterator for Tones<'_> {
+    type Item = Result<(u32, u32)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if core::mem::take(&mut self.gap) {
+            return Some(Ok((0, self.map.gap_ms)));
+        }
+        if self.failed {
+            return None;
+        }
+        let c = self.chars.next()?;
+        let Some(freq_hz) = self.map.frequency(c) else {
+            self.failed = true;
+            return Some(Err(DollcodeError::InvalidChar(c, self.position)));
+        };
+        self.position += 1;
+        self.gap = self.map.gap_ms > 0;
+        Some(Ok((freq_hz, self.map.tone_ms)))
+    }
+}
+
+/// Receive side of the tone mapping
+///
+/// The caller reports each tone or silence a detector hears, as a frequency
+/// (0 for silence) and a length. A tone may be reported in several parts;
+/// its symbol is returned once it has lasted half of
+/// [`tone_ms`](ToneMap::tone_ms), and the same symbol again only after a
+/// silence of at least half of [`gap_ms`](ToneMap::gap_ms). Shorter tones
+/// and silences are ignored as noise.
+#[derive(Debug, Clone, Copy)]
+pub struct ToneDecoder {
+    map: ToneMap,
+    /// The symbol being heard, its length so far and whether it was returned
+    current: Option<(char, u32, bool)>,
+}
+
+impl ToneDecoder {
+    /// Creates a decoder for the tones of `map`
+    pub fn new(map: ToneMap) -> Self {
+        Self { map, current: None }
+    }
+
+    /// Reports a tone of `freq_hz`, or silence if 0, lasting `ms`.
+    ///
+    /// Returns the glyph or [`DELIMITER`] the tone completes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DollcodeError::InvalidInput`] for a frequency that matches
+    /// no symbol.
+    pub fn push(&mut self, freq_hz: u32, ms: u32) -> Result<Option<char>> {
+        if freq_hz == 0 {
+            if ms.saturating_mul(2) >= self.map.gap_ms {
+                self.current = None;
+            }
+            return Ok(None);
+        }
+        let c = self
+            .map
+            .symbol(freq_hz)
+            .ok_or(DollcodeError::InvalidInput)?;
+        let (_, heard, returned) = match &mut self.current {
+            Some(current) if current.0 == c => current,
+            current => current.insert((c, 0, false)),
+        };
+        *heard = heard.saturating_add(ms);
+        if *returned || heard.saturating_mul(2) < self.map.tone_ms {
+            return Ok(None);
+        }
+        *returned = true;
+        Ok(Some(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::{String, Vec};
+
+    fn decode(events: &[(u32, u32)]) -> Result<String<64>> {
+        let mut decoder = ToneDecoder::new(ToneMap::DEFAULT);
+        let mut received = String::new();
+        for &(freq_hz, ms) in events {
+            if let Some(c) = decoder.push(freq_hz, ms)? {
+                received.push(c).unwrap();
+            }
+        }
+        Ok(received)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let encoded = "▘▖▘▌\u{200D}▌▘▖▌\u{200D}";
+        let tones: Vec<_, 32> = Tones::new(encoded, ToneMap::DEFAULT)
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(tones.len(), 20);
+        assert_eq!(decode(&tones).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_without_gaps() {
+        let map = ToneMap {
+            gap_ms: 0,
+            ..ToneMap::DEFAULT
+        };
+        let tones: Vec<_, 4> = Tones::new("▖▌", map).collect::<Result<_>>().unwrap();
+        assert_eq!(tones, [(1200, 60), (2000, 60)]);
+    }
+
+    #[test]
+    fn test_noise() {
+        // A tone reported in parts, a click of silence inside it, and a
+        // blip too short to count
+        let events = [
+            (1600, 20),
+            (0, 2),
+            (1610, 40),
+            (0, 20),
+            (2000, 10),
+            (0, 20),
+            (1600, 60),
+        ];
+        assert_eq!(decode(&events).unwrap(), "▘▘");
+    }
+
+    #[test]
+    fn test_symbol() {
+        let map = ToneMap::DEFAULT;
+        assert_eq!(map.symbol(1399), Some('▖'));
+        assert_eq!(map.symbol(1401), Some('▘'));
+        assert_eq!(map.symbol(1400), None);
+        assert_eq!(map.symbol(2599), Some(DELIMITER));
+        assert_eq!(map.symbol(5000), None);
+        assert_eq!(map.frequency('▌'), Some(2000));
+        assert_eq!(map.frequency('x'), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut tones = Tones::new("▖x", ToneMap::DEFAULT);
+        assert!(matches!(tones.next(), Some(Ok((1200, 60)))));
+        assert!(matches!(tones.next(), Some(Ok((0, 20)))));
+        assert!(matches!(
+            tones.next(),
+            Some(Err(DollcodeError::InvalidChar('x', 1)))
+        ));
+        assert!(tones.next().is_none());
+
+        assert!(matches!(
+            decode(&[(440, 60)]),
+            Err(DollcodeError::InvalidInput)
+        ));
+    }
+}
@@ -0,0 +1,92 @@
+//! Parsers for embedding dollcode in `winnow` grammars.
+//!
+//! The same parsers as the [`nom`](crate::nom) module, generic over the
+//! grammar's error type and reading from complete `&str` input:
+//!
+//! - [`dollcode_number`]: a run of glyphs, as a number
+//! - [`dollcode_text_segment`]: up to five glyphs and a delimiter, as a
+//!   printable ASCII character
+//! - [`dollcode_frame`]: glyphs and a delimiter, as a number, the framing used
+//!   by [`message`](crate::message)
+//!
+//! # Examples
+//!
+//! ```rust
+//! use dollcode::winnow::dollcode_number;
+//! use winnow::{combinator::preceded, ModalResult, Parser};
+//!
+//! fn request_id(input: &mut &str) -> ModalResult<u64> {
+//!     preceded("id=", dollcode_number).parse_next(input)
+//! }
+//!
+//! let mut input = "id=▖▖▖▌ ok";
+//! assert_eq!(request_id(&mut input), Ok(42));
+//! assert_eq!(input, " ok");
+//! ```
+
+use crate::{digit_value, text::TextDecoder, text::DELIMITER, FromDollcode};
+use ::winnow::{combinator::terminated, error::ParserError, token::take_while, Parser};
+
+fn is_glyph(c: char) -> bool {
+    digit_value(c).is_some()
+}
+
+/// Parses a run of glyphs as a number. Fails without consuming input if
+/// there are no glyphs or the number does not fit in a `u64`.
+pub fn dollcode_number<'a, E: ParserError<&'a str>>(input: &mut &'a str) -> Result<u64, E> {
+    take_while(1.., is_glyph)
+        .verify_map(|glyphs| u64::decode_dollcode(glyphs).ok())
+        .parse_next(input)
+}
+
+/// Parses one delimited text segment as the character it encodes
+pub fn dollcode_text_segment<'a, E: ParserError<&'a str>>(input: &mut &'a str) -> Result<char, E> {
+    terminated(take_while(1..=5, is_glyph), DELIMITER)
+        .verify_map(|glyphs| TextDecoder::new(glyphs).next().and_then(Result::ok))
+        .parse_next(input)
+}
+
+/// Parses a frame, glyphs followed by a delimiter, as a number. A lone
+/// delimiter is the frame of 0.
+pub fn dollcode_frame<'a, E: ParserError<&'a str>>(input: &mut &'a str) -> Result<u64, E> {
+    terminated(take_while(0.., is_glyph), DELIMITER)
+        .verify_map(|glyphs| u64::decode_dollcode(glyphs).ok())
+        .parse_next(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::winnow::{combinator::repeat, error::ContextError};
+
+    type Result<T> = core::result::Result<T, ContextError>;
+
+    #[test]
+    fn test_parsers() {
+        let mut input = "▖▖▖▌,";
+        assert_eq!(dollcode_number::<ContextError>(&mut input), Ok(42));
+        assert_eq!(input, ",");
+
+        let mut input = "▘▖▘▌\u{200d}▌▘▖▌\u{200d}!";
+        let count: Result<usize> =
+            repeat(1.., dollcode_text_segment::<ContextError>).parse_next(&mut input);
+        assert_eq!(count, Ok(2));
+        assert_eq!(input, "!");
+
+        let mut input = "\u{200d}▖▖▖▌\u{200d}";
+        assert_eq!(dollcode_frame::<ContextError>(&mut input), Ok(0));
+        assert_eq!(dollcode_frame::<ContextError>(&mut input), Ok(42));
+        assert_eq!(input, "");
+    }
+
+    #[test]
+    fn test_failures() {
+        let overflow: heapless::String<128> = ['▌'; 41].iter().collect();
+        let mut input = overflow.as_str();
+        assert!(dollcode_number::<ContextError>(&mut input).is_err());
+        assert_eq!(input, overflow);
+
+        assert!(dollcode_frame::<ContextError>(&mut "▖▖").is_err());
+        assert!(dollcode_text_segment::<ContextError>(&mut "▖▖▖▖▖▖\u{200d}").is_err());
+    }
+}
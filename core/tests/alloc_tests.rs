@@ -0,0 +1,52 @@
+#![cfg(feature = "alloc")]
+
+use dollcode::{
+    decode_text_to_string, encode_text_to_string, encode_to_string, text::TextIterator,
+    to_dollcode, DollcodeError,
+};
+
+#[test]
+fn test_numbers() {
+    for num in [0, 1, 42, 0xDEADBEEF, u64::MAX] {
+        assert_eq!(
+            encode_to_string(num).unwrap(),
+            to_dollcode(num).unwrap().to_string()
+        );
+    }
+}
+
+#[test]
+fn test_text_round_trip() {
+    let text = "Hello, World! ~{}";
+    let encoded = encode_text_to_string(text).unwrap();
+    let expected: String = TextIterator::new(text)
+        .flat_map(|segment| segment.unwrap().chars().collect::<Vec<_>>())
+        .collect();
+    assert_eq!(encoded, expected);
+    assert_eq!(decode_text_to_string(&encoded).unwrap(), text);
+    assert_eq!(encode_text_to_string("").unwrap(), "");
+}
+
+#[test]
+fn test_errors() {
+    assert!(matches!(
+        encode_text_to_string("Hé"),
+        Err(DollcodeError::InvalidChar('é', 1))
+    ));
+    assert!(matches!(
+        decode_text_to_string("▖x\u{200d}"),
+        Err(DollcodeError::InvalidChar('x', 1))
+    ));
+}
+
+#[test]
+fn test_base_conversions() {
+    use dollcode::convert::{from_base32, from_base64, from_hex, to_base32, to_base64, to_hex};
+
+    let encoded = from_base64("3q2+7w==").unwrap();
+    assert_eq!(encoded, from_hex("deadbeef").unwrap());
+    assert_eq!(encoded, from_base32("32W353Y=").unwrap());
+    assert_eq!(to_hex(&encoded).unwrap(), "deadbeef");
+    assert_eq!(to_base32(&encoded).unwrap(), "32W353Y=");
+    assert_eq!(to_base64(&encoded).unwrap(), "3q2+7w==");
+}
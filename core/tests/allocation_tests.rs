@@ -0,0 +1,143 @@
+//! Enforces the zero-allocation guarantee with a counting global allocator.
+//!
+//! Allocations are counted per thread so the test harness's own allocations
+//! on other threads don't interfere.
+
+use core::fmt::Write;
+use dollcode::{
+    batch::{decode_all, encode_all},
+    digit_char, digit_value, from_dollcode,
+    text::{StreamDecoder, TextDecoder, TextIterator, TextSegment},
+    to_dollcode, Dollcode, DollcodeError,
+};
+use heapless::{String, Vec};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// Runs `f` and fails if it allocated on this thread
+fn assert_no_alloc<R>(name: &str, f: impl FnOnce() -> R) -> R {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    let allocations = ALLOCATIONS.with(Cell::get) - before;
+    assert_eq!(
+        allocations, 0,
+        "{} performed {} allocation(s)",
+        name, allocations
+    );
+    result
+}
+
+#[test]
+fn test_allocator_counts() {
+    // Sanity check that the harness detects allocations at all
+    let before = ALLOCATIONS.with(Cell::get);
+    let boxed = Box::new(42u64);
+    assert!(ALLOCATIONS.with(Cell::get) > before);
+    drop(boxed);
+}
+
+#[test]
+fn test_number_apis_do_not_allocate() {
+    assert_no_alloc("number apis", || {
+        for num in [0, 1, 42, 0xDEADBEEF, u64::MAX] {
+            let encoded = to_dollcode(num).unwrap();
+            assert_eq!(from_dollcode(encoded.as_chars()).unwrap(), num);
+            assert_eq!(encoded.is_empty(), num == 0);
+
+            let mut displayed: String<256> = String::new();
+            write!(displayed, "{}", encoded).unwrap();
+        }
+
+        let empty = Dollcode::default();
+        assert!(empty.is_empty());
+        assert!(from_dollcode(&['x']).is_err());
+        assert_eq!(digit_value('▖'), Some(1));
+        assert_eq!(digit_char(3), Some('▌'));
+    });
+}
+
+#[test]
+fn test_text_apis_do_not_allocate() {
+    assert_no_alloc("text apis", || {
+        let mut encoded: String<1024> = String::new();
+        for segment in TextIterator::new("Hello, World! ~") {
+            for &c in segment.unwrap().as_chars() {
+                encoded.push(c).unwrap();
+            }
+        }
+
+        let mut decoded: String<64> = String::new();
+        for c in TextDecoder::new(&encoded) {
+            decoded.push(c.unwrap()).unwrap();
+        }
+        assert_eq!(decoded, "Hello, World! ~");
+
+        let mut decoder = StreamDecoder::new();
+        for c in encoded.chars() {
+            decoder.push(c).unwrap();
+        }
+        decoder.finish().unwrap();
+
+        let segment = TextSegment::default();
+        assert!(segment.is_empty() && segment.as_chars().is_empty());
+        assert!(TextIterator::new("☺").next().unwrap().is_err());
+        assert!(TextDecoder::new("▖▌X").next().unwrap().is_err());
+    });
+}
+
+#[test]
+fn test_batch_apis_do_not_allocate() {
+    assert_no_alloc("batch apis", || {
+        let mut encoded: String<512> = String::new();
+        encode_all(&[1, 42, u64::MAX], &mut |dollcode| {
+            write!(encoded, "{} ", dollcode).unwrap();
+        })
+        .unwrap();
+
+        let mut decoded: Vec<u64, 8> = Vec::new();
+        decode_all(&encoded, &mut |value| decoded.push(value).unwrap()).unwrap();
+        assert_eq!(decoded, [1, 42, u64::MAX]);
+    });
+}
+
+#[test]
+fn test_error_display_does_not_allocate() {
+    assert_no_alloc("error display", || {
+        let mut message: String<128> = String::new();
+        for error in [
+            DollcodeError::InvalidInput,
+            DollcodeError::InvalidChar('☺', 5),
+            DollcodeError::Overflow,
+        ] {
+            message.clear();
+            write!(message, "{} {:?}", error, error).unwrap();
+        }
+    });
+}
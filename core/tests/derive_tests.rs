@@ -0,0 +1,74 @@
+#![cfg(feature = "derive")]
+
+use dollcode::{
+    message::{from_str, DollcodeEncode},
+    DollcodeError,
+};
+use heapless::String;
+
+#[derive(Debug, PartialEq, DollcodeEncode)]
+struct Position(i32, i32);
+
+#[derive(Debug, PartialEq, DollcodeEncode)]
+struct Heartbeat;
+
+#[derive(Debug, PartialEq, DollcodeEncode)]
+struct Tagged<T> {
+    tag: u8,
+    value: T,
+}
+
+#[derive(Debug, PartialEq, DollcodeEncode)]
+struct Report {
+    id: u64,
+    name: String<32>,
+    active: bool,
+    unit: char,
+    position: Position,
+    heartbeat: Heartbeat,
+    reading: Tagged<i16>,
+}
+
+fn encoded<T: DollcodeEncode>(value: &T) -> String<1024> {
+    let mut out = String::new();
+    value.encode(&mut out).unwrap();
+    out
+}
+
+#[test]
+fn test_derived_frames() {
+    assert_eq!(encoded(&Heartbeat), "");
+    assert_eq!(encoded(&Position(-1, 1)), "▖\u{200D}▘\u{200D}");
+    assert_eq!(
+        encoded(&Tagged {
+            tag: 2,
+            value: 42u64
+        }),
+        "▘\u{200D}▖▖▖▌\u{200D}"
+    );
+}
+
+#[test]
+fn test_derived_round_trip() {
+    let report = Report {
+        id: u64::MAX,
+        name: String::try_from("dollcode ▖▘▌").unwrap(),
+        active: true,
+        unit: '°',
+        position: Position(i32::MIN, i32::MAX),
+        heartbeat: Heartbeat,
+        reading: Tagged {
+            tag: 7,
+            value: -300,
+        },
+    };
+    let encoded = encoded(&report);
+    assert_eq!(from_str::<Report>(&encoded).unwrap(), report);
+
+    // A message cut short is missing frames
+    let cut: std::string::String = encoded.chars().take(20).collect();
+    assert!(matches!(
+        from_str::<Report>(&cut),
+        Err(DollcodeError::InvalidInput)
+    ));
+}
@@ -0,0 +1,152 @@
+#![cfg(any(feature = "futures", feature = "tokio"))]
+
+use dollcode::DollcodeError;
+use futures::{executor::block_on, StreamExt};
+use std::io;
+
+/// Returns the dollcode error carried by `e`
+fn dollcode_error(e: &io::Error) -> &DollcodeError {
+    assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    e.get_ref().unwrap().downcast_ref().unwrap()
+}
+
+#[cfg(feature = "futures")]
+mod futures_io {
+    use super::*;
+    use core::{
+        pin::Pin,
+        task::{Context, Poll},
+    };
+    use dollcode::framed::{MessageSink, MessageStream, MAX_LINE};
+    use futures::{io::AsyncRead, SinkExt};
+
+    /// Hands out its data a byte at a time, pending before each byte
+    struct Trickle<'a> {
+        data: &'a [u8],
+        ready: bool,
+    }
+
+    impl AsyncRead for Trickle<'_> {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            let ready = self.ready;
+            self.ready = !ready;
+            if !ready {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            let len = buf.len().min(self.data.len()).min(1);
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Poll::Ready(Ok(len))
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let messages = ["GET /status", "", "{\"ok\": true}"].map(String::from);
+        let mut sink = MessageSink::new(Vec::new());
+        block_on(async {
+            for message in messages.clone() {
+                sink.feed(message).await.unwrap();
+            }
+            sink.close().await.unwrap();
+        });
+        let sent = sink.into_inner();
+        assert_eq!(sent.iter().filter(|&&b| b == b'\n').count(), 3);
+
+        let stream = MessageStream::new(Trickle {
+            data: &sent,
+            ready: false,
+        });
+        let received: Vec<String> = block_on(stream.map(Result::unwrap).collect::<Vec<_>>());
+        assert_eq!(received, messages);
+    }
+
+    #[test]
+    fn test_line_breaks() {
+        let input = "▖▖▖▌\u{200d}\r\n\n▘▖\u{200d}".as_bytes();
+        let received: Vec<u64> = block_on(
+            MessageStream::<_, u64>::new(input)
+                .map(Result::unwrap)
+                .collect(),
+        );
+        assert_eq!(received, [42, 7]);
+
+        let mut stream = MessageStream::<_, u64>::new(" \n\n ".as_bytes());
+        assert!(block_on(stream.next()).is_none());
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut stream =
+            MessageStream::<_, u8>::new("▖▖▖▌▌▌\u{200d}\n▖7\u{200d}\n▘\u{200d}\n".as_bytes());
+        block_on(async {
+            let e = stream.next().await.unwrap().unwrap_err();
+            assert!(matches!(dollcode_error(&e), DollcodeError::Overflow));
+            let e = stream.next().await.unwrap().unwrap_err();
+            assert!(matches!(
+                dollcode_error(&e),
+                DollcodeError::InvalidChar('7', 1)
+            ));
+            assert_eq!(stream.next().await.unwrap().unwrap(), 2);
+            assert!(stream.next().await.is_none());
+        });
+
+        let long = vec![b' '; MAX_LINE];
+        let mut stream = MessageStream::<_, u64>::new(&long[..]);
+        let e = block_on(stream.next()).unwrap().unwrap_err();
+        assert!(matches!(dollcode_error(&e), DollcodeError::Overflow));
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod tokio_codec {
+    use super::*;
+    use bytes::BytesMut;
+    use dollcode::framed::MessageCodec;
+    use tokio_util::codec::{Decoder, Encoder, FramedRead};
+
+    #[test]
+    fn test_codec() {
+        let mut codec = MessageCodec::<u64>::new();
+        let mut buf = BytesMut::new();
+        codec.encode(42, &mut buf).unwrap();
+        codec.encode(u64::MAX, &mut buf).unwrap();
+        let encoded = buf.clone().freeze();
+
+        // Fed a byte at a time, each message appears once its line is complete
+        let mut decoded = Vec::new();
+        let mut src = BytesMut::new();
+        for &byte in encoded.iter() {
+            src.extend_from_slice(&[byte]);
+            if let Some(value) = codec.decode(&mut src).unwrap() {
+                decoded.push(value);
+            }
+        }
+        assert_eq!(decoded, [42, u64::MAX]);
+        assert!(src.is_empty());
+
+        let mut src = BytesMut::from("▘▖\u{200d}");
+        assert_eq!(codec.decode(&mut src).unwrap(), None);
+        assert_eq!(codec.decode_eof(&mut src).unwrap(), Some(7));
+        assert_eq!(codec.decode_eof(&mut src).unwrap(), None);
+    }
+
+    #[test]
+    fn test_framed_read() {
+        let input = "▖▖▖▌\u{200d}\n▖x\u{200d}\n".as_bytes();
+        let mut frames = FramedRead::new(input, MessageCodec::<u64>::new());
+        block_on(async {
+            assert_eq!(frames.next().await.unwrap().unwrap(), 42);
+            let e = frames.next().await.unwrap().unwrap_err();
+            assert!(matches!(
+                dollcode_error(&e),
+                DollcodeError::InvalidChar('x', 1)
+            ));
+        });
+    }
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "std")]
+
+use dollcode::{
+    encode_text_to_string,
+    io::{DollcodeReader, DollcodeWriter},
+    DollcodeError,
+};
+use std::io::{self, BufWriter, Read, Write};
+
+/// Returns the dollcode error carried by `e`
+fn dollcode_error(e: &io::Error) -> &DollcodeError {
+    assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    e.get_ref().unwrap().downcast_ref().unwrap()
+}
+
+#[test]
+fn test_copy_round_trip() {
+    let text = "GET /status\r\nHost: example.org\r\n\r\n{\"ok\": true}\n";
+    let mut writer = DollcodeWriter::new(BufWriter::new(Vec::new()));
+    io::copy(&mut text.as_bytes(), &mut writer).unwrap();
+    let encoded = writer.into_inner().into_inner().unwrap();
+
+    let lines: Vec<&str> = text.split("\r\n").collect();
+    let expected: Vec<String> = lines
+        .iter()
+        .map(|line| {
+            line.split('\n')
+                .map(|part| encode_text_to_string(part).unwrap())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect();
+    assert_eq!(
+        String::from_utf8(encoded.clone()).unwrap(),
+        expected.join("\r\n")
+    );
+
+    let mut decoded = String::new();
+    DollcodeReader::new(&encoded[..])
+        .read_to_string(&mut decoded)
+        .unwrap();
+    assert_eq!(decoded, text);
+}
+
+/// Hands out its data a few bytes at a time, splitting glyphs
+struct Trickle<'a>(&'a [u8]);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = buf.len().min(self.0.len()).min(2);
+        buf[..len].copy_from_slice(&self.0[..len]);
+        self.0 = &self.0[len..];
+        Ok(len)
+    }
+}
+
+#[test]
+fn test_split_input() {
+    let encoded =
+        encode_text_to_string("Hi").unwrap() + "\n" + &encode_text_to_string("!").unwrap();
+    let mut reader = DollcodeReader::new(Trickle(encoded.as_bytes()));
+    let mut byte = [0u8];
+    for expected in *b"Hi\n!" {
+        reader.read_exact(&mut byte).unwrap();
+        assert_eq!(byte[0], expected);
+    }
+    assert_eq!(reader.read(&mut byte).unwrap(), 0);
+}
+
+#[test]
+fn test_numbers() {
+    let mut writer = DollcodeWriter::new(Vec::new());
+    for value in [42, 0, u64::MAX] {
+        writer.write_number(value).unwrap();
+    }
+    writer.write_all(b"\nok").unwrap();
+    let encoded = writer.into_inner();
+    assert!(String::from_utf8_lossy(&encoded).starts_with("▖▖▖▌\u{200d}\u{200d}"));
+
+    let mut reader = DollcodeReader::new(Trickle(&encoded));
+    assert_eq!(reader.read_number().unwrap(), Some(42));
+    assert_eq!(reader.read_number().unwrap(), Some(0));
+    assert_eq!(reader.read_number().unwrap(), Some(u64::MAX));
+    let mut rest = String::new();
+    reader.read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, "\nok");
+    assert_eq!(reader.read_number().unwrap(), None);
+}
+
+#[test]
+fn test_errors() {
+    let mut writer = DollcodeWriter::new(Vec::new());
+    assert_eq!(writer.write(b"a\tb").unwrap(), 1);
+    let e = writer.write(b"\tb").unwrap_err();
+    assert!(matches!(
+        dollcode_error(&e),
+        DollcodeError::InvalidChar('\t', 1)
+    ));
+    let e = writer.write_all("é".as_bytes()).unwrap_err();
+    assert!(matches!(
+        dollcode_error(&e),
+        DollcodeError::InvalidChar(_, 1)
+    ));
+
+    let mut decoded = String::new();
+    let e = DollcodeReader::new("▖x".as_bytes())
+        .read_to_string(&mut decoded)
+        .unwrap_err();
+    assert!(matches!(
+        dollcode_error(&e),
+        DollcodeError::InvalidChar('x', _)
+    ));
+
+    // A glyph cut short by the end of the stream
+    let e = DollcodeReader::new(&"▖".as_bytes()[..2])
+        .read_to_string(&mut decoded)
+        .unwrap_err();
+    assert!(matches!(dollcode_error(&e), DollcodeError::InvalidInput));
+
+    let mut reader = DollcodeReader::new("▖▖".as_bytes());
+    let e = reader.read_number().unwrap_err();
+    assert!(matches!(dollcode_error(&e), DollcodeError::InvalidInput));
+    let mut reader = DollcodeReader::new("▖7\u{200d}".as_bytes());
+    let e = reader.read_number().unwrap_err();
+    assert!(matches!(
+        dollcode_error(&e),
+        DollcodeError::InvalidChar('7', 1)
+    ));
+}
@@ -0,0 +1,119 @@
+use dollcode::{
+    batch::{decode_all, encode_all},
+    from_dollcode,
+    text::{StreamDecoder, TextDecoder, TextIterator, DELIMITER},
+    to_dollcode, DollcodeError, DOLLCODE_CHAR_MAP, MAX_DOLLCODE_SIZE,
+};
+use proptest::prelude::*;
+
+// Printable ASCII strings accepted by the text encoder
+fn printable_ascii() -> impl Strategy<Value = String> {
+    proptest::string::string_regex("[ -~]{0,64}").unwrap()
+}
+
+fn encode_text(text: &str) -> String {
+    TextIterator::new(text)
+        .flat_map(|segment| segment.unwrap().as_chars().to_vec())
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn prop_number_roundtrip(num in any::<u64>()) {
+        let encoded = to_dollcode(num).unwrap();
+        prop_assert!(encoded.len() <= MAX_DOLLCODE_SIZE);
+        prop_assert_eq!(from_dollcode(encoded.as_chars()).unwrap(), num);
+    }
+
+    #[test]
+    fn prop_number_encoding_is_canonical(num in any::<u64>()) {
+        // Display and as_chars agree, and only glyphs are produced
+        let encoded = to_dollcode(num).unwrap();
+        let displayed = encoded.to_string();
+        prop_assert!(displayed.chars().eq(encoded.as_chars().iter().copied()));
+        prop_assert!(displayed.chars().all(|c| DOLLCODE_CHAR_MAP.contains(&c)));
+    }
+
+    #[test]
+    fn prop_number_order_preserved(a in any::<u64>(), b in any::<u64>()) {
+        // Bijective base-3 sorts by length first, then lexicographically by digit
+        let (ea, eb) = (to_dollcode(a).unwrap(), to_dollcode(b).unwrap());
+        let key = |chars: &[char]| {
+            (chars.len(), chars.iter().map(|&c| dollcode::digit_value(c).unwrap()).collect::<Vec<_>>())
+        };
+        prop_assert_eq!(a.cmp(&b), key(ea.as_chars()).cmp(&key(eb.as_chars())));
+    }
+
+    #[test]
+    fn prop_decode_rejects_foreign_chars(
+        prefix in proptest::collection::vec(proptest::sample::select(&DOLLCODE_CHAR_MAP[..]), 0..8),
+        c in any::<char>().prop_filter("non-glyph", |c| !DOLLCODE_CHAR_MAP.contains(c)),
+    ) {
+        let mut chars = prefix;
+        chars.push(c);
+        prop_assert!(matches!(from_dollcode(&chars), Err(DollcodeError::InvalidInput)));
+    }
+
+    #[test]
+    fn prop_text_roundtrip(text in printable_ascii()) {
+        let encoded = encode_text(&text);
+        let decoded: String = TextDecoder::new(&encoded).map(Result::unwrap).collect();
+        prop_assert_eq!(decoded, text);
+    }
+
+    #[test]
+    fn prop_text_segments_are_delimited(text in printable_ascii()) {
+        for segment in TextIterator::new(&text) {
+            let segment = segment.unwrap();
+            let (&last, glyphs) = segment.as_chars().split_last().unwrap();
+            prop_assert_eq!(last, DELIMITER);
+            prop_assert!((3..=5).contains(&glyphs.len()));
+            prop_assert!(glyphs.iter().all(|c| DOLLCODE_CHAR_MAP.contains(c)));
+        }
+    }
+
+    #[test]
+    fn prop_text_rejects_non_printable(
+        text in printable_ascii(),
+        c in any::<char>().prop_filter("non-printable", |c| !(' '..='~').contains(c)),
+        at in any::<prop::sample::Index>(),
+    ) {
+        let mut input: Vec<char> = text.chars().collect();
+        let pos = at.index(input.len() + 1);
+        input.insert(pos, c);
+        let input: String = input.into_iter().collect();
+
+        let error = TextIterator::new(&input).find_map(Result::err);
+        prop_assert!(matches!(error, Some(DollcodeError::InvalidChar(e, p)) if e == c && p == pos));
+    }
+
+    #[test]
+    fn prop_stream_decoder_matches_text_decoder(text in printable_ascii()) {
+        let encoded = encode_text(&text);
+        let mut decoder = StreamDecoder::new();
+        let mut decoded = String::new();
+
+        for c in encoded.chars() {
+            if let Some(c) = decoder.push(c).unwrap() {
+                decoded.push(c);
+            }
+        }
+        prop_assert!(decoder.finish().unwrap().is_none());
+        prop_assert_eq!(decoded, text);
+    }
+
+    #[test]
+    fn prop_batch_roundtrip(values in proptest::collection::vec(any::<u64>(), 0..64)) {
+        let mut encoded = String::new();
+        encode_all(&values, &mut |dollcode| {
+            encoded.push_str(&dollcode.to_string());
+            encoded.push('\n');
+        }).unwrap();
+
+        // Zero encodes to an empty sequence, which carries no value in a batch
+        let mut decoded = Vec::new();
+        decode_all(&encoded, &mut |value| decoded.push(value)).unwrap();
+        let expected: Vec<u64> = values.into_iter().filter(|&v| v != 0).collect();
+        prop_assert_eq!(decoded, expected);
+    }
+}
@@ -0,0 +1,66 @@
+#![cfg(all(feature = "serde", feature = "std"))]
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record {
+    #[serde(with = "dollcode::serde_u64")]
+    id: u64,
+    #[serde(with = "dollcode::serde_u64::option", default)]
+    parent: Option<u64>,
+    #[serde(with = "dollcode::serde_text")]
+    name: String,
+    #[serde(with = "dollcode::serde_bytes")]
+    digest: Vec<u8>,
+    count: u32,
+}
+
+#[test]
+fn test_round_trip() {
+    let record = Record {
+        id: 42,
+        parent: Some(u64::MAX),
+        name: "Hi".to_string(),
+        digest: vec![0x2A, 0xFF],
+        count: 7,
+    };
+    let json = serde_json::to_value(&record).unwrap();
+    assert_eq!(json["id"], "▖▖▖▌");
+    assert_eq!(json["name"], "▘▖▘▌\u{200d}▌▘▖▌\u{200d}");
+    assert_eq!(json["digest"], "▖▖▘▘▌▖▘▖▖▘▘▖");
+    assert_eq!(json["count"], 7);
+    assert_eq!(serde_json::from_value::<Record>(json).unwrap(), record);
+
+    let orphan = Record {
+        parent: None,
+        ..record
+    };
+    let json = serde_json::to_string(&orphan).unwrap();
+    assert!(json.contains(r#""parent":null"#));
+    assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), orphan);
+}
+
+#[test]
+fn test_errors() {
+    let error = serde_json::from_str::<Record>(r#"{"id":"▖x","name":"","digest":"","count":0}"#)
+        .unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("invalid value: string \"▖x\", expected a dollcode number"));
+
+    let error =
+        serde_json::from_str::<Record>(r#"{"id":"","parent":5,"name":"","digest":"","count":0}"#)
+            .unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("invalid type: integer `5`, expected a dollcode number"));
+
+    let unencodable = Record {
+        id: 0,
+        parent: None,
+        name: "é".to_string(),
+        digest: Vec::new(),
+        count: 0,
+    };
+    assert!(serde_json::to_string(&unencodable).is_err());
+}
@@ -0,0 +1,83 @@
+//! Derive macro for `dollcode::message::DollcodeEncode`.
+//!
+//! Use it through the `derive` feature of `dollcode`, which re-exports it
+//! next to the trait. Structs with named, unnamed or no fields are supported;
+//! their fields are encoded in declaration order, so every field type must
+//! implement `DollcodeEncode` itself.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, Index};
+
+/// Derives `DollcodeEncode` for a struct, writing its fields in order
+#[proc_macro_derive(DollcodeEncode)]
+pub fn derive_dollcode_encode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(mut input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "DollcodeEncode can only be derived for structs",
+        ));
+    };
+
+    let (encode, decode) = match &data.fields {
+        Fields::Named(fields) => {
+            let names: Vec<_> = fields.named.iter().map(|f| &f.ident).collect();
+            (
+                quote! { #( self.#names.encode(out)?; )* },
+                quote! { Self { #( #names: DollcodeEncode::decode(fields)?, )* } },
+            )
+        }
+        Fields::Unnamed(fields) => {
+            let indices = (0..fields.unnamed.len()).map(Index::from);
+            let decodes = fields
+                .unnamed
+                .iter()
+                .map(|_| quote! { DollcodeEncode::decode(fields)? });
+            (
+                quote! { #( self.#indices.encode(out)?; )* },
+                quote! { Self( #( #decodes, )* ) },
+            )
+        }
+        Fields::Unit => (quote! {}, quote! { Self }),
+    };
+
+    for param in input.generics.type_params_mut() {
+        param
+            .bounds
+            .push(parse_quote!(::dollcode::message::DollcodeEncode));
+    }
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::dollcode::message::DollcodeEncode for #name #ty_generics
+        #where_clause
+        {
+            #[allow(unused_variables)]
+            fn encode<W: ::core::fmt::Write + ?::core::marker::Sized>(
+                &self,
+                out: &mut W,
+            ) -> ::core::fmt::Result {
+                use ::dollcode::message::DollcodeEncode;
+                #encode
+                ::core::result::Result::Ok(())
+            }
+
+            #[allow(unused_variables)]
+            fn decode(
+                fields: &mut ::dollcode::message::Fields<'_>,
+            ) -> ::dollcode::Result<Self> {
+                use ::dollcode::message::DollcodeEncode;
+                ::core::result::Result::Ok(#decode)
+            }
+        }
+    })
+}
@@ -0,0 +1,227 @@
+/* dollcode C API, generated by cbindgen from ffi/src/lib.rs. Do not edit. */
+
+#ifndef DOLLCODE_H
+#define DOLLCODE_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Longest encoding of a `uint64_t` in bytes, without the terminator
+ */
+#define DC_MAX_NUMBER_LEN 123
+
+/**
+ * Longest encoding of one text character in bytes: five glyphs and a
+ * delimiter
+ */
+#define DC_MAX_TEXT_LEN_PER_CHAR 18
+
+/**
+ * Outcome of a call
+ */
+typedef enum {
+  /**
+   * The call succeeded
+   */
+  DC_STATUS_OK = 0,
+  /**
+   * The input is not a valid dollcode sequence
+   */
+  DC_STATUS_INVALID_INPUT = 1,
+  /**
+   * The input has a character outside printable ASCII
+   */
+  DC_STATUS_INVALID_CHAR = 2,
+  /**
+   * The value does not fit in 64 bits
+   */
+  DC_STATUS_OVERFLOW = 3,
+  /**
+   * The output buffer is too small; `written` holds the length needed
+   */
+  DC_STATUS_BUFFER_TOO_SMALL = 4,
+  /**
+   * A required pointer is null
+   */
+  DC_STATUS_NULL_POINTER = 5,
+  /**
+   * The input is not valid UTF-8
+   */
+  DC_STATUS_INVALID_UTF8 = 6,
+  /**
+   * A stream's sink callback returned nonzero
+   */
+  DC_STATUS_SINK_ERROR = 7,
+} DcStatus;
+
+/**
+ * Direction of a streaming context
+ */
+typedef enum {
+  /**
+   * Printable ASCII text into dollcode
+   */
+  DC_MODE_ENCODE_TEXT = 0,
+  /**
+   * Dollcode back into text
+   */
+  DC_MODE_DECODE_TEXT = 1,
+} DcMode;
+
+/**
+ * Streaming conversion state, created by [`dc_ctx_new`] or
+ * [`dc_ctx_init`]
+ */
+typedef struct DcCtx DcCtx;
+
+/**
+ * Receives `len` bytes of output at `data`, valid only during the call.
+ * Returns 0 to continue, anything else to stop the conversion with
+ * `DC_STATUS_SINK_ERROR`.
+ */
+typedef int (*DcSink)(const char *data, size_t len, void *user_data);
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Encodes `value` into `out`.
+ *
+ * # Safety
+ *
+ * `out` must be valid for writes of `out_len` bytes, or null with an
+ * `out_len` of 0. `written` must be null or valid for a write.
+ */
+DcStatus dc_encode_u64(uint64_t value, char *out, size_t out_len, size_t *written);
+
+/**
+ * Decodes `len` bytes of dollcode at `input` into `*value`.
+ *
+ * Only glyphs are accepted, without whitespace or delimiters.
+ *
+ * # Safety
+ *
+ * `input` must be valid for reads of `len` bytes, or null with a `len` of
+ * 0. `value` must be valid for a write.
+ */
+DcStatus dc_decode_u64(const char *input, size_t len, uint64_t *value);
+
+/**
+ * Encodes `len` bytes of printable ASCII text at `input` into `out`.
+ *
+ * The output takes at most [`DC_MAX_TEXT_LEN_PER_CHAR`] bytes per input
+ * character.
+ *
+ * # Safety
+ *
+ * `input` must be valid for reads of `len` bytes, or null with a `len` of
+ * 0. `out` must be valid for writes of `out_len` bytes, or null with an
+ * `out_len` of 0. `written` must be null or valid for a write.
+ */
+DcStatus dc_encode_text(const char *input, size_t len, char *out, size_t out_len, size_t *written);
+
+/**
+ * Decodes `len` bytes of dollcode text at `input` into `out`.
+ *
+ * The output is never longer than the input.
+ *
+ * # Safety
+ *
+ * `input` must be valid for reads of `len` bytes, or null with a `len` of
+ * 0. `out` must be valid for writes of `out_len` bytes, or null with an
+ * `out_len` of 0. `written` must be null or valid for a write.
+ */
+DcStatus dc_decode_text(const char *input, size_t len, char *out, size_t out_len, size_t *written);
+
+/**
+ * Describes `status`, one of the `DcStatus` values, in English, as a
+ * static NUL-terminated string. Other values are described as an unknown
+ * status.
+ */
+const char *dc_status_message(int status);
+
+/**
+ * Version of the library, as a static NUL-terminated string
+ */
+const char *dc_version(void);
+
+/**
+ * Creates a context converting in `mode` and passing output to `sink`,
+ * which is called with `user_data`. `mode` is a `DcMode` value; returns
+ * null if it is not one, or if `sink` is null.
+ *
+ * Free the context with [`dc_ctx_free`]. Needs the `std` feature.
+ */
+DcCtx *dc_ctx_new(int mode, DcSink sink, void *user_data);
+
+/**
+ * Size in bytes of a context, for placing one with [`dc_ctx_init`]
+ */
+size_t dc_ctx_size(void);
+
+/**
+ * Sets up a context in the `size` bytes at `ctx`, like [`dc_ctx_new`] but
+ * without allocating, for use without the `std` feature. The memory must
+ * be aligned for any type, as from `malloc` or declared with
+ * `_Alignas(max_align_t)`, and at least [`dc_ctx_size`] bytes.
+ *
+ * Returns `DC_STATUS_BUFFER_TOO_SMALL` if `size` is too small,
+ * `DC_STATUS_NULL_POINTER` if `ctx` or `sink` is null, and
+ * `DC_STATUS_INVALID_INPUT` if `mode` is not a `DcMode` value or `ctx` is
+ * not aligned. The context holds nothing to free.
+ *
+ * # Safety
+ *
+ * `ctx` must be null or valid for writes of `size` bytes, and not hold a
+ * context in use.
+ */
+DcStatus dc_ctx_init(DcCtx *ctx, size_t size, int mode, DcSink sink, void *user_data);
+
+/**
+ * Converts `len` bytes at `data`, calling the sink with all output that is
+ * complete before returning.
+ *
+ * After a failure the context keeps returning the same status until
+ * [`dc_ctx_finish`]. Output up to the failing character has been passed
+ * to the sink.
+ *
+ * # Safety
+ *
+ * `ctx` must come from [`dc_ctx_new`] or [`dc_ctx_init`], and not be
+ * freed. `data` must be valid for reads of `len` bytes, or null with a
+ * `len` of 0.
+ */
+DcStatus dc_ctx_feed(DcCtx *ctx, const char *data, size_t len);
+
+/**
+ * Ends the input, converting a final segment without its delimiter, and
+ * returns the first failure of the conversion, if any.
+ *
+ * The context is then ready for new input.
+ *
+ * # Safety
+ *
+ * `ctx` must come from [`dc_ctx_new`] or [`dc_ctx_init`], and not be
+ * freed.
+ */
+DcStatus dc_ctx_finish(DcCtx *ctx);
+
+/**
+ * Frees a context. Null is ignored. Needs the `std` feature.
+ *
+ * # Safety
+ *
+ * `ctx` must be null or come from [`dc_ctx_new`], and not be used again.
+ */
+void dc_ctx_free(DcCtx *ctx);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* DOLLCODE_H */
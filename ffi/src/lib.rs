@@ -0,0 +1,454 @@
+//! C bindings for dollcode.
+//!
+//! Every function returns a [`DcStatus`] and writes its result into buffers
+//! owned by the caller, so nothing is allocated on either side of the
+//! boundary. Text output is UTF-8 followed by a NUL terminator, and the
+//! length reported through `written` does not count the terminator.
+//!
+//! The C declarations are in `include/dollcode.h`:
+//!
+//! ```c
+//! #include "dollcode.h"
+//!
+//! char buf[DC_MAX_NUMBER_LEN + 1];
+//! size_t len;
+//! if (dc_encode_u64(42, buf, sizeof buf, &len) == DC_STATUS_OK) {
+//!     puts(buf); /* ▖▖▖▌ */
+//! }
+//! ```
+//!
+//! Passing a null `out` with an `out_len` of 0 asks for the length needed:
+//! the call returns [`DcStatus::BufferTooSmall`] with the length of the
+//! output, without its terminator, in `written`.
+//!
+//! Only [`stream::dc_ctx_new`] and [`stream::dc_ctx_free`] need the standard
+//! library, behind the default `std` feature. Without it the library is
+//! `no_std` for targets without an operating system: streaming contexts go
+//! in memory of your own with [`stream::dc_ctx_init`], and a panic, which
+//! the conversions never cause, loops forever. Build it for a target that
+//! aborts on panic, as bare-metal targets do.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+pub mod stream;
+
+use core::{
+    ffi::{c_char, c_int},
+    fmt::{self, Write},
+    slice, str,
+};
+use dollcode::{
+    digit_value, from_dollcode,
+    text::{TextDecoder, TextIterator, DELIMITER},
+    to_dollcode, DollcodeError, DOLLCODE_CHAR_MAP, MAX_DOLLCODE_SIZE,
+};
+
+/// Outcome of a call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DcStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// The input is not a valid dollcode sequence
+    InvalidInput = 1,
+    /// The input has a character outside printable ASCII
+    InvalidChar = 2,
+    /// The value does not fit in 64 bits
+    Overflow = 3,
+    /// The output buffer is too small; `written` holds the length needed
+    BufferTooSmall = 4,
+    /// A required pointer is null
+    NullPointer = 5,
+    /// The input is not valid UTF-8
+    InvalidUtf8 = 6,
+    /// A stream's sink callback returned nonzero
+    SinkError = 7,
+}
+
+impl DcStatus {
+    /// Every status, for checking integers passed from C
+    const ALL: [Self; 8] = [
+        Self::Ok,
+        Self::InvalidInput,
+        Self::InvalidChar,
+        Self::Overflow,
+        Self::BufferTooSmall,
+        Self::NullPointer,
+        Self::InvalidUtf8,
+        Self::SinkError,
+    ];
+
+    /// Returns the status with the value `code`, if there is one
+    fn from_code(code: c_int) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|&status| status as c_int == code)
+    }
+}
+
+impl From<DollcodeError> for DcStatus {
+    fn from(e: DollcodeError) -> Self {
+        match e {
+            DollcodeError::InvalidInput => Self::InvalidInput,
+            DollcodeError::InvalidChar(..) => Self::InvalidChar,
+            DollcodeError::Overflow => Self::Overflow,
+        }
+    }
+}
+
+/// Longest encoding of a `uint64_t` in bytes, without the terminator
+pub const DC_MAX_NUMBER_LEN: usize = 123;
+
+/// Longest encoding of one text character in bytes: five glyphs and a
+/// delimiter
+pub const DC_MAX_TEXT_LEN_PER_CHAR: usize = 18;
+
+// The header needs literals, so check them against the core crate
+const GLYPH_LEN: usize = DOLLCODE_CHAR_MAP[0].len_utf8();
+const _: () = assert!(DC_MAX_NUMBER_LEN == MAX_DOLLCODE_SIZE * GLYPH_LEN);
+const _: () = assert!(DC_MAX_TEXT_LEN_PER_CHAR == 5 * GLYPH_LEN + DELIMITER.len_utf8());
+
+/// Caller's output buffer. Counts everything written to it, so the length
+/// needed is known even when the output does not fit.
+pub(crate) struct Output<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+    written: Option<&'a mut usize>,
+}
+
+impl<'a> Output<'a> {
+    /// Wraps `len` bytes at `ptr`, which may be null when `len` is 0.
+    ///
+    /// # Safety
+    ///
+    /// A non-null `ptr` must be valid for writes of `len` bytes, and a
+    /// non-null `written` for a write of a `size_t`.
+    pub(crate) unsafe fn new(
+        ptr: *mut c_char,
+        len: usize,
+        written: *mut usize,
+    ) -> Result<Self, DcStatus> {
+        let buf = match (ptr.is_null(), len) {
+            (true, 0) => &mut [][..],
+            (true, _) => return Err(DcStatus::NullPointer),
+            _ => slice::from_raw_parts_mut(ptr.cast(), len),
+        };
+        Ok(Self {
+            buf,
+            len: 0,
+            written: written.as_mut(),
+        })
+    }
+
+    /// Reports the length and terminates the output, if it fits with its
+    /// terminator
+    pub(crate) fn finish(self) -> Result<(), DcStatus> {
+        if let Some(written) = self.written {
+            *written = self.len;
+        }
+        let nul = self.buf.get_mut(self.len).ok_or(DcStatus::BufferTooSmall)?;
+        *nul = 0;
+        Ok(())
+    }
+}
+
+impl Write for Output<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if let Some(dest) = self.buf.get_mut(self.len..end) {
+            dest.copy_from_slice(s.as_bytes());
+        }
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Borrows `len` bytes of UTF-8 at `ptr`, which may be null when `len` is 0.
+///
+/// # Safety
+///
+/// A non-null `ptr` must be valid for reads of `len` bytes.
+pub(crate) unsafe fn input<'a>(ptr: *const c_char, len: usize) -> Result<&'a str, DcStatus> {
+    let bytes = match (ptr.is_null(), len) {
+        (true, 0) => &[][..],
+        (true, _) => return Err(DcStatus::NullPointer),
+        _ => slice::from_raw_parts(ptr.cast(), len),
+    };
+    str::from_utf8(bytes).map_err(|_| DcStatus::InvalidUtf8)
+}
+
+fn status(result: Result<(), DcStatus>) -> DcStatus {
+    result.err().unwrap_or(DcStatus::Ok)
+}
+
+/// Encodes `value` into `out`.
+///
+/// # Safety
+///
+/// `out` must be valid for writes of `out_len` bytes, or null with an
+/// `out_len` of 0. `written` must be null or valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn dc_encode_u64(
+    value: u64,
+    out: *mut c_char,
+    out_len: usize,
+    written: *mut usize,
+) -> DcStatus {
+    status((|| {
+        let mut output = Output::new(out, out_len, written)?;
+        let _ = write!(output, "{}", to_dollcode(value)?);
+        output.finish()
+    })())
+}
+
+/// Decodes `len` bytes of dollcode at `input` into `*value`.
+///
+/// Only glyphs are accepted, without whitespace or delimiters.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `len` bytes, or null with a `len` of
+/// 0. `value` must be valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn dc_decode_u64(
+    input: *const c_char,
+    len: usize,
+    value: *mut u64,
+) -> DcStatus {
+    status((|| {
+        let text = self::input(input, len)?;
+        let value = value.as_mut().ok_or(DcStatus::NullPointer)?;
+
+        let mut chars = ['\0'; MAX_DOLLCODE_SIZE];
+        let mut count = 0;
+        for c in text.chars() {
+            if digit_value(c).is_none() {
+                return Err(DcStatus::InvalidInput);
+            }
+            *chars.get_mut(count).ok_or(DcStatus::Overflow)? = c;
+            count += 1;
+        }
+        *value = from_dollcode(&chars[..count])?;
+        Ok(())
+    })())
+}
+
+/// Encodes `len` bytes of printable ASCII text at `input` into `out`.
+///
+/// The output takes at most [`DC_MAX_TEXT_LEN_PER_CHAR`] bytes per input
+/// character.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `len` bytes, or null with a `len` of
+/// 0. `out` must be valid for writes of `out_len` bytes, or null with an
+/// `out_len` of 0. `written` must be null or valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn dc_encode_text(
+    input: *const c_char,
+    len: usize,
+    out: *mut c_char,
+    out_len: usize,
+    written: *mut usize,
+) -> DcStatus {
+    status((|| {
+        let text = self::input(input, len)?;
+        let mut output = Output::new(out, out_len, written)?;
+        for segment in TextIterator::new(text) {
+            let _ = write!(output, "{}", segment?);
+        }
+        output.finish()
+    })())
+}
+
+/// Decodes `len` bytes of dollcode text at `input` into `out`.
+///
+/// The output is never longer than the input.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `len` bytes, or null with a `len` of
+/// 0. `out` must be valid for writes of `out_len` bytes, or null with an
+/// `out_len` of 0. `written` must be null or valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn dc_decode_text(
+    input: *const c_char,
+    len: usize,
+    out: *mut c_char,
+    out_len: usize,
+    written: *mut usize,
+) -> DcStatus {
+    status((|| {
+        let text = self::input(input, len)?;
+        let mut output = Output::new(out, out_len, written)?;
+        for c in TextDecoder::new(text) {
+            let _ = output.write_char(c?);
+        }
+        output.finish()
+    })())
+}
+
+/// Describes `status`, one of the `DcStatus` values, in English, as a
+/// static NUL-terminated string. Other values are described as an unknown
+/// status.
+#[no_mangle]
+pub extern "C" fn dc_status_message(status: c_int) -> *const c_char {
+    // Taken as an integer, as a C caller can pass any value and a Rust enum
+    // holding an undeclared one is undefined behavior
+    let Some(status) = DcStatus::from_code(status) else {
+        return c"unknown status".as_ptr();
+    };
+    let message = match status {
+        DcStatus::Ok => c"success",
+        DcStatus::InvalidInput => c"invalid dollcode sequence",
+        DcStatus::InvalidChar => c"unsupported character",
+        DcStatus::Overflow => c"value does not fit in 64 bits",
+        DcStatus::BufferTooSmall => c"output buffer too small",
+        DcStatus::NullPointer => c"null pointer",
+        DcStatus::InvalidUtf8 => c"input is not valid UTF-8",
+        DcStatus::SinkError => c"sink reported an error",
+    };
+    message.as_ptr()
+}
+
+/// Version of the library, as a static NUL-terminated string
+#[no_mangle]
+pub extern "C" fn dc_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+#[cfg(not(any(feature = "std", test)))]
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{ffi::CStr, ptr};
+
+    type Encoder =
+        unsafe extern "C" fn(*const c_char, usize, *mut c_char, usize, *mut usize) -> DcStatus;
+
+    /// Runs a text conversion into a buffer of `capacity` bytes
+    fn convert(f: Encoder, input: &str, capacity: usize) -> (DcStatus, String, usize) {
+        let mut buf = vec![0 as c_char; capacity];
+        let mut written = 0;
+        let status = unsafe {
+            f(
+                input.as_ptr().cast(),
+                input.len(),
+                buf.as_mut_ptr(),
+                buf.len(),
+                &mut written,
+            )
+        };
+        let text = match status {
+            DcStatus::Ok => unsafe { CStr::from_ptr(buf.as_ptr()) }
+                .to_str()
+                .unwrap()
+                .to_string(),
+            _ => String::new(),
+        };
+        (status, text, written)
+    }
+
+    fn encode_u64(value: u64, capacity: usize) -> (DcStatus, String, usize) {
+        let mut buf = vec![0 as c_char; capacity];
+        let mut written = 0;
+        let status = unsafe { dc_encode_u64(value, buf.as_mut_ptr(), buf.len(), &mut written) };
+        let text = unsafe { CStr::from_ptr(buf.as_ptr()) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        (status, text, written)
+    }
+
+    fn decode_u64(input: &str) -> Result<u64, DcStatus> {
+        let mut value = 0;
+        match unsafe { dc_decode_u64(input.as_ptr().cast(), input.len(), &mut value) } {
+            DcStatus::Ok => Ok(value),
+            status => Err(status),
+        }
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(encode_u64(42, 64), (DcStatus::Ok, "▖▖▖▌".to_string(), 12));
+        assert_eq!(decode_u64("▖▖▖▌"), Ok(42));
+
+        let (status, max, written) = encode_u64(u64::MAX, DC_MAX_NUMBER_LEN + 1);
+        assert_eq!(status, DcStatus::Ok);
+        assert!(written <= DC_MAX_NUMBER_LEN);
+        assert_eq!(decode_u64(&max), Ok(u64::MAX));
+
+        assert_eq!(decode_u64("▖x"), Err(DcStatus::InvalidInput));
+        assert_eq!(decode_u64(&"▌".repeat(45)), Err(DcStatus::Overflow));
+
+        let mut value = 0;
+        let status = unsafe { dc_decode_u64([0xFFu8].as_ptr().cast(), 1, &mut value) };
+        assert_eq!(status, DcStatus::InvalidUtf8);
+        let status = unsafe { dc_decode_u64(ptr::null(), 3, &mut value) };
+        assert_eq!(status, DcStatus::NullPointer);
+    }
+
+    #[test]
+    fn test_text() {
+        let (status, encoded, written) = convert(dc_encode_text, "Hi", 64);
+        assert_eq!(status, DcStatus::Ok);
+        assert_eq!(encoded, "▘▖▘▌\u{200d}▌▘▖▌\u{200d}");
+        assert_eq!(written, encoded.len());
+        assert_eq!(
+            convert(dc_decode_text, &encoded, 64),
+            (DcStatus::Ok, "Hi".to_string(), 2)
+        );
+
+        assert_eq!(convert(dc_encode_text, "é", 64).0, DcStatus::InvalidChar);
+        assert_eq!(
+            convert(dc_decode_text, "x\u{200d}", 64).0,
+            DcStatus::InvalidChar
+        );
+        assert_eq!(
+            convert(dc_encode_text, "", 1),
+            (DcStatus::Ok, String::new(), 0)
+        );
+    }
+
+    #[test]
+    fn test_buffer_too_small() {
+        // Length query with no buffer
+        let mut written = 0;
+        let status = unsafe { dc_encode_u64(42, ptr::null_mut(), 0, &mut written) };
+        assert_eq!(status, DcStatus::BufferTooSmall);
+        assert_eq!(written, 12);
+
+        // No room for the terminator
+        assert_eq!(encode_u64(42, 12).0, DcStatus::BufferTooSmall);
+        assert_eq!(encode_u64(42, 13).0, DcStatus::Ok);
+
+        let (status, _, written) = convert(dc_encode_text, "Hi", 10);
+        assert_eq!(status, DcStatus::BufferTooSmall);
+        assert_eq!(written, 30);
+
+        let status = unsafe { dc_encode_u64(42, ptr::null_mut(), 8, ptr::null_mut()) };
+        assert_eq!(status, DcStatus::NullPointer);
+    }
+
+    #[test]
+    fn test_strings() {
+        let message = |status| unsafe { CStr::from_ptr(dc_status_message(status)) };
+        assert_eq!(
+            message(DcStatus::Overflow as c_int).to_str().unwrap(),
+            "value does not fit in 64 bits"
+        );
+        for status in DcStatus::ALL {
+            assert_ne!(message(status as c_int).to_str().unwrap(), "unknown status");
+        }
+        for status in [-1, 8, c_int::MAX] {
+            assert_eq!(message(status).to_str().unwrap(), "unknown status");
+        }
+        let version = unsafe { CStr::from_ptr(dc_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+    }
+}
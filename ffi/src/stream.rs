@@ -0,0 +1,451 @@
+//! Push-based text conversion for input that arrives in pieces, such as
+//! reads from an event loop or a UART buffer.
+//!
+//! A context takes input of any size with [`dc_ctx_feed`], split anywhere,
+//! and passes what it converted to a sink callback before returning. Line
+//! breaks are passed through, so line-oriented input keeps its lines.
+
+use crate::DcStatus;
+use core::{
+    ffi::{c_char, c_int, c_void},
+    slice, str,
+};
+use dollcode::text::{StreamDecoder, SEGMENT_TABLE};
+
+/// Largest chunk handed to the sink at once
+const CHUNK_SIZE: usize = 256;
+
+/// Direction of a streaming context
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DcMode {
+    /// Printable ASCII text into dollcode
+    EncodeText = 0,
+    /// Dollcode back into text
+    DecodeText = 1,
+}
+
+impl DcMode {
+    /// Returns the mode with the integer value `code`, if any
+    fn from_code(code: c_int) -> Option<Self> {
+        [Self::EncodeText, Self::DecodeText]
+            .into_iter()
+            .find(|&mode| mode as c_int == code)
+    }
+}
+
+/// Receives `len` bytes of output at `data`, valid only during the call.
+/// Returns 0 to continue, anything else to stop the conversion with
+/// `DC_STATUS_SINK_ERROR`.
+pub type DcSink =
+    Option<unsafe extern "C" fn(data: *const c_char, len: usize, user_data: *mut c_void) -> c_int>;
+
+/// Streaming conversion state, created by [`dc_ctx_new`] or
+/// [`dc_ctx_init`]
+pub struct DcCtx {
+    mode: DcMode,
+    /// Never `None`, checked by [`dc_ctx_new`]
+    sink: DcSink,
+    user_data: *mut c_void,
+    decoder: StreamDecoder,
+    /// Start of a UTF-8 character split across feeds
+    pending: [u8; 4],
+    pending_len: usize,
+    out: [u8; CHUNK_SIZE],
+    out_len: usize,
+    /// First failure, returned until the context is finished
+    error: Option<DcStatus>,
+}
+
+impl DcCtx {
+    /// Returns a context for the arguments of [`dc_ctx_new`], or `None` if
+    /// `mode` is not a `DcMode` value or `sink` is null
+    fn new(mode: c_int, sink: DcSink, user_data: *mut c_void) -> Option<Self> {
+        // Taken as an integer: an enum holding an undeclared value is
+        // undefined behavior, and C callers can pass any int
+        let mode = DcMode::from_code(mode)?;
+        sink?;
+        Some(Self {
+            mode,
+            sink,
+            user_data,
+            decoder: StreamDecoder::new(),
+            pending: [0; 4],
+            pending_len: 0,
+            out: [0; CHUNK_SIZE],
+            out_len: 0,
+            error: None,
+        })
+    }
+
+    fn feed(&mut self, input: &[u8]) -> Result<(), DcStatus> {
+        let converted = input.iter().try_for_each(|&byte| match self.mode {
+            DcMode::EncodeText => self.encode(byte),
+            DcMode::DecodeText => self.decode(byte),
+        });
+        // Output before a failure is still delivered
+        self.flush().and(converted)
+    }
+
+    fn encode(&mut self, byte: u8) -> Result<(), DcStatus> {
+        if matches!(byte, b'\n' | b'\r') {
+            return self.emit(&[byte]);
+        }
+        let segment = byte
+            .checked_sub(b' ')
+            .and_then(|index| SEGMENT_TABLE.get(usize::from(index)))
+            .ok_or(DcStatus::InvalidChar)?;
+        for c in segment.chars() {
+            self.emit_char(c)?;
+        }
+        Ok(())
+    }
+
+    fn decode(&mut self, byte: u8) -> Result<(), DcStatus> {
+        self.pending[self.pending_len] = byte;
+        self.pending_len += 1;
+        let width = match self.pending[0] {
+            0x00..=0x7F => 1,
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => return Err(DcStatus::InvalidUtf8),
+        };
+        if self.pending_len < width {
+            return Ok(());
+        }
+        self.pending_len = 0;
+        let c = str::from_utf8(&self.pending[..width])
+            .ok()
+            .and_then(|s| s.chars().next())
+            .ok_or(DcStatus::InvalidUtf8)?;
+
+        if matches!(c, '\n' | '\r') {
+            self.finish_segment()?;
+            return self.emit_char(c);
+        }
+        if let Some(decoded) = self.decoder.push(c)? {
+            self.emit_char(decoded)?;
+        }
+        Ok(())
+    }
+
+    /// Writes out a segment left without its delimiter
+    fn finish_segment(&mut self) -> Result<(), DcStatus> {
+        if let Some(decoded) = self.decoder.finish()? {
+            self.emit_char(decoded)?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), DcStatus> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        if self.pending_len > 0 {
+            return Err(DcStatus::InvalidUtf8);
+        }
+        self.finish_segment()?;
+        self.flush()
+    }
+
+    fn emit_char(&mut self, c: char) -> Result<(), DcStatus> {
+        self.emit(c.encode_utf8(&mut [0; 4]).as_bytes())
+    }
+
+    fn emit(&mut self, bytes: &[u8]) -> Result<(), DcStatus> {
+        if self.out_len + bytes.len() > CHUNK_SIZE {
+            self.flush()?;
+        }
+        self.out[self.out_len..self.out_len + bytes.len()].copy_from_slice(bytes);
+        self.out_len += bytes.len();
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), DcStatus> {
+        if self.out_len == 0 {
+            return Ok(());
+        }
+        let len = self.out_len;
+        self.out_len = 0;
+        let sink = self.sink.ok_or(DcStatus::NullPointer)?;
+        // SAFETY: the sink was given as valid by the caller of dc_ctx_new
+        match unsafe { sink(self.out.as_ptr().cast(), len, self.user_data) } {
+            0 => Ok(()),
+            _ => Err(DcStatus::SinkError),
+        }
+    }
+
+    /// Clears everything but the mode and sink, ready for new input
+    fn reset(&mut self) {
+        self.decoder = StreamDecoder::new();
+        self.pending_len = 0;
+        self.out_len = 0;
+        self.error = None;
+    }
+}
+
+/// Creates a context converting in `mode` and passing output to `sink`,
+/// which is called with `user_data`. `mode` is a `DcMode` value; returns
+/// null if it is not one, or if `sink` is null.
+///
+/// Free the context with [`dc_ctx_free`]. Needs the `std` feature.
+#[cfg(feature = "std")]
+#[no_mangle]
+pub extern "C" fn dc_ctx_new(mode: c_int, sink: DcSink, user_data: *mut c_void) -> *mut DcCtx {
+    DcCtx::new(mode, sink, user_data)
+        .map_or(core::ptr::null_mut(), |ctx| Box::into_raw(Box::new(ctx)))
+}
+
+/// Size in bytes of a context, for placing one with [`dc_ctx_init`]
+#[no_mangle]
+pub extern "C" fn dc_ctx_size() -> usize {
+    size_of::<DcCtx>()
+}
+
+/// Sets up a context in the `size` bytes at `ctx`, like [`dc_ctx_new`] but
+/// without allocating, for use without the `std` feature. The memory must
+/// be aligned for any type, as from `malloc` or declared with
+/// `_Alignas(max_align_t)`, and at least [`dc_ctx_size`] bytes.
+///
+/// Returns `DC_STATUS_BUFFER_TOO_SMALL` if `size` is too small,
+/// `DC_STATUS_NULL_POINTER` if `ctx` or `sink` is null, and
+/// `DC_STATUS_INVALID_INPUT` if `mode` is not a `DcMode` value or `ctx` is
+/// not aligned. The context holds nothing to free.
+///
+/// # Safety
+///
+/// `ctx` must be null or valid for writes of `size` bytes, and not hold a
+/// context in use.
+#[no_mangle]
+pub unsafe extern "C" fn dc_ctx_init(
+    ctx: *mut DcCtx,
+    size: usize,
+    mode: c_int,
+    sink: DcSink,
+    user_data: *mut c_void,
+) -> DcStatus {
+    if ctx.is_null() || sink.is_none() {
+        return DcStatus::NullPointer;
+    }
+    if size < size_of::<DcCtx>() {
+        return DcStatus::BufferTooSmall;
+    }
+    if !ctx.is_aligned() {
+        return DcStatus::InvalidInput;
+    }
+    match DcCtx::new(mode, sink, user_data) {
+        Some(new) => {
+            ctx.write(new);
+            DcStatus::Ok
+        }
+        None => DcStatus::InvalidInput,
+    }
+}
+
+/// Converts `len` bytes at `data`, calling the sink with all output that is
+/// complete before returning.
+///
+/// After a failure the context keeps returning the same status until
+/// [`dc_ctx_finish`]. Output up to the failing character has been passed
+/// to the sink.
+///
+/// # Safety
+///
+/// `ctx` must come from [`dc_ctx_new`] or [`dc_ctx_init`], and not be
+/// freed. `data` must be valid for reads of `len` bytes, or null with a
+/// `len` of 0.
+#[no_mangle]
+pub unsafe extern "C" fn dc_ctx_feed(ctx: *mut DcCtx, data: *const c_char, len: usize) -> DcStatus {
+    let Some(ctx) = ctx.as_mut() else {
+        return DcStatus::NullPointer;
+    };
+    if let Some(error) = ctx.error {
+        return error;
+    }
+    let input = match (data.is_null(), len) {
+        (true, 0) => &[][..],
+        (true, _) => return DcStatus::NullPointer,
+        _ => slice::from_raw_parts(data.cast(), len),
+    };
+    match ctx.feed(input) {
+        Ok(()) => DcStatus::Ok,
+        Err(error) => {
+            ctx.error = Some(error);
+            error
+        }
+    }
+}
+
+/// Ends the input, converting a final segment without its delimiter, and
+/// returns the first failure of the conversion, if any.
+///
+/// The context is then ready for new input.
+///
+/// # Safety
+///
+/// `ctx` must come from [`dc_ctx_new`] or [`dc_ctx_init`], and not be
+/// freed.
+#[no_mangle]
+pub unsafe extern "C" fn dc_ctx_finish(ctx: *mut DcCtx) -> DcStatus {
+    let Some(ctx) = ctx.as_mut() else {
+        return DcStatus::NullPointer;
+    };
+    let status = match ctx.finish() {
+        Ok(()) => DcStatus::Ok,
+        Err(error) => error,
+    };
+    ctx.reset();
+    status
+}
+
+/// Frees a context. Null is ignored. Needs the `std` feature.
+///
+/// # Safety
+///
+/// `ctx` must be null or come from [`dc_ctx_new`], and not be used again.
+#[cfg(feature = "std")]
+#[no_mangle]
+pub unsafe extern "C" fn dc_ctx_free(ctx: *mut DcCtx) {
+    if !ctx.is_null() {
+        drop(Box::from_raw(ctx));
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use core::ptr;
+
+    /// Appends to the `Vec<u8>` at `user_data`, failing on a `!`
+    unsafe extern "C" fn collect(data: *const c_char, len: usize, user_data: *mut c_void) -> c_int {
+        let out = &mut *user_data.cast::<Vec<u8>>();
+        let data = slice::from_raw_parts(data.cast::<u8>(), len);
+        out.extend_from_slice(data);
+        c_int::from(data.contains(&b'!'))
+    }
+
+    /// Feeds `input` in pieces of `step` bytes, returning the output and
+    /// the status of finishing
+    fn run(mode: DcMode, input: impl AsRef<[u8]>, step: usize) -> (String, DcStatus) {
+        let mut out = Vec::<u8>::new();
+        let ctx = dc_ctx_new(mode as c_int, Some(collect), (&raw mut out).cast());
+        for piece in input.as_ref().chunks(step) {
+            unsafe { dc_ctx_feed(ctx, piece.as_ptr().cast(), piece.len()) };
+        }
+        let status = unsafe { dc_ctx_finish(ctx) };
+        unsafe { dc_ctx_free(ctx) };
+        (String::from_utf8(out).unwrap(), status)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let text = "Hi there\nline two\r\n".repeat(20);
+        for step in [1, 2, 5, 4096] {
+            let (encoded, status) = run(DcMode::EncodeText, &text, step);
+            assert_eq!(status, DcStatus::Ok);
+            assert!(encoded.starts_with("▘▖▘▌\u{200d}▌▘▖▌\u{200d}"));
+            assert_eq!(
+                run(DcMode::DecodeText, &encoded, step),
+                (text.clone(), DcStatus::Ok)
+            );
+        }
+    }
+
+    #[test]
+    fn test_final_segment() {
+        // The last delimiter is optional
+        assert_eq!(
+            run(DcMode::DecodeText, "▘▖▘▌\u{200d}▌▘▖▌", 1),
+            ("Hi".to_string(), DcStatus::Ok)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        for step in [1, 64] {
+            let (out, status) = run(DcMode::EncodeText, "Hi é", step);
+            assert_eq!(status, DcStatus::InvalidChar);
+            assert_eq!(out, "▘▖▘▌\u{200d}▌▘▖▌\u{200d}▌▖▘\u{200d}");
+        }
+
+        assert_eq!(run(DcMode::DecodeText, "▖x", 1).1, DcStatus::InvalidChar);
+        assert_eq!(
+            run(DcMode::DecodeText, &"▖".as_bytes()[..2], 1).1,
+            DcStatus::InvalidUtf8
+        );
+
+        // A sink refusing output stops the conversion
+        assert_eq!(
+            run(DcMode::DecodeText, "▌▖▌\u{200d}", 1).1,
+            DcStatus::SinkError
+        );
+
+        assert!(dc_ctx_new(DcMode::EncodeText as c_int, None, ptr::null_mut()).is_null());
+        let mut out = Vec::<u8>::new();
+        for mode in [-1, 2, c_int::MAX] {
+            assert!(dc_ctx_new(mode, Some(collect), (&raw mut out).cast()).is_null());
+        }
+        assert_eq!(
+            unsafe { dc_ctx_feed(ptr::null_mut(), ptr::null(), 0) },
+            DcStatus::NullPointer
+        );
+    }
+
+    #[test]
+    fn test_reuse() {
+        let mut out = Vec::<u8>::new();
+        let ctx = dc_ctx_new(
+            DcMode::EncodeText as c_int,
+            Some(collect),
+            (&raw mut out).cast(),
+        );
+        unsafe {
+            assert_eq!(dc_ctx_feed(ctx, c"é".as_ptr(), 2), DcStatus::InvalidChar);
+            assert_eq!(dc_ctx_feed(ctx, c"H".as_ptr(), 1), DcStatus::InvalidChar);
+            assert_eq!(dc_ctx_finish(ctx), DcStatus::InvalidChar);
+
+            // Finishing starts over
+            assert_eq!(dc_ctx_feed(ctx, c"H".as_ptr(), 1), DcStatus::Ok);
+            assert_eq!(dc_ctx_finish(ctx), DcStatus::Ok);
+            dc_ctx_free(ctx);
+        }
+        assert_eq!(String::from_utf8(out).unwrap(), "▘▖▘▌\u{200d}");
+    }
+
+    #[test]
+    fn test_init() {
+        let mut out = Vec::<u8>::new();
+        let user_data = (&raw mut out).cast();
+        let mut storage = std::mem::MaybeUninit::<DcCtx>::uninit();
+        let ctx = storage.as_mut_ptr();
+        let size = dc_ctx_size();
+        let mode = DcMode::EncodeText as c_int;
+        unsafe {
+            assert_eq!(
+                dc_ctx_init(ctx, size - 1, mode, Some(collect), user_data),
+                DcStatus::BufferTooSmall
+            );
+            assert_eq!(
+                dc_ctx_init(ctx, size, mode, None, user_data),
+                DcStatus::NullPointer
+            );
+            assert_eq!(
+                dc_ctx_init(ctx, size, 2, Some(collect), user_data),
+                DcStatus::InvalidInput
+            );
+            assert_eq!(
+                dc_ctx_init(ctx.byte_add(1), size, mode, Some(collect), user_data),
+                DcStatus::InvalidInput
+            );
+
+            assert_eq!(
+                dc_ctx_init(ctx, size, mode, Some(collect), user_data),
+                DcStatus::Ok
+            );
+            assert_eq!(dc_ctx_feed(ctx, c"Hi".as_ptr(), 2), DcStatus::Ok);
+            assert_eq!(dc_ctx_finish(ctx), DcStatus::Ok);
+        }
+        assert_eq!(String::from_utf8(out).unwrap(), "▘▖▘▌\u{200d}▌▘▖▌\u{200d}");
+    }
+}
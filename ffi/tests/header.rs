@@ -0,0 +1,27 @@
+use std::{env, fs, path::Path};
+
+/// The committed header must match what cbindgen generates from the source,
+/// so C users never build against stale declarations
+#[test]
+fn test_header_is_current() {
+    let crate_dir = env!("CARGO_MANIFEST_DIR");
+    let config = cbindgen::Config::from_file(Path::new(crate_dir).join("cbindgen.toml")).unwrap();
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_crate(crate_dir)
+        .with_config(config)
+        .generate()
+        .unwrap()
+        .write(&mut generated);
+
+    let path = Path::new(crate_dir).join("include/dollcode.h");
+    if env::var_os("DOLLCODE_BLESS").is_some() {
+        fs::write(&path, &generated).unwrap();
+    }
+    let committed = fs::read(&path).unwrap_or_default();
+    assert!(
+        committed == generated,
+        "include/dollcode.h is out of date, regenerate it with \
+         `DOLLCODE_BLESS=1 cargo test -p dollcode-ffi`"
+    );
+}
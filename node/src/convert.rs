@@ -0,0 +1,434 @@
+//! The conversions behind the exported functions, free of napi types so
+//! they can be tested without Node.
+
+use crate::{Conversion, Detection, Interpretations};
+use dollcode::{
+    bytes::{ByteDecoder, ByteEncoder},
+    from_dollcode,
+    input::{self, InputKind, UrlDecoder},
+    text::{TextDecoder, TextIterator, DELIMITER},
+    to_dollcode, DollcodeError, MAX_DOLLCODE_SIZE,
+};
+use napi::Either;
+use std::fmt::Write;
+
+/// Codes of thrown errors, named as in the WebAssembly package
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    EmptyInput,
+    InvalidChar,
+    InvalidSequence,
+    NumberOverflow,
+    InvalidDecimal,
+    InvalidHex,
+    InvalidUtf8,
+    NotDollcode,
+}
+
+impl AsRef<str> for ErrorCode {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::EmptyInput => "EMPTY_INPUT",
+            Self::InvalidChar => "INVALID_CHAR",
+            Self::InvalidSequence => "INVALID_SEQUENCE",
+            Self::NumberOverflow => "NUMBER_OVERFLOW",
+            Self::InvalidDecimal => "INVALID_DECIMAL",
+            Self::InvalidHex => "INVALID_HEX",
+            Self::InvalidUtf8 => "INVALID_UTF8",
+            Self::NotDollcode => "NOT_DOLLCODE",
+        }
+    }
+}
+
+/// A failed conversion, thrown as an `Error` with its `code`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl Error {
+    fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn empty() -> Self {
+        Self::new(ErrorCode::EmptyInput, "Empty input")
+    }
+
+    fn invalid_sequence() -> Self {
+        Self::new(ErrorCode::InvalidSequence, "Invalid dollcode sequence")
+    }
+}
+
+impl From<DollcodeError> for Error {
+    fn from(e: DollcodeError) -> Self {
+        match e {
+            DollcodeError::InvalidChar(c, position) => Self::new(
+                ErrorCode::InvalidChar,
+                format!("Character '{c}' at position {position} is not supported (valid: printable ASCII)"),
+            ),
+            DollcodeError::InvalidInput => Self::new(
+                ErrorCode::InvalidSequence,
+                "Only ▖, ▘, and ▌ characters are allowed for dollcode sequences",
+            ),
+            DollcodeError::Overflow => Self::new(
+                ErrorCode::NumberOverflow,
+                "Dollcode value exceeds the 64-bit range",
+            ),
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Classifies non-empty input with [`input::classify`], as the
+/// WebAssembly package does
+pub fn classify(input: &str) -> Result<InputKind> {
+    if input.is_empty() {
+        return Err(Error::empty());
+    }
+    Ok(input::classify(input)?)
+}
+
+/// Reports how [`auto`] would treat the input. Invalid input is reported
+/// with the message of the error [`auto`] would return.
+pub fn detect(input: &str) -> Detection {
+    match classify(input) {
+        Ok(kind) => Detection {
+            kind: kind.name().to_string(),
+            reason: kind.reason().to_string(),
+            ambiguous: kind.is_number(),
+            alternative: kind.is_number().then(|| InputKind::Text.name().to_string()),
+        },
+        Err(e) => Detection {
+            kind: "invalid".to_string(),
+            reason: e.message,
+            ambiguous: false,
+            alternative: None,
+        },
+    }
+}
+
+/// Decodes dollcode, plain or percent-encoded, encodes decimal and
+/// `0x`-prefixed hex numbers as numbers, and anything else as text
+pub fn auto(input: &str) -> Result<Either<String, Conversion>> {
+    match classify(input)? {
+        InputKind::DollcodeNumber | InputKind::DollcodeText => decode(input).map(Either::B),
+        InputKind::DollcodeUrl => decode(&url_glyphs(input)).map(Either::B),
+        InputKind::Decimal => encode_decimal(input).map(Either::A),
+        InputKind::Hex => encode_hex(input).map(Either::A),
+        InputKind::Text => encode_text(input).map(Either::A),
+    }
+}
+
+/// Encodes decimal digits, without a sign
+pub fn encode_decimal(input: &str) -> Result<String> {
+    let value = input::parse_decimal(input)
+        .ok_or_else(|| Error::new(ErrorCode::InvalidDecimal, "Invalid decimal number"))?;
+    Ok(to_dollcode(value)?.to_string())
+}
+
+/// Encodes a hex number with an optional `0x` prefix
+pub fn encode_hex(input: &str) -> Result<String> {
+    let value = input::parse_hex(input)
+        .ok_or_else(|| Error::new(ErrorCode::InvalidHex, "Invalid hexadecimal number"))?;
+    Ok(to_dollcode(value)?.to_string())
+}
+
+/// Converts input like [`auto`], returning the dollcode percent-encoded for
+/// URLs. Dollcode input is encoded as it is.
+pub fn encode_url(input: &str) -> Result<String> {
+    let glyphs: String = match classify(input)? {
+        InputKind::DollcodeNumber | InputKind::DollcodeText => glyphs(input)?.into_iter().collect(),
+        InputKind::DollcodeUrl => url_glyphs(input),
+        InputKind::Decimal => encode_decimal(input)?,
+        InputKind::Hex => encode_hex(input)?,
+        InputKind::Text => encode_text(input)?,
+    };
+    let mut url = String::with_capacity(glyphs.len() * 3);
+    for byte in glyphs.bytes() {
+        // Writing to a string cannot fail
+        let _ = write!(url, "%{byte:02X}");
+    }
+    Ok(url)
+}
+
+/// Glyphs of input checked with [`input::is_url_encoded`]
+fn url_glyphs(input: &str) -> String {
+    UrlDecoder::new(input)
+        .map_while(std::result::Result::ok)
+        .map(|(_, c)| c)
+        .collect()
+}
+
+pub fn encode_text(input: &str) -> Result<String> {
+    if input.is_empty() {
+        return Err(Error::empty());
+    }
+    let mut output = String::with_capacity(input.len() * 18);
+    for segment in TextIterator::new(input) {
+        output.extend(segment?.chars());
+    }
+    Ok(output)
+}
+
+/// Decodes into text if the input contains delimiters, or a number
+/// otherwise
+pub fn decode(input: &str) -> Result<Conversion> {
+    if input.contains(DELIMITER) {
+        decode_text(input)
+    } else {
+        decode_number(input)
+    }
+}
+
+pub fn decode_text(input: &str) -> Result<Conversion> {
+    let glyphs: String = glyphs(input)?.into_iter().collect();
+    let text = TextDecoder::new(&glyphs).collect::<dollcode::Result<_>>()?;
+    let glyph_count = glyphs.chars().filter(|&c| c != DELIMITER).count();
+    Ok(Conversion::from_text(text, glyph_count))
+}
+
+pub fn decode_number(input: &str) -> Result<Conversion> {
+    let glyphs = glyphs(input)?;
+    if glyphs.len() > MAX_DOLLCODE_SIZE {
+        return Err(DollcodeError::Overflow.into());
+    }
+    let value = from_dollcode(&glyphs)?;
+    Ok(Conversion::from_number(value, glyphs.len()))
+}
+
+/// Returns every reading of a dollcode sequence: delimited sequences are
+/// text, and undelimited ones a number and possibly fixed-width text
+pub fn interpret(input: &str) -> Result<Interpretations> {
+    let reading = |decoded: Conversion| {
+        let number = decoded.kind == "number";
+        Interpretations::new(
+            number.then(|| decoded.clone()),
+            (!number).then_some(decoded),
+        )
+    };
+    let glyphs: String = match classify(input)? {
+        InputKind::DollcodeNumber => glyphs(input)?.into_iter().collect(),
+        InputKind::DollcodeText => return decode_text(input).map(reading),
+        InputKind::DollcodeUrl => return decode(&url_glyphs(input)).map(reading),
+        InputKind::Decimal | InputKind::Hex | InputKind::Text => {
+            return Err(Error::new(
+                ErrorCode::NotDollcode,
+                "Input is not a dollcode sequence",
+            ))
+        }
+    };
+
+    match (decode_number(&glyphs), decode_fixed_width(&glyphs).ok()) {
+        (Err(e), None) => Err(e),
+        (number, text) => Ok(Interpretations::new(number.ok(), text)),
+    }
+}
+
+/// Cleans pasted dollcode with [`input::normalize`]
+pub fn normalize(input: &str) -> Result<String> {
+    let mut output = String::with_capacity(input.len());
+    input::normalize(input, &mut output)?;
+    Ok(output)
+}
+
+/// Encodes printable ASCII text without delimiters, six glyphs per
+/// character
+pub fn encode_fixed_width(input: &str) -> Result<String> {
+    if input.is_empty() {
+        return Err(Error::empty());
+    }
+    if let Some((position, c)) = input
+        .chars()
+        .enumerate()
+        .find(|&(_, c)| !matches!(c, ' '..='~'))
+    {
+        return Err(DollcodeError::InvalidChar(c, position).into());
+    }
+    Ok(ByteEncoder::new(input.as_bytes()).collect())
+}
+
+/// Decodes text written by [`encode_fixed_width`]. Whitespace between
+/// glyphs is ignored.
+pub fn decode_fixed_width(input: &str) -> Result<Conversion> {
+    let glyphs: String = glyphs(input)?.into_iter().collect();
+    let text = ByteDecoder::new(&glyphs)
+        .map(|byte| match byte {
+            Ok(byte @ b' '..=b'~') => Ok(char::from(byte)),
+            Err(DollcodeError::InvalidChar(c, position)) => {
+                Err(DollcodeError::InvalidChar(c, position).into())
+            }
+            _ => Err(Error::invalid_sequence()),
+        })
+        .collect::<Result<String>>()?;
+    Ok(Conversion::from_text(text, glyphs.chars().count()))
+}
+
+pub fn encode_bytes(data: &[u8]) -> Result<String> {
+    if data.is_empty() {
+        return Err(Error::empty());
+    }
+    Ok(ByteEncoder::new(data).collect())
+}
+
+pub fn decode_bytes(input: &str) -> Result<Vec<u8>> {
+    let glyphs: String = glyphs(input)?.into_iter().collect();
+    Ok(ByteDecoder::new(&glyphs).collect::<dollcode::Result<_>>()?)
+}
+
+/// Reads a non-empty UTF-8 string from a buffer
+pub fn buffer_str(input: &[u8]) -> Result<&str> {
+    if input.is_empty() {
+        return Err(Error::empty());
+    }
+    std::str::from_utf8(input).map_err(|e| {
+        Error::new(
+            ErrorCode::InvalidUtf8,
+            format!("Input is not valid UTF-8 after byte {}", e.valid_up_to()),
+        )
+    })
+}
+
+/// Characters of non-empty dollcode input with whitespace skipped, so
+/// sequences wrapped across lines decode unchanged
+fn glyphs(input: &str) -> Result<Vec<char>> {
+    let glyphs: Vec<char> = input.chars().filter(|c| !c.is_whitespace()).collect();
+    if glyphs.is_empty() {
+        return Err(Error::empty());
+    }
+    Ok(glyphs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code<T>(result: Result<T>) -> ErrorCode {
+        match result {
+            Ok(_) => panic!("expected an error"),
+            Err(e) => e.code,
+        }
+    }
+
+    #[test]
+    fn test_auto() {
+        let encoded = |input| match auto(input).unwrap() {
+            Either::A(encoded) => encoded,
+            Either::B(decoded) => panic!("decoded {decoded:?}"),
+        };
+        assert_eq!(encoded("42"), "▖▖▖▌");
+        assert_eq!(encoded("0xFF"), "▘▘▌▌▌");
+        assert_eq!(encoded("Hi"), "▘▖▘▌\u{200d}▌▘▖▌\u{200d}");
+        assert_eq!(encoded("0x"), "▖▖▌▌\u{200d}▌▌▌▌\u{200d}");
+
+        assert!(matches!(
+            auto("▖▖▖ ▌").unwrap(),
+            Either::B(c) if c == Conversion::from_number(42, 4)
+        ));
+        assert!(matches!(
+            auto("▘▖▘▌\u{200d}▌▘▖▌").unwrap(),
+            Either::B(c) if c == Conversion::from_text("Hi".to_string(), 8)
+        ));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(code(auto("")), ErrorCode::EmptyInput);
+        assert_eq!(code(decode(" \n")), ErrorCode::EmptyInput);
+        assert_eq!(code(encode_text("café")), ErrorCode::InvalidChar);
+        // Stray characters in dollcode are reported where they are
+        assert_eq!(code(auto("id=▖▖▖▌")), ErrorCode::InvalidChar);
+        assert_eq!(
+            code(decode_number(&"▌".repeat(45))),
+            ErrorCode::NumberOverflow
+        );
+        assert_eq!(
+            code(encode_decimal("18446744073709551616")),
+            ErrorCode::InvalidDecimal
+        );
+        assert_eq!(code(encode_hex("0xg")), ErrorCode::InvalidHex);
+        // Signs and spaces are not part of a number
+        assert_eq!(code(encode_decimal("+42")), ErrorCode::InvalidDecimal);
+        assert_eq!(code(encode_decimal(" 42")), ErrorCode::InvalidDecimal);
+        assert_eq!(code(encode_hex("+2a")), ErrorCode::InvalidHex);
+        assert_eq!(code(encode_hex("0x0x2a")), ErrorCode::InvalidHex);
+        assert_eq!(code(buffer_str(&[b'H', 0xFF])), ErrorCode::InvalidUtf8);
+
+        let error = encode_text("café").unwrap_err();
+        assert_eq!(
+            error.message,
+            "Character 'é' at position 3 is not supported (valid: printable ASCII)"
+        );
+    }
+
+    #[test]
+    fn test_bytes() {
+        let encoded = encode_bytes(&[0, 0xFF]).unwrap();
+        assert_eq!(encoded.chars().count(), 12);
+        assert_eq!(decode_bytes(&encoded).unwrap(), [0, 0xFF]);
+        assert_eq!(code(encode_bytes(&[])), ErrorCode::EmptyInput);
+    }
+
+    #[test]
+    fn test_detect() {
+        let detection = detect("42");
+        assert_eq!(detection.kind, "decimal");
+        assert!(detection.ambiguous);
+        assert_eq!(detection.alternative.as_deref(), Some("text"));
+
+        assert_eq!(detect("+42").kind, "text");
+        assert_eq!(detect("%E2%96%96").kind, "dollcode-url");
+        assert!(!detect("▖▖▖▌").ambiguous);
+
+        let invalid = detect("café");
+        assert_eq!(invalid.kind, "invalid");
+        assert_eq!(
+            invalid.reason,
+            "Character 'é' at position 3 is not supported (valid: printable ASCII)"
+        );
+    }
+
+    #[test]
+    fn test_url() {
+        let url = encode_url("42").unwrap();
+        assert_eq!(url, "%E2%96%96%E2%96%96%E2%96%96%E2%96%8C");
+        assert!(matches!(
+            auto(&url).unwrap(),
+            Either::B(c) if c == Conversion::from_number(42, 4)
+        ));
+        assert_eq!(encode_url("▖▖ ▖▌").unwrap(), url);
+        assert_eq!(encode_url(&url.to_lowercase()).unwrap(), url);
+    }
+
+    #[test]
+    fn test_fixed_width() {
+        let encoded = encode_fixed_width("Hi").unwrap();
+        assert_eq!(encoded.chars().count(), 12);
+        assert_eq!(
+            decode_fixed_width(&encoded).unwrap(),
+            Conversion::from_text("Hi".to_string(), 12)
+        );
+        assert_eq!(code(encode_fixed_width("é")), ErrorCode::InvalidChar);
+        assert_eq!(code(decode_fixed_width("▖▖▖")), ErrorCode::InvalidSequence);
+
+        // Both readings of undelimited glyphs are offered
+        let readings = interpret(&encoded).unwrap();
+        assert!(readings.ambiguous);
+        assert_eq!(readings.text.unwrap().text.as_deref(), Some("Hi"));
+        let readings = interpret("▖▖▖▌").unwrap();
+        assert!(!readings.ambiguous);
+        assert_eq!(readings.number, Some(Conversion::from_number(42, 4)));
+        assert_eq!(code(interpret("42")), ErrorCode::NotDollcode);
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("▗▝ ▐\u{fe0f}").unwrap(), "▖▘▌");
+        assert_eq!(code(normalize("▖x")), ErrorCode::InvalidChar);
+    }
+}
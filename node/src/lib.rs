@@ -0,0 +1,303 @@
+//! Native Node.js bindings for dollcode, built with napi-rs.
+//!
+//! The functions mirror the conversions of the WebAssembly package, in
+//! camelCase, for server-side pipelines where marshaling strings through
+//! wasm-bindgen dominates the cost. Input is classified and parsed by the
+//! core crate's [`dollcode::input`] module, as in the browser, so both
+//! packages accept and reject the same input. Text and dollcode can also be
+//! passed as UTF-8 `Buffer`s with [`convert_text_buffer`] and
+//! [`decode_text_buffer`], which never create JavaScript strings, and
+//! binary data with [`convert_bytes`] and [`decode_to_bytes`].
+//!
+//! Detection ([`detect`], [`interpret`], [`normalize`]), fixed-width text,
+//! percent-encoded URLs and batches are covered. The WebAssembly package's
+//! editor and display helpers are not: `describe`, `convertWithStats` and
+//! `Profiler`, `convertFormatted` and `export`, `renderSvg` and `toQr`,
+//! `EditSession`, `ConversionStream`, `verify`, `mapToInput` and
+//! `mapToOutput`, and `setLimits`. Use the WebAssembly package where those
+//! are needed.
+//!
+//! There are no input limits as in the browser; input of any size is
+//! converted in one call. Numbers returned by [`convert`] are plain strings
+//! rather than the browser's `Encoding` objects; [`detect`] reports that
+//! they could also be read as text.
+//!
+//! Failures are thrown as `Error`s whose `code` is one of the WebAssembly
+//! package's `ErrorCode`s, such as `"INVALID_CHAR"`:
+//!
+//! ```js
+//! const { convert } = require("dollcode-node");
+//! convert("42"); // "▖▖▖▌"
+//! convert("▖▖▖▌"); // { kind: "number", decimal: "42", hex: "0x2a", glyphCount: 4 }
+//! ```
+
+mod convert;
+
+pub use convert::ErrorCode;
+
+use napi::{bindgen_prelude::Buffer, Either};
+use napi_derive::napi;
+
+type Result<T> = napi::Result<T, ErrorCode>;
+
+impl From<convert::Error> for napi::Error<ErrorCode> {
+    fn from(e: convert::Error) -> Self {
+        Self::new(e.code, e.message)
+    }
+}
+
+/// Structured result of decoding a dollcode sequence. Fields that do not
+/// apply to the decoded kind are `undefined`.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conversion {
+    #[napi(ts_type = "'number' | 'text'")]
+    pub kind: String,
+    /// Decimal digits of a decoded number, as a string to preserve u64
+    /// precision
+    pub decimal: Option<String>,
+    /// `0x`-prefixed lowercase hex of a decoded number
+    pub hex: Option<String>,
+    /// Decoded ASCII text
+    pub text: Option<String>,
+    /// Number of ▖, ▘ and ▌ glyphs in the input, excluding delimiters
+    pub glyph_count: u32,
+}
+
+impl Conversion {
+    fn from_number(value: u64, glyph_count: usize) -> Self {
+        Self {
+            kind: "number".to_string(),
+            decimal: Some(value.to_string()),
+            hex: Some(format!("{value:#x}")),
+            text: None,
+            glyph_count: glyph_count as u32,
+        }
+    }
+
+    fn from_text(text: String, glyph_count: usize) -> Self {
+        Self {
+            kind: "text".to_string(),
+            decimal: None,
+            hex: None,
+            text: Some(text),
+            glyph_count: glyph_count as u32,
+        }
+    }
+}
+
+/// How [`convert`] would treat an input, as returned by [`detect`]
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Detection {
+    /// One of `"decimal"`, `"hex"`, `"text"`, `"dollcode-number"`,
+    /// `"dollcode-text"`, `"dollcode-url"` or `"invalid"`
+    pub kind: String,
+    /// Explanation of the classification, or the error message for invalid
+    /// input
+    pub reason: String,
+    /// Whether the input could also be read as another kind, as numbers can
+    /// be read as text
+    pub ambiguous: bool,
+    /// The other kind the input could be read as, if ambiguous
+    pub alternative: Option<String>,
+}
+
+/// Every reading of a dollcode sequence, as returned by [`interpret`]
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interpretations {
+    /// The sequence decoded as a number
+    pub number: Option<Conversion>,
+    /// The sequence decoded as delimited or fixed-width text
+    pub text: Option<Conversion>,
+    /// Whether both readings are valid and the user should choose
+    pub ambiguous: bool,
+}
+
+impl Interpretations {
+    fn new(number: Option<Conversion>, text: Option<Conversion>) -> Self {
+        Self {
+            ambiguous: number.is_some() && text.is_some(),
+            number,
+            text,
+        }
+    }
+}
+
+/// A failed entry of [`convert_batch`]
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchError {
+    /// One of the codes of errors thrown by the other functions
+    pub code: String,
+    pub message: String,
+}
+
+/// Outcome of converting one entry of [`convert_batch`]. Exactly one of
+/// `value` and `error` is defined.
+#[napi(object)]
+pub struct BatchItem {
+    /// Whether the entry converted successfully
+    pub ok: bool,
+    /// What [`convert`] returned for the entry
+    #[napi(ts_type = "string | Conversion")]
+    pub value: Option<Either<String, Conversion>>,
+    /// What [`convert`] threw for the entry
+    pub error: Option<BatchError>,
+}
+
+/// Classifies input the same way [`convert`] does, without converting it.
+/// Only the shape of the input is checked, so numbers and dollcode may still
+/// fail to convert if out of range.
+#[napi]
+pub fn detect(input: String) -> Detection {
+    convert::detect(&input)
+}
+
+/// Converts input to dollcode based on its content: dollcode, plain or
+/// percent-encoded, is decoded into a [`Conversion`], decimal and
+/// `0x`-prefixed hex numbers are encoded as numbers, and anything else as
+/// text.
+#[napi(ts_return_type = "string | Conversion")]
+pub fn convert(input: String) -> Result<Either<String, Conversion>> {
+    Ok(convert::auto(&input)?)
+}
+
+/// Converts every string in `inputs` with [`convert`]. A failing entry does
+/// not stop the batch; its error is reported in its own item.
+#[napi]
+pub fn convert_batch(inputs: Vec<String>) -> Vec<BatchItem> {
+    inputs
+        .iter()
+        .map(|input| match convert::auto(input) {
+            Ok(value) => BatchItem {
+                ok: true,
+                value: Some(value),
+                error: None,
+            },
+            Err(e) => BatchItem {
+                ok: false,
+                value: None,
+                error: Some(BatchError {
+                    code: e.code.as_ref().to_string(),
+                    message: e.message,
+                }),
+            },
+        })
+        .collect()
+}
+
+/// Converts input like [`convert`] and returns the dollcode percent-encoded
+/// for URLs, which [`convert`] decodes directly. Dollcode input is encoded
+/// as it is.
+#[napi]
+pub fn convert_to_url(input: String) -> Result<String> {
+    Ok(convert::encode_url(&input)?)
+}
+
+/// Converts a decimal number, digits only, to dollcode
+#[napi]
+pub fn convert_decimal(input: String) -> Result<String> {
+    Ok(convert::encode_decimal(&input)?)
+}
+
+/// Converts a hexadecimal number, with or without a `0x` prefix, to dollcode
+#[napi]
+pub fn convert_hex(input: String) -> Result<String> {
+    Ok(convert::encode_hex(&input)?)
+}
+
+/// Converts printable ASCII text to dollcode, a delimited segment per
+/// character. `"42"` encodes the characters `4` and `2`.
+#[napi]
+pub fn convert_text(input: String) -> Result<String> {
+    Ok(convert::encode_text(&input)?)
+}
+
+/// Decodes dollcode into a [`Conversion`]: text if it contains delimiters, a
+/// number otherwise. Whitespace between glyphs is ignored.
+#[napi]
+pub fn convert_dollcode(input: String) -> Result<Conversion> {
+    Ok(convert::decode(&input)?)
+}
+
+/// Decodes delimited dollcode as ASCII text. A final segment without a
+/// trailing delimiter is accepted.
+#[napi]
+pub fn decode_to_text(input: String) -> Result<Conversion> {
+    Ok(convert::decode_text(&input)?)
+}
+
+/// Decodes undelimited dollcode as a number
+#[napi]
+pub fn decode_to_number(input: String) -> Result<Conversion> {
+    Ok(convert::decode_number(&input)?)
+}
+
+/// Returns every reading of a dollcode sequence. Sequences with delimiters
+/// are text; those without decode as a number and are also tried as
+/// [`convert_text_fixed_width`] output. Throws `NOT_DOLLCODE` for other
+/// input, and the number's error if there is no valid reading.
+#[napi]
+pub fn interpret(input: String) -> Result<Interpretations> {
+    Ok(convert::interpret(&input)?)
+}
+
+/// Cleans pasted dollcode into its canonical form without decoding it:
+/// lookalike glyphs become ▖, ▘ and ▌, and whitespace, variation selectors
+/// and zero-width spaces are removed
+#[napi]
+pub fn normalize(input: String) -> Result<String> {
+    Ok(convert::normalize(&input)?)
+}
+
+/// Converts printable ASCII text to dollcode without delimiters, six glyphs
+/// per character, for places that strip zero-width joiners. Decode it with
+/// [`decode_fixed_width`].
+#[napi]
+pub fn convert_text_fixed_width(input: String) -> Result<String> {
+    Ok(convert::encode_fixed_width(&input)?)
+}
+
+/// Decodes text produced by [`convert_text_fixed_width`]. Whitespace
+/// between glyphs is ignored.
+#[napi]
+pub fn decode_fixed_width(input: String) -> Result<Conversion> {
+    Ok(convert::decode_fixed_width(&input)?)
+}
+
+/// Encodes binary data, six glyphs per byte
+#[napi]
+pub fn convert_bytes(data: Buffer) -> Result<String> {
+    Ok(convert::encode_bytes(&data)?)
+}
+
+/// Decodes glyphs produced by [`convert_bytes`] back into a `Buffer`.
+/// Whitespace between glyphs is ignored.
+#[napi]
+pub fn decode_to_bytes(input: String) -> Result<Buffer> {
+    Ok(convert::decode_bytes(&input)?.into())
+}
+
+/// Encodes UTF-8 text from a `Buffer` into UTF-8 dollcode, as
+/// [`convert_text`] does
+#[napi]
+pub fn convert_text_buffer(input: Buffer) -> Result<Buffer> {
+    let encoded = convert::encode_text(convert::buffer_str(&input)?)?;
+    Ok(encoded.into_bytes().into())
+}
+
+/// Decodes UTF-8 delimited dollcode from a `Buffer` into UTF-8 text, as
+/// [`decode_to_text`] does
+#[napi]
+pub fn decode_text_buffer(input: Buffer) -> Result<Buffer> {
+    let decoded = convert::decode_text(convert::buffer_str(&input)?)?;
+    Ok(decoded.text.unwrap_or_default().into_bytes().into())
+}
+
+/// Version of the dollcode bindings
+#[napi]
+pub fn version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
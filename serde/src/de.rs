@@ -0,0 +1,344 @@
+//! Reading values back from frames.
+
+use crate::error::{Error, Result};
+use dollcode::{
+    bytes::{ByteDecoder, GLYPHS_PER_BYTE},
+    digit_value,
+    text::DELIMITER,
+    DollcodeError,
+};
+use serde::de::{
+    self, DeserializeSeed, EnumAccess, Error as _, IntoDeserializer, SeqAccess, Unexpected,
+    VariantAccess, Visitor,
+};
+use std::str::Chars;
+
+/// Deserializes values from a string of frames. Whitespace between glyphs is
+/// ignored, so output wrapped across lines reads back unchanged.
+#[derive(Debug)]
+pub struct Deserializer<'de> {
+    chars: Chars<'de>,
+    /// Characters read so far, for error positions
+    position: usize,
+}
+
+impl<'de> Deserializer<'de> {
+    /// Creates a deserializer reading from the start of `input`
+    pub fn new(input: &'de str) -> Self {
+        Self {
+            chars: input.chars(),
+            position: 0,
+        }
+    }
+
+    /// Checks that nothing but whitespace is left
+    pub fn end(&mut self) -> Result<()> {
+        match self.next() {
+            Some(_) => Err(Error::TrailingInput(self.position - 1)),
+            None => Ok(()),
+        }
+    }
+
+    /// Next character that is not whitespace
+    fn next(&mut self) -> Option<char> {
+        self.chars.by_ref().find(|c| {
+            self.position += 1;
+            !c.is_whitespace()
+        })
+    }
+
+    /// Reads a number frame
+    fn word(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        loop {
+            let c = self.next().ok_or(Error::UnexpectedEnd)?;
+            if c == DELIMITER {
+                return Ok(value);
+            }
+            let digit = digit_value(c).ok_or(DollcodeError::InvalidChar(c, self.position - 1))?;
+            value = value
+                .checked_mul(3)
+                .and_then(|v| v.checked_add(digit.into()))
+                .ok_or(DollcodeError::Overflow)?;
+        }
+    }
+
+    /// Reads a number frame that must fit in `T`
+    fn narrow<T: TryFrom<u64>>(&mut self, expected: &str) -> Result<T> {
+        let value = self.word()?;
+        T::try_from(value).map_err(|_| Error::invalid_value(Unexpected::Unsigned(value), &expected))
+    }
+
+    /// Reads a frame of 0 or 1
+    fn flag(&mut self) -> Result<bool> {
+        match self.word()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(Error::invalid_value(Unexpected::Unsigned(value), &"0 or 1")),
+        }
+    }
+
+    fn signed(&mut self) -> Result<i64> {
+        let n = self.word()?;
+        Ok((n >> 1) as i64 ^ -((n & 1) as i64))
+    }
+
+    /// Reads a length frame and that many bytes in the fixed-width encoding
+    fn bytes(&mut self) -> Result<Vec<u8>> {
+        let len = self.narrow::<usize>("a length")?;
+        let glyph_count = len
+            .checked_mul(GLYPHS_PER_BYTE)
+            .ok_or(DollcodeError::Overflow)?;
+        let glyphs = (0..glyph_count)
+            .map(|_| self.next().ok_or(Error::UnexpectedEnd))
+            .collect::<Result<String>>()?;
+        Ok(ByteDecoder::new(&glyphs).collect::<dollcode::Result<_>>()?)
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(Error::AnyNotSupported)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_bool(self.flag()?)
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let v = self.signed()?;
+        visitor.visit_i8(
+            v.try_into()
+                .map_err(|_| Error::invalid_value(Unexpected::Signed(v), &"an i8"))?,
+        )
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let v = self.signed()?;
+        visitor.visit_i16(
+            v.try_into()
+                .map_err(|_| Error::invalid_value(Unexpected::Signed(v), &"an i16"))?,
+        )
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let v = self.signed()?;
+        visitor.visit_i32(
+            v.try_into()
+                .map_err(|_| Error::invalid_value(Unexpected::Signed(v), &"an i32"))?,
+        )
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i64(self.signed()?)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u8(self.narrow("a u8")?)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u16(self.narrow("a u16")?)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u32(self.narrow("a u32")?)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u64(self.word()?)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f32(f32::from_bits(self.narrow("the bits of an f32")?))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f64(f64::from_bits(self.word()?))
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let value = self.word()?;
+        let c = u32::try_from(value)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| Error::invalid_value(Unexpected::Unsigned(value), &"a char"))?;
+        visitor.visit_char(c)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let bytes = self.bytes()?;
+        let string = String::from_utf8(bytes).map_err(|e| {
+            Error::invalid_value(Unexpected::Bytes(e.as_bytes()), &"a UTF-8 string")
+        })?;
+        visitor.visit_string(string)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_byte_buf(self.bytes()?)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.flag()? {
+            visitor.visit_some(self)
+        } else {
+            visitor.visit_none()
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let len = self.narrow("a length")?;
+        visitor.visit_seq(Elements { de: self, len })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(Elements { de: self, len })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let len = self.narrow("a length")?;
+        visitor.visit_map(Elements { de: self, len })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_u32(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(Error::AnyNotSupported)
+    }
+}
+
+/// Elements of a sequence, tuple or struct, or entries of a map
+struct Elements<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    len: usize,
+}
+
+impl<'de> SeqAccess<'de> for Elements<'_, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Elements<'_, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        self.next_element_seed(seed)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<'de> EnumAccess<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
+        let index: u32 = self.narrow("a variant index")?;
+        let variant = seed.deserialize(IntoDeserializer::<Error>::into_deserializer(index))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
@@ -0,0 +1,63 @@
+//! Errors from serializing and deserializing.
+
+use dollcode::DollcodeError;
+use std::fmt::{self, Display};
+
+/// Everything that can make serializing or deserializing fail
+#[derive(Debug)]
+pub enum Error {
+    /// Reported by a `Serialize` or `Deserialize` implementation
+    Message(String),
+    /// A frame contains something other than glyphs, or a value beyond `u64`
+    Dollcode(DollcodeError),
+    /// The input ended inside a value
+    UnexpectedEnd,
+    /// Input is left over after the value, starting at this character
+    TrailingInput(usize),
+    /// A sequence or map was serialized without its length, which the format
+    /// writes up front
+    LengthUnknown,
+    /// The type asked for whatever value comes next, which the format cannot
+    /// tell as it does not describe itself
+    AnyNotSupported,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Message(message) => write!(f, "{message}"),
+            Self::Dollcode(DollcodeError::InvalidChar(c, position)) => {
+                write!(f, "unexpected character {c:?} at position {position}")
+            }
+            Self::Dollcode(DollcodeError::InvalidInput) => write!(f, "invalid dollcode sequence"),
+            Self::Dollcode(DollcodeError::Overflow) => write!(f, "value does not fit in 64 bits"),
+            Self::UnexpectedEnd => write!(f, "unexpected end of input"),
+            Self::TrailingInput(position) => write!(f, "trailing input at position {position}"),
+            Self::LengthUnknown => write!(f, "sequences and maps must have a known length"),
+            Self::AnyNotSupported => write!(f, "the format is not self-describing"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<DollcodeError> for Error {
+    fn from(e: DollcodeError) -> Self {
+        Self::Dollcode(e)
+    }
+}
+
+impl serde::ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Message(msg.to_string())
+    }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Message(msg.to_string())
+    }
+}
+
+/// Result type for serializing and deserializing
+pub type Result<T> = std::result::Result<T, Error>;
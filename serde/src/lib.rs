@@ -0,0 +1,88 @@
+//! A serde data format that writes values as framed dollcode, so any
+//! `Serialize` type can be stored or sent as glyphs and read back.
+//!
+//! # Format
+//!
+//! Values are written as a series of frames, each a dollcode number
+//! followed by the zero-width joiner, so `42` is `▖▖▖▌\u{200D}` and `0` is
+//! a lone joiner. As with postcard or bincode, the format does not describe
+//! itself, and reading needs the same type that was written:
+//!
+//! - Unsigned integers, `bool`s, `char`s and the bits of floats are one
+//!   frame each
+//! - Signed integers are zigzag encoded first, so small negative numbers stay
+//!   short
+//! - Strings and byte arrays are a length frame followed by their bytes,
+//!   six glyphs each as in [`dollcode::bytes`]
+//! - `None` is a frame of 0, and `Some` a frame of 1 followed by the value
+//! - Sequences and maps are a length frame followed by their elements
+//! - Structs and tuples are their fields in order, without names
+//! - Enum variants are a frame of the variant index followed by any fields
+//!
+//! Whitespace between glyphs is ignored when reading.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Reading {
+//!     sensor: u8,
+//!     delta: i32,
+//! }
+//!
+//! let reading = Reading { sensor: 3, delta: -2 };
+//! let encoded = dollcode_serde::to_string(&reading)?;
+//! assert_eq!(encoded, "▌\u{200D}▌\u{200D}");
+//! assert_eq!(dollcode_serde::from_str::<Reading>(&encoded)?, reading);
+//! # Ok::<(), dollcode_serde::Error>(())
+//! ```
+
+mod de;
+mod error;
+mod ser;
+
+pub use de::Deserializer;
+pub use error::{Error, Result};
+pub use ser::Serializer;
+
+use serde::{Deserialize, Serialize};
+use std::io;
+
+/// Serializes `value` into a string of frames.
+///
+/// # Errors
+///
+/// Returns [`Error::LengthUnknown`] for sequences and maps serialized
+/// without a length, or any error reported by the `Serialize`
+/// implementation.
+pub fn to_string<T: ?Sized + Serialize>(value: &T) -> Result<String> {
+    let mut serializer = Serializer::new();
+    value.serialize(&mut serializer)?;
+    Ok(serializer.into_inner())
+}
+
+/// Serializes `value` into `writer` as UTF-8.
+///
+/// # Errors
+///
+/// Returns the errors of [`to_string`], and writing failures as
+/// [`Error::Message`].
+pub fn to_writer<W: io::Write, T: ?Sized + Serialize>(mut writer: W, value: &T) -> Result<()> {
+    writer
+        .write_all(to_string(value)?.as_bytes())
+        .map_err(|e| Error::Message(e.to_string()))
+}
+
+/// Deserializes a `T` from frames, which must make up the whole input.
+///
+/// # Errors
+///
+/// Returns an error if the input is not frames of a `T`, or has anything
+/// but whitespace after them.
+pub fn from_str<'de, T: Deserialize<'de>>(input: &'de str) -> Result<T> {
+    let mut deserializer = Deserializer::new(input);
+    let value = T::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
@@ -0,0 +1,143 @@
+use dollcode::DollcodeError;
+use dollcode_serde::{from_str, to_string, Error};
+use serde::{Deserialize, Serialize, Serializer};
+use std::{collections::BTreeMap, fmt::Debug};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Command {
+    Stop,
+    Move(i16, i16),
+    Say { text: String, loud: bool },
+    Tag(char),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Unit;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Message {
+    id: u64,
+    name: String,
+    ratio: f64,
+    scale: f32,
+    offset: Option<i64>,
+    missing: Option<u8>,
+    commands: Vec<Command>,
+    labels: BTreeMap<String, u32>,
+    pair: (u8, bool),
+    unit: Unit,
+}
+
+fn round_trip<T>(value: &T) -> String
+where
+    T: Debug + PartialEq + Serialize + for<'de> Deserialize<'de>,
+{
+    let encoded = to_string(value).unwrap();
+    assert_eq!(&from_str::<T>(&encoded).unwrap(), value, "{encoded}");
+    encoded
+}
+
+#[test]
+fn test_frames() {
+    assert_eq!(round_trip(&0u8), "\u{200D}");
+    assert_eq!(round_trip(&42u64), "▖▖▖▌\u{200D}");
+    assert_eq!(round_trip(&-1i32), "▖\u{200D}");
+    assert_eq!(round_trip(&true), "▖\u{200D}");
+    assert_eq!(round_trip(&Some(2u8)), "▖\u{200D}▘\u{200D}");
+    assert_eq!(round_trip(&Command::Stop), "\u{200D}");
+    // A length of 1, then the byte 0x2A
+    assert_eq!(round_trip(&"*".to_string()), "▖\u{200D}▖▖▘▘▌▖");
+}
+
+#[test]
+fn test_round_trip() {
+    let message = Message {
+        id: u64::MAX,
+        name: "dollcode ▖▘▌".to_string(),
+        ratio: -0.25,
+        scale: f32::INFINITY,
+        offset: Some(i64::MIN),
+        missing: None,
+        commands: vec![
+            Command::Stop,
+            Command::Move(-3, 7),
+            Command::Say {
+                text: String::new(),
+                loud: true,
+            },
+            Command::Tag('é'),
+        ],
+        labels: [("a".to_string(), 1), ("b".to_string(), u32::MAX)].into(),
+        pair: (255, false),
+        unit: Unit,
+    };
+    let encoded = round_trip(&message);
+
+    // Only glyphs and joiners are written, and wrapping is ignored
+    assert!(encoded
+        .chars()
+        .all(|c| matches!(c, '▖' | '▘' | '▌' | '\u{200D}')));
+    let wrapped: String = encoded
+        .chars()
+        .enumerate()
+        .flat_map(|(i, c)| (i % 40 == 39).then_some('\n').into_iter().chain([c]))
+        .collect();
+    assert_eq!(from_str::<Message>(&wrapped).unwrap(), message);
+}
+
+/// Serializes its numbers through an iterator with no exact length
+struct Evens(Vec<u8>);
+
+impl Serialize for Evens {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().filter(|&&n| n % 2 == 0))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Untagged {
+    Number(#[allow(dead_code)] u8),
+}
+
+#[test]
+fn test_errors() {
+    assert!(matches!(
+        to_string(&Evens(vec![1, 2])),
+        Err(Error::LengthUnknown)
+    ));
+
+    assert!(matches!(from_str::<u8>(""), Err(Error::UnexpectedEnd)));
+    assert!(matches!(from_str::<u8>("▖▖"), Err(Error::UnexpectedEnd)));
+    assert!(matches!(
+        from_str::<String>("▘\u{200D}▖▖▘▘▌▖"),
+        Err(Error::UnexpectedEnd)
+    ));
+    assert!(matches!(
+        from_str::<u8>("▖\u{200D} ▖\u{200D}"),
+        Err(Error::TrailingInput(3))
+    ));
+    assert!(matches!(
+        from_str::<u8>("▖x\u{200D}"),
+        Err(Error::Dollcode(DollcodeError::InvalidChar('x', 1)))
+    ));
+    assert!(matches!(
+        from_str::<u64>(&format!("{}\u{200D}", "▌".repeat(41))),
+        Err(Error::Dollcode(DollcodeError::Overflow))
+    ));
+    assert!(matches!(
+        from_str::<Untagged>("▖\u{200D}"),
+        Err(Error::AnyNotSupported)
+    ));
+
+    let error = from_str::<u8>(&format!("{}\u{200D}", dollcode::to_dollcode(256).unwrap()));
+    assert_eq!(
+        error.unwrap_err().to_string(),
+        "invalid value: integer `256`, expected a u8"
+    );
+    let error = from_str::<bool>("▘\u{200D}").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid value: integer `2`, expected 0 or 1"
+    );
+}
@@ -0,0 +1,138 @@
+//! SQLite functions and collation for dollcode.
+//!
+//! [`register`] adds to a connection:
+//!
+//! - `dollcode_encode(x)`, which encodes an integer as a dollcode number and
+//!   text as delimited dollcode text
+//! - `dollcode_decode(s)`, which decodes a sequence with delimiters as text
+//!   and any other sequence as an integer
+//! - the `DOLLCODE` collation, which orders encoded numbers by their value
+//!
+//! Both functions return NULL for NULL. The empty string decodes as 0, so
+//! empty text does not round-trip.
+//!
+//! ```rust
+//! # #[cfg(feature = "extension")]
+//! # fn main() {}
+//! # #[cfg(not(feature = "extension"))]
+//! # fn main() -> rusqlite::Result<()> {
+//! let db = rusqlite::Connection::open_in_memory()?;
+//! dollcode_sqlite::register(&db)?;
+//!
+//! let code: String = db.query_row("SELECT dollcode_encode(42)", [], |row| row.get(0))?;
+//! assert_eq!(code, "▖▖▖▌");
+//!
+//! let value: i64 = db.query_row("SELECT dollcode_decode(?1)", [&code], |row| row.get(0))?;
+//! assert_eq!(value, 42);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! With the `extension` feature the library is also a loadable extension.
+//! SQLite is then only reachable through a connection loading it, so
+//! connections cannot be opened directly, and the tests do not run:
+//!
+//! ```sh
+//! cargo build --release -p dollcode-sqlite --features extension
+//! sqlite3
+//! sqlite> .load target/release/libdollcode_sqlite
+//! sqlite> SELECT id FROM orders ORDER BY code COLLATE DOLLCODE;
+//! ```
+
+use core::cmp::Ordering;
+use dollcode::{digit_value, text::DELIMITER, DollcodeError, FromDollcode, ToDollcode};
+use rusqlite::{
+    functions::{Context, FunctionFlags},
+    types::{Value, ValueRef},
+    Connection, Error, Result,
+};
+
+/// Name of the collation ordering encoded numbers by value
+pub const COLLATION: &str = "DOLLCODE";
+
+/// Registers the dollcode functions and collation on `db`
+///
+/// # Errors
+///
+/// Returns SQLite's error if a function or the collation cannot be created.
+pub fn register(db: &Connection) -> Result<()> {
+    let flags = FunctionFlags::SQLITE_UTF8
+        | FunctionFlags::SQLITE_DETERMINISTIC
+        | FunctionFlags::SQLITE_INNOCUOUS;
+    db.create_scalar_function(c"dollcode_encode", 1, flags, encode)?;
+    db.create_scalar_function(c"dollcode_decode", 1, flags, decode)?;
+    db.create_collation(COLLATION, compare)
+}
+
+/// Orders two encoded numbers by their value.
+///
+/// In bijective base 3 a longer sequence is always the larger number, and
+/// sequences of equal length compare glyph by glyph on their digit values.
+/// The glyphs' code points are in a different order, with ▌ first. Other
+/// characters sort before the glyphs, so the order stays total for strings
+/// that are not dollcode.
+pub fn compare(a: &str, b: &str) -> Ordering {
+    let key = |c: char| (digit_value(c), c);
+    a.chars()
+        .count()
+        .cmp(&b.chars().count())
+        .then_with(|| a.chars().map(key).cmp(b.chars().map(key)))
+}
+
+/// Wraps a dollcode error for SQLite
+fn user_error(e: DollcodeError) -> Error {
+    Error::UserFunctionError(e.to_string().into())
+}
+
+/// `dollcode_encode(x)`
+fn encode(ctx: &Context<'_>) -> Result<Value> {
+    let mut encoded = String::new();
+    match ctx.get_raw(0) {
+        ValueRef::Null => return Ok(Value::Null),
+        ValueRef::Integer(value) => {
+            let value = u64::try_from(value).map_err(|_| user_error(DollcodeError::Overflow))?;
+            value.encode_dollcode(&mut encoded)
+        }
+        ValueRef::Text(text) => {
+            let text = core::str::from_utf8(text).map_err(Error::Utf8Error)?;
+            text.encode_dollcode(&mut encoded)
+        }
+        value => return Err(Error::InvalidFunctionParameterType(0, value.data_type())),
+    }
+    .map_err(user_error)?;
+    Ok(Value::Text(encoded))
+}
+
+/// `dollcode_decode(s)`
+fn decode(ctx: &Context<'_>) -> Result<Value> {
+    let input = match ctx.get_raw(0) {
+        ValueRef::Null => return Ok(Value::Null),
+        ValueRef::Text(input) => core::str::from_utf8(input).map_err(Error::Utf8Error)?,
+        value => return Err(Error::InvalidFunctionParameterType(0, value.data_type())),
+    };
+    if input.contains(DELIMITER) {
+        return String::decode_dollcode(input)
+            .map(Value::Text)
+            .map_err(user_error);
+    }
+    let value = u64::decode_dollcode(input).map_err(user_error)?;
+    i64::try_from(value)
+        .map(Value::Integer)
+        .map_err(|_| user_error(DollcodeError::Overflow))
+}
+
+/// Entry point called by SQLite when the library is loaded as an extension
+///
+/// # Safety
+///
+/// Must only be called by SQLite, with the arguments of an extension entry
+/// point.
+#[cfg(feature = "extension")]
+#[no_mangle]
+pub unsafe extern "C" fn sqlite3_extension_init(
+    db: *mut rusqlite::ffi::sqlite3,
+    pz_err_msg: *mut *mut core::ffi::c_char,
+    p_api: *mut rusqlite::ffi::sqlite3_api_routines,
+) -> core::ffi::c_int {
+    Connection::extension_init2(db, pz_err_msg, p_api, |db| register(&db).map(|()| false))
+}
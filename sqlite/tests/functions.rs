@@ -0,0 +1,88 @@
+// With the `extension` feature SQLite is only reachable through a
+// connection loading the library, so connections cannot be opened here
+#![cfg(not(feature = "extension"))]
+
+use rusqlite::{types::Value, Connection};
+
+fn connection() -> Connection {
+    let db = Connection::open_in_memory().unwrap();
+    dollcode_sqlite::register(&db).unwrap();
+    db
+}
+
+fn query(db: &Connection, sql: &str) -> rusqlite::Result<Value> {
+    db.query_row(sql, [], |row| row.get(0))
+}
+
+#[test]
+fn test_encode_decode() {
+    let db = connection();
+    let text = "▘▖▘▌\u{200d}▌▘▖▌\u{200d}";
+    let cases = [
+        ("SELECT dollcode_encode(42)", Value::Text("▖▖▖▌".into())),
+        ("SELECT dollcode_encode('Hi')", Value::Text(text.into())),
+        ("SELECT dollcode_encode(NULL)", Value::Null),
+        ("SELECT dollcode_decode('▖▖▖▌')", Value::Integer(42)),
+        (
+            "SELECT dollcode_decode(dollcode_encode('Hi'))",
+            Value::Text("Hi".into()),
+        ),
+        ("SELECT dollcode_decode('')", Value::Integer(0)),
+        ("SELECT dollcode_decode(NULL)", Value::Null),
+    ];
+    for (sql, expected) in cases {
+        assert_eq!(query(&db, sql).unwrap(), expected, "{sql}");
+    }
+}
+
+#[test]
+fn test_errors() {
+    let db = connection();
+    for sql in [
+        "SELECT dollcode_encode(-1)",
+        "SELECT dollcode_encode(1.5)",
+        "SELECT dollcode_encode('é')",
+        "SELECT dollcode_decode('▖x')",
+        "SELECT dollcode_decode(42)",
+    ] {
+        assert!(query(&db, sql).is_err(), "{sql}");
+    }
+
+    // u64::MAX does not fit an SQLite integer
+    let max = dollcode::to_dollcode(u64::MAX).unwrap().to_string();
+    let result: rusqlite::Result<i64> =
+        db.query_row("SELECT dollcode_decode(?1)", [max], |row| row.get(0));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_collation() {
+    let db = connection();
+    db.execute_batch("CREATE TABLE ids (code TEXT)").unwrap();
+    let values = [300i64, 2, 42, 0, 13, 3, 4, 1_000_000];
+    for value in values {
+        db.execute("INSERT INTO ids VALUES (dollcode_encode(?1))", [value])
+            .unwrap();
+    }
+
+    let mut statement = db
+        .prepare("SELECT dollcode_decode(code) FROM ids ORDER BY code COLLATE DOLLCODE")
+        .unwrap();
+    let sorted: Vec<i64> = statement
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<rusqlite::Result<_>>()
+        .unwrap();
+    let mut expected = values.to_vec();
+    expected.sort_unstable();
+    assert_eq!(sorted, expected);
+
+    let smaller: i64 = db
+        .query_row(
+            "SELECT count(*) FROM ids WHERE code < dollcode_encode(42) COLLATE DOLLCODE",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(smaller, 5);
+}
@@ -0,0 +1,117 @@
+//! Field formatting for `tracing-subscriber`.
+
+use core::fmt;
+use dollcode::to_dollcode;
+use tracing_core::field::{Field, Visit};
+use tracing_subscriber::{
+    field::{MakeVisitor, VisitFmt, VisitOutput},
+    fmt::format::{DefaultVisitor, Writer},
+};
+
+/// Formats the named fields as dollcode and everything else as
+/// `tracing-subscriber`'s default formatter does.
+///
+/// Unsigned values and non-negative signed values up to `u64::MAX` are
+/// encoded; negative values and other types keep their usual form.
+#[derive(Debug, Clone, Copy)]
+pub struct DollcodeFields {
+    names: &'static [&'static str],
+}
+
+impl DollcodeFields {
+    /// Creates a formatter encoding the fields called `names`
+    pub const fn new(names: &'static [&'static str]) -> Self {
+        Self { names }
+    }
+}
+
+impl<'a> MakeVisitor<Writer<'a>> for DollcodeFields {
+    type Visitor = DollcodeVisitor<'a>;
+
+    fn make_visitor(&self, target: Writer<'a>) -> DollcodeVisitor<'a> {
+        DollcodeVisitor {
+            inner: DefaultVisitor::new(target, true),
+            names: self.names,
+        }
+    }
+}
+
+/// Visitor created by [`DollcodeFields`]
+#[derive(Debug)]
+pub struct DollcodeVisitor<'a> {
+    inner: DefaultVisitor<'a>,
+    names: &'static [&'static str],
+}
+
+impl DollcodeVisitor<'_> {
+    /// Records `value` as dollcode if `field` is selected, returning false if
+    /// it is left to the default formatting
+    fn record_encoded(&mut self, field: &Field, value: impl TryInto<u64>) -> bool {
+        if !self.names.contains(&field.name()) {
+            return false;
+        }
+        let Some(code) = value.try_into().ok().and_then(|v| to_dollcode(v).ok()) else {
+            return false;
+        };
+        self.inner.record_debug(field, &format_args!("{code}"));
+        true
+    }
+}
+
+impl Visit for DollcodeVisitor<'_> {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if !self.record_encoded(field, value) {
+            self.inner.record_u64(field, value);
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        if !self.record_encoded(field, value) {
+            self.inner.record_i64(field, value);
+        }
+    }
+
+    fn record_u128(&mut self, field: &Field, value: u128) {
+        if !self.record_encoded(field, value) {
+            self.inner.record_u128(field, value);
+        }
+    }
+
+    fn record_i128(&mut self, field: &Field, value: i128) {
+        if !self.record_encoded(field, value) {
+            self.inner.record_i128(field, value);
+        }
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.inner.record_f64(field, value);
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.inner.record_bool(field, value);
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.inner.record_str(field, value);
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        self.inner.record_error(field, value);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.inner.record_debug(field, value);
+    }
+}
+
+impl VisitOutput<fmt::Result> for DollcodeVisitor<'_> {
+    fn finish(self) -> fmt::Result {
+        self.inner.finish()
+    }
+}
+
+impl VisitFmt for DollcodeVisitor<'_> {
+    fn writer(&mut self) -> &mut dyn fmt::Write {
+        self.inner.writer()
+    }
+}
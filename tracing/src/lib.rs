@@ -0,0 +1,72 @@
+//! Renders selected numeric fields of `tracing` events and `log` records as
+//! dollcode, and decodes them back when reading the output.
+//!
+//! The fields are chosen by name, so IDs appear as dollcode while counts and
+//! durations stay decimal:
+//!
+//! - [`DollcodeFields`] formats fields for `tracing-subscriber`'s `fmt`
+//!   layer.
+//! - [`DollcodeLogger`] wraps a [`log::Log`] and rewrites key-values before
+//!   passing records on.
+//! - [`decode_line`] turns the fields of a written line back into decimal.
+//!
+//! ```rust
+//! use dollcode_tracing::DollcodeFields;
+//!
+//! tracing_subscriber::fmt()
+//!     .fmt_fields(DollcodeFields::new(&["order_id"]))
+//!     .init();
+//!
+//! // Written after the timestamp, level and target:
+//! // shipped order_id=▖▖▖▌ items=3
+//! tracing::info!(order_id = 42u64, items = 3, "shipped");
+//! ```
+
+mod fields;
+mod logger;
+
+pub use fields::{DollcodeFields, DollcodeVisitor};
+pub use logger::DollcodeLogger;
+
+use dollcode::FromDollcode;
+
+/// Replaces the dollcode value of each `name=value` pair in `line` whose name
+/// is in `fields` with its decimal value.
+///
+/// Values end at whitespace, a comma or a closing brace, so the fields of
+/// spans such as `request{order_id=▖▖▖▌}:` are decoded too. Pairs whose
+/// value is not a dollcode number are left as they are, as is output with
+/// ANSI colors.
+///
+/// ```rust
+/// let line = "INFO shipped order_id=▖▖▖▌ items=3";
+/// assert_eq!(
+///     dollcode_tracing::decode_line(line, &["order_id"]),
+///     "INFO shipped order_id=42 items=3"
+/// );
+/// ```
+pub fn decode_line(line: &str, fields: &[&str]) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(pos) = rest.find('=') {
+        let (before, after) = rest.split_at(pos + 1);
+        output.push_str(before);
+        let end = after
+            .find(|c: char| c.is_whitespace() || c == ',' || c == '}')
+            .unwrap_or(after.len());
+        let (value, after) = after.split_at(end);
+        let name = before[..pos]
+            .rsplit(|c: char| c.is_whitespace() || c == ',' || c == '{')
+            .next()
+            .unwrap_or_default();
+        match u64::decode_dollcode(value) {
+            Ok(decoded) if !value.is_empty() && fields.contains(&name) => {
+                output.push_str(&decoded.to_string());
+            }
+            _ => output.push_str(value),
+        }
+        rest = after;
+    }
+    output.push_str(rest);
+    output
+}
@@ -0,0 +1,122 @@
+//! Key-value rewriting for the `log` crate.
+
+use dollcode::{to_dollcode, Dollcode};
+use log::{
+    kv::{self, Key, Source, Value, VisitSource},
+    Log, Metadata, Record,
+};
+
+/// Wraps a logger, encoding the named key-values of each record as dollcode.
+///
+/// Values that convert to `u64` are encoded; other values are passed on as
+/// they are.
+///
+/// ```rust
+/// use dollcode_tracing::DollcodeLogger;
+///
+/// # struct Stderr;
+/// # impl log::Log for Stderr {
+/// #     fn enabled(&self, _: &log::Metadata) -> bool { true }
+/// #     fn log(&self, _: &log::Record) {}
+/// #     fn flush(&self) {}
+/// # }
+/// static LOGGER: DollcodeLogger<Stderr> = DollcodeLogger::new(Stderr, &["order_id"]);
+///
+/// log::set_logger(&LOGGER).unwrap();
+/// log::set_max_level(log::LevelFilter::Info);
+///
+/// // order_id is passed on as ▖▖▖▌
+/// log::info!(order_id = 42; "shipped");
+/// ```
+#[derive(Debug)]
+pub struct DollcodeLogger<L> {
+    inner: L,
+    names: &'static [&'static str],
+}
+
+impl<L: Log> DollcodeLogger<L> {
+    /// Wraps `inner`, encoding the key-values called `names`
+    pub const fn new(inner: L, names: &'static [&'static str]) -> Self {
+        Self { inner, names }
+    }
+
+    /// Returns the wrapped logger
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+}
+
+impl<L: Log> Log for DollcodeLogger<L> {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        let mut codes = Vec::new();
+        let mut collect = Collect {
+            names: self.names,
+            codes: &mut codes,
+        };
+        if record.key_values().visit(&mut collect).is_err() || codes.is_empty() {
+            return self.inner.log(record);
+        }
+        let source = Encoded {
+            inner: record.key_values(),
+            codes,
+        };
+        self.inner
+            .log(&record.to_builder().key_values(&source).build());
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Encodes the selected values of a record
+struct Collect<'a> {
+    names: &'static [&'static str],
+    codes: &'a mut Vec<(&'static str, Dollcode)>,
+}
+
+impl<'kvs> VisitSource<'kvs> for Collect<'_> {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        let name = self.names.iter().find(|name| **name == key.as_str());
+        if let (Some(name), Some(value)) = (name, value.to_u64()) {
+            if let Ok(code) = to_dollcode(value) {
+                self.codes.push((name, code));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A record's key-values with the selected ones replaced by their encoding
+struct Encoded<'a> {
+    inner: &'a dyn Source,
+    codes: Vec<(&'static str, Dollcode)>,
+}
+
+impl Source for Encoded<'_> {
+    fn visit<'kvs>(&'kvs self, visitor: &mut dyn VisitSource<'kvs>) -> Result<(), kv::Error> {
+        self.inner.visit(&mut Replace {
+            codes: &self.codes,
+            visitor,
+        })
+    }
+}
+
+/// Passes pairs on to `visitor`, swapping in the encoded values
+struct Replace<'a, 'kvs> {
+    codes: &'kvs [(&'static str, Dollcode)],
+    visitor: &'a mut dyn VisitSource<'kvs>,
+}
+
+impl<'kvs> VisitSource<'kvs> for Replace<'_, 'kvs> {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        match self.codes.iter().find(|(name, _)| *name == key.as_str()) {
+            Some((_, code)) => self.visitor.visit_pair(key, Value::from_display(code)),
+            None => self.visitor.visit_pair(key, value),
+        }
+    }
+}
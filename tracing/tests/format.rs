@@ -0,0 +1,130 @@
+use dollcode_tracing::{decode_line, DollcodeFields, DollcodeLogger};
+use log::{
+    kv::{self, Key, Value, VisitSource},
+    Log, Metadata, Record,
+};
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+const FIELDS: &[&str] = &["order_id", "user_id"];
+
+#[derive(Clone, Default)]
+struct Output(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_tracing_fields() {
+    let output = Output::default();
+    let writer = output.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .fmt_fields(DollcodeFields::new(FIELDS))
+        .with_writer(move || writer.clone())
+        .with_ansi(false)
+        .without_time()
+        .with_target(false)
+        .finish();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = tracing::info_span!("request", user_id = 13u32);
+        let _entered = span.enter();
+        tracing::info!(order_id = 42u64, items = 3, "shipped");
+        tracing::info!(order_id = -1i64, "refunded");
+    });
+
+    let written = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+    let lines: Vec<&str> = written.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            " INFO request{user_id=▖▖▖}: shipped order_id=▖▖▖▌ items=3",
+            " INFO request{user_id=▖▖▖}: refunded order_id=-1",
+        ]
+    );
+    assert_eq!(
+        decode_line(lines[0], FIELDS),
+        " INFO request{user_id=13}: shipped order_id=42 items=3"
+    );
+}
+
+#[derive(Default)]
+struct Capture(Mutex<Vec<String>>);
+
+struct Line(String);
+
+impl<'kvs> VisitSource<'kvs> for Line {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        self.0.push_str(&format!(" {key}={value}"));
+        Ok(())
+    }
+}
+
+impl Log for Capture {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        let mut line = Line(record.args().to_string());
+        record.key_values().visit(&mut line).unwrap();
+        self.0.lock().unwrap().push(line.0);
+    }
+
+    fn flush(&self) {}
+}
+
+#[test]
+fn test_log_key_values() {
+    let logger = DollcodeLogger::new(Capture::default(), FIELDS);
+    logger.log(
+        &Record::builder()
+            .args(format_args!("shipped"))
+            .key_values(&[("order_id", 42u64), ("items", 3)])
+            .build(),
+    );
+    logger.log(
+        &Record::builder()
+            .args(format_args!("signed in"))
+            .key_values(&[("user_id", "guest")])
+            .build(),
+    );
+    logger.log(&Record::builder().args(format_args!("idle")).build());
+
+    let lines = logger.into_inner().0.into_inner().unwrap();
+    assert_eq!(
+        lines,
+        [
+            "shipped order_id=▖▖▖▌ items=3",
+            "signed in user_id=guest",
+            "idle"
+        ]
+    );
+    assert_eq!(
+        decode_line(&lines[0], FIELDS),
+        "shipped order_id=42 items=3"
+    );
+}
+
+#[test]
+fn test_decode_line() {
+    let cases = [
+        ("id=▖▖▖▌", "id=▖▖▖▌"),
+        ("order_id=▖▖▖▌,user_id=▘", "order_id=42,user_id=2"),
+        ("order_id= user_id=x", "order_id= user_id=x"),
+        ("a == b", "a == b"),
+        ("no fields", "no fields"),
+    ];
+    for (line, expected) in cases {
+        assert_eq!(decode_line(line, FIELDS), expected, "{line}");
+    }
+}
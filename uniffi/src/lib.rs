@@ -0,0 +1,126 @@
+//! Swift and Kotlin bindings for dollcode, generated with UniFFI from
+//! `src/dollcode.udl`.
+//!
+//! The functions here are the whole interface: numbers and text are passed
+//! as strings of glyphs, and failures are thrown as [`DollcodeError`] with
+//! the offending character and its position where there is one.
+
+// The generated scaffolding has blank lines after its doc comments
+#![allow(clippy::empty_line_after_doc_comments)]
+
+use dollcode::{
+    from_dollcode,
+    text::{TextDecoder, TextIterator},
+    to_dollcode,
+};
+use std::fmt;
+
+uniffi::include_scaffolding!("dollcode");
+
+/// Why a conversion failed, thrown to Swift and Kotlin callers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DollcodeError {
+    /// Not a valid dollcode sequence
+    InvalidInput,
+    /// A character that cannot be encoded or decoded
+    InvalidChar {
+        /// The character, as a string since neither language has a
+        /// matching type
+        character: String,
+        /// 0-based character position
+        position: u64,
+    },
+    /// The value does not fit in 64 bits
+    Overflow,
+}
+
+impl From<dollcode::DollcodeError> for DollcodeError {
+    fn from(e: dollcode::DollcodeError) -> Self {
+        match e {
+            dollcode::DollcodeError::InvalidInput => Self::InvalidInput,
+            dollcode::DollcodeError::InvalidChar(c, position) => Self::InvalidChar {
+                character: c.to_string(),
+                position: position as u64,
+            },
+            dollcode::DollcodeError::Overflow => Self::Overflow,
+        }
+    }
+}
+
+impl fmt::Display for DollcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidInput => write!(f, "invalid dollcode sequence"),
+            Self::InvalidChar {
+                character,
+                position,
+            } => write!(
+                f,
+                "unsupported character {character:?} at position {position}"
+            ),
+            Self::Overflow => write!(f, "value does not fit in 64 bits"),
+        }
+    }
+}
+
+impl std::error::Error for DollcodeError {}
+
+type Result<T> = std::result::Result<T, DollcodeError>;
+
+/// Encodes `value` as glyphs
+pub fn encode_number(value: u64) -> Result<String> {
+    Ok(to_dollcode(value)?.to_string())
+}
+
+/// Decodes glyphs, without whitespace or delimiters, into a number
+pub fn decode_number(input: &str) -> Result<u64> {
+    let chars: Vec<char> = input.chars().collect();
+    Ok(from_dollcode(&chars)?)
+}
+
+/// Encodes printable ASCII `text`, a delimited segment per character
+pub fn encode_text(text: &str) -> Result<String> {
+    TextIterator::new(text)
+        .map(|segment| Ok(segment?.to_string()))
+        .collect()
+}
+
+/// Decodes delimited segments back into text
+pub fn decode_text(input: &str) -> Result<String> {
+    Ok(TextDecoder::new(input).collect::<dollcode::Result<_>>()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(encode_number(42).unwrap(), "▖▖▖▌");
+        assert_eq!(decode_number("▖▖▖▌").unwrap(), 42);
+        let max = encode_number(u64::MAX).unwrap();
+        assert_eq!(decode_number(&max).unwrap(), u64::MAX);
+        assert_eq!(decode_number(&"▌".repeat(45)), Err(DollcodeError::Overflow));
+        assert_eq!(decode_number("▖x"), Err(DollcodeError::InvalidInput));
+    }
+
+    #[test]
+    fn test_text() {
+        let encoded = encode_text("Hi").unwrap();
+        assert_eq!(encoded, "▘▖▘▌\u{200d}▌▘▖▌\u{200d}");
+        assert_eq!(decode_text(&encoded).unwrap(), "Hi");
+
+        let error = encode_text("café").unwrap_err();
+        assert_eq!(
+            error,
+            DollcodeError::InvalidChar {
+                character: "é".to_string(),
+                position: 3,
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "unsupported character \"é\" at position 3"
+        );
+    }
+}
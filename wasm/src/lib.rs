@@ -5,7 +5,8 @@
 //! # Input Types & Limits
 //!
 //! - **Text**: ASCII printable characters (32-126)
-//!   - Maximum length: 100 characters
+//!   - Maximum length: 10,000 characters by default, configurable with
+//!     [`set_limits`] up to 65,536
 //!   - Each character produces 5 dollcode chars + 1 delimiter
 //!
 //! - **Decimal Numbers**: 0-18,446,744,073,709,551,615 (u64::MAX)
@@ -14,44 +15,100 @@
 //! - **Hexadecimal**: 0x0-0xFFFFFFFFFFFFFFFF
 //!   - Maximum length: 18 chars (including 0x prefix)
 //!
+//! - **Fixed-width text**: ASCII text without delimiters via
+//!   [`convert_text_fixed_width`] / [`decode_fixed_width`]
+//!   - Each character produces 6 dollcode chars
+//!
+//! - **Bytes**: Arbitrary binary data via [`convert_bytes`] / [`decode_to_bytes`]
+//!   - Maximum length: the text limit, in bytes
+//!   - Each byte produces 6 dollcode chars
+//!   - Files of any size via [`encode_file`] / [`encode_file_chunked`]
+//!
+//! - **Buffers**: UTF-8 text and delimited dollcode as `ArrayBuffer`s via
+//!   [`convert_text_buffer`] / [`decode_text_buffer`], for transferring
+//!   results out of Web Workers without copying
+//!
 //! - **dollcode**: Sequences of ▖, ▘, ▌
+//!   - Whitespace and line breaks between glyphs are ignored
 //!   - Maximum length: 41 chars for numbers (log_3(2^64))
-//!   - For text: up to CHAR_BUF_SIZE (1800 bytes)
+//!   - For text: up to 18 bytes per allowed text character (180,000 bytes by
+//!     default)
+//!
+//! - **URL-encoded dollcode**: `%XX` escapes of the glyphs, as produced by
+//!   [`convert_to_url`] for sharing in links, decoded automatically
+//!
+//! The limits and alphabet are exported to JavaScript as `MAX_INPUT_CHARS()`,
+//! `MAX_DECIMAL_DIGITS()`, `MAX_HEX_LENGTH()`, `GLYPHS()` and `DELIMITER()`,
+//! so front ends can read them instead of hardcoding copies.
+//!
+//! # TypeScript
+//!
+//! The generated declarations type structured results, mode strings and
+//! errors precisely instead of as `any`. Thrown errors follow the exported
+//! `DollcodeError` interface, whose `code` is an `ErrorCode` union:
+//!
+//! ```ts
+//! try {
+//!     convert(input);
+//! } catch (e) {
+//!     const { code, position } = e as DollcodeError;
+//! }
+//! ```
+//!
+//! # Profiling
+//!
+//! A [`Profiler`] runs conversions and reports the duration, input kind and
+//! sizes of each one to a JavaScript callback.
 //!
 //! # Memory Usage
 //!
-//! Uses fixed stack buffers with zero heap allocation:
-//! - Text input: 100 chars maximum
+//! Uses fixed stack buffers with zero heap allocation on the Rust side:
 //! - Dollcode output: 18 bytes per input char (6 chars × 3 bytes UTF-8)
-//! - Total output buffer: 1800 bytes (100 chars × 18 bytes)
+//! - Output buffer: 1800 bytes, flushed into the returned JS string whenever
+//!   it fills, so input length is not bounded by the stack
 //!
 //! # Examples
 //!
 //! ```rust
-//! # use dollcode_wasm::convert;
-//! // Convert numbers
-//! assert_eq!(convert("42").unwrap(), "▖▖▖▌");
-//! assert_eq!(convert("0xFF").unwrap(), "▘▘▌▌▌");
+//! # use dollcode_wasm::{convert, convert_decimal, convert_hex};
+//! // Convert numbers into a structured `Encoding` object
+//! // ({ dollcode, ambiguous, alternative } in JavaScript)
+//! let number = convert("42").unwrap();
+//! assert!(!number.is_string());
+//! assert_eq!(convert_decimal("42").unwrap(), "▖▖▖▌");
+//! assert_eq!(convert_hex("0xFF").unwrap(), "▘▘▌▌▌");
 //!
 //! // Convert text
 //! assert_eq!(convert("Hi").unwrap(), "▘▖▘▌\u{200d}▌▘▖▌\u{200d}");
 //!
-//! // Decode dollcode
+//! // Decode dollcode into a structured `Conversion` object
+//! // ({ kind, decimal, hex, text, glyphCount } in JavaScript)
 //! let result = convert("▖▖▖▌").unwrap();
-//! assert!(result.as_string().unwrap().contains("42"));
+//! assert!(!result.is_string());
 //! ```
 //!
-use core::{any::Any, fmt::Write};
+use core::sync::atomic::{AtomicUsize, Ordering};
 use dollcode::{
-    from_dollcode,
-    text::{TextDecoder, TextIterator},
-    to_dollcode, DollcodeError, MAX_DOLLCODE_SIZE,
+    bytes::{encoded_len, ByteDecoder, ByteEncoder, GLYPHS_PER_BYTE},
+    digit_value, from_dollcode, glyph_name,
+    input::{self, canonical_glyph, is_dollcode_char, is_ignorable, InputKind, UrlDecoder},
+    render,
+    text::{StreamDecoder, TextIterator, DELIMITER},
+    to_dollcode, DollcodeError, Sink, ToDollcode, DOLLCODE_CHAR_MAP, MAX_DOLLCODE_SIZE,
 };
 use heapless::String;
+use js_sys::{Array, JsString, Reflect, Uint8Array};
 use wasm_bindgen::prelude::*;
 
-/// Maximum input text length in characters
-const INPUT_SIZE: usize = 100;
+mod qr;
+
+use qr::QrMatrix;
+
+/// Default maximum input text length in characters
+const INPUT_SIZE: usize = 10_000;
+
+/// Largest input text length accepted by [`set_limits`]
+const MAX_INPUT_SIZE: usize = 65_536;
 
 /// Maximum decimal number input length (u64::MAX digits)
 const MAX_DECIMAL_DIGITS: usize = 20;
@@ -59,334 +116,3135 @@ const MAX_DECIMAL_DIGITS: usize = 20;
 /// Maximum hex input length including 0x prefix
 const MAX_HEX_LENGTH: usize = 18;
 
-/// Buffer size for dollcode output
+/// Dollcode bytes per input char
 /// Each input char produces:
 /// - 5 dollcode chars maximum
 /// - 1 delimiter char
 ///
 /// Each UTF-8 char is 3 bytes
 /// Total: (5 + 1) × 3 = 18 bytes per input char
-const CHAR_BUF_SIZE: usize = INPUT_SIZE * 18;
-
-// Error messages
-const ERR_EMPTY: &str = "Empty input";
-const ERR_DOLLCODE_TOO_LONG: &str = "Dollcode sequence exceeds maximum length";
-const ERR_INPUT_TOO_LONG: &str = "Input exceeds maximum length";
-const ERR_DECIMAL_TOO_LONG: &str = "Decimal number exceeds maximum digits";
-const ERR_HEX_TOO_LONG: &str = "Hex number exceeds maximum length";
-const ERR_BUFFER_FULL: &str = "Output buffer full";
-const ERR_INVALID_SEQUENCE: &str = "Invalid dollcode sequence";
-const ERR_INVALID_DECIMAL: &str = "Invalid decimal number";
-const ERR_INVALID_HEX: &str = "Invalid hexadecimal number";
-const ERR_INVALID_CHARS: &str = "Input contains invalid characters";
-
-/// Convert Error types to JsValue with context
-fn to_js_err(e: impl core::fmt::Debug + Any) -> JsValue {
-    let mut msg: String<128> = String::new();
-
-    if let Some(e) = (&e as &dyn Any).downcast_ref::<DollcodeError>() {
-        match e {
-            DollcodeError::InvalidChar(c, _) => {
-                let _ = write!(
-                    &mut msg,
-                    "Character '{}' is not supported\n(valid: printable ASCII)",
-                    c
-                );
-            }
-            DollcodeError::Overflow => {
-                let _ =
-                    msg.push_str("Input exceeds maximum length\n(text: 100, decimal: 20, hex: 18)");
-            }
-            DollcodeError::InvalidInput => {
-                let _ =
-                    msg.push_str("Only ▖, ▘, and ▌ characters are allowed for dollcode sequences");
-            }
-        }
-    } else {
-        let _ = msg.push_str("Conversion error occurred");
-    }
+const BYTES_PER_CHAR: usize = 18;
+
+/// Input characters whose output fits in one output chunk
+const CHUNK_CHARS: usize = 100;
+
+/// Buffer size for one chunk of dollcode output
+const CHAR_BUF_SIZE: usize = CHUNK_CHARS * BYTES_PER_CHAR;
+
+/// Current maximum input text length, set with [`set_limits`]
+static INPUT_LIMIT: AtomicUsize = AtomicUsize::new(INPUT_SIZE);
+
+// Error codes and messages
+const ERR_EMPTY: ErrorKind = ErrorKind::new("EMPTY_INPUT", "Empty input");
+const ERR_DOLLCODE_TOO_LONG: ErrorKind = ErrorKind::new(
+    "DOLLCODE_TOO_LONG",
+    "Dollcode sequence exceeds maximum length",
+);
+const ERR_INPUT_TOO_LONG: ErrorKind =
+    ErrorKind::new("INPUT_TOO_LONG", "Input exceeds maximum length");
+const ERR_DECIMAL_TOO_LONG: ErrorKind =
+    ErrorKind::new("DECIMAL_TOO_LONG", "Decimal number exceeds maximum digits");
+const ERR_HEX_TOO_LONG: ErrorKind =
+    ErrorKind::new("HEX_TOO_LONG", "Hex number exceeds maximum length");
+const ERR_BUFFER_FULL: ErrorKind = ErrorKind::new("BUFFER_FULL", "Output buffer full");
+const ERR_INVALID_SEQUENCE: ErrorKind =
+    ErrorKind::new("INVALID_SEQUENCE", "Invalid dollcode sequence");
+const ERR_UNEXPECTED_DELIMITER: ErrorKind = ErrorKind::new(
+    "UNEXPECTED_DELIMITER",
+    "Zero-width joiners are only valid in text sequences",
+);
+const ERR_NUMBER_OVERFLOW: ErrorKind =
+    ErrorKind::new("NUMBER_OVERFLOW", "Dollcode value exceeds the 64-bit range");
+const ERR_INVALID_DECIMAL: ErrorKind = ErrorKind::new("INVALID_DECIMAL", "Invalid decimal number");
+const ERR_INVALID_HEX: ErrorKind = ErrorKind::new("INVALID_HEX", "Invalid hexadecimal number");
+const ERR_BATCH_ENTRY: ErrorKind =
+    ErrorKind::new("INVALID_BATCH_ENTRY", "Batch entries must be strings");
+const ERR_QR_TOO_LONG: ErrorKind =
+    ErrorKind::new("QR_TOO_LONG", "Dollcode is too long for a QR code");
+const ERR_INVALID_OPTION: ErrorKind = ErrorKind::new("INVALID_OPTION", "Invalid rendering option");
+const ERR_INVALID_UTF8: ErrorKind = ErrorKind::new("INVALID_UTF8", "Input is not valid UTF-8");
+const ERR_INVALID_EDIT: ErrorKind =
+    ErrorKind::new("INVALID_EDIT", "Edit range is outside the document");
+const ERR_NOT_DOLLCODE: ErrorKind =
+    ErrorKind::new("NOT_DOLLCODE", "Input is not a dollcode sequence");
+const ERR_INVALID_LIMIT: ErrorKind = ErrorKind::new(
+    "INVALID_LIMIT",
+    "Input limit must be between 1 and 65536 characters",
+);
 
-    JsValue::from_str(&msg)
+/// Returns the current maximum input text length in characters
+fn input_limit() -> usize {
+    INPUT_LIMIT.load(Ordering::Relaxed)
 }
 
-/// Converts input to dollcode based on content type.
+/// Sets the maximum text input length in characters for all conversions.
 ///
-/// Input type is detected in the following order:
-/// 1. Dollcode sequences (if contains ▖, ▘, or ▌)
-/// 2. Decimal numbers (if all digits)
-/// 3. Hex numbers (if starts with 0x)
-/// 4. Text (ASCII printable)
+/// The limit on dollcode input scales with it at 18 bytes per character.
+/// Decimal and hex limits are fixed by the `u64` range. The default is 10,000
+/// characters; for inputs beyond the maximum of 65,536 use
+/// [`ConversionStream`].
 ///
 /// # Errors
 ///
-/// Returns errors for:
-/// - Invalid characters
-/// - Exceeding length limits
-/// - Invalid dollcode sequences
-/// - Numbers outside u64 range
+/// Returns an error if `max_input_chars` is 0 or above 65,536.
 #[wasm_bindgen]
-pub fn convert(input: &str) -> Result<JsValue, JsValue> {
-    if input.is_empty() {
-        return Err(JsValue::from_str(ERR_EMPTY));
-    }
-
-    // General input validation: ensure only allowed characters are present
-    if let Some(c) = input.chars().find(|&c| {
-        !(
-            // ASCII printable characters (codes 32 to 126)
-            (c as u32 >= 32 && c as u32 <= 126) ||
-            // Dollcode characters
-            c == '▖' || c == '▘' || c == '▌' ||
-            // Zero Width Joiner
-            c == '\u{200D}'
-        )
-    }) {
-        return Err(to_js_err(DollcodeError::InvalidChar(c, 0)));
+pub fn set_limits(max_input_chars: usize) -> Result<(), JsValue> {
+    if !(1..=MAX_INPUT_SIZE).contains(&max_input_chars) {
+        return Err(JsValue::from(ERR_INVALID_LIMIT));
     }
 
-    // Check for dollcode characters first
-    if input
-        .chars()
-        .any(|c| matches!(c, '▖' | '▘' | '▌' | '\u{200D}'))
-    {
-        if input.len() > CHAR_BUF_SIZE {
-            return Err(JsValue::from_str(ERR_DOLLCODE_TOO_LONG));
+    INPUT_LIMIT.store(max_input_chars, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Returns the maximum text input length in characters currently in effect
+#[wasm_bindgen]
+pub fn max_input_chars() -> usize {
+    input_limit()
+}
+
+/// Returns the largest text input length [`set_limits`] accepts.
+///
+/// Exported to JavaScript as `MAX_INPUT_CHARS()`.
+#[wasm_bindgen(js_name = MAX_INPUT_CHARS)]
+pub fn max_input_limit() -> usize {
+    MAX_INPUT_SIZE
+}
+
+/// Returns the maximum decimal input length in digits.
+///
+/// Exported to JavaScript as `MAX_DECIMAL_DIGITS()`.
+#[wasm_bindgen(js_name = MAX_DECIMAL_DIGITS)]
+pub fn max_decimal_digits() -> usize {
+    MAX_DECIMAL_DIGITS
+}
+
+/// Returns the maximum hex input length, including the `0x` prefix.
+///
+/// Exported to JavaScript as `MAX_HEX_LENGTH()`.
+#[wasm_bindgen(js_name = MAX_HEX_LENGTH)]
+pub fn max_hex_length() -> usize {
+    MAX_HEX_LENGTH
+}
+
+/// Returns the three dollcode glyphs in digit order (`["▖", "▘", "▌"]`).
+///
+/// Exported to JavaScript as `GLYPHS()`.
+#[wasm_bindgen(js_name = GLYPHS, unchecked_return_type = "[string, string, string]")]
+pub fn glyphs() -> Array {
+    DOLLCODE_CHAR_MAP
+        .iter()
+        .map(|&c| JsValue::from(JsString::from(c)))
+        .collect()
+}
+
+/// Returns the text segment delimiter (U+200D zero-width joiner).
+///
+/// Exported to JavaScript as `DELIMITER()`.
+#[wasm_bindgen(js_name = DELIMITER)]
+pub fn delimiter() -> JsString {
+    JsString::from(DELIMITER)
+}
+
+/// Machine-readable error code paired with its default message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ErrorKind {
+    code: &'static str,
+    message: &'static str,
+}
+
+impl ErrorKind {
+    const fn new(code: &'static str, message: &'static str) -> Self {
+        Self { code, message }
+    }
+
+    /// Builds the error pointing at the character index `position`
+    fn at(self, position: usize) -> JsValue {
+        js_error(self.code, self.message, Some(position), None)
+    }
+}
+
+impl From<ErrorKind> for JsValue {
+    fn from(kind: ErrorKind) -> Self {
+        js_error(kind.code, kind.message, None, None)
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &'static str = r#"
+/** Stable identifier of a {@link DollcodeError}, suitable for localization. */
+export type ErrorCode =
+    | "EMPTY_INPUT"
+    | "DOLLCODE_TOO_LONG"
+    | "INPUT_TOO_LONG"
+    | "DECIMAL_TOO_LONG"
+    | "HEX_TOO_LONG"
+    | "BUFFER_FULL"
+    | "INVALID_SEQUENCE"
+    | "UNEXPECTED_DELIMITER"
+    | "NUMBER_OVERFLOW"
+    | "INVALID_DECIMAL"
+    | "INVALID_HEX"
+    | "INVALID_CHARS"
+    | "INVALID_BATCH_ENTRY"
+    | "INVALID_LIMIT"
+    | "NOT_DOLLCODE"
+    | "INVALID_EDIT"
+    | "INVALID_UTF8"
+    | "QR_TOO_LONG"
+    | "INVALID_OPTION"
+    | "INVALID_CHAR"
+    | "OVERFLOW"
+    | "CONVERSION_ERROR";
+
+/** Error thrown by every fallible function in this module. */
+export interface DollcodeError extends Error {
+    name: "DollcodeError";
+    code: ErrorCode;
+    /** Character index of the problem in the input, if known */
+    position?: number;
+    /** The rejected character, if any */
+    offendingChar?: string;
+}
+
+/** Input category reported by `detect`. */
+export type InputKind =
+    | "decimal"
+    | "hex"
+    | "text"
+    | "dollcode-number"
+    | "dollcode-text"
+    | "dollcode-url"
+    | "invalid";
+
+/** Kind of value recovered by decoding dollcode. */
+export type ConversionKind = "number" | "text";
+
+/** Direction of a `ConversionStream`. */
+export type StreamMode = "encode" | "decode";
+
+/** Build information returned by `version`. */
+export interface BuildInfo {
+    version: string;
+    debug: boolean;
+    features: string[];
+    limits: {
+        maxInputChars: number;
+        maxInputLimit: number;
+        maxDecimalDigits: number;
+        maxHexLength: number;
+        maxDollcodeSize: number;
+    };
+}
+
+/** Canonical conversion case returned by `test_vectors`. */
+export interface TestVector {
+    group: "number" | "ascii" | "edge";
+    input: string;
+    kind: InputKind;
+    /** Converted dollcode, or the decoded decimal number or text */
+    output?: string;
+    error?: ErrorCode;
+}
+
+/** Options for `render_svg`. */
+export interface SvgOptions {
+    /** Height in pixels, a whole number from 1 to 4096. Defaults to 24. */
+    height?: number;
+    /** Glyph fill. Defaults to `currentColor`. */
+    color?: string;
+    /** Fill behind the glyphs. Defaults to transparent. */
+    background?: string;
+}
+"#;
+
+/// Builds the error thrown to JavaScript.
+///
+/// Errors are `Error` instances named `DollcodeError` with extra properties:
+/// - `code`: stable identifier such as `"INVALID_CHAR"`, for localization
+/// - `position`: character index of the problem in the input, if known
+/// - `offendingChar`: the rejected character, if any
+fn js_error(
+    code: &str,
+    message: &str,
+    position: Option<usize>,
+    offending: Option<char>,
+) -> JsValue {
+    let error = js_sys::Error::new(message);
+    error.set_name("DollcodeError");
+
+    let position = position.map_or(JsValue::UNDEFINED, |p| JsValue::from_f64(p as f64));
+    let offending = offending.map_or(JsValue::UNDEFINED, |c| {
+        let mut buf = [0u8; 4];
+        JsValue::from_str(c.encode_utf8(&mut buf))
+    });
+
+    // Setting properties on a fresh Error object cannot fail
+    let _ = Reflect::set(&error, &JsValue::from_str("code"), &JsValue::from_str(code));
+    let _ = Reflect::set(&error, &JsValue::from_str("position"), &position);
+    let _ = Reflect::set(&error, &JsValue::from_str("offendingChar"), &offending);
+
+    error.into()
+}
+
+/// Convert core errors to JsValue with context.
+///
+/// Messages are assembled by hand rather than with `write!`, which keeps
+/// `core::fmt` out of the module.
+fn to_js_err(e: DollcodeError) -> JsValue {
+    match e {
+        DollcodeError::InvalidChar(c, position) => {
+            let mut utf8 = [0u8; 4];
+            let msg = join(&[
+                "Character '",
+                c.encode_utf8(&mut utf8),
+                "' is not supported\n(valid: printable ASCII)",
+            ]);
+            js_error("INVALID_CHAR", &msg, Some(position), Some(c))
         }
-        if !input
-            .chars()
-            .all(|c| matches!(c, '▖' | '▘' | '▌' | '\u{200D}'))
-        {
-            return Err(to_js_err(DollcodeError::InvalidInput));
+        DollcodeError::Overflow => {
+            let msg = join(&[
+                "Input exceeds maximum length\n(text: ",
+                &format_radix(input_limit() as u64, 10, LOWER_DIGITS),
+                ", decimal: ",
+                &format_radix(MAX_DECIMAL_DIGITS as u64, 10, LOWER_DIGITS),
+                ", hex: ",
+                &format_radix(MAX_HEX_LENGTH as u64, 10, LOWER_DIGITS),
+                ")",
+            ]);
+            js_error("OVERFLOW", &msg, None, None)
         }
-        return convert_dollcode(input);
+        DollcodeError::InvalidInput => js_error(
+            "INVALID_SEQUENCE",
+            "Only ▖, ▘, and ▌ characters are allowed for dollcode sequences",
+            None,
+            None,
+        ),
     }
+}
 
-    // Other input types use INPUT_SIZE
-    if input.chars().count() > INPUT_SIZE {
-        return Err(JsValue::from_str(ERR_INPUT_TOO_LONG));
+/// Concatenates message parts into a fixed buffer, truncating on overflow
+fn join(parts: &[&str]) -> String<128> {
+    let mut out = String::new();
+    for part in parts {
+        let _ = out.push_str(part);
     }
+    out
+}
 
-    // Try decimal first if all digits
-    if input.chars().all(|c| c.is_ascii_digit()) {
-        if input.len() > MAX_DECIMAL_DIGITS {
-            return Err(JsValue::from_str(ERR_DECIMAL_TOO_LONG));
+/// Digits for formatting numbers in bases up to 16
+const LOWER_DIGITS: &[u8; 16] = b"0123456789abcdef";
+const UPPER_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Formats `value` in `radix` (2 to 16) using the given digit set, without
+/// going through `core::fmt`
+#[inline(never)]
+fn format_radix(mut value: u64, radix: u64, digits: &[u8; 16]) -> String<64> {
+    let mut buf = [0u8; 64];
+    let mut start = buf.len();
+
+    loop {
+        start -= 1;
+        buf[start] = digits[(value % radix) as usize];
+        value /= radix;
+        if value == 0 {
+            break;
         }
-        return convert_decimal(input);
     }
 
-    // Then try hex if valid prefix and digits
-    if input.len() > 2
-        && input.starts_with("0x")
-        && input[2..].chars().all(|c| c.is_ascii_hexdigit())
-    {
-        if input.len() > MAX_HEX_LENGTH {
-            return Err(JsValue::from_str(ERR_HEX_TOO_LONG));
+    buf[start..].iter().map(|&b| b as char).collect()
+}
+
+/// Reason an input was rejected during classification
+#[derive(Debug)]
+enum Rejection {
+    Message(ErrorKind),
+    Error(DollcodeError),
+}
+
+impl From<Rejection> for JsValue {
+    fn from(rejection: Rejection) -> Self {
+        match rejection {
+            Rejection::Message(kind) => kind.into(),
+            Rejection::Error(e) => to_js_err(e),
         }
-        return convert_hex(input);
     }
+}
+
+/// Determines how `convert` would treat the input, without converting it.
+///
+/// Input is classified by [`input::classify`], shared with the native
+/// bindings, then checked against the size limits of its kind.
+fn classify(input: &str) -> Result<InputKind, Rejection> {
+    if input.is_empty() {
+        return Err(Rejection::Message(ERR_EMPTY));
+    }
+    let kind = input::classify(input).map_err(Rejection::Error)?;
 
-    // Finally try text - verify input is valid ASCII
-    if input.chars().any(|c| (c as u32) < 32 || (c as u32) > 126) {
-        return Err(JsValue::from_str(ERR_INVALID_CHARS));
+    let limit = input_limit();
+    let exceeded = match kind {
+        // Each glyph takes three escapes
+        InputKind::DollcodeUrl => {
+            (input.len() > limit * BYTES_PER_CHAR * 3).then_some(ERR_DOLLCODE_TOO_LONG)
+        }
+        InputKind::DollcodeNumber | InputKind::DollcodeText => {
+            (input.len() > limit * BYTES_PER_CHAR).then_some(ERR_DOLLCODE_TOO_LONG)
+        }
+        // Numbers and text are printable ASCII, a byte per character
+        _ if input.len() > limit => Some(ERR_INPUT_TOO_LONG),
+        InputKind::Decimal => (input.len() > MAX_DECIMAL_DIGITS).then_some(ERR_DECIMAL_TOO_LONG),
+        InputKind::Hex => (input.len() > MAX_HEX_LENGTH).then_some(ERR_HEX_TOO_LONG),
+        InputKind::Text => None,
+    };
+    match exceeded {
+        Some(kind) => Err(Rejection::Message(kind)),
+        None => Ok(kind),
     }
+}
 
-    convert_text(input)
+/// Classification of an input as reported by [`detect`].
+///
+/// Exposed to JavaScript as an object with `kind`, `reason`, `ambiguous`
+/// and `alternative` properties. `kind` is one of `"decimal"`, `"hex"`,
+/// `"text"`, `"dollcode-number"`, `"dollcode-text"`, `"dollcode-url"` or
+/// `"invalid"`; for invalid input `reason` carries the error `convert` would
+/// have returned.
+///
+/// Numbers are also valid text, so `"42"` is reported as `"decimal"` with
+/// `ambiguous` set and `alternative` of `"text"`. The UI can then offer
+/// [`convert_text`] alongside [`convert`].
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct Detection {
+    kind: &'static str,
+    reason: JsValue,
+    alternative: Option<InputKind>,
 }
 
-/// Converts decimal numbers to dollcode
 #[wasm_bindgen]
-pub fn convert_decimal(input: &str) -> Result<JsValue, JsValue> {
-    let num = input
-        .parse::<u64>()
-        .map_err(|_| JsValue::from_str(ERR_INVALID_DECIMAL))?;
+impl Detection {
+    /// Detected input category
+    #[wasm_bindgen(getter, unchecked_return_type = "InputKind")]
+    pub fn kind(&self) -> JsValue {
+        JsValue::from_str(self.kind)
+    }
 
-    let dollcode = to_dollcode(num).map_err(to_js_err)?;
+    /// Explanation of the classification
+    #[wasm_bindgen(getter, unchecked_return_type = "string | DollcodeError")]
+    pub fn reason(&self) -> JsValue {
+        self.reason.clone()
+    }
 
-    let mut output: String<CHAR_BUF_SIZE> = String::new();
-    for &c in dollcode.as_chars() {
-        output
-            .push(c)
-            .map_err(|_| JsValue::from_str(ERR_BUFFER_FULL))?;
+    /// Whether the input could also be read as another kind
+    #[wasm_bindgen(getter)]
+    pub fn ambiguous(&self) -> bool {
+        self.alternative.is_some()
     }
 
-    Ok(JsValue::from_str(&output))
+    /// The other kind the input could be read as, if ambiguous
+    #[wasm_bindgen(getter, unchecked_return_type = "InputKind | undefined")]
+    pub fn alternative(&self) -> JsValue {
+        self.alternative
+            .map_or(JsValue::UNDEFINED, |kind| JsValue::from_str(kind.name()))
+    }
 }
 
-/// Converts hexadecimal numbers to dollcode
+/// Classifies input the same way [`convert`] does, without converting it.
+///
+/// Cheap enough to call on every keystroke to show the detected mode live.
+/// Only the shape of the input is checked, so a `"decimal"` or
+/// `"dollcode-number"` result may still fail to convert if the value is out
+/// of range.
 #[wasm_bindgen]
-pub fn convert_hex(input: &str) -> Result<JsValue, JsValue> {
-    let input = input.trim_start_matches("0x");
-    let num = u64::from_str_radix(input, 16).map_err(|_| JsValue::from_str(ERR_INVALID_HEX))?;
-
-    let dollcode = to_dollcode(num).map_err(to_js_err)?;
+pub fn detect(input: &str) -> Detection {
+    match classify(input) {
+        Ok(kind) => Detection {
+            kind: kind.name(),
+            reason: JsValue::from_str(kind.reason()),
+            // Numbers passed classification, so they are short printable text
+            alternative: matches!(kind, InputKind::Decimal | InputKind::Hex)
+                .then_some(InputKind::Text),
+        },
+        Err(rejection) => Detection {
+            kind: "invalid",
+            reason: rejection.into(),
+            alternative: None,
+        },
+    }
+}
 
-    let mut output: String<CHAR_BUF_SIZE> = String::new();
-    for &c in dollcode.as_chars() {
-        output
-            .push(c)
-            .map_err(|_| JsValue::from_str(ERR_BUFFER_FULL))?;
+/// Converts input to dollcode based on content type.
+///
+/// Input type is detected as described for [`detect`]. Numbers are returned
+/// as an [`Encoding`] flagging that the input is also valid text, and text as
+/// a string.
+///
+/// # Errors
+///
+/// Returns errors for:
+/// - Invalid characters
+/// - Exceeding length limits
+/// - Invalid dollcode sequences
+/// - Numbers outside u64 range
+#[wasm_bindgen(unchecked_return_type = "string | Encoding | Conversion")]
+pub fn convert(input: &str) -> Result<JsValue, JsValue> {
+    match classify(input)? {
+        InputKind::DollcodeNumber | InputKind::DollcodeText => convert_dollcode(input),
+        InputKind::DollcodeUrl => decode_url(input).map(JsValue::from),
+        InputKind::Decimal => {
+            Encoding::of_number(convert_decimal(input)?, input).map(JsValue::from)
+        }
+        InputKind::Hex => Encoding::of_number(convert_hex(input)?, input).map(JsValue::from),
+        InputKind::Text => convert_text(input),
     }
+}
 
-    Ok(JsValue::from_str(&output))
+/// Dollcode for a number, as returned by [`convert`].
+///
+/// Exposed to JavaScript as an object with `dollcode`, `ambiguous` and
+/// `alternative` properties. Numbers are also valid text, so `"42"` is
+/// `ambiguous` and its `alternative` is the encoding of the text `"42"`, as
+/// [`convert_text`] returns, letting the UI offer both. `toString()` returns
+/// the `dollcode`, so callers that used the string of earlier releases can
+/// coerce it with `String()`.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct Encoding {
+    dollcode: JsValue,
+    alternative: JsValue,
+}
+
+impl Encoding {
+    /// Pairs the encoded number `dollcode` with the text encoding of `input`
+    fn of_number(dollcode: JsValue, input: &str) -> Result<Self, JsValue> {
+        Ok(Self {
+            dollcode,
+            alternative: convert_text(input)?,
+        })
+    }
 }
 
-/// Converts ASCII text to dollcode
 #[wasm_bindgen]
-pub fn convert_text(input: &str) -> Result<JsValue, JsValue> {
-    if input.is_empty() {
-        return Err(JsValue::from_str(ERR_EMPTY));
+impl Encoding {
+    /// Dollcode for the number
+    #[wasm_bindgen(getter, unchecked_return_type = "string")]
+    pub fn dollcode(&self) -> JsValue {
+        self.dollcode.clone()
     }
 
-    let mut output: String<CHAR_BUF_SIZE> = String::new();
+    /// Whether the input could also be read as text
+    #[wasm_bindgen(getter)]
+    pub fn ambiguous(&self) -> bool {
+        !self.alternative.is_undefined()
+    }
 
-    for result in TextIterator::new(input) {
-        let segment = result.map_err(to_js_err)?;
-        for &c in segment.as_chars() {
-            output
-                .push(c)
-                .map_err(|_| JsValue::from_str(ERR_BUFFER_FULL))?;
+    /// Dollcode for the input read as text, if ambiguous
+    #[wasm_bindgen(getter, unchecked_return_type = "string | undefined")]
+    pub fn alternative(&self) -> JsValue {
+        self.alternative.clone()
+    }
+
+    /// The dollcode for the number
+    #[wasm_bindgen(js_name = toString, unchecked_return_type = "string")]
+    pub fn to_js_string(&self) -> JsValue {
+        self.dollcode.clone()
+    }
+}
+
+/// Describes the dollcode for `input` in words, for use as an `aria-label`.
+///
+/// Numbers and text describe their encoding; dollcode describes itself and
+/// its decoded value. Glyphs are spelled out with their names from the core
+/// crate, with text segments separated by commas:
+///
+/// ```js
+/// describe("Hi");
+/// // 'Dollcode for the text "Hi": high low high tall, tall high low tall'
+/// describe("▖▖▖▌");
+/// // "Dollcode for the number 42: low low low tall"
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`convert`].
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn describe(input: &str) -> Result<JsValue, JsValue> {
+    let kind = classify(input)?;
+    let mut output = StreamOutput::new();
+    output.push_str("Dollcode for the ");
+
+    let decoded = match kind {
+        InputKind::Decimal => Conversion::from_number(parse_decimal(input)?, 0),
+        InputKind::Hex => Conversion::from_number(parse_hex(input)?, 0),
+        InputKind::Text => Conversion::from_text(JsValue::from_str(input), 0),
+        InputKind::DollcodeNumber | InputKind::DollcodeText => decode_dollcode(input)?,
+        InputKind::DollcodeUrl => decode_url(input)?,
+    };
+    match decoded.kind {
+        ConversionKind::Number => {
+            output.push_str("number ");
+            output.push_str(&format_radix(decoded.value, 10, LOWER_DIGITS));
+        }
+        ConversionKind::Text => {
+            output.push_str("text \"");
+            output.push_js(&decoded.text.unchecked_into());
+            output.push('"');
         }
     }
+    output.push(':');
 
-    Ok(JsValue::from_str(&output))
+    let mut separator = " ";
+    for_each_glyph(input, kind, |c| match glyph_name(c) {
+        Some(name) => {
+            output.push_str(separator);
+            output.push_str(name);
+            separator = " ";
+        }
+        None => separator = ", ",
+    })?;
+
+    Ok(output.finish())
 }
 
-/// Converts dollcode back to numbers and text
+/// Character range returned by [`map_to_output`] and [`map_to_input`].
+///
+/// Exposed to JavaScript as an object with `start` and `end` properties.
 #[wasm_bindgen]
-pub fn convert_dollcode(input: &str) -> Result<JsValue, JsValue> {
-    if input.is_empty() {
-        return Ok(JsValue::from_str(""));
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Selection {
+    start: usize,
+    end: usize,
+}
+
+#[wasm_bindgen]
+impl Selection {
+    /// Index of the first selected character
+    #[wasm_bindgen(getter)]
+    pub fn start(&self) -> usize {
+        self.start
     }
 
-    // First check if it contains any ZWJs - if so, treat as text
-    if input.chars().any(|c| c == '\u{200D}') {
-        // Text mode - use CHAR_BUF_SIZE
-        let mut chars = ['\0'; CHAR_BUF_SIZE];
-        let mut len = 0;
+    /// Index one past the last selected character
+    #[wasm_bindgen(getter)]
+    pub fn end(&self) -> usize {
+        self.end
+    }
+}
 
-        for c in input.chars() {
-            if len >= CHAR_BUF_SIZE {
-                return Err(JsValue::from_str(ERR_BUFFER_FULL));
-            }
+/// Maps a caret (`start == end`) or selection in `input` to the matching
+/// range of its [`convert`] output, for synchronized highlighting.
+///
+/// Indices count characters, which equal JavaScript string indices since
+/// every supported character is a single UTF-16 unit. A text character maps
+/// to its segment and a decoded segment to its character. Numbers have no
+/// finer correspondence, so any selection covers the whole value. Partly
+/// selected units are included whole; a caret moves to the start of its
+/// unit. Decoded numbers map onto their decimal digits.
+///
+/// # Errors
+///
+/// Returns the same errors as [`convert`].
+#[wasm_bindgen]
+pub fn map_to_output(input: &str, start: usize, end: usize) -> Result<Selection, JsValue> {
+    map_selection(input, start, end, true)
+}
 
-            let normalized = match c {
-                '▖' | '▘' | '▌' | '\u{200D}' => c,
-                c if c as u32 == 0x2596 => '▖',
-                c if c as u32 == 0x2598 => '▘',
-                c if c as u32 == 0x258C => '▌',
-                _ => continue,
-            };
+/// Maps a caret or selection in the [`convert`] output of `input` back to
+/// the matching range of `input`. The reverse of [`map_to_output`].
+///
+/// # Errors
+///
+/// Returns the same errors as [`convert`].
+#[wasm_bindgen]
+pub fn map_to_input(input: &str, start: usize, end: usize) -> Result<Selection, JsValue> {
+    map_selection(input, start, end, false)
+}
 
-            chars[len] = normalized;
-            len += 1;
-        }
+fn map_selection(
+    input: &str,
+    start: usize,
+    end: usize,
+    to_output: bool,
+) -> Result<Selection, JsValue> {
+    let (start, end) = (start.min(end), start.max(end));
+    let mut from = 0;
+    let mut to = 0;
+    let mut mapped_start = None;
+    let mut mapped_end = None;
 
-        let mut decoded = String::<CHAR_BUF_SIZE>::new();
-        let normalized_str: String<CHAR_BUF_SIZE> = chars[..len].iter().collect();
+    for_each_unit(input, classify(input)?, |input_len, output_len| {
+        let (from_len, to_len) = if to_output {
+            (input_len, output_len)
+        } else {
+            (output_len, input_len)
+        };
 
-        for result in TextDecoder::new(&normalized_str) {
-            match result {
-                Ok(c) => {
-                    decoded
-                        .push(c)
-                        .map_err(|_| JsValue::from_str(ERR_BUFFER_FULL))?;
-                }
-                Err(_) => {
-                    return Ok(JsValue::from_str(ERR_INVALID_SEQUENCE));
-                }
+        if mapped_start.is_none() && start < from + from_len {
+            mapped_start = Some(to);
+        }
+        if mapped_end.is_none() {
+            if end <= from {
+                mapped_end = Some(to);
+            } else if end < from + from_len {
+                mapped_end = Some(to + to_len);
             }
         }
 
-        Ok(JsValue::from_str(&decoded))
+        from += from_len;
+        to += to_len;
+    })?;
+
+    let mapped_start = mapped_start.unwrap_or(to);
+    let mapped_end = if start == end {
+        mapped_start
     } else {
-        // Number mode - use MAX_DOLLCODE_SIZE
-        let mut chars = ['\0'; MAX_DOLLCODE_SIZE];
-        let mut len = 0;
+        mapped_end.unwrap_or(to)
+    };
+    Ok(Selection {
+        start: mapped_start,
+        end: mapped_end.max(mapped_start),
+    })
+}
 
-        for c in input.chars() {
-            if len >= MAX_DOLLCODE_SIZE {
-                return Err(JsValue::from_str(ERR_DOLLCODE_TOO_LONG));
+/// Splits a conversion into units that correspond between input and output,
+/// calling `f` with the character length of each unit on both sides
+fn for_each_unit(
+    input: &str,
+    kind: InputKind,
+    mut f: impl FnMut(usize, usize),
+) -> Result<(), JsValue> {
+    match kind {
+        InputKind::Text => {
+            for result in TextIterator::new(input) {
+                f(1, result.map_err(to_js_err)?.len());
             }
-
-            let normalized = match c {
-                '▖' | '▘' | '▌' => c,
-                c if c as u32 == 0x2596 => '▖',
-                c if c as u32 == 0x2598 => '▘',
-                c if c as u32 == 0x258C => '▌',
-                _ => continue,
-            };
-
-            chars[len] = normalized;
-            len += 1;
         }
+        InputKind::Decimal | InputKind::Hex => {
+            let mut glyphs = 0;
+            for_each_glyph(input, kind, |_| glyphs += 1)?;
+            f(input.chars().count(), glyphs);
+        }
+        InputKind::DollcodeText => {
+            // Validate first so every segment is known to decode
+            decode_dollcode(input)?;
 
-        if let Ok(num) = from_dollcode(&chars[..len]) {
-            let mut result: String<CHAR_BUF_SIZE> = String::new();
-            let _ = writeln!(&mut result, "Dec (base10): {}", num);
-            let _ = write!(&mut result, "Hex (base16): 0x{:x}", num);
-            Ok(JsValue::from_str(&result))
-        } else {
-            Ok(JsValue::from_str(ERR_INVALID_SEQUENCE))
+            let mut len = 0;
+            let mut pending = false;
+            for c in input.chars() {
+                if c == DELIMITER && !pending {
+                    // An empty segment ends decoding; the rest maps to nothing
+                    break;
+                }
+                len += 1;
+                if c == DELIMITER {
+                    f(len, 1);
+                    len = 0;
+                    pending = false;
+                } else if !c.is_whitespace() {
+                    pending = true;
+                }
+            }
+            if pending {
+                f(len, 1);
+            }
+        }
+        InputKind::DollcodeNumber | InputKind::DollcodeUrl => {
+            let decoded = if kind == InputKind::DollcodeUrl {
+                decode_url(input)?
+            } else {
+                decode_dollcode(input)?
+            };
+            let output_len = match decoded.kind {
+                ConversionKind::Number => format_radix(decoded.value, 10, LOWER_DIGITS).len(),
+                ConversionKind::Text => decoded.text.unchecked_ref::<JsString>().length() as usize,
+            };
+            f(input.chars().count(), output_len);
         }
     }
+
+    Ok(())
 }
 
-/// Initializes panic hook for WASM
-#[wasm_bindgen(start)]
-pub fn init() {
-    console_error_panic_hook::set_once();
+/// Share of a limit at which [`ConversionStats::near_limit`] is set
+const NEAR_LIMIT: f64 = 0.9;
+
+/// Result of [`convert_with_stats`]: the converted value plus counters
+/// describing the dollcode side of the conversion.
+///
+/// Exposed to JavaScript as an object with `value`, `glyphCount`,
+/// `byteLength`, `segmentCount`, `renderedWidth`, `limitUsage` and
+/// `nearLimit` properties. For encodings the counters describe the output;
+/// for decodings they describe the input.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct ConversionStats {
+    value: JsValue,
+    glyph_count: usize,
+    delimiter_count: usize,
+    segment_count: usize,
+    limit_usage: f64,
 }
 
-#[cfg(target_arch = "wasm32")]
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use wasm_bindgen_test::*;
+#[wasm_bindgen]
+impl ConversionStats {
+    /// Value `convert` returned
+    #[wasm_bindgen(getter, unchecked_return_type = "string | Encoding | Conversion")]
+    pub fn value(&self) -> JsValue {
+        self.value.clone()
+    }
 
-    #[wasm_bindgen_test]
-    fn test_input_size_limits() {
-        let max_input = "A".repeat(INPUT_SIZE);
-        assert!(convert(&max_input).is_ok());
+    /// Number of ▖, ▘ and ▌ glyphs, excluding delimiters
+    #[wasm_bindgen(getter, js_name = glyphCount)]
+    pub fn glyph_count(&self) -> usize {
+        self.glyph_count
+    }
 
-        let too_long = "A".repeat(INPUT_SIZE + 1);
-        assert_eq!(
-            convert(&too_long).unwrap_err(),
-            JsValue::from_str(ERR_INPUT_TOO_LONG)
-        );
+    /// UTF-8 length of the dollcode in bytes
+    #[wasm_bindgen(getter, js_name = byteLength)]
+    pub fn byte_length(&self) -> usize {
+        // Glyphs and the delimiter are all three bytes long
+        (self.glyph_count + self.delimiter_count) * 3
+    }
 
-        let too_long_decimal = "9".repeat(MAX_DECIMAL_DIGITS + 1);
-        assert_eq!(
-            convert(&too_long_decimal).unwrap_err(),
-            JsValue::from_str(ERR_DECIMAL_TOO_LONG)
-        );
+    /// Number of text segments, or 0 for a number
+    #[wasm_bindgen(getter, js_name = segmentCount)]
+    pub fn segment_count(&self) -> usize {
+        self.segment_count
+    }
 
-        assert_eq!(
-            convert("0xFFFFFFFFFFFFFFFFF").unwrap_err(),
-            JsValue::from_str(ERR_HEX_TOO_LONG)
-        );
+    /// Estimated width in monospace cells.
+    ///
+    /// Glyphs take one cell and delimiters none; East Asian fonts may render
+    /// the glyphs twice as wide.
+    #[wasm_bindgen(getter, js_name = renderedWidth)]
+    pub fn rendered_width(&self) -> usize {
+        self.glyph_count
     }
 
-    #[wasm_bindgen_test]
-    fn test_buffer_overflow_prevention() {
-        let too_long = "▖".repeat(CHAR_BUF_SIZE + 1);
-        assert_eq!(
-            convert(&too_long).unwrap_err(),
-            JsValue::from_str(ERR_DOLLCODE_TOO_LONG)
-        );
+    /// Fraction of the applicable input limit used, from 0 to 1
+    #[wasm_bindgen(getter, js_name = limitUsage)]
+    pub fn limit_usage(&self) -> f64 {
+        self.limit_usage
     }
 
-    #[wasm_bindgen_test]
-    fn test_character_validation() {
-        assert!(convert("\x1F").is_err());
-        assert!(convert("\x7F").is_err());
-        assert!(convert("☺").is_err());
-        assert!(convert(" ").is_ok());
-        assert!(convert("~").is_ok());
-        assert!(convert("Hello!").is_ok());
+    /// Whether the input is within 10% of its limit
+    #[wasm_bindgen(getter, js_name = nearLimit)]
+    pub fn near_limit(&self) -> bool {
+        self.limit_usage >= NEAR_LIMIT
     }
+}
 
-    #[wasm_bindgen_test]
-    fn test_dollcode_validation() {
+/// Converts input like [`convert`], also returning counters for the UI.
+///
+/// # Errors
+///
+/// Returns the same errors as [`convert`].
+#[wasm_bindgen]
+pub fn convert_with_stats(input: &str) -> Result<ConversionStats, JsValue> {
+    let kind = classify(input)?;
+    let value = convert(input)?;
+
+    let mut glyph_count = 0;
+    let mut delimiter_count = 0;
+    let mut pending = false;
+    for_each_glyph(input, kind, |c| {
+        pending = c != DELIMITER;
+        if pending {
+            glyph_count += 1;
+        } else {
+            delimiter_count += 1;
+        }
+    })?;
+
+    // A final text segment may omit its delimiter
+    let segment_count = match delimiter_count {
+        0 => 0,
+        n => n + usize::from(pending),
+    };
+
+    let (used, limit) = match kind {
+        InputKind::Decimal => (input.len(), MAX_DECIMAL_DIGITS),
+        InputKind::Hex => (input.len(), MAX_HEX_LENGTH),
+        InputKind::Text => (input.chars().count(), input_limit()),
+        InputKind::DollcodeNumber => (glyph_count, MAX_DOLLCODE_SIZE),
+        InputKind::DollcodeText => (input.len(), input_limit() * BYTES_PER_CHAR),
+        InputKind::DollcodeUrl => (input.len(), input_limit() * BYTES_PER_CHAR * 3),
+    };
+
+    Ok(ConversionStats {
+        value,
+        glyph_count,
+        delimiter_count,
+        segment_count,
+        limit_usage: used as f64 / limit as f64,
+    })
+}
+
+#[wasm_bindgen]
+extern "C" {
+    /// High-resolution clock available in browsers, workers and Node.js
+    #[wasm_bindgen(js_namespace = performance, js_name = now)]
+    fn performance_now() -> f64;
+}
+
+/// Timing and input characteristics of one conversion run through a
+/// [`Profiler`].
+///
+/// Exposed to JavaScript as an object with `operation`, `kind`,
+/// `inputLength`, `outputLength`, `durationMs` and `errorCode` properties.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct ConversionTiming {
+    operation: &'static str,
+    kind: &'static str,
+    input_length: usize,
+    output_length: Option<usize>,
+    duration_ms: f64,
+    error_code: JsValue,
+}
+
+#[wasm_bindgen]
+impl ConversionTiming {
+    /// Name of the function that ran, such as `"convert"`
+    #[wasm_bindgen(getter)]
+    pub fn operation(&self) -> JsValue {
+        JsValue::from_str(self.operation)
+    }
+
+    /// How the input was classified, or `"invalid"`
+    #[wasm_bindgen(getter, unchecked_return_type = "InputKind")]
+    pub fn kind(&self) -> JsValue {
+        JsValue::from_str(self.kind)
+    }
+
+    /// Input length in characters
+    #[wasm_bindgen(getter, js_name = inputLength)]
+    pub fn input_length(&self) -> usize {
+        self.input_length
+    }
+
+    /// Length of the output string or decoded text in UTF-16 code units
+    #[wasm_bindgen(getter, js_name = outputLength)]
+    pub fn output_length(&self) -> Option<usize> {
+        self.output_length
+    }
+
+    /// Wall-clock time spent in the conversion, in milliseconds
+    #[wasm_bindgen(getter, js_name = durationMs)]
+    pub fn duration_ms(&self) -> f64 {
+        self.duration_ms
+    }
+
+    /// `code` of the thrown error, if the conversion failed
+    #[wasm_bindgen(getter, js_name = errorCode, unchecked_return_type = "ErrorCode | undefined")]
+    pub fn error_code(&self) -> JsValue {
+        self.error_code.clone()
+    }
+}
+
+/// Runs conversions while reporting a [`ConversionTiming`] for each one to a
+/// callback, for profiling real-world use without wrapping calls in JS
+/// timers.
+///
+/// ```js
+/// const profiler = new Profiler((t) => console.debug(t.operation, t.kind, t.durationMs));
+/// profiler.convert("Hello");
+/// ```
+///
+/// Results and errors are passed through unchanged. Errors thrown by the
+/// callback are ignored.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct Profiler {
+    callback: js_sys::Function,
+}
+
+#[wasm_bindgen]
+impl Profiler {
+    /// Creates a profiler reporting to `callback`
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        #[wasm_bindgen(unchecked_param_type = "(timing: ConversionTiming) => void")]
+        callback: js_sys::Function,
+    ) -> Self {
+        Self { callback }
+    }
+
+    /// Runs [`convert`] and reports its timing
+    #[wasm_bindgen(unchecked_return_type = "string | Encoding | Conversion")]
+    pub fn convert(&self, input: &str) -> Result<JsValue, JsValue> {
+        let kind = classify(input).ok();
+        self.measure("convert", kind, input, || convert(input))
+    }
+
+    /// Runs [`convert_text`] and reports its timing
+    #[wasm_bindgen(unchecked_return_type = "string")]
+    pub fn convert_text(&self, input: &str) -> Result<JsValue, JsValue> {
+        self.measure("convert_text", Some(InputKind::Text), input, || {
+            convert_text(input)
+        })
+    }
+
+    /// Runs [`decode_to_text`] and reports its timing
+    pub fn decode_to_text(&self, input: &str) -> Result<Conversion, JsValue> {
+        let kind = Some(InputKind::DollcodeText);
+        self.measure("decode_to_text", kind, input, || decode_to_text(input))
+    }
+
+    /// Times `f` and reports the outcome to the callback
+    fn measure<T: TimedOutput>(
+        &self,
+        operation: &'static str,
+        kind: Option<InputKind>,
+        input: &str,
+        f: impl FnOnce() -> Result<T, JsValue>,
+    ) -> Result<T, JsValue> {
+        let start = performance_now();
+        let result = f();
+        let duration_ms = performance_now() - start;
+
+        let (output_length, error_code) = match &result {
+            Ok(value) => (value.output_length(), JsValue::UNDEFINED),
+            Err(error) => (
+                None,
+                Reflect::get(error, &JsValue::from_str("code")).unwrap_or_default(),
+            ),
+        };
+
+        let timing = ConversionTiming {
+            operation,
+            kind: kind.map_or("invalid", InputKind::name),
+            input_length: input.chars().count(),
+            output_length,
+            duration_ms,
+            error_code,
+        };
+        let _ = self.callback.call1(&JsValue::NULL, &timing.into());
+
+        result
+    }
+}
+
+/// Result of a conversion timed by a [`Profiler`]
+trait TimedOutput {
+    /// Length of the textual output in UTF-16 code units, if there is one
+    fn output_length(&self) -> Option<usize>;
+}
+
+impl TimedOutput for JsValue {
+    fn output_length(&self) -> Option<usize> {
+        self.is_string()
+            .then(|| self.unchecked_ref::<JsString>().length() as usize)
+    }
+}
+
+impl TimedOutput for Conversion {
+    fn output_length(&self) -> Option<usize> {
+        self.text.output_length()
+    }
+}
+
+/// Outcome of converting one entry of a [`convert_batch`] call.
+///
+/// Exposed to JavaScript as an object with `ok`, `value` and `error`
+/// properties. Exactly one of `value` and `error` is defined.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct BatchItem {
+    result: Result<JsValue, JsValue>,
+}
+
+#[wasm_bindgen]
+impl BatchItem {
+    /// Whether the entry converted successfully
+    #[wasm_bindgen(getter)]
+    pub fn ok(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    /// Value `convert` returned for the entry
+    #[wasm_bindgen(
+        getter,
+        unchecked_return_type = "string | Encoding | Conversion | undefined"
+    )]
+    pub fn value(&self) -> JsValue {
+        self.result.clone().unwrap_or(JsValue::UNDEFINED)
+    }
+
+    /// Error `convert` raised for the entry
+    #[wasm_bindgen(getter, unchecked_return_type = "DollcodeError | undefined")]
+    pub fn error(&self) -> JsValue {
+        self.result.clone().err().unwrap_or(JsValue::UNDEFINED)
+    }
+}
+
+/// Converts every string in `inputs` with [`convert`] in a single call.
+///
+/// Returns an array of [`BatchItem`]s in input order. A failing entry does
+/// not stop the batch; its error is reported in its own item. Non-string
+/// entries are reported as errors.
+#[wasm_bindgen(unchecked_return_type = "BatchItem[]")]
+pub fn convert_batch(#[wasm_bindgen(unchecked_param_type = "string[]")] inputs: &Array) -> Array {
+    inputs
+        .iter()
+        .map(|input| {
+            let result = match input.as_string() {
+                Some(input) => convert(&input),
+                None => Err(JsValue::from(ERR_BATCH_ENTRY)),
+            };
+            JsValue::from(BatchItem { result })
+        })
+        .collect()
+}
+
+/// Accumulates converted output in a fixed buffer, flushing it to a JS string
+/// whenever it fills so output length is not bounded by the buffer.
+struct StreamOutput {
+    buf: String<CHAR_BUF_SIZE>,
+    out: JsString,
+}
+
+impl StreamOutput {
+    fn new() -> Self {
+        Self {
+            buf: String::new(),
+            out: JsString::from(""),
+        }
+    }
+
+    fn push(&mut self, c: char) {
+        if self.buf.push(c).is_err() {
+            self.flush();
+            let _ = self.buf.push(c);
+        }
+    }
+
+    fn push_str(&mut self, s: &str) {
+        s.chars().for_each(|c| self.push(c));
+    }
+
+    fn push_js(&mut self, s: &JsString) {
+        self.flush();
+        self.out = self.out.concat(s);
+    }
+
+    fn flush(&mut self) {
+        if !self.buf.is_empty() {
+            self.out = self.out.concat(&JsValue::from_str(&self.buf));
+            self.buf.clear();
+        }
+    }
+
+    fn finish(mut self) -> JsValue {
+        self.flush();
+        self.out.into()
+    }
+}
+
+impl core::fmt::Write for StreamOutput {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+/// Writes output bytes into a preallocated `Uint8Array` through a fixed
+/// buffer, so results of known length reach JavaScript without an extra copy.
+struct BufferOutput {
+    buf: heapless::Vec<u8, CHAR_BUF_SIZE>,
+    out: Uint8Array,
+    written: u32,
+}
+
+impl BufferOutput {
+    fn new(len: usize) -> Self {
+        Self {
+            buf: heapless::Vec::new(),
+            out: Uint8Array::new_with_length(len as u32),
+            written: 0,
+        }
+    }
+
+    fn push_bytes(&mut self, bytes: &[u8]) {
+        if self.buf.extend_from_slice(bytes).is_err() {
+            self.flush();
+            let _ = self.buf.extend_from_slice(bytes);
+        }
+    }
+
+    fn push(&mut self, c: char) {
+        let mut utf8 = [0u8; 4];
+        self.push_bytes(c.encode_utf8(&mut utf8).as_bytes());
+    }
+
+    fn flush(&mut self) {
+        let end = self.written + self.buf.len() as u32;
+        self.out.subarray(self.written, end).copy_from(&self.buf);
+        self.written = end;
+        self.buf.clear();
+    }
+
+    fn finish(mut self) -> Uint8Array {
+        self.flush();
+        self.out
+    }
+}
+
+/// Parses a decimal number
+fn parse_decimal(input: &str) -> Result<u64, JsValue> {
+    input::parse_decimal(input).ok_or_else(|| ERR_INVALID_DECIMAL.into())
+}
+
+/// Parses a hex number with an optional `0x` prefix
+fn parse_hex(input: &str) -> Result<u64, JsValue> {
+    input::parse_hex(input).ok_or_else(|| ERR_INVALID_HEX.into())
+}
+
+/// Converts decimal numbers to dollcode
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn convert_decimal(input: &str) -> Result<JsValue, JsValue> {
+    let num = parse_decimal(input)?;
+
+    let dollcode = to_dollcode(num).map_err(to_js_err)?;
+
+    let mut output: String<CHAR_BUF_SIZE> = String::new();
+    for &c in dollcode.as_chars() {
+        output.push(c).map_err(|_| JsValue::from(ERR_BUFFER_FULL))?;
+    }
+
+    Ok(JsValue::from_str(&output))
+}
+
+/// Converts hexadecimal numbers to dollcode
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn convert_hex(input: &str) -> Result<JsValue, JsValue> {
+    let num = parse_hex(input)?;
+
+    let dollcode = to_dollcode(num).map_err(to_js_err)?;
+
+    let mut output: String<CHAR_BUF_SIZE> = String::new();
+    for &c in dollcode.as_chars() {
+        output.push(c).map_err(|_| JsValue::from(ERR_BUFFER_FULL))?;
+    }
+
+    Ok(JsValue::from_str(&output))
+}
+
+/// Converts ASCII text to dollcode.
+///
+/// Input is always treated as text, so `"42"` encodes the characters `4` and
+/// `2` where [`convert`] would encode the number.
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn convert_text(input: &str) -> Result<JsValue, JsValue> {
+    if input.is_empty() {
+        return Err(JsValue::from(ERR_EMPTY));
+    }
+
+    let mut output = StreamOutput::new();
+
+    for result in TextIterator::new(input) {
+        let segment = result.map_err(to_js_err)?;
+        for c in segment.chars() {
+            output.push(c);
+        }
+    }
+
+    Ok(output.finish())
+}
+
+/// Encodes binary data, such as a file or hash from a `Uint8Array`.
+///
+/// Every byte becomes six glyphs; see [`decode_to_bytes`] for the reverse.
+///
+/// # Errors
+///
+/// Returns an error for empty input or more bytes than the input limit.
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn convert_bytes(data: &[u8]) -> Result<JsValue, JsValue> {
+    if data.is_empty() {
+        return Err(JsValue::from(ERR_EMPTY));
+    }
+    if data.len() > input_limit() {
+        return Err(JsValue::from(ERR_INPUT_TOO_LONG));
+    }
+
+    let mut output = StreamOutput::new();
+    for c in ByteEncoder::new(data) {
+        output.push(c);
+    }
+
+    Ok(output.finish())
+}
+
+/// Decodes glyphs produced by [`convert_bytes`] back into a `Uint8Array`.
+///
+/// # Errors
+///
+/// Returns an error for characters other than ▖, ▘ and ▌, groups that do not
+/// encode a byte, or a trailing partial group.
+#[wasm_bindgen]
+pub fn decode_to_bytes(input: &str) -> Result<Uint8Array, JsValue> {
+    if input.is_empty() {
+        return Err(JsValue::from(ERR_EMPTY));
+    }
+    if input.len() > encoded_len(input_limit()) * 3 {
+        return Err(JsValue::from(ERR_DOLLCODE_TOO_LONG));
+    }
+
+    let len = input.chars().count().div_ceil(GLYPHS_PER_BYTE);
+    let mut output = BufferOutput::new(len);
+
+    for (index, byte) in ByteDecoder::new(input).enumerate() {
+        let byte = byte.map_err(|e| match e {
+            DollcodeError::InvalidInput => ERR_INVALID_SEQUENCE.at(index * GLYPHS_PER_BYTE),
+            e => to_js_err(e),
+        })?;
+        output.push_bytes(&[byte]);
+    }
+
+    Ok(output.finish())
+}
+
+/// Bytes of a file encoded per chunk by [`encode_file`] and
+/// [`encode_file_chunked`]
+const FILE_CHUNK_BYTES: usize = 4096;
+
+/// Encodes a file's bytes with the [`convert_bytes`] codec into a
+/// downloadable `text/plain` `Blob`.
+///
+/// The buffer is read in chunks, so unlike [`convert_bytes`] the input is not
+/// bound by the text limit. Each byte still becomes six glyphs, so the blob
+/// is 18 times the size of the file.
+///
+/// ```js
+/// const blob = encode_file(await file.arrayBuffer());
+/// link.href = URL.createObjectURL(blob);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error for an empty buffer.
+#[wasm_bindgen]
+pub fn encode_file(data: &js_sys::ArrayBuffer) -> Result<web_sys::Blob, JsValue> {
+    let parts = Array::new();
+    encode_file_chunks(data, |chunk| {
+        parts.push(&chunk);
+        Ok(())
+    })?;
+
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type("text/plain;charset=utf-8");
+    web_sys::Blob::new_with_str_sequence_and_options(&parts, &options)
+}
+
+/// Encodes a file's bytes like [`encode_file`], passing the output to
+/// `on_chunk` piece by piece instead of collecting it, e.g. to write it to a
+/// stream or report progress.
+///
+/// Every chunk but the last encodes 4,096 bytes.
+///
+/// # Errors
+///
+/// Returns an error for an empty buffer, or rethrows the first error thrown
+/// by `on_chunk`, after which no more chunks are encoded.
+#[wasm_bindgen]
+pub fn encode_file_chunked(
+    data: &js_sys::ArrayBuffer,
+    #[wasm_bindgen(unchecked_param_type = "(chunk: string) => void")] on_chunk: &js_sys::Function,
+) -> Result<(), JsValue> {
+    encode_file_chunks(data, |chunk| {
+        on_chunk.call1(&JsValue::NULL, &chunk).map(drop)
+    })
+}
+
+/// Feeds the encoding of `data` to `f` one chunk at a time
+fn encode_file_chunks(
+    data: &js_sys::ArrayBuffer,
+    mut f: impl FnMut(JsValue) -> Result<(), JsValue>,
+) -> Result<(), JsValue> {
+    let bytes = Uint8Array::new(data);
+    let len = bytes.length() as usize;
+    if len == 0 {
+        return Err(ERR_EMPTY.into());
+    }
+
+    let mut buf = [0u8; FILE_CHUNK_BYTES];
+    for start in (0..len).step_by(FILE_CHUNK_BYTES) {
+        let chunk = &mut buf[..FILE_CHUNK_BYTES.min(len - start)];
+        bytes
+            .subarray(start as u32, (start + chunk.len()) as u32)
+            .copy_to(chunk);
+
+        let mut encoded: String<{ encoded_len(FILE_CHUNK_BYTES) * 3 }> = String::new();
+        for c in ByteEncoder::new(chunk) {
+            let _ = encoded.push(c);
+        }
+        f(JsValue::from_str(&encoded))?;
+    }
+
+    Ok(())
+}
+
+/// Encodes UTF-8 text from a transferable buffer into UTF-8 dollcode.
+///
+/// Together with [`decode_text_buffer`] this lets long conversions run in a
+/// Web Worker and hand results back by transferring the returned
+/// `ArrayBuffer` instead of structured-cloning a large string:
+///
+/// ```js
+/// // worker.js
+/// onmessage = ({ data }) => {
+///     const output = convert_text_buffer(new Uint8Array(data));
+///     postMessage(output, [output]);
+/// };
+/// ```
+///
+/// Input is always treated as text, as with [`convert_text`].
+///
+/// # Errors
+///
+/// Returns an error for empty input, input over the text limit, invalid
+/// UTF-8, or characters outside printable ASCII.
+#[wasm_bindgen]
+pub fn convert_text_buffer(input: &[u8]) -> Result<js_sys::ArrayBuffer, JsValue> {
+    let input = buffer_str(input)?;
+    if input.len() > input_limit() {
+        return Err(ERR_INPUT_TOO_LONG.into());
+    }
+
+    // Size the output exactly first; every glyph and delimiter is 3 bytes
+    let mut len = 0;
+    for result in TextIterator::new(input) {
+        len += result.map_err(to_js_err)?.len() * 3;
+    }
+
+    let mut output = BufferOutput::new(len);
+    for segment in TextIterator::new(input).flatten() {
+        segment.chars().for_each(|c| output.push(c));
+    }
+
+    Ok(output.finish().buffer())
+}
+
+/// Decodes UTF-8 delimited dollcode from a transferable buffer into UTF-8
+/// text.
+///
+/// The reverse of [`convert_text_buffer`], with the same rules as
+/// [`decode_to_text`].
+///
+/// # Errors
+///
+/// Returns an error for empty input, invalid UTF-8, or any error
+/// [`decode_to_text`] reports.
+#[wasm_bindgen]
+pub fn decode_text_buffer(input: &[u8]) -> Result<js_sys::ArrayBuffer, JsValue> {
+    let input = buffer_str(input)?;
+    if input.len() > input_limit() * BYTES_PER_CHAR {
+        return Err(ERR_DOLLCODE_TOO_LONG.into());
+    }
+
+    // Validate and count in a first pass; decoded characters are ASCII
+    let mut len = 0;
+    decode_text_into(input.chars().enumerate(), |_| len += 1)?;
+
+    let mut output = BufferOutput::new(len);
+    decode_text_into(input.chars().enumerate(), |c| output.push(c))?;
+
+    Ok(output.finish().buffer())
+}
+
+/// Reads a non-empty UTF-8 string from a buffer passed in from JavaScript
+fn buffer_str(input: &[u8]) -> Result<&str, JsValue> {
+    if input.is_empty() {
+        return Err(ERR_EMPTY.into());
+    }
+
+    core::str::from_utf8(input).map_err(|e| {
+        let valid = &input[..e.valid_up_to()];
+        let position = core::str::from_utf8(valid).map_or(0, |s| s.chars().count());
+        ERR_INVALID_UTF8.at(position)
+    })
+}
+
+/// Converts ASCII text to dollcode without delimiters, for platforms that
+/// strip zero-width joiners.
+///
+/// Every character is written as a fixed group of six glyphs using the same
+/// codec as [`convert_bytes`], so the output contains only ▖, ▘ and ▌. It is
+/// indistinguishable from a number, so decode it with
+/// [`decode_fixed_width`] rather than [`convert`].
+///
+/// # Errors
+///
+/// Returns an error for empty input, input over the text limit, or
+/// characters outside printable ASCII.
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn convert_text_fixed_width(input: &str) -> Result<JsValue, JsValue> {
+    if input.is_empty() {
+        return Err(JsValue::from(ERR_EMPTY));
+    }
+    if input.chars().count() > input_limit() {
+        return Err(JsValue::from(ERR_INPUT_TOO_LONG));
+    }
+    if let Some((position, c)) = input
+        .chars()
+        .enumerate()
+        .find(|&(_, c)| !matches!(c, ' '..='~'))
+    {
+        return Err(to_js_err(DollcodeError::InvalidChar(c, position)));
+    }
+
+    let mut output = StreamOutput::new();
+    for c in ByteEncoder::new(input.as_bytes()) {
+        output.push(c);
+    }
+
+    Ok(output.finish())
+}
+
+/// Decodes text produced by [`convert_text_fixed_width`], returning a
+/// [`Conversion`].
+///
+/// # Errors
+///
+/// Returns an error for empty input, characters other than ▖, ▘ and ▌, or
+/// groups that are incomplete or do not encode printable ASCII. Errors carry
+/// the index of the offending character or group.
+#[wasm_bindgen]
+pub fn decode_fixed_width(input: &str) -> Result<Conversion, JsValue> {
+    if input.is_empty() {
+        return Err(ERR_EMPTY.into());
+    }
+    if input.len() > encoded_len(input_limit()) * 3 {
+        return Err(ERR_DOLLCODE_TOO_LONG.into());
+    }
+
+    decode_fixed_width_glyphs(input.chars().enumerate())
+}
+
+/// Decodes fixed-width text from positioned characters. Invalid groups are
+/// reported at the position of their first character.
+fn decode_fixed_width_glyphs(glyphs: impl Glyphs) -> Result<Conversion, JsValue> {
+    let mut output = StreamOutput::new();
+    let mut glyph_count = 0usize;
+    let mut group_start = 0;
+    let mut value: u16 = 0;
+
+    for (position, c) in glyphs {
+        let digit =
+            digit_value(c).ok_or_else(|| to_js_err(DollcodeError::InvalidChar(c, position)))?;
+        if glyph_count.is_multiple_of(GLYPHS_PER_BYTE) {
+            group_start = position;
+            value = 0;
+        }
+        value = value * 3 + u16::from(digit - 1);
+        glyph_count += 1;
+
+        if glyph_count.is_multiple_of(GLYPHS_PER_BYTE) {
+            match u8::try_from(value) {
+                Ok(byte @ b' '..=b'~') => output.push(char::from(byte)),
+                _ => return Err(ERR_INVALID_SEQUENCE.at(group_start)),
+            }
+        }
+    }
+    if !glyph_count.is_multiple_of(GLYPHS_PER_BYTE) {
+        return Err(ERR_INVALID_SEQUENCE.at(group_start));
+    }
+
+    Ok(Conversion::from_text(output.finish(), glyph_count))
+}
+
+/// Kind of value recovered from a dollcode sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConversionKind {
+    /// Undelimited glyphs decoded as a number
+    Number,
+    /// Delimited segments decoded as ASCII text
+    Text,
+}
+
+/// Structured result of decoding a dollcode sequence.
+///
+/// Exposed to JavaScript as an object with `kind`, `decimal`, `hex`, `text`,
+/// `glyphCount`, `ambiguous` and `alternative` properties so callers can
+/// format the output themselves. Fields that do not apply to the decoded kind
+/// are `undefined`. `toString()` produces the same text as earlier releases
+/// for existing callers.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct Conversion {
+    kind: ConversionKind,
+    value: u64,
+    text: JsValue,
+    glyph_count: usize,
+    /// Fixed-width text a sequence read as a number also decodes to
+    alternative: JsValue,
+}
+
+impl Conversion {
+    fn from_number(value: u64, glyph_count: usize) -> Self {
+        Self {
+            kind: ConversionKind::Number,
+            value,
+            text: JsValue::UNDEFINED,
+            glyph_count,
+            alternative: JsValue::UNDEFINED,
+        }
+    }
+
+    fn from_text(text: JsValue, glyph_count: usize) -> Self {
+        Self {
+            kind: ConversionKind::Text,
+            value: 0,
+            text,
+            glyph_count,
+            alternative: JsValue::UNDEFINED,
+        }
+    }
+
+    /// Records the fixed-width text reading of `input`, if a number read
+    /// from it also has one, as [`interpret`] would report
+    fn with_alternative(mut self, input: &str) -> Self {
+        if self.kind == ConversionKind::Number {
+            if let Ok(text) = decode_fixed_width_glyphs(dollcode_glyphs(input)) {
+                self.alternative = text.text;
+            }
+        }
+        self
+    }
+
+    /// Decimal digits of a number or the decoded text
+    fn readable(&self) -> JsValue {
+        match self.kind {
+            ConversionKind::Number => self.format_number("", 10),
+            ConversionKind::Text => self.text.clone(),
+        }
+    }
+
+    /// Formats the numeric value after `prefix` into a fixed buffer
+    fn format_number(&self, prefix: &str, radix: u64) -> JsValue {
+        if self.kind != ConversionKind::Number {
+            return JsValue::UNDEFINED;
+        }
+
+        let mut out: String<24> = String::new();
+        let _ = out.push_str(prefix);
+        let _ = out.push_str(&format_radix(self.value, radix, LOWER_DIGITS));
+        JsValue::from_str(&out)
+    }
+}
+
+#[wasm_bindgen]
+impl Conversion {
+    /// Either `"number"` or `"text"`
+    #[wasm_bindgen(getter, unchecked_return_type = "ConversionKind")]
+    pub fn kind(&self) -> JsValue {
+        JsValue::from_str(match self.kind {
+            ConversionKind::Number => "number",
+            ConversionKind::Text => "text",
+        })
+    }
+
+    /// Decimal digits of a decoded number, as a string to preserve u64 precision
+    #[wasm_bindgen(getter, unchecked_return_type = "string | undefined")]
+    pub fn decimal(&self) -> JsValue {
+        self.format_number("", 10)
+    }
+
+    /// `0x`-prefixed lowercase hex of a decoded number
+    #[wasm_bindgen(getter, unchecked_return_type = "string | undefined")]
+    pub fn hex(&self) -> JsValue {
+        self.format_number("0x", 16)
+    }
+
+    /// Decoded ASCII text
+    #[wasm_bindgen(getter, unchecked_return_type = "string | undefined")]
+    pub fn text(&self) -> JsValue {
+        self.text.clone()
+    }
+
+    /// Number of ▖, ▘ and ▌ glyphs in the input, excluding delimiters
+    #[wasm_bindgen(getter, js_name = glyphCount)]
+    pub fn glyph_count(&self) -> usize {
+        self.glyph_count
+    }
+
+    /// Whether a sequence decoded as a number is also valid fixed-width text
+    #[wasm_bindgen(getter)]
+    pub fn ambiguous(&self) -> bool {
+        !self.alternative.is_undefined()
+    }
+
+    /// The fixed-width text reading of a sequence decoded as a number, if
+    /// ambiguous
+    #[wasm_bindgen(getter, unchecked_return_type = "string | undefined")]
+    pub fn alternative(&self) -> JsValue {
+        self.alternative.clone()
+    }
+
+    /// Human-readable summary matching the previous string output
+    #[wasm_bindgen(js_name = toString, unchecked_return_type = "string")]
+    pub fn to_js_string(&self) -> JsValue {
+        match self.kind {
+            ConversionKind::Number => {
+                let mut result: String<64> = String::new();
+                let _ = result.push_str("Dec (base10): ");
+                let _ = result.push_str(&format_radix(self.value, 10, LOWER_DIGITS));
+                let _ = result.push_str("\nHex (base16): 0x");
+                let _ = result.push_str(&format_radix(self.value, 16, LOWER_DIGITS));
+                JsValue::from_str(&result)
+            }
+            ConversionKind::Text => self.text.clone(),
+        }
+    }
+}
+
+/// Converts dollcode back to numbers and text
+///
+/// Sequences containing the delimiter are decoded as text, anything else as a
+/// number. Whitespace and line breaks between glyphs are ignored. Returns a
+/// [`Conversion`] object for valid sequences. Use [`decode_to_text`] or
+/// [`decode_to_number`] when the payload type is known.
+///
+/// A number that is also valid fixed-width text is flagged as `ambiguous`,
+/// with the text as its `alternative`.
+#[wasm_bindgen(unchecked_return_type = "string | Conversion")]
+pub fn convert_dollcode(input: &str) -> Result<JsValue, JsValue> {
+    if input.is_empty() {
+        return Ok(JsValue::from_str(""));
+    }
+
+    decode_dollcode(input).map(|conversion| conversion.with_alternative(input).into())
+}
+
+/// Decodes delimited dollcode as ASCII text, returning a [`Conversion`].
+///
+/// A final segment without a trailing delimiter is accepted.
+///
+/// # Errors
+///
+/// Returns an error for empty input, characters other than glyphs and the
+/// delimiter, or segments that do not decode to printable ASCII. Errors carry
+/// the index of the offending character or segment.
+#[wasm_bindgen]
+pub fn decode_to_text(input: &str) -> Result<Conversion, JsValue> {
+    if input.is_empty() {
+        return Err(ERR_EMPTY.into());
+    }
+
+    decode_text(input)
+}
+
+/// Decodes undelimited dollcode as a number, returning a [`Conversion`].
+///
+/// # Errors
+///
+/// Returns an error for empty input, delimiters or other non-glyph
+/// characters, sequences longer than 41 glyphs, or values beyond `u64`.
+#[wasm_bindgen]
+pub fn decode_to_number(input: &str) -> Result<Conversion, JsValue> {
+    if input.is_empty() {
+        return Err(ERR_EMPTY.into());
+    }
+
+    decode_number(input.chars().enumerate())
+}
+
+/// Decodes a dollcode sequence into a [`Conversion`], choosing text or number
+/// by the presence of delimiters.
+fn decode_dollcode(input: &str) -> Result<Conversion, JsValue> {
+    if input.len() > input_limit() * BYTES_PER_CHAR {
+        return Err(ERR_DOLLCODE_TOO_LONG.into());
+    }
+
+    let glyphs = dollcode_glyphs(input);
+    if input.contains(DELIMITER) {
+        decode_text_glyphs(glyphs)
+    } else {
+        decode_number(glyphs)
+    }
+}
+
+/// Characters of dollcode input with whitespace skipped, so sequences wrapped
+/// across lines or spaced for readability decode unchanged. Positions still
+/// refer to the original input.
+fn dollcode_glyphs(input: &str) -> impl Glyphs + '_ {
+    input
+        .chars()
+        .enumerate()
+        .filter(|&(_, c)| !c.is_whitespace())
+}
+
+/// Characters of an input paired with their position in it
+trait Glyphs: Iterator<Item = (usize, char)> + Clone {}
+
+impl<T: Iterator<Item = (usize, char)> + Clone> Glyphs for T {}
+
+/// Decodes a delimited text sequence.
+fn decode_text(input: &str) -> Result<Conversion, JsValue> {
+    if input.len() > input_limit() * BYTES_PER_CHAR {
+        return Err(ERR_DOLLCODE_TOO_LONG.into());
+    }
+
+    decode_text_glyphs(input.chars().enumerate())
+}
+
+/// Decodes delimited text from positioned characters.
+fn decode_text_glyphs(glyphs: impl Glyphs) -> Result<Conversion, JsValue> {
+    // Decode in a single pass into the output
+    let mut decoded = StreamOutput::new();
+    let glyph_count = decode_text_into(glyphs, |c| decoded.push(c))?;
+    Ok(Conversion::from_text(decoded.finish(), glyph_count))
+}
+
+/// Decodes delimited text from positioned characters, feeding each decoded
+/// character to `f` and returning the number of glyphs read.
+///
+/// Invalid text segments are reported at the position of their first character.
+fn decode_text_into(glyphs: impl Glyphs, mut f: impl FnMut(char)) -> Result<usize, JsValue> {
+    let mut decoder = StreamDecoder::new();
+    let mut segment_start = 0;
+    let mut glyph_count = 0;
+
+    for (position, c) in glyphs {
+        if !is_dollcode_char(c) {
+            return Err(to_js_err(DollcodeError::InvalidChar(c, position)));
+        }
+
+        // An empty segment ends the text, as with TextDecoder
+        if c == DELIMITER && !decoder.is_pending() {
+            break;
+        }
+
+        if !decoder.is_pending() {
+            segment_start = position;
+        }
+        if c != DELIMITER {
+            glyph_count += 1;
+        }
+
+        match decoder.push(c) {
+            Ok(Some(c)) => f(c),
+            Ok(None) => {}
+            Err(_) => return Err(ERR_INVALID_SEQUENCE.at(segment_start)),
+        }
+    }
+
+    match decoder.finish() {
+        Ok(Some(c)) => f(c),
+        Ok(None) => {}
+        Err(_) => return Err(ERR_INVALID_SEQUENCE.at(segment_start)),
+    }
+
+    Ok(glyph_count)
+}
+
+/// Decodes an undelimited number sequence of at most `MAX_DOLLCODE_SIZE` glyphs.
+fn decode_number(glyphs: impl Glyphs) -> Result<Conversion, JsValue> {
+    let mut chars = ['\0'; MAX_DOLLCODE_SIZE];
+    let mut len = 0;
+
+    for (position, c) in glyphs {
+        if c == DELIMITER {
+            return Err(ERR_UNEXPECTED_DELIMITER.at(position));
+        }
+        if digit_value(c).is_none() {
+            return Err(to_js_err(DollcodeError::InvalidChar(c, position)));
+        }
+
+        let slot = chars
+            .get_mut(len)
+            .ok_or_else(|| ERR_DOLLCODE_TOO_LONG.at(position))?;
+        *slot = c;
+        len += 1;
+    }
+
+    from_dollcode(&chars[..len])
+        .map(|num| Conversion::from_number(num, len))
+        .map_err(|_| JsValue::from(ERR_NUMBER_OVERFLOW))
+}
+
+/// Output representation for [`convert_formatted`].
+///
+/// Exported to JavaScript as a numeric enum.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Glyphs and zero-width joiners as-is
+    Raw,
+    /// `%XX` escapes of the UTF-8 bytes, safe in query strings
+    Url,
+    /// Hexadecimal character references such as `&#x2596;`, for HTML emails
+    /// and static pages that strip raw zero-width joiners
+    Html,
+    /// A `data:text/plain` URI of the encoded payload
+    DataUri,
+}
+
+/// Prefix of [`OutputFormat::DataUri`] output
+const DATA_URI_PREFIX: &str = "data:text/plain;charset=utf-8,";
+
+/// Converts input like [`convert`] and returns the dollcode in the requested
+/// representation.
+///
+/// Numbers and text are encoded; dollcode input, including URL-encoded
+/// dollcode, is re-emitted in the new format.
+///
+/// ```js
+/// convert_formatted("42", OutputFormat.Html);
+/// // "&#x2596;&#x2596;&#x2596;&#x258C;"
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`convert`].
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn convert_formatted(input: &str, format: OutputFormat) -> Result<JsValue, JsValue> {
+    let mut output = StreamOutput::new();
+    if format == OutputFormat::DataUri {
+        output.push_str(DATA_URI_PREFIX);
+    }
+
+    for_each_glyph(input, classify(input)?, |c| {
+        push_formatted(&mut output, format, c)
+    })?;
+
+    Ok(output.finish())
+}
+
+/// Feeds the dollcode side of a classified input to `f`: the encoding of
+/// numbers and text, or the glyphs of dollcode input
+fn for_each_glyph(input: &str, kind: InputKind, mut f: impl FnMut(char)) -> Result<(), JsValue> {
+    match kind {
+        InputKind::Decimal | InputKind::Hex => {
+            let num = if input.starts_with("0x") {
+                parse_hex(input)?
+            } else {
+                parse_decimal(input)?
+            };
+            let dollcode = to_dollcode(num).map_err(to_js_err)?;
+            dollcode.as_chars().iter().for_each(|&c| f(c));
+        }
+        InputKind::Text => {
+            for result in TextIterator::new(input) {
+                let segment = result.map_err(to_js_err)?;
+                segment.chars().for_each(&mut f);
+            }
+        }
+        InputKind::DollcodeNumber | InputKind::DollcodeText => {
+            dollcode_glyphs(input).for_each(|(_, c)| f(c))
+        }
+        InputKind::DollcodeUrl => url_glyphs(input).for_each(|(_, c)| f(c)),
+    }
+
+    Ok(())
+}
+
+/// Converts input like [`convert`] and returns the dollcode percent-encoded
+/// for use in URLs.
+///
+/// Raw glyphs and zero-width joiners are often mangled when shared as links,
+/// so every character is written as `%XX` escapes of its UTF-8 bytes (▖
+/// becomes `%E2%96%96`). [`convert`] recognizes and decodes this form
+/// directly. Equivalent to [`convert_formatted`] with [`OutputFormat::Url`].
+///
+/// # Errors
+///
+/// Returns the same errors as [`convert`].
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn convert_to_url(input: &str) -> Result<JsValue, JsValue> {
+    convert_formatted(input, OutputFormat::Url)
+}
+
+/// QR code of converted dollcode, returned by [`to_qr`].
+///
+/// Exposed to JavaScript as an object with a `size` property, a `modules()`
+/// matrix and a `toSvg()` renderer.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct QrCode {
+    matrix: QrMatrix,
+}
+
+/// Light modules required around a QR code for scanners to find it
+const QR_QUIET_ZONE: usize = 4;
+
+#[wasm_bindgen]
+impl QrCode {
+    /// Side length in modules, excluding the quiet zone
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.matrix.size()
+    }
+
+    /// Modules in row-major order, `1` for dark and `0` for light
+    pub fn modules(&self) -> Uint8Array {
+        let size = self.matrix.size();
+        let mut output = BufferOutput::new(size * size);
+        for y in 0..size {
+            for x in 0..size {
+                output.push_bytes(&[u8::from(self.matrix.get(x, y))]);
+            }
+        }
+        output.finish()
+    }
+
+    /// Renders the code as a standalone SVG document with a quiet zone,
+    /// scaling to whatever size it is displayed at
+    #[wasm_bindgen(js_name = toSvg, unchecked_return_type = "string")]
+    pub fn to_svg(&self) -> JsValue {
+        let size = self.matrix.size();
+        let extent = format_radix((size + QR_QUIET_ZONE * 2) as u64, 10, LOWER_DIGITS);
+
+        let mut output = StreamOutput::new();
+        output.push_str("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 ");
+        output.push_str(&extent);
+        output.push(' ');
+        output.push_str(&extent);
+        output.push_str("\" shape-rendering=\"crispEdges\">");
+        output.push_str("<rect width=\"100%\" height=\"100%\" fill=\"#fff\"/>");
+        output.push_str("<path fill=\"#000\" d=\"");
+        for y in 0..size {
+            for x in (0..size).filter(|&x| self.matrix.get(x, y)) {
+                output.push('M');
+                output.push_str(&format_radix((x + QR_QUIET_ZONE) as u64, 10, LOWER_DIGITS));
+                output.push(',');
+                output.push_str(&format_radix((y + QR_QUIET_ZONE) as u64, 10, LOWER_DIGITS));
+                output.push_str("h1v1h-1z");
+            }
+        }
+        output.push_str("\"/></svg>");
+        output.finish()
+    }
+}
+
+/// Converts input like [`convert`] and returns the dollcode as a QR code, so
+/// it can be shared by scanning instead of copying fragile Unicode.
+///
+/// The code holds the raw dollcode in UTF-8; dollcode input is re-encoded
+/// as is.
+///
+/// ```js
+/// qr.innerHTML = to_qr("Hi").toSvg();
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`convert`], or an error if the dollcode is
+/// longer than a QR code holds (2,331 bytes, around 130 characters of text).
+#[wasm_bindgen]
+pub fn to_qr(input: &str) -> Result<QrCode, JsValue> {
+    let mut payload: heapless::Vec<u8, { qr::MAX_PAYLOAD }> = heapless::Vec::new();
+    let mut full = false;
+    for_each_glyph(input, classify(input)?, |c| {
+        let mut utf8 = [0u8; 4];
+        full |= payload
+            .extend_from_slice(c.encode_utf8(&mut utf8).as_bytes())
+            .is_err();
+    })?;
+
+    match QrMatrix::encode(&payload) {
+        Some(matrix) if !full => Ok(QrCode { matrix }),
+        _ => Err(ERR_QR_TOO_LONG.into()),
+    }
+}
+
+/// Default [`render_svg`] height in pixels
+const SVG_HEIGHT: u32 = 24;
+
+/// Largest accepted [`render_svg`] height in pixels
+const MAX_SVG_HEIGHT: u32 = 4096;
+
+/// Longest accepted [`render_svg`] color
+const MAX_SVG_COLOR: usize = 64;
+
+/// Renders the dollcode for `input` as a standalone SVG document.
+///
+/// Glyphs are drawn as rectangles rather than text, so the result looks the
+/// same everywhere regardless of how fonts render ▖, ▘ and ▌. Each glyph is
+/// half as wide as it is tall; delimiters take no space, as in text.
+/// Numbers and text are encoded, and dollcode input is drawn as is.
+///
+/// `options` is an optional object:
+/// - `height`: height in pixels, a whole number (default 24)
+/// - `color`: glyph fill (default `currentColor`)
+/// - `background`: fill behind the glyphs (default transparent)
+///
+/// ```js
+/// render_svg("Hi", { height: 48, color: "#c0ffee", background: "black" });
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`convert`], or `INVALID_OPTION` for a height
+/// outside 1 to 4096 or a color containing characters other than letters,
+/// digits, spaces and `#(),.%-`.
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn render_svg(
+    input: &str,
+    #[wasm_bindgen(unchecked_param_type = "SvgOptions | undefined")] options: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let kind = classify(input)?;
+
+    let height = match svg_option(options, "height").as_f64() {
+        None if svg_option(options, "height").is_undefined() => SVG_HEIGHT,
+        Some(h) if h.fract() == 0.0 && (1.0..=f64::from(MAX_SVG_HEIGHT)).contains(&h) => h as u32,
+        _ => return Err(ERR_INVALID_OPTION.into()),
+    };
+    let color = svg_color(options, "color")?;
+    let background = svg_color(options, "background")?;
+
+    // Reports conversion errors before anything is drawn
+    for_each_glyph(input, kind, |_| {})?;
+
+    let style = render::SvgStyle {
+        cell_width: height / 2,
+        cell_height: height,
+        color: color.as_deref().unwrap_or(render::SvgStyle::DEFAULT.color),
+        background: background.as_deref(),
+        ..render::SvgStyle::DEFAULT
+    };
+    let mut output = StreamOutput::new();
+    render::svg(&InputGlyphs { input, kind }, &style, &mut output).map_err(to_js_err)?;
+    Ok(output.finish())
+}
+
+/// Input converted like [`convert`], as glyphs for the core renderers
+struct InputGlyphs<'a> {
+    input: &'a str,
+    kind: InputKind,
+}
+
+impl ToDollcode for InputGlyphs<'_> {
+    fn encode_dollcode(&self, sink: &mut impl Sink) -> dollcode::Result<()> {
+        let mut result = Ok(());
+        for_each_glyph(self.input, self.kind, |c| {
+            if result.is_ok() {
+                result = sink.push(c);
+            }
+        })
+        .map_err(|_| DollcodeError::InvalidInput)?;
+        result
+    }
+}
+
+/// Reads `key` from an options object, or `undefined` if there is none
+fn svg_option(options: &JsValue, key: &str) -> JsValue {
+    if options.is_object() {
+        Reflect::get(options, &JsValue::from_str(key)).unwrap_or_default()
+    } else {
+        JsValue::UNDEFINED
+    }
+}
+
+/// Reads a color option, rejecting anything that could escape the SVG
+/// attribute it is written into
+fn svg_color(options: &JsValue, key: &str) -> Result<Option<String<MAX_SVG_COLOR>>, JsValue> {
+    let value = svg_option(options, key);
+    if value.is_undefined() {
+        return Ok(None);
+    }
+
+    let color: JsString = value
+        .dyn_into()
+        .map_err(|_| JsValue::from(ERR_INVALID_OPTION))?;
+    let safe = |unit: u16| {
+        char::from_u32(unit.into()).filter(|&c| c.is_ascii_alphanumeric() || " #(),.%-".contains(c))
+    };
+    let mut output = String::new();
+    for unit in color.iter() {
+        let c = safe(unit).ok_or(JsValue::from(ERR_INVALID_OPTION))?;
+        output
+            .push(c)
+            .map_err(|_| JsValue::from(ERR_INVALID_OPTION))?;
+    }
+    if output.is_empty() {
+        return Err(ERR_INVALID_OPTION.into());
+    }
+    Ok(Some(output))
+}
+
+/// Clipboard flavor produced by [`export`].
+///
+/// Exported to JavaScript as a numeric enum.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Glyphs and zero-width joiners as-is, for `text/plain`
+    Plain,
+    /// Monospace `<span>` markup with one span per text segment, for
+    /// `text/html`
+    Html,
+    /// A minimal RTF document in a monospace font, for `text/rtf`
+    Rtf,
+}
+
+/// Inline style of the outer [`ExportFormat::Html`] span; clipboard HTML
+/// loses stylesheets, so nothing relies on classes alone
+const EXPORT_HTML_STYLE: &str = "font-family:monospace;white-space:pre-wrap";
+
+/// Opening of an [`ExportFormat::Rtf`] document, selecting a monospace font
+const EXPORT_RTF_HEADER: &str = "{\\rtf1\\ansi\\deff0{\\fonttbl{\\f0\\fmodern Courier New;}}\\f0 ";
+
+/// Converts input like [`convert`] and returns the dollcode as clipboard
+/// content in the requested format, so copy buttons can offer styled
+/// versions alongside plain text:
+///
+/// ```js
+/// await navigator.clipboard.write([new ClipboardItem({
+///     "text/plain": export_(input, ExportFormat.Plain),
+///     "text/html": export_(input, ExportFormat.Html),
+/// })]);
+/// ```
+///
+/// Zero-width joiners are written as `&#x200D;` in HTML and glyphs as `\u`
+/// escapes in RTF, so neither depends on the clipboard preserving raw
+/// Unicode.
+///
+/// Exported to JavaScript as `export_`, since `export` is a reserved word.
+///
+/// # Errors
+///
+/// Returns the same errors as [`convert`].
+#[wasm_bindgen(js_name = export_, unchecked_return_type = "string")]
+pub fn export(input: &str, format: ExportFormat) -> Result<JsValue, JsValue> {
+    let mut output = StreamOutput::new();
+    let mut in_segment = false;
+
+    match format {
+        ExportFormat::Plain => {}
+        ExportFormat::Html => {
+            output.push_str("<span class=\"dollcode\" style=\"");
+            output.push_str(EXPORT_HTML_STYLE);
+            output.push_str("\">");
+        }
+        ExportFormat::Rtf => output.push_str(EXPORT_RTF_HEADER),
+    }
+
+    for_each_glyph(input, classify(input)?, |c| match format {
+        ExportFormat::Plain => output.push(c),
+        ExportFormat::Html => {
+            if !in_segment {
+                output.push_str("<span class=\"dollcode-segment\">");
+                in_segment = true;
+            }
+            if c == DELIMITER {
+                output.push_str("&#x200D;</span>");
+                in_segment = false;
+            } else {
+                output.push(c);
+            }
+        }
+        ExportFormat::Rtf => {
+            // Every glyph is below U+8000, so the signed 16-bit escape is
+            // the code point itself; `?` is the fallback for old readers
+            output.push_str("\\u");
+            output.push_str(&format_radix(u32::from(c).into(), 10, LOWER_DIGITS));
+            output.push('?');
+        }
+    })?;
+
+    match format {
+        ExportFormat::Plain => {}
+        ExportFormat::Html => {
+            // Numbers and a final text segment have no closing delimiter
+            if in_segment {
+                output.push_str("</span>");
+            }
+            output.push_str("</span>");
+        }
+        ExportFormat::Rtf => output.push('}'),
+    }
+
+    Ok(output.finish())
+}
+
+/// Appends `c` in the given output representation
+fn push_formatted(output: &mut StreamOutput, format: OutputFormat, c: char) {
+    match format {
+        OutputFormat::Raw => output.push(c),
+        OutputFormat::Url | OutputFormat::DataUri => push_url_encoded(output, c),
+        OutputFormat::Html => {
+            output.push_str("&#x");
+            output.push_str(&format_radix(u32::from(c).into(), 16, UPPER_DIGITS));
+            output.push(';');
+        }
+    }
+}
+
+/// Appends the percent-encoded UTF-8 bytes of `c`
+fn push_url_encoded(output: &mut StreamOutput, c: char) {
+    let mut buf = [0u8; 4];
+    for &byte in c.encode_utf8(&mut buf).as_bytes() {
+        output.push('%');
+        output.push(UPPER_DIGITS[usize::from(byte >> 4)] as char);
+        output.push(UPPER_DIGITS[usize::from(byte & 0xF)] as char);
+    }
+}
+
+/// Glyphs of input already checked with [`input::is_url_encoded`]
+fn url_glyphs(input: &str) -> impl Glyphs + '_ {
+    UrlDecoder::new(input).map_while(Result::ok)
+}
+
+/// Decodes percent-encoded dollcode into a [`Conversion`]. Error positions
+/// refer to the encoded input.
+fn decode_url(input: &str) -> Result<Conversion, JsValue> {
+    let glyphs = url_glyphs(input);
+    if glyphs.clone().any(|(_, c)| c == DELIMITER) {
+        decode_text_glyphs(glyphs)
+    } else {
+        decode_number(glyphs)
+    }
+}
+
+/// Every way a dollcode sequence can be read, as returned by [`interpret`].
+///
+/// Exposed to JavaScript as an object with `number`, `text` and `ambiguous`
+/// properties. `number` and `text` are [`Conversion`]s, or `undefined` when
+/// the sequence cannot be read that way.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct Interpretations {
+    number: Option<Conversion>,
+    text: Option<Conversion>,
+}
+
+#[wasm_bindgen]
+impl Interpretations {
+    /// The sequence decoded as a number
+    #[wasm_bindgen(getter)]
+    pub fn number(&self) -> Option<Conversion> {
+        self.number.clone()
+    }
+
+    /// The sequence decoded as delimited or fixed-width text
+    #[wasm_bindgen(getter)]
+    pub fn text(&self) -> Option<Conversion> {
+        self.text.clone()
+    }
+
+    /// Whether both readings are valid and the user should choose
+    #[wasm_bindgen(getter)]
+    pub fn ambiguous(&self) -> bool {
+        self.number.is_some() && self.text.is_some()
+    }
+}
+
+/// Returns all plausible readings of a dollcode sequence.
+///
+/// Sequences with delimiters are always text. Sequences without them decode
+/// as a number, and are also tried as [`convert_text_fixed_width`] output,
+/// so the UI can offer both instead of assuming a number.
+///
+/// # Errors
+///
+/// Returns an error if the input is not dollcode, or if it has no valid
+/// reading; the error is then the one from decoding it as a number.
+#[wasm_bindgen]
+pub fn interpret(input: &str) -> Result<Interpretations, JsValue> {
+    let (number, text) = match classify(input)? {
+        InputKind::DollcodeNumber => {
+            if input.len() > encoded_len(input_limit()) * 3 {
+                return Err(ERR_DOLLCODE_TOO_LONG.into());
+            }
+            // Both readings skip the same whitespace
+            let glyphs = dollcode_glyphs(input);
+            (
+                decode_number(glyphs.clone()),
+                decode_fixed_width_glyphs(glyphs),
+            )
+        }
+        InputKind::DollcodeText => {
+            return Ok(Interpretations {
+                number: None,
+                text: Some(decode_dollcode(input)?),
+            });
+        }
+        InputKind::DollcodeUrl => {
+            let decoded = decode_url(input)?;
+            return Ok(match decoded.kind {
+                ConversionKind::Number => Interpretations {
+                    number: Some(decoded),
+                    text: None,
+                },
+                ConversionKind::Text => Interpretations {
+                    number: None,
+                    text: Some(decoded),
+                },
+            });
+        }
+        InputKind::Decimal | InputKind::Hex | InputKind::Text => {
+            return Err(ERR_NOT_DOLLCODE.into());
+        }
+    };
+
+    match (number, text.ok()) {
+        (Err(e), None) => Err(e),
+        (number, text) => Ok(Interpretations {
+            number: number.ok(),
+            text,
+        }),
+    }
+}
+
+/// Cleans pasted dollcode into its canonical form without decoding it.
+///
+/// Lookalike glyphs are mapped to ▖, ▘ and ▌, while whitespace, line breaks,
+/// variation selectors and zero-width spaces are removed. Delimiters are
+/// kept, so the result can be passed to [`convert`] unchanged.
+///
+/// ```js
+/// normalize("▗▝ ▐️"); // "▖▘▌"
+/// ```
+///
+/// # Errors
+///
+/// Returns an error for input over the dollcode length limit, or for
+/// characters that are neither dollcode nor one of the above, with their
+/// position in the original input.
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn normalize(input: &str) -> Result<JsValue, JsValue> {
+    if input.len() > input_limit() * BYTES_PER_CHAR {
+        return Err(ERR_DOLLCODE_TOO_LONG.into());
+    }
+
+    let mut output = StreamOutput::new();
+
+    for (position, c) in input.chars().enumerate() {
+        if is_ignorable(c) {
+            continue;
+        }
+
+        let c = canonical_glyph(c);
+        if !is_dollcode_char(c) {
+            return Err(to_js_err(DollcodeError::InvalidChar(c, position)));
+        }
+        output.push(c);
+    }
+
+    Ok(output.finish())
+}
+
+/// Conversion direction of a [`ConversionStream`] or [`verify`] check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StreamMode {
+    /// ASCII text to dollcode
+    Encode,
+    /// Delimited dollcode back to ASCII text
+    Decode,
+}
+
+impl StreamMode {
+    /// Name exposed to JavaScript
+    fn name(self) -> &'static str {
+        match self {
+            StreamMode::Encode => "encode",
+            StreamMode::Decode => "decode",
+        }
+    }
+}
+
+/// Incremental text converter for inputs too large for a single [`convert`] call.
+///
+/// Feed slices of a large input (for example from `FileReader`) to `push`
+/// and call `finish` once at the end. Each call returns the output for the
+/// input seen so far; concatenating every result gives the full conversion.
+/// The direction is detected from the first non-blank chunk: chunks
+/// containing ▖, ▘, ▌ or the delimiter are decoded, anything else is encoded.
+/// A segment split across chunks is carried over to the next call.
+///
+/// While decoding, ASCII whitespace between glyphs is skipped. Error
+/// positions count from the start of the whole stream.
+///
+/// ```js
+/// const stream = new ConversionStream();
+/// let output = "";
+/// for (const slice of slices) output += stream.push(slice);
+/// output += stream.finish();
+/// ```
+#[wasm_bindgen]
+#[derive(Debug, Default)]
+pub struct ConversionStream {
+    mode: Option<StreamMode>,
+    decoder: StreamDecoder,
+    /// Characters encoded so far, used to offset error positions
+    position: usize,
+}
+
+#[wasm_bindgen]
+impl ConversionStream {
+    /// Creates a stream whose direction is chosen by the first chunk
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `"encode"`, `"decode"`, or `undefined` before any input was seen
+    #[wasm_bindgen(getter, unchecked_return_type = "StreamMode | undefined")]
+    pub fn mode(&self) -> JsValue {
+        self.mode
+            .map_or(JsValue::UNDEFINED, |mode| JsValue::from_str(mode.name()))
+    }
+
+    /// Converts the next slice of input.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as `convert` for unsupported characters and
+    /// invalid sequences.
+    #[wasm_bindgen(unchecked_return_type = "string")]
+    pub fn push(&mut self, chunk: &str) -> Result<JsValue, JsValue> {
+        let mode = match self.mode {
+            Some(mode) => mode,
+            None if chunk.trim().is_empty() => return Ok(JsValue::from_str("")),
+            None => {
+                let mode = if chunk.chars().any(is_dollcode_char) {
+                    StreamMode::Decode
+                } else {
+                    StreamMode::Encode
+                };
+                *self.mode.insert(mode)
+            }
+        };
+
+        let mut output = StreamOutput::new();
+
+        match mode {
+            StreamMode::Encode => {
+                for result in TextIterator::new(chunk) {
+                    let segment = result.map_err(|e| match e {
+                        DollcodeError::InvalidChar(c, pos) => {
+                            to_js_err(DollcodeError::InvalidChar(c, self.position + pos))
+                        }
+                        e => to_js_err(e),
+                    })?;
+                    for c in segment.chars() {
+                        output.push(c);
+                    }
+                }
+                self.position += chunk.chars().count();
+            }
+            StreamMode::Decode => {
+                for c in chunk.chars().filter(|c| !c.is_ascii_whitespace()) {
+                    if let Some(c) = self.decoder.push(c).map_err(to_js_err)? {
+                        output.push(c);
+                    }
+                }
+            }
+        }
+
+        Ok(output.finish())
+    }
+
+    /// Completes the stream, returning output for a trailing segment.
+    ///
+    /// The stream is reset afterwards and can be reused for another input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a trailing partial segment does not decode.
+    #[wasm_bindgen(unchecked_return_type = "string")]
+    pub fn finish(&mut self) -> Result<JsValue, JsValue> {
+        let last = self.decoder.finish();
+        *self = Self::new();
+
+        let mut output = StreamOutput::new();
+        if let Some(c) = last.map_err(to_js_err)? {
+            output.push(c);
+        }
+        Ok(output.finish())
+    }
+}
+
+/// Replacement to apply to previously returned output, produced by
+/// [`EditSession::edit`].
+///
+/// Exposed to JavaScript as an object with `start`, `end` and `text`
+/// properties: replace output characters `start..end` with `text`.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct OutputPatch {
+    start: usize,
+    end: usize,
+    text: JsValue,
+}
+
+#[wasm_bindgen]
+impl OutputPatch {
+    /// Index of the first replaced output character
+    #[wasm_bindgen(getter)]
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Index one past the last replaced output character
+    #[wasm_bindgen(getter)]
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Dollcode to insert in place of the replaced range
+    #[wasm_bindgen(getter, unchecked_return_type = "string")]
+    pub fn text(&self) -> JsValue {
+        self.text.clone()
+    }
+}
+
+/// Stateful text encoder for live editing.
+///
+/// The front end reports each change to the document as an [`edit`] and
+/// applies the returned [`OutputPatch`] to its copy of the output, instead
+/// of re-converting the whole document on every keystroke. Every character
+/// encodes to its own segment, so an edit only changes the segments of the
+/// characters it touches. The session keeps the output length of each
+/// document character to find where those segments start.
+///
+/// ```js
+/// const session = new EditSession();
+/// let output = "";
+/// const apply = (p) => output = output.slice(0, p.start) + p.text + output.slice(p.end);
+/// apply(session.edit(0, 0, "Hello"));
+/// apply(session.edit(5, 0, "!"));
+/// ```
+///
+/// [`edit`]: EditSession::edit
+#[wasm_bindgen]
+#[derive(Debug, Default)]
+pub struct EditSession {
+    /// Output length of the segment of each document character
+    lengths: heapless::Vec<u8, MAX_INPUT_SIZE>,
+    /// A document offset and the output offset its segment starts at, kept
+    /// from the last edit so that nearby edits only sum the lengths between
+    cursor: (usize, usize),
+}
+
+#[wasm_bindgen]
+impl EditSession {
+    /// Creates a session for an empty document
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Length of the document in characters
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.lengths.len()
+    }
+
+    /// Replaces `delete_count` characters at `offset` with `insert`,
+    /// returning the matching change to the output.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the range lies outside the document, if `insert`
+    /// contains characters outside printable ASCII, or if the document would
+    /// exceed the input limit. The document is unchanged on error.
+    pub fn edit(
+        &mut self,
+        offset: usize,
+        delete_count: usize,
+        insert: &str,
+    ) -> Result<OutputPatch, JsValue> {
+        let tail = offset
+            .checked_add(delete_count)
+            .filter(|&tail| tail <= self.lengths.len())
+            .ok_or_else(|| JsValue::from(ERR_INVALID_EDIT))?;
+
+        if let Some((i, c)) = insert
+            .chars()
+            .enumerate()
+            .find(|&(_, c)| !matches!(c, ' '..='~'))
+        {
+            return Err(to_js_err(DollcodeError::InvalidChar(c, offset + i)));
+        }
+        let len = self.lengths.len() - delete_count + insert.len();
+        if len > input_limit() {
+            return Err(ERR_INPUT_TOO_LONG.into());
+        }
+
+        let start = self.output_offset(offset);
+        let end = start + output_len(&self.lengths[offset..tail]);
+
+        let mut text = StreamOutput::new();
+        let mut inserted = 0;
+        for result in TextIterator::new(insert) {
+            let segment = result.map_err(to_js_err)?;
+            for c in segment.chars() {
+                text.push(c);
+            }
+            inserted += segment.len();
+        }
+
+        // Splice the lengths in place: close the gap of the deleted
+        // characters, then rotate the new lengths from the end into it
+        self.lengths.copy_within(tail.., offset);
+        self.lengths.truncate(self.lengths.len() - delete_count);
+        for result in TextIterator::new(insert) {
+            // Checked above: segments are valid and fit in the input limit
+            let length = result.map_or(0, |segment| segment.len() as u8);
+            let _ = self.lengths.push(length);
+        }
+        self.lengths[offset..].rotate_right(insert.len());
+        self.cursor = (offset + insert.len(), start + inserted);
+
+        Ok(OutputPatch {
+            start,
+            end,
+            text: text.finish(),
+        })
+    }
+}
+
+impl EditSession {
+    /// Output offset of the segment of document character `offset`, summed
+    /// from the cursor
+    fn output_offset(&self, offset: usize) -> usize {
+        let (at, output) = self.cursor;
+        if offset >= at {
+            output + output_len(&self.lengths[at..offset])
+        } else {
+            output - output_len(&self.lengths[offset..at])
+        }
+    }
+}
+
+/// Total of segment output lengths
+fn output_len(lengths: &[u8]) -> usize {
+    lengths.iter().map(|&length| usize::from(length)).sum()
+}
+
+/// Outcome of a [`verify`] roundtrip check.
+///
+/// Exposed to JavaScript as an object with `mode`, `lossless` and
+/// `divergesAt` properties.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct Verification {
+    mode: StreamMode,
+    diverges_at: Option<usize>,
+}
+
+#[wasm_bindgen]
+impl Verification {
+    /// `"encode"` if the input was converted to dollcode first, `"decode"`
+    /// if it was dollcode
+    #[wasm_bindgen(getter, unchecked_return_type = "StreamMode")]
+    pub fn mode(&self) -> JsValue {
+        JsValue::from_str(self.mode.name())
+    }
+
+    /// Whether converting and converting back reproduces the input
+    #[wasm_bindgen(getter)]
+    pub fn lossless(&self) -> bool {
+        self.diverges_at.is_none()
+    }
+
+    /// Character index in the input where the roundtrip first differs
+    #[wasm_bindgen(getter, js_name = divergesAt)]
+    pub fn diverges_at(&self) -> Option<usize> {
+        self.diverges_at
+    }
+}
+
+/// Checks that `input` survives a roundtrip through [`convert`].
+///
+/// Numbers and text are encoded then decoded; dollcode is decoded then
+/// re-encoded. Numbers are compared by value, so `0xff` and `0xFF` both
+/// verify. A final text segment may omit its delimiter. Dollcode that
+/// [`convert`] would only partly decode, such as text after an empty
+/// segment, is reported as diverging where decoding stops.
+///
+/// # Errors
+///
+/// Returns the same errors as [`convert`] for input it rejects.
+#[wasm_bindgen]
+pub fn verify(input: &str) -> Result<Verification, JsValue> {
+    let (mode, diverges_at) = match classify(input)? {
+        InputKind::Decimal => (StreamMode::Encode, verify_number(parse_decimal(input)?)?),
+        InputKind::Hex => (StreamMode::Encode, verify_number(parse_hex(input)?)?),
+        InputKind::Text => (StreamMode::Encode, verify_text(input)?),
+        InputKind::DollcodeNumber | InputKind::DollcodeText => {
+            let end = input.chars().count();
+            (
+                StreamMode::Decode,
+                verify_dollcode(dollcode_glyphs(input), end)?,
+            )
+        }
+        InputKind::DollcodeUrl => (
+            StreamMode::Decode,
+            verify_dollcode(url_glyphs(input), input.len())?,
+        ),
+    };
+
+    Ok(Verification { mode, diverges_at })
+}
+
+/// Encodes and decodes a number, returning `Some(0)` if the value changes
+fn verify_number(num: u64) -> Result<Option<usize>, JsValue> {
+    let dollcode = to_dollcode(num).map_err(to_js_err)?;
+    let decoded = from_dollcode(dollcode.as_chars()).map_err(to_js_err)?;
+    Ok((decoded != num).then_some(0))
+}
+
+/// Encodes and decodes text, returning the index of the first character that
+/// does not come back unchanged
+fn verify_text(input: &str) -> Result<Option<usize>, JsValue> {
+    let mut decoder = StreamDecoder::new();
+    let mut expected = input.chars();
+    let mut position = 0;
+
+    for result in TextIterator::new(input) {
+        let segment = result.map_err(to_js_err)?;
+        for c in segment.chars() {
+            match decoder.push(c) {
+                Ok(None) => {}
+                Ok(Some(decoded)) if expected.next() == Some(decoded) => position += 1,
+                _ => return Ok(Some(position)),
+            }
+        }
+    }
+
+    Ok(expected.next().map(|_| position))
+}
+
+/// Decodes dollcode and re-encodes the result, returning the position of the
+/// first glyph that differs. `end` is the position just past the input.
+fn verify_dollcode(glyphs: impl Glyphs, end: usize) -> Result<Option<usize>, JsValue> {
+    if glyphs.clone().any(|(_, c)| c == DELIMITER) {
+        return verify_dollcode_text(glyphs, end);
+    }
+
+    let value = decode_number(glyphs.clone())?.value;
+    let dollcode = to_dollcode(value).map_err(to_js_err)?;
+    let mut encoded = dollcode.as_chars().iter();
+
+    for (position, c) in glyphs {
+        if encoded.next() != Some(&c) {
+            return Ok(Some(position));
+        }
+    }
+    Ok(encoded.next().map(|_| end))
+}
+
+/// Decodes delimited dollcode segment by segment, re-encoding each decoded
+/// character and comparing it with the glyphs it came from
+fn verify_dollcode_text(mut glyphs: impl Glyphs, end: usize) -> Result<Option<usize>, JsValue> {
+    let mut decoder = StreamDecoder::new();
+    let mut segment = glyphs.clone();
+    let mut start = 0;
+
+    while let Some((position, c)) = glyphs.clone().next() {
+        if !decoder.is_pending() {
+            // An empty segment ends decoding, dropping everything after it
+            if c == DELIMITER {
+                return Ok(Some(position));
+            }
+            segment = glyphs.clone();
+            start = position;
+        }
+        glyphs.next();
+
+        let decoded = decoder
+            .push(c)
+            .map_err(|_| ERR_INVALID_SEQUENCE.at(start))?;
+        if let Some(decoded) = decoded {
+            if let Some(position) = compare_segment(decoded, segment.clone(), end) {
+                return Ok(Some(position));
+            }
+        }
+    }
+
+    match decoder
+        .finish()
+        .map_err(|_| ERR_INVALID_SEQUENCE.at(start))?
+    {
+        Some(decoded) => Ok(compare_segment(decoded, segment, end)),
+        None => Ok(None),
+    }
+}
+
+/// Re-encodes `decoded` and compares it with the start of `segment`,
+/// returning the position of the first mismatch
+fn compare_segment(decoded: char, mut segment: impl Glyphs, end: usize) -> Option<usize> {
+    let mut buf = [0u8; 4];
+    let Some(Ok(encoded)) = TextIterator::new(decoded.encode_utf8(&mut buf)).next() else {
+        return Some(end);
+    };
+
+    for c in encoded.chars() {
+        match segment.next() {
+            Some((_, glyph)) if glyph == c => {}
+            Some((position, _)) => return Some(position),
+            // The final segment may omit its delimiter
+            None if c == DELIMITER => {}
+            None => return Some(end),
+        }
+    }
+
+    None
+}
+
+/// Numbers covered by [`test_vectors`]: digit boundaries of bijective base 3
+/// and the edges of common integer widths
+const VECTOR_NUMBERS: [u64; 16] = [
+    0,
+    1,
+    3,
+    4,
+    12,
+    13,
+    39,
+    40,
+    42,
+    255,
+    256,
+    1000,
+    65_535,
+    u32::MAX as u64,
+    u32::MAX as u64 + 1,
+    u64::MAX,
+];
+
+/// Inputs exercising classification, decoding and error paths in
+/// [`test_vectors`]
+const VECTOR_EDGE_CASES: [&str; 22] = [
+    "0x0",
+    "0xff",
+    "0xFFFFFFFFFFFFFFFF",
+    "Hello, World!",
+    "007",
+    "▖",
+    "▖▖▖▌",
+    "▖▖ ▖▌",
+    "▘▖▘▌\u{200d}▌▘▖▌\u{200d}",
+    "▘▖▘▌\u{200d}▌▘▖▌",
+    "%E2%96%96%E2%96%96%E2%96%96%E2%96%8C",
+    "",
+    "\u{7f}",
+    "café",
+    "18446744073709551616",
+    "0x10000000000000000",
+    "0xZZ",
+    "▌▌▌▌▌\u{200d}",
+    "▖\u{200d}\u{200d}▖",
+    "▖x",
+    "▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌▌",
+    "%E2%96",
+];
+
+/// Returns the canonical conversion test vectors as an array of plain
+/// objects, generated by running this implementation so front-end test
+/// suites stay in sync with it.
+///
+/// Each vector has a `group` (`"number"`, `"ascii"` or `"edge"`), the
+/// `input`, its `kind` as reported by [`detect`], and either the `output`
+/// of [`convert`] or the `error` code it throws. Decoded numbers are given
+/// as decimal strings and decoded text as the text itself.
+///
+/// ```js
+/// for (const { input, output, error } of test_vectors()) {
+///     if (error) expect(() => convert(input)).toThrow(expect.objectContaining({ code: error }));
+///     else expect(String(myConvert(input))).toBe(output);
+/// }
+/// ```
+#[wasm_bindgen(unchecked_return_type = "TestVector[]")]
+pub fn test_vectors() -> Array {
+    let vectors = Array::new();
+
+    for number in VECTOR_NUMBERS {
+        let input = format_radix(number, 10, LOWER_DIGITS);
+        vectors.push(&test_vector("number", &input));
+    }
+
+    let mut utf8 = [0u8; 4];
+    for c in ' '..='~' {
+        vectors.push(&test_vector("ascii", c.encode_utf8(&mut utf8)));
+    }
+
+    for input in VECTOR_EDGE_CASES {
+        vectors.push(&test_vector("edge", input));
+    }
+
+    vectors
+}
+
+/// Runs `input` through [`convert`] and records the outcome
+fn test_vector(group: &str, input: &str) -> JsValue {
+    let classified = classify(input);
+    let kind = classified.as_ref().map_or("invalid", |kind| kind.name());
+
+    let result = classified
+        .map_err(JsValue::from)
+        .and_then(|kind| match kind {
+            InputKind::DollcodeNumber | InputKind::DollcodeText => {
+                decode_dollcode(input).map(|c| c.readable())
+            }
+            InputKind::DollcodeUrl => decode_url(input).map(|c| c.readable()),
+            InputKind::Decimal => convert_decimal(input),
+            InputKind::Hex => convert_hex(input),
+            InputKind::Text => convert_text(input),
+        });
+
+    let vector = js_sys::Object::new();
+    let set = |key: &str, value: &JsValue| {
+        // Setting properties on a fresh object cannot fail
+        let _ = Reflect::set(&vector, &JsValue::from_str(key), value);
+    };
+    set("group", &JsValue::from_str(group));
+    set("input", &JsValue::from_str(input));
+    set("kind", &JsValue::from_str(kind));
+    match result {
+        Ok(output) => set("output", &output),
+        Err(error) => set(
+            "error",
+            &Reflect::get(&error, &JsValue::from_str("code")).unwrap_or_default(),
+        ),
+    }
+
+    vector.into()
+}
+
+/// Cargo features this module was built with, as reported by [`version`]
+const FEATURES: &[(&str, bool)] = &[(
+    "console_error_panic_hook",
+    cfg!(feature = "console_error_panic_hook"),
+)];
+
+/// Returns build information for display and bug reports.
+///
+/// The result is a plain object, so `JSON.stringify` gives a complete
+/// report:
+///
+/// ```js
+/// version();
+/// // { version: "0.1.0", debug: false, features: [],
+/// //   limits: { maxInputChars: 10000, maxInputLimit: 65536,
+/// //             maxDecimalDigits: 20, maxHexLength: 18, maxDollcodeSize: 41 } }
+/// ```
+///
+/// `maxInputChars` is the current limit set with [`set_limits`].
+#[wasm_bindgen(unchecked_return_type = "BuildInfo")]
+pub fn version() -> JsValue {
+    let set = |object: &js_sys::Object, key: &str, value: &JsValue| {
+        // Setting properties on a fresh object cannot fail
+        let _ = Reflect::set(object, &JsValue::from_str(key), value);
+    };
+    let number = |n: usize| JsValue::from_f64(n as f64);
+
+    let features = Array::new();
+    for &(name, _) in FEATURES.iter().filter(|(_, enabled)| *enabled) {
+        features.push(&JsValue::from_str(name));
+    }
+
+    let limits = js_sys::Object::new();
+    set(&limits, "maxInputChars", &number(input_limit()));
+    set(&limits, "maxInputLimit", &number(MAX_INPUT_SIZE));
+    set(&limits, "maxDecimalDigits", &number(MAX_DECIMAL_DIGITS));
+    set(&limits, "maxHexLength", &number(MAX_HEX_LENGTH));
+    set(&limits, "maxDollcodeSize", &number(MAX_DOLLCODE_SIZE));
+
+    let info = js_sys::Object::new();
+    set(
+        &info,
+        "version",
+        &JsValue::from_str(env!("CARGO_PKG_VERSION")),
+    );
+    set(&info, "debug", &JsValue::from_bool(cfg!(debug_assertions)));
+    set(&info, "features", &features);
+    set(&info, "limits", &limits);
+    info.into()
+}
+
+/// Initializes panic hook for WASM.
+///
+/// Panic messages are only forwarded to the console with the
+/// `console_error_panic_hook` feature, which pulls in `core::fmt`; builds
+/// without it are smaller.
+#[wasm_bindgen(start)]
+pub fn init() {
+    #[cfg(feature = "console_error_panic_hook")]
+    console_error_panic_hook::set_once();
+}
+
+#[cfg(target_arch = "wasm32")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    extern crate std;
+
+    fn alloc_string(value: &JsValue) -> std::string::String {
+        value.as_string().unwrap()
+    }
+
+    fn error_field(error: &JsValue, key: &str) -> JsValue {
+        assert!(error.is_instance_of::<js_sys::Error>());
+        Reflect::get(error, &JsValue::from_str(key)).unwrap()
+    }
+
+    fn assert_error(error: JsValue, kind: ErrorKind) {
+        assert_eq!(error_field(&error, "code"), JsValue::from_str(kind.code));
+        assert_eq!(
+            error_field(&error, "message"),
+            JsValue::from_str(kind.message)
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_input_size_limits() {
+        let max_input = "A".repeat(INPUT_SIZE);
+        assert!(convert(&max_input).is_ok());
+
+        let too_long = "A".repeat(INPUT_SIZE + 1);
+        assert_error(convert(&too_long).unwrap_err(), ERR_INPUT_TOO_LONG);
+
+        let too_long_decimal = "9".repeat(MAX_DECIMAL_DIGITS + 1);
+        assert_error(
+            convert(&too_long_decimal).unwrap_err(),
+            ERR_DECIMAL_TOO_LONG,
+        );
+
+        assert_error(
+            convert("0xFFFFFFFFFFFFFFFFF").unwrap_err(),
+            ERR_HEX_TOO_LONG,
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_long_text() {
+        // A few paragraphs convert in one call, spanning many output chunks
+        let text = "The quick brown fox jumps over the lazy dog. ".repeat(100);
+        assert!(text.len() > 4000);
+
+        let encoded = alloc_string(&convert(&text).unwrap());
+        assert!(encoded.len() > CHAR_BUF_SIZE * 10);
+        assert_eq!(
+            decode_dollcode(&encoded).unwrap().text(),
+            JsValue::from_str(&text)
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_buffer_overflow_prevention() {
+        let too_long = "▖".repeat(INPUT_SIZE * BYTES_PER_CHAR + 1);
+        assert_error(convert(&too_long).unwrap_err(), ERR_DOLLCODE_TOO_LONG);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_character_validation() {
+        assert!(convert("\x1F").is_err());
+        assert!(convert("\x7F").is_err());
+        assert!(convert("☺").is_err());
+        assert!(convert(" ").is_ok());
+        assert!(convert("~").is_ok());
+        assert!(convert("Hello!").is_ok());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_dollcode_validation() {
         assert!(convert("▖").is_ok());
         assert!(convert("▘").is_ok());
         assert!(convert("▌").is_ok());
@@ -397,88 +3255,974 @@ mod tests {
     }
 
     #[wasm_bindgen_test]
-    fn test_decimal_conversion_limits() {
-        // Test error cases with constant strings
+    fn test_decimal_conversion_limits() {
+        // Test error cases with constant strings
+        assert_error(
+            convert("18446744073709551616").unwrap_err(),
+            ERR_INVALID_DECIMAL,
+        );
+        assert_eq!(
+            convert_decimal("000042").unwrap(),
+            convert_decimal("42").unwrap()
+        );
+        // Signs are not part of a number, as in the native bindings
+        assert_error(convert_decimal("+42").unwrap_err(), ERR_INVALID_DECIMAL);
+        assert_error(convert_hex("0x0x2A").unwrap_err(), ERR_INVALID_HEX);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_hex_conversion_edge_cases() {
+        assert!(convert("0x0").is_ok());
+        assert!(convert("0xf").is_ok());
+        assert!(convert("0xF").is_ok());
+        assert!(convert("0xDEADBEEF").is_ok());
+        assert!(convert("0xG").is_ok()); // Valid as text
+        assert!(convert("0x-1").is_ok()); // Valid as text
+        assert!(convert("0xFFFFFFFFFFFFFFFF").is_ok());
+        assert!(convert("0x").is_ok()); // Actual behavior: converts as text
+    }
+
+    #[wasm_bindgen_test]
+    fn test_text_delimiter_handling() {
+        assert_eq!(
+            convert("Hi").unwrap(),
+            JsValue::from_str("▘▖▘▌\u{200D}▌▘▖▌\u{200D}")
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_dollcode_decoding() {
+        // Test number decoding (NOTE: Core gives numeric output)
+        let number = decode_dollcode("▖▖▖▌").unwrap();
+        assert_eq!(number.kind(), JsValue::from_str("number"));
+        assert_eq!(number.decimal(), JsValue::from_str("42"));
+        assert_eq!(number.hex(), JsValue::from_str("0x2a"));
+        assert!(number.text().is_undefined());
+        assert_eq!(number.glyph_count(), 4);
+        assert_eq!(
+            number.to_js_string(),
+            JsValue::from_str("Dec (base10): 42\nHex (base16): 0x2a")
+        );
+
+        // Test text decoding
+        let text = decode_dollcode("▘▖▘▌\u{200D}▌▘▖▌\u{200D}").unwrap();
+        assert_eq!(text.kind(), JsValue::from_str("text"));
+        assert_eq!(text.text(), JsValue::from_str("Hi"));
+        assert!(text.decimal().is_undefined());
+        assert!(text.hex().is_undefined());
+        assert_eq!(text.glyph_count(), 8);
+
+        // Test invalid sequences
+        let error = convert("▌▘▖▌\u{200D}▖▘▌\u{200D}").unwrap_err();
+        assert_error(error.clone(), ERR_INVALID_SEQUENCE);
+        assert_eq!(error_field(&error, "position"), JsValue::from_f64(5.0));
+
+        let error = convert("▖▘▌!").unwrap_err();
+        assert_eq!(
+            error_field(&error, "code"),
+            JsValue::from_str("INVALID_CHAR")
+        );
+        assert_eq!(error_field(&error, "position"), JsValue::from_f64(3.0));
+        assert_eq!(error_field(&error, "offendingChar"), JsValue::from_str("!"));
+        assert_eq!(
+            error_field(&error, "name"),
+            JsValue::from_str("DollcodeError")
+        );
+
+        assert_error(
+            convert(&"▌".repeat(MAX_DOLLCODE_SIZE)).unwrap_err(),
+            ERR_NUMBER_OVERFLOW,
+        );
+        assert!(error_field(&convert("").unwrap_err(), "position").is_undefined());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_empty_and_whitespace() {
+        assert_error(convert("").unwrap_err(), ERR_EMPTY);
+
+        assert!(convert(" ").is_ok());
+        assert!(convert("   ").is_ok());
+        assert!(convert("Hello World").is_ok());
+
+        // Text input processes whitespace, so these aren't equal
+        assert!(convert("42").is_ok());
+        assert!(convert("  42  ").is_ok());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_detect() {
+        let cases = [
+            ("42", "decimal"),
+            ("0xFF", "hex"),
+            ("0x", "text"),
+            ("Hello!", "text"),
+            ("▖▖▖▌", "dollcode-number"),
+            ("▘▖▘▌\u{200D}", "dollcode-text"),
+            ("", "invalid"),
+            ("☺", "invalid"),
+            ("▖A", "invalid"),
+        ];
+        for (input, kind) in cases {
+            assert_eq!(detect(input).kind(), JsValue::from_str(kind), "{}", input);
+        }
+
+        // Numbers can also be read as text
+        let detection = detect("42");
+        assert!(detection.ambiguous());
+        assert_eq!(detection.alternative(), JsValue::from_str("text"));
+        assert!(detect("0xFF").ambiguous());
+        for input in ["Hello!", "▖▖▖▌", ""] {
+            assert!(!detect(input).ambiguous(), "{}", input);
+            assert!(detect(input).alternative().is_undefined());
+        }
+
+        // As does convert, offering the text encoding alongside the number
+        let field = |value: &JsValue, key: &str| Reflect::get(value, &key.into()).unwrap();
+        for (input, number) in [("42", "▖▖▖▌"), ("0xFF", "▘▘▌▌▌")] {
+            let encoding = convert(input).unwrap();
+            assert_eq!(field(&encoding, "dollcode"), JsValue::from_str(number));
+            assert_eq!(field(&encoding, "ambiguous"), JsValue::TRUE);
+            assert_eq!(
+                field(&encoding, "alternative"),
+                convert_text(input).unwrap()
+            );
+            assert_eq!(
+                JsValue::from(js_sys::Object::from(encoding).to_string()),
+                JsValue::from_str(number)
+            );
+        }
+        assert!(convert("Hello!").unwrap().is_string());
+
+        // Invalid input reports the error convert would return
+        assert_error(detect("").reason(), ERR_EMPTY);
+        assert_eq!(
+            error_field(&detect("▖A").reason(), "message"),
+            error_field(&convert("▖A").unwrap_err(), "message")
+        );
+        assert_error(
+            detect(&"9".repeat(MAX_DECIMAL_DIGITS + 1)).reason(),
+            ERR_DECIMAL_TOO_LONG,
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_convert_with_stats() {
+        let stats = convert_with_stats("Hi").unwrap();
+        assert_eq!(stats.value(), convert("Hi").unwrap());
+        assert_eq!(stats.glyph_count(), 8);
+        assert_eq!(stats.byte_length(), 30);
+        assert_eq!(stats.segment_count(), 2);
+        assert_eq!(stats.rendered_width(), 8);
+        assert!(!stats.near_limit());
+
+        let stats = convert_with_stats("42").unwrap();
+        assert_eq!(stats.glyph_count(), 4);
+        assert_eq!(stats.byte_length(), 12);
+        assert_eq!(stats.segment_count(), 0);
+        assert_eq!(stats.limit_usage(), 0.1);
+
+        // Decoding describes the input, including a final undelimited segment
+        let stats = convert_with_stats("▘▖▘▌\u{200D}▌▘▖▌").unwrap();
+        assert!(!stats.value().is_string());
+        assert_eq!(stats.segment_count(), 2);
+        assert_eq!(stats.byte_length(), 27);
+
+        set_limits(10).unwrap();
+        let stats = convert_with_stats("AAAAAAAAA").unwrap();
+        assert_eq!(stats.limit_usage(), 0.9);
+        assert!(stats.near_limit());
+        set_limits(INPUT_SIZE).unwrap();
+
+        assert_error(convert_with_stats("").unwrap_err(), ERR_EMPTY);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_describe() {
+        let hi = "Dollcode for the text \"Hi\": high low high tall, tall high low tall";
+        assert_eq!(describe("Hi").unwrap(), JsValue::from_str(hi));
         assert_eq!(
-            convert("18446744073709551616").unwrap_err(),
-            JsValue::from_str(ERR_INVALID_DECIMAL)
+            describe("▘▖▘▌\u{200D}\n▌▘▖▌").unwrap(),
+            JsValue::from_str(hi)
         );
-        assert_eq!(convert("000042").unwrap(), convert("42").unwrap());
+
+        let number = JsValue::from_str("Dollcode for the number 42: low low low tall");
+        for input in ["42", "0x2A", "▖▖▖▌"] {
+            assert_eq!(describe(input).unwrap(), number, "{}", input);
+        }
+
+        assert_error(describe("").unwrap_err(), ERR_EMPTY);
     }
 
     #[wasm_bindgen_test]
-    fn test_hex_conversion_edge_cases() {
-        assert!(convert("0x0").is_ok());
-        assert!(convert("0xf").is_ok());
-        assert!(convert("0xF").is_ok());
-        assert!(convert("0xDEADBEEF").is_ok());
-        assert!(convert("0xG").is_ok()); // Valid as text
-        assert!(convert("0x-1").is_ok()); // Valid as text
-        assert!(convert("0xFFFFFFFFFFFFFFFF").is_ok());
-        assert!(convert("0x").is_ok()); // Actual behavior: converts as text
+    fn test_selection_mapping() {
+        let range = |selection: Selection| (selection.start(), selection.end());
+
+        // Each text character maps to its five-glyph segment and delimiter
+        assert_eq!(range(map_to_output("Hi", 1, 2).unwrap()), (5, 10));
+        assert_eq!(range(map_to_output("Hi", 0, 2).unwrap()), (0, 10));
+        assert_eq!(range(map_to_output("Hi", 1, 1).unwrap()), (5, 5));
+        assert_eq!(range(map_to_output("Hi", 2, 2).unwrap()), (10, 10));
+        assert_eq!(range(map_to_input("Hi", 3, 7).unwrap()), (0, 2));
+        assert_eq!(range(map_to_input("Hi", 7, 7).unwrap()), (1, 1));
+
+        // Decoding maps segments to characters
+        let encoded = "▘▖▘▌\u{200D}▌▘▖▌\u{200D}";
+        assert_eq!(range(map_to_output(encoded, 5, 10).unwrap()), (1, 2));
+        assert_eq!(range(map_to_output(encoded, 2, 6).unwrap()), (0, 2));
+        assert_eq!(range(map_to_input(encoded, 1, 2).unwrap()), (5, 10));
+
+        // Numbers only map as a whole
+        assert_eq!(range(map_to_output("42", 0, 1).unwrap()), (0, 4));
+        assert_eq!(range(map_to_output("▖▖▖▌", 1, 2).unwrap()), (0, 2));
+
+        // Out of range positions clamp to the end
+        assert_eq!(range(map_to_output("Hi", 5, 9).unwrap()), (10, 10));
+
+        assert_error(map_to_output("", 0, 0).unwrap_err(), ERR_EMPTY);
     }
 
     #[wasm_bindgen_test]
-    fn test_text_delimiter_handling() {
+    fn test_convert_batch() {
+        let inputs = Array::of3(&"42".into(), &"☺".into(), &JsValue::from(7));
+        let results = convert_batch(&inputs);
+        assert_eq!(results.length(), 3);
+
+        let item = |i| -> (bool, JsValue, JsValue) {
+            let item = results.get(i);
+            let get = |key: &str| js_sys::Reflect::get(&item, &key.into()).unwrap();
+            (get("ok").as_bool().unwrap(), get("value"), get("error"))
+        };
+
+        let (ok, value, error) = item(0);
+        assert!(ok);
         assert_eq!(
-            convert("Hi").unwrap(),
-            JsValue::from_str("▘▖▘▌\u{200D}▌▘▖▌\u{200D}")
+            Reflect::get(&value, &"dollcode".into()).unwrap(),
+            convert_decimal("42").unwrap()
         );
+        assert!(error.is_undefined());
+
+        let (ok, value, error) = item(1);
+        assert!(!ok);
+        assert!(value.is_undefined());
+        assert_eq!(error_field(&error, "offendingChar"), JsValue::from_str("☺"));
+
+        let (ok, _, error) = item(2);
+        assert!(!ok);
+        assert_error(error, ERR_BATCH_ENTRY);
+
+        assert_eq!(convert_batch(&Array::new()).length(), 0);
     }
 
     #[wasm_bindgen_test]
-    fn test_dollcode_decoding() {
-        // Test number decoding (NOTE: Core gives numeric output)
+    fn test_conversion_stream() {
+        let text = "Hello, World! ".repeat(INPUT_SIZE);
+
+        // Encode in uneven slices well past the single-call limit
+        let mut stream = ConversionStream::new();
+        let mut encoded = alloc_string(&stream.push("").unwrap());
+        assert!(stream.mode().is_undefined());
+        for slice in text.as_bytes().chunks(37) {
+            let slice = core::str::from_utf8(slice).unwrap();
+            encoded += &alloc_string(&stream.push(slice).unwrap());
+        }
+        assert_eq!(stream.mode(), JsValue::from_str("encode"));
+        encoded += &alloc_string(&stream.finish().unwrap());
+        assert!(stream.mode().is_undefined());
+
+        let expected: std::string::String = TextIterator::new(&text)
+            .flat_map(|segment| segment.unwrap().chars())
+            .collect();
+        assert_eq!(encoded, expected);
+
+        // Decode with chunk boundaries falling inside segments
+        let glyphs: std::vec::Vec<char> = encoded.chars().collect();
+        let mut decoded = std::string::String::new();
+        for slice in glyphs.chunks(7) {
+            let slice: std::string::String = slice.iter().collect();
+            decoded += &alloc_string(&stream.push(&slice).unwrap());
+        }
+        assert_eq!(stream.mode(), JsValue::from_str("decode"));
+        decoded += &alloc_string(&stream.finish().unwrap());
+        assert_eq!(decoded, text);
+
+        // Errors report positions across the whole stream
+        let mut stream = ConversionStream::new();
+        stream.push("abc").unwrap();
+        let error = stream.push("d☺").unwrap_err();
+        assert_eq!(error_field(&error, "position"), JsValue::from_f64(4.0));
+        assert_eq!(error_field(&error, "offendingChar"), JsValue::from_str("☺"));
+
+        // A dangling partial segment fails on finish
+        let mut stream = ConversionStream::new();
+        stream.push("▖").unwrap();
+        assert!(stream.finish().is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_set_limits() {
+        assert_eq!(max_input_chars(), INPUT_SIZE);
+        assert!(set_limits(0).is_err());
+        assert!(set_limits(MAX_INPUT_SIZE + 1).is_err());
+        assert_eq!(max_input_chars(), INPUT_SIZE);
+
+        set_limits(1000).unwrap();
+        let text = "Hello, World! ".repeat(70);
+        assert_eq!(text.len(), 980);
+        let encoded = convert(&text).unwrap().as_string().unwrap();
+        assert!(encoded.len() > CHAR_BUF_SIZE);
+
+        let decoded = decode_dollcode(&encoded).unwrap();
+        assert_eq!(decoded.text(), JsValue::from_str(&text));
+        assert_error(convert(&"A".repeat(1001)).unwrap_err(), ERR_INPUT_TOO_LONG);
+
+        set_limits(500).unwrap();
+        assert_error(convert(&text).unwrap_err(), ERR_INPUT_TOO_LONG);
+
+        set_limits(INPUT_SIZE).unwrap();
+        assert!(convert(&text).is_ok());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_exported_constants() {
+        assert_eq!(max_input_limit(), 65_536);
+        assert_eq!(max_decimal_digits(), "18446744073709551615".len());
+        assert_eq!(max_hex_length(), "0xFFFFFFFFFFFFFFFF".len());
+
+        let glyphs = glyphs();
+        assert_eq!(glyphs.length(), 3);
+        assert_eq!(glyphs.get(0), JsValue::from_str("▖"));
+        assert_eq!(glyphs.get(1), JsValue::from_str("▘"));
+        assert_eq!(glyphs.get(2), JsValue::from_str("▌"));
+        assert_eq!(delimiter(), "\u{200D}");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_format_radix() {
+        assert_eq!(format_radix(0, 10, LOWER_DIGITS), "0");
         assert_eq!(
-            convert("▖▖▖▌").unwrap(),
-            JsValue::from_str("Dec (base10): 42\nHex (base16): 0x2a")
+            format_radix(u64::MAX, 10, LOWER_DIGITS),
+            "18446744073709551615"
         );
+        assert_eq!(format_radix(u64::MAX, 16, LOWER_DIGITS), "ffffffffffffffff");
+        assert_eq!(format_radix(0x258C, 16, UPPER_DIGITS), "258C");
+        assert_eq!(format_radix(5, 2, LOWER_DIGITS), "101");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_profiler() {
+        let timings = Array::new();
+        let collect = js_sys::Function::new_with_args("timings", "return t => timings.push(t)");
+        let profiler = Profiler::new(collect.call1(&JsValue::NULL, &timings).unwrap().into());
+
+        assert_eq!(profiler.convert("Hi").unwrap(), convert("Hi").unwrap());
+        assert!(profiler.decode_to_text("▘▖▘▌\u{200d}").is_ok());
+        assert_error(profiler.convert_text("").unwrap_err(), ERR_EMPTY);
+        assert!(profiler.convert("\u{7f}").is_err());
+        assert_eq!(timings.length(), 4);
+
+        let timing = |i: u32| timings.get(i).unchecked_into::<JsValue>();
+        let field = |i: u32, key: &str| Reflect::get(&timing(i), &key.into()).unwrap();
+
+        assert_eq!(field(0, "operation"), "convert");
+        assert_eq!(field(0, "kind"), "text");
+        assert_eq!(field(0, "inputLength"), JsValue::from_f64(2.0));
+        assert_eq!(field(0, "outputLength"), JsValue::from_f64(10.0));
+        assert!(field(0, "durationMs").as_f64().unwrap() >= 0.0);
+        assert!(field(0, "errorCode").is_undefined());
+
+        assert_eq!(field(1, "operation"), "decode_to_text");
+        assert_eq!(field(1, "outputLength"), JsValue::from_f64(1.0));
+
+        assert_eq!(field(2, "errorCode"), "EMPTY_INPUT");
+        assert!(field(2, "outputLength").is_undefined());
+        assert_eq!(field(3, "kind"), "invalid");
+        assert_eq!(field(3, "errorCode"), "INVALID_CHAR");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_to_qr() {
+        let qr = to_qr("Hi").unwrap();
+        assert_eq!(qr.size(), 29);
+
+        let modules = qr.modules().to_vec();
+        assert_eq!(modules.len(), 29 * 29);
+        assert!(modules.iter().all(|&m| m <= 1));
+        assert_eq!(modules[..7], [1; 7]);
+
+        let svg = alloc_string(&qr.to_svg());
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 37 37\""));
+        assert!(svg.contains("M4,4h1v1h-1z"));
+        assert!(svg.ends_with("\"/></svg>"));
 
-        // Test invalid sequence
+        // Dollcode input is encoded the same way as its source
         assert_eq!(
-            convert("▖▘▌!").unwrap_err(),
-            to_js_err(DollcodeError::InvalidInput)
+            to_qr("▖▖▖▌").unwrap().modules().to_vec(),
+            to_qr("42").unwrap().modules().to_vec()
         );
+
+        assert_error(to_qr(&"~".repeat(200)).unwrap_err(), ERR_QR_TOO_LONG);
+        assert_error(to_qr("").unwrap_err(), ERR_EMPTY);
     }
 
     #[wasm_bindgen_test]
-    fn test_empty_and_whitespace() {
-        assert_eq!(convert("").unwrap_err(), JsValue::from_str(ERR_EMPTY));
+    fn test_render_svg() {
+        let svg = alloc_string(&render_svg("42", &JsValue::UNDEFINED).unwrap());
+        assert_eq!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"48\" height=\"24\" \
+             viewBox=\"0 0 48 24\" shape-rendering=\"crispEdges\">\
+             <path fill=\"currentColor\" d=\"M0,12h6v12h-6zM12,12h6v12h-6z\
+             M24,12h6v12h-6zM36,0h6v24h-6z\"/></svg>"
+        );
 
-        assert!(convert(" ").is_ok());
-        assert!(convert("   ").is_ok());
-        assert!(convert("Hello World").is_ok());
+        // Delimiters take no space, and dollcode input is drawn as is
+        let options = js_sys::Object::new();
+        Reflect::set(&options, &"height".into(), &JsValue::from_f64(10.0)).unwrap();
+        Reflect::set(&options, &"color".into(), &"#c0ffee".into()).unwrap();
+        Reflect::set(&options, &"background".into(), &"rgb(0, 0, 0)".into()).unwrap();
+        let svg = alloc_string(&render_svg("▘▖\u{200d}▌", &options).unwrap());
+        assert!(svg.contains("width=\"15\" height=\"10\" viewBox=\"0 0 15 10\""));
+        assert!(svg.contains("<rect width=\"100%\" height=\"100%\" fill=\"rgb(0, 0, 0)\"/>"));
+        assert!(svg.contains("fill=\"#c0ffee\" d=\"M0,0h2v5h-2zM5,5h2v5h-2zM10,0h2v10h-2z\""));
 
-        // Text input processes whitespace, so these aren't equal
-        assert!(convert("42").is_ok());
-        assert!(convert("  42  ").is_ok());
+        for (key, value) in [
+            ("height", JsValue::from_f64(0.0)),
+            ("height", JsValue::from_f64(1.5)),
+            ("height", "24".into()),
+            ("color", "red\" onload=\"x".into()),
+            ("background", JsValue::from_f64(1.0)),
+        ] {
+            let options = js_sys::Object::new();
+            Reflect::set(&options, &key.into(), &value).unwrap();
+            assert_error(render_svg("42", &options).unwrap_err(), ERR_INVALID_OPTION);
+        }
+        assert_error(render_svg("", &JsValue::UNDEFINED).unwrap_err(), ERR_EMPTY);
     }
 
     #[wasm_bindgen_test]
-    fn test_unicode_normalization() {
+    fn test_export() {
+        assert_eq!(
+            export("Hi", ExportFormat::Plain).unwrap(),
+            convert("Hi").unwrap()
+        );
+
+        assert_eq!(
+            export("Hi", ExportFormat::Html).unwrap(),
+            "<span class=\"dollcode\" style=\"font-family:monospace;white-space:pre-wrap\">\
+             <span class=\"dollcode-segment\">▘▖▘▌&#x200D;</span>\
+             <span class=\"dollcode-segment\">▌▘▖▌&#x200D;</span></span>"
+        );
+        assert_eq!(
+            export("42", ExportFormat::Html).unwrap(),
+            "<span class=\"dollcode\" style=\"font-family:monospace;white-space:pre-wrap\">\
+             <span class=\"dollcode-segment\">▖▖▖▌</span></span>"
+        );
+
         assert_eq!(
-            convert("▖▘▌").unwrap(),
-            convert("\u{2596}\u{2598}\u{258C}").unwrap()
+            export("42", ExportFormat::Rtf).unwrap(),
+            "{\\rtf1\\ansi\\deff0{\\fonttbl{\\f0\\fmodern Courier New;}}\\f0 \
+             \\u9622?\\u9622?\\u9622?\\u9612?}"
         );
+        let rtf = alloc_string(&export("▘\u{200d}", ExportFormat::Rtf).unwrap());
+        assert!(rtf.ends_with("\\u9624?\\u8205?}"));
+
+        assert_error(export("", ExportFormat::Html).unwrap_err(), ERR_EMPTY);
     }
 
     #[wasm_bindgen_test]
-    fn test_error_messages() {
-        let long_input = "A".repeat(INPUT_SIZE + 1);
+    fn test_encode_file() {
+        // Spans several chunks, with a partial one at the end
+        let data: std::vec::Vec<u8> = (0..FILE_CHUNK_BYTES * 2 + 10).map(|i| i as u8).collect();
+        let buffer = Uint8Array::from(&data[..]).buffer();
+
+        let chunks = Array::new();
+        let collect = js_sys::Function::new_with_args("chunks", "return c => chunks.push(c)");
+        let on_chunk = collect.call1(&JsValue::NULL, &chunks).unwrap().into();
+        encode_file_chunked(&buffer, &on_chunk).unwrap();
+        assert_eq!(chunks.length(), 3);
+
+        let encoded = alloc_string(&chunks.join(""));
+        assert_eq!(encoded.chars().count(), data.len() * GLYPHS_PER_BYTE);
+        let decoded = decode_to_bytes(&encoded).unwrap().to_vec();
+        assert_eq!(decoded, data);
+
+        let blob = encode_file(&buffer).unwrap();
+        assert_eq!(blob.size() as usize, encoded.len());
+        assert_eq!(blob.type_(), "text/plain;charset=utf-8");
+
+        // Errors from the callback stop encoding
+        let throw = js_sys::Function::new_with_args("c", "throw new Error('stop')");
+        assert!(encode_file_chunked(&buffer, &throw).is_err());
+
+        let empty = js_sys::ArrayBuffer::new(0);
+        assert_error(encode_file(&empty).unwrap_err(), ERR_EMPTY);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_test_vectors() {
+        let vectors = test_vectors();
+        assert_eq!(
+            vectors.length() as usize,
+            VECTOR_NUMBERS.len() + 95 + VECTOR_EDGE_CASES.len()
+        );
+
+        let field = |vector: &JsValue, key: &str| Reflect::get(vector, &key.into()).unwrap();
+        let find = |input: &str| vectors.iter().find(|v| field(v, "input") == input).unwrap();
+
+        let answer = find("42");
+        assert_eq!(field(&answer, "group"), "number");
+        assert_eq!(field(&answer, "kind"), "decimal");
+        assert_eq!(field(&answer, "output"), "▖▖▖▌");
+        assert!(field(&answer, "error").is_undefined());
+
+        let space = find(" ");
+        assert_eq!(field(&space, "group"), "ascii");
+        assert_eq!(field(&space, "output"), convert(" ").unwrap());
+
+        let decoded = find("▘▖▘▌\u{200d}▌▘▖▌\u{200d}");
+        assert_eq!(field(&decoded, "kind"), "dollcode-text");
+        assert_eq!(field(&decoded, "output"), "Hi");
+        assert_eq!(field(&find("▖▖▖▌"), "output"), "42");
+
+        let empty = find("");
+        assert_eq!(field(&empty, "kind"), "invalid");
+        assert_eq!(field(&empty, "error"), "EMPTY_INPUT");
+        assert!(field(&empty, "output").is_undefined());
+
+        // Every vector has exactly one outcome
+        for vector in vectors.iter() {
+            assert!(
+                field(&vector, "output").is_undefined() != field(&vector, "error").is_undefined()
+            );
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn test_version() {
+        let info = version();
+        let field = |value: &JsValue, key: &str| Reflect::get(value, &key.into()).unwrap();
+
+        assert_eq!(field(&info, "version"), env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            field(&info, "debug"),
+            JsValue::from_bool(cfg!(debug_assertions))
+        );
+        let features: Array = field(&info, "features").dyn_into().unwrap();
+        assert_eq!(
+            features.includes(&"console_error_panic_hook".into(), 0),
+            cfg!(feature = "console_error_panic_hook")
+        );
+
+        let limits = field(&info, "limits");
+        assert_eq!(
+            field(&limits, "maxInputChars"),
+            JsValue::from_f64(INPUT_SIZE as f64)
+        );
+        assert_eq!(field(&limits, "maxDollcodeSize"), JsValue::from_f64(41.0));
+
+        set_limits(500).unwrap();
+        assert_eq!(
+            field(&field(&version(), "limits"), "maxInputChars"),
+            JsValue::from_f64(500.0)
+        );
+        set_limits(INPUT_SIZE).unwrap();
+    }
+
+    #[wasm_bindgen_test]
+    fn test_text_buffers() {
+        let text = "Hello, World! ~";
+        let encoded = Uint8Array::new(&convert_text_buffer(text.as_bytes()).unwrap()).to_vec();
+        let expected = alloc_string(&convert_text(text).unwrap());
+        assert_eq!(encoded, expected.as_bytes());
+
+        let decoded = Uint8Array::new(&decode_text_buffer(&encoded).unwrap()).to_vec();
+        assert_eq!(decoded, text.as_bytes());
+
+        // Outputs larger than the internal buffer are written in chunks
+        let long = "x".repeat(CHUNK_CHARS * 3);
+        let encoded = Uint8Array::new(&convert_text_buffer(long.as_bytes()).unwrap()).to_vec();
+        let decoded = Uint8Array::new(&decode_text_buffer(&encoded).unwrap()).to_vec();
+        assert_eq!(decoded, long.as_bytes());
+
+        assert_error(convert_text_buffer(&[]).unwrap_err(), ERR_EMPTY);
+        let error = convert_text_buffer(b"ab\xFF").unwrap_err();
+        assert_eq!(error_field(&error, "code"), "INVALID_UTF8");
+        assert_eq!(error_field(&error, "position"), JsValue::from_f64(2.0));
+        let error = convert_text_buffer("a\u{7f}".as_bytes()).unwrap_err();
+        assert_eq!(error_field(&error, "code"), "INVALID_CHAR");
+        let error = decode_text_buffer("▌▌▌▌▌\u{200d}".as_bytes()).unwrap_err();
+        assert_eq!(error_field(&error, "code"), "INVALID_SEQUENCE");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_edit_session() {
+        let mut session = EditSession::new();
+        let mut document = std::string::String::new();
+        let mut output = std::string::String::new();
+
+        let edits = [
+            (0, 0, "Hello"),
+            (5, 0, ", World!"),
+            (0, 1, "J"),
+            (5, 2, ""),
+            (11, 0, "?"),
+            (3, 4, "p ~ "),
+        ];
+        for (offset, delete_count, insert) in edits {
+            let patch = session.edit(offset, delete_count, insert).unwrap();
+            document.replace_range(offset..offset + delete_count, insert);
+
+            // Output indices count characters, as JavaScript strings do
+            let mut chars: std::vec::Vec<char> = output.chars().collect();
+            chars.splice(
+                patch.start()..patch.end(),
+                alloc_string(&patch.text()).chars(),
+            );
+            output = chars.into_iter().collect();
+
+            assert_eq!(session.length(), document.len());
+            assert_eq!(output, alloc_string(&convert_text(&document).unwrap()));
+        }
+
+        // Invalid edits leave the document untouched
+        assert_error(session.edit(20, 0, "x").unwrap_err(), ERR_INVALID_EDIT);
+        assert_error(session.edit(2, 99, "").unwrap_err(), ERR_INVALID_EDIT);
+        let error = session.edit(1, 0, "a☺").unwrap_err();
+        assert_eq!(error_field(&error, "position"), JsValue::from_f64(2.0));
+        assert_eq!(session.length(), document.len());
+
+        set_limits(document.len()).unwrap();
+        assert_error(session.edit(0, 0, "x").unwrap_err(), ERR_INPUT_TOO_LONG);
+        assert!(session.edit(0, 1, "x").is_ok());
+        set_limits(INPUT_SIZE).unwrap();
+    }
+
+    #[wasm_bindgen_test]
+    fn test_verify() {
+        for input in ["42", "0xFF", "0xff", "Hello, World!", "~"] {
+            let verification = verify(input).unwrap();
+            assert_eq!(verification.mode(), JsValue::from_str("encode"));
+            assert!(verification.lossless(), "{input} should roundtrip");
+        }
+
+        for input in [
+            "▖▖▖▌",
+            "▘▖▘▌\u{200D}▌▘▖▌\u{200D}",
+            // Missing final delimiter
+            "▘▖▘▌\u{200D}▌▘▖▌",
+        ] {
+            let verification = verify(input).unwrap();
+            assert_eq!(verification.mode(), JsValue::from_str("decode"));
+            assert!(verification.lossless(), "{input} should roundtrip");
+            assert_eq!(verification.diverges_at(), None);
+        }
+
+        // Decoding stops at the empty segment, losing the second character
+        let verification = verify("▖▌▌▘\u{200D}\u{200D}▖▌▌▘\u{200D}").unwrap();
+        assert!(!verification.lossless());
+        assert_eq!(verification.diverges_at(), Some(5));
+
+        assert_error(verify("").unwrap_err(), ERR_EMPTY);
+        let error = verify("▖▘▌!").unwrap_err();
+        assert_eq!(
+            error_field(&error, "code"),
+            JsValue::from_str("INVALID_CHAR")
+        );
+        assert_eq!(error_field(&error, "position"), JsValue::from_f64(3.0));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_fixed_width_text() {
+        let encoded = alloc_string(&convert_text_fixed_width("Hi!").unwrap());
+        assert_eq!(encoded.chars().count(), 3 * GLYPHS_PER_BYTE);
+        assert!(!encoded.contains(DELIMITER));
+
+        let decoded = decode_fixed_width(&encoded).unwrap();
+        assert_eq!(decoded.kind(), JsValue::from_str("text"));
+        assert_eq!(decoded.text(), JsValue::from_str("Hi!"));
+        assert_eq!(decoded.glyph_count(), 18);
+
+        let error = convert_text_fixed_width("ok☺").unwrap_err();
+        assert_eq!(error_field(&error, "position"), JsValue::from_f64(2.0));
+
+        // "A" followed by 0x00, a valid byte but not printable text
+        let error = decode_fixed_width("▖▖▌▘▖▌▖▖▖▖▖▖").unwrap_err();
+        assert_error(error.clone(), ERR_INVALID_SEQUENCE);
+        assert_eq!(error_field(&error, "position"), JsValue::from_f64(6.0));
+
+        let error = decode_fixed_width(&encoded[..encoded.len() - 3]).unwrap_err();
+        assert_error(error.clone(), ERR_INVALID_SEQUENCE);
+        assert_eq!(error_field(&error, "position"), JsValue::from_f64(12.0));
+
+        assert_error(decode_fixed_width("").unwrap_err(), ERR_EMPTY);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_bytes_roundtrip() {
+        let data: std::vec::Vec<u8> = (0..=255).collect();
+        set_limits(data.len()).unwrap();
+
+        let encoded = alloc_string(&convert_bytes(&data).unwrap());
+        assert_eq!(encoded.chars().count(), data.len() * GLYPHS_PER_BYTE);
+        assert_eq!(decode_to_bytes(&encoded).unwrap().to_vec(), data);
+
+        assert_error(convert_bytes(&[0; 257]).unwrap_err(), ERR_INPUT_TOO_LONG);
+        set_limits(INPUT_SIZE).unwrap();
+
+        assert_error(convert_bytes(&[]).unwrap_err(), ERR_EMPTY);
+        assert_eq!(alloc_string(&convert_bytes(&[0x2A]).unwrap()), "▖▖▘▘▌▖");
+
+        // Partial trailing group points at its first glyph
+        let error = decode_to_bytes("▖▖▘▘▌▖▖▖").unwrap_err();
+        assert_error(error.clone(), ERR_INVALID_SEQUENCE);
+        assert_eq!(error_field(&error, "position"), JsValue::from_f64(6.0));
+
+        let error = decode_to_bytes("▖▖X").unwrap_err();
+        assert_eq!(error_field(&error, "offendingChar"), JsValue::from_str("X"));
+        assert_eq!(error_field(&error, "position"), JsValue::from_f64(2.0));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_explicit_decoding() {
+        // Text decoding accepts a missing final delimiter
+        let text = decode_to_text("▘▖▘▌\u{200D}▌▘▖▌").unwrap();
+        assert_eq!(text.text(), JsValue::from_str("Hi"));
+        assert_eq!(text.glyph_count(), 8);
+
+        // The same glyphs decode deterministically as either type
+        assert_eq!(
+            decode_to_text("▖▌▌▘").unwrap().text(),
+            JsValue::from_str("A")
+        );
+        assert_eq!(
+            decode_to_number("▖▌▌▘").unwrap().decimal(),
+            JsValue::from_str("65")
+        );
+
+        let error = decode_to_number("▖▌\u{200D}").unwrap_err();
+        assert_error(error.clone(), ERR_UNEXPECTED_DELIMITER);
+        assert_eq!(error_field(&error, "position"), JsValue::from_f64(2.0));
+
+        let error = decode_to_text("▖▌▌▘ ").unwrap_err();
+        assert_eq!(
+            error_field(&error, "code"),
+            JsValue::from_str("INVALID_CHAR")
+        );
+        assert_eq!(error_field(&error, "position"), JsValue::from_f64(4.0));
+
+        let error = decode_to_number(&"▖".repeat(MAX_DOLLCODE_SIZE + 1)).unwrap_err();
+        assert_error(error.clone(), ERR_DOLLCODE_TOO_LONG);
+        assert_eq!(
+            error_field(&error, "position"),
+            JsValue::from_f64(MAX_DOLLCODE_SIZE as f64)
+        );
+
+        assert_error(decode_to_text("").unwrap_err(), ERR_EMPTY);
+        assert_error(decode_to_number("").unwrap_err(), ERR_EMPTY);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_normalize() {
+        let normalized = normalize("▗▝\u{2590}\u{FE0F}\u{200D}").unwrap();
+        assert_eq!(normalized, JsValue::from_str("▖▘▌\u{200D}"));
+
+        // Wrapped and spaced for readability
+        let pasted = " ▘▖▘▌\u{200D}\r\n▌▘▖▌\u{200D}\u{200B}\t";
+        let normalized = normalize(pasted).unwrap();
+        assert_eq!(normalized, JsValue::from_str("▘▖▘▌\u{200D}▌▘▖▌\u{200D}"));
+        let decoded = decode_dollcode(&normalized.as_string().unwrap()).unwrap();
+        assert_eq!(decoded.text(), JsValue::from_str("Hi"));
+
+        assert_eq!(normalize("").unwrap(), JsValue::from_str(""));
+
+        let error = normalize("▖ ▘x").unwrap_err();
+        assert_eq!(
+            error_field(&error, "code"),
+            JsValue::from_str("INVALID_CHAR")
+        );
+        assert_eq!(error_field(&error, "position"), JsValue::from_f64(3.0));
+        assert_eq!(error_field(&error, "offendingChar"), JsValue::from_str("x"));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_url_encoding() {
+        let url = alloc_string(&convert_to_url("Hi").unwrap());
+        assert!(url.starts_with("%E2%96%98%E2%96%96"));
+        assert!(url.bytes().all(|b| b == b'%' || b.is_ascii_hexdigit()));
+        assert_eq!(detect(&url).kind(), JsValue::from_str("dollcode-url"));
+
+        assert!(!convert(&url).unwrap().is_string());
+        assert_eq!(decode_url(&url).unwrap().text(), JsValue::from_str("Hi"));
+        assert!(verify(&url).unwrap().lossless());
+
+        let url = alloc_string(&convert_to_url("42").unwrap());
+        assert_eq!(url, "%E2%96%96%E2%96%96%E2%96%96%E2%96%8C");
+        assert_eq!(convert_to_url("▖▖▖▌").unwrap(), JsValue::from_str(&url));
+        assert_eq!(convert_to_url(&url).unwrap(), JsValue::from_str(&url));
+
+        // Lowercase escapes decode too
+        let lowercase = url.to_lowercase();
+        assert_eq!(detect(&lowercase).kind(), JsValue::from_str("dollcode-url"));
+        assert_eq!(
+            decode_url(&lowercase).unwrap().decimal(),
+            JsValue::from_str("42")
+        );
+
+        // Anything else with escapes is still text
+        assert_eq!(detect("%20off").kind(), JsValue::from_str("text"));
+        assert_eq!(detect("%E2%96").kind(), JsValue::from_str("text"));
+
+        // Errors point into the encoded input
+        let error = convert(
+            // "A" followed by an out-of-range segment
+            "%E2%96%96%E2%96%8C%E2%96%8C%E2%96%98%E2%80%8D%E2%96%8C%E2%96%8C%E2%96%8C%E2%96%8C%E2%96%8C%E2%80%8D",
+        )
+        .unwrap_err();
+        assert_error(error.clone(), ERR_INVALID_SEQUENCE);
+        assert_eq!(error_field(&error, "position"), JsValue::from_f64(45.0));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_output_formats() {
+        assert_eq!(
+            convert_formatted("42", OutputFormat::Raw).unwrap(),
+            convert_decimal("42").unwrap()
+        );
+        assert_eq!(
+            convert_formatted("42", OutputFormat::Html).unwrap(),
+            JsValue::from_str("&#x2596;&#x2596;&#x2596;&#x258C;")
+        );
+        assert_eq!(
+            convert_formatted("A", OutputFormat::Html).unwrap(),
+            JsValue::from_str("&#x2596;&#x258C;&#x258C;&#x2598;&#x200D;")
+        );
+
+        let uri = alloc_string(&convert_formatted("Hi", OutputFormat::DataUri).unwrap());
+        let payload = uri.strip_prefix(DATA_URI_PREFIX).unwrap();
+        assert_eq!(convert_to_url("Hi").unwrap(), JsValue::from_str(payload));
+
+        // Dollcode input is re-emitted rather than decoded
+        let url = alloc_string(&convert_to_url("42").unwrap());
+        assert_eq!(
+            convert_formatted(&url, OutputFormat::Raw).unwrap(),
+            JsValue::from_str("▖▖▖▌")
+        );
+        assert_eq!(
+            convert_formatted("▖▖▖▌", OutputFormat::Html).unwrap(),
+            convert_formatted("42", OutputFormat::Html).unwrap()
+        );
+
+        assert_error(
+            convert_formatted("", OutputFormat::Html).unwrap_err(),
+            ERR_EMPTY,
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_whitespace_tolerance() {
+        let wrapped = "▘▖▘▌\u{200D}\r\n▌▘▖▌\u{200D}\n";
+        assert_eq!(detect(wrapped).kind(), JsValue::from_str("dollcode-text"));
+        assert_eq!(
+            decode_dollcode(wrapped).unwrap().text(),
+            JsValue::from_str("Hi")
+        );
+        assert!(verify(wrapped).unwrap().lossless());
+
+        let spaced = "▖▖ ▖▌\t";
+        let number = decode_dollcode(spaced).unwrap();
+        assert_eq!(number.decimal(), JsValue::from_str("42"));
+        assert_eq!(number.glyph_count(), 4);
         assert_eq!(
-            convert(&long_input).unwrap_err(),
-            JsValue::from_str(ERR_INPUT_TOO_LONG)
+            convert_formatted(spaced, OutputFormat::Raw).unwrap(),
+            JsValue::from_str("▖▖▖▌")
         );
 
-        assert_eq!(convert("").unwrap_err(), JsValue::from_str(ERR_EMPTY));
+        // Positions still count the skipped whitespace
+        let error = convert("▖▖ ▖x").unwrap_err();
+        assert_eq!(error_field(&error, "position"), JsValue::from_f64(4.0));
+        let error = convert("▘▖▘▌\u{200D}\n▌▌▌▌▌\u{200D}").unwrap_err();
+        assert_error(error.clone(), ERR_INVALID_SEQUENCE);
+        assert_eq!(error_field(&error, "position"), JsValue::from_f64(6.0));
+
+        // Text input still rejects control characters
+        assert!(convert("a\nb").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_interpret() {
+        // "A" in fixed-width text is also a valid number
+        let both = interpret("▖▖▌▘▖▌").unwrap();
+        assert!(both.ambiguous());
+        assert_eq!(both.text().unwrap().text(), JsValue::from_str("A"));
+        assert_eq!(
+            both.number().unwrap().to_js_string(),
+            decode_dollcode("▖▖▌▘▖▌").unwrap().to_js_string()
+        );
 
-        let long_dollcode = "▖".repeat(CHAR_BUF_SIZE + 1);
+        // Spaced and wrapped input is read the same both ways
+        let wrapped = interpret("▖▖▌ ▘▖\n▌").unwrap();
+        assert!(wrapped.ambiguous());
+        assert_eq!(wrapped.text().unwrap().text(), JsValue::from_str("A"));
         assert_eq!(
-            convert(&long_dollcode).unwrap_err(),
-            JsValue::from_str(ERR_DOLLCODE_TOO_LONG)
+            wrapped.number().unwrap().to_js_string(),
+            both.number().unwrap().to_js_string()
+        );
+
+        // Not a whole number of fixed-width groups
+        let number = interpret("▖▖▖▌").unwrap();
+        assert!(!number.ambiguous());
+        assert_eq!(number.number().unwrap().decimal(), JsValue::from_str("42"));
+        assert!(number.text().is_none());
+
+        // Too long for a number
+        let fixed = alloc_string(&convert_text_fixed_width("Hello!!").unwrap());
+        let text = interpret(&fixed).unwrap();
+        assert!(text.number().is_none());
+        assert_eq!(text.text().unwrap().text(), JsValue::from_str("Hello!!"));
+
+        let delimited = interpret("▘▖▘▌\u{200D}▌▘▖▌\u{200D}").unwrap();
+        assert!(delimited.number().is_none());
+        assert_eq!(delimited.text().unwrap().text(), JsValue::from_str("Hi"));
+
+        // convert flags the same readings on the number
+        let field = |value: &JsValue, key: &str| Reflect::get(value, &key.into()).unwrap();
+        let converted = convert("▖▖▌▘▖▌").unwrap();
+        assert_eq!(field(&converted, "kind"), JsValue::from_str("number"));
+        assert_eq!(field(&converted, "ambiguous"), JsValue::TRUE);
+        assert_eq!(field(&converted, "alternative"), JsValue::from_str("A"));
+        let converted = convert("▖▖▖▌").unwrap();
+        assert_eq!(field(&converted, "ambiguous"), JsValue::FALSE);
+        assert!(field(&converted, "alternative").is_undefined());
+        let text = decode_dollcode("▘▖▘▌\u{200D}▌▘▖▌\u{200D}").unwrap();
+        assert!(!text
+            .with_alternative("▘▖▘▌\u{200D}▌▘▖▌\u{200D}")
+            .ambiguous());
+
+        assert_error(interpret("42").unwrap_err(), ERR_NOT_DOLLCODE);
+        assert_error(
+            interpret(&"▌".repeat(MAX_DOLLCODE_SIZE)).unwrap_err(),
+            ERR_NUMBER_OVERFLOW,
         );
     }
 
+    #[wasm_bindgen_test]
+    fn test_unicode_normalization() {
+        let escaped = decode_dollcode("\u{2596}\u{2598}\u{258C}").unwrap();
+        let literal = decode_dollcode("▖▘▌").unwrap();
+        assert_eq!(literal.to_js_string(), escaped.to_js_string());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_error_messages() {
+        let long_input = "A".repeat(INPUT_SIZE + 1);
+        assert_error(convert(&long_input).unwrap_err(), ERR_INPUT_TOO_LONG);
+
+        assert_error(convert("").unwrap_err(), ERR_EMPTY);
+
+        let long_dollcode = "▖".repeat(INPUT_SIZE * BYTES_PER_CHAR + 1);
+        assert_error(convert(&long_dollcode).unwrap_err(), ERR_DOLLCODE_TOO_LONG);
+    }
+
     #[wasm_bindgen_test]
     fn test_number_limits() {
         const MAX_U64_DEC: &str = "18446744073709551615";
@@ -0,0 +1,606 @@
+//! Minimal QR code encoder for sharing dollcode by scanning.
+//!
+//! Only what [`to_qr`](crate::to_qr) needs is implemented: byte mode, error
+//! correction level M (15% recovery), versions 1 to 40 and automatic mask
+//! selection, following ISO/IEC 18004. The symbol is built in fixed buffers
+//! sized for the largest version.
+
+/// Largest QR version
+const MAX_VERSION: usize = 40;
+
+/// Side length of the largest symbol in modules
+const MAX_SIZE: usize = MAX_VERSION * 4 + 17;
+
+/// Module count of the largest symbol
+const MAX_MODULES: usize = MAX_SIZE * MAX_SIZE;
+
+/// Total codewords of the largest symbol
+const MAX_CODEWORDS: usize = 3706;
+
+/// Largest byte-mode payload at level M
+pub(crate) const MAX_PAYLOAD: usize = 2331;
+
+/// Error correction codewords per block at level M, indexed by version
+const ECC_PER_BLOCK: [u8; MAX_VERSION + 1] = [
+    0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28, 28,
+    28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28,
+];
+
+/// Error correction blocks at level M, indexed by version
+const BLOCKS: [u8; MAX_VERSION + 1] = [
+    0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21, 23,
+    25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49,
+];
+
+/// Format information bits for level M, before the mask number
+const FORMAT_LEVEL_M: u32 = 0b00;
+
+/// A finished QR symbol; `true` modules are dark.
+#[derive(Debug, Clone)]
+pub(crate) struct QrMatrix {
+    size: usize,
+    modules: [bool; MAX_MODULES],
+}
+
+impl QrMatrix {
+    /// Encodes `payload` in byte mode using the smallest version that fits.
+    ///
+    /// Returns `None` if the payload is longer than [`MAX_PAYLOAD`].
+    pub(crate) fn encode(payload: &[u8]) -> Option<Self> {
+        let version =
+            (1..=MAX_VERSION).find(|&v| data_bits(v, payload.len()) <= data_codewords(v) * 8)?;
+
+        let mut data = [0u8; MAX_CODEWORDS];
+        let len = data_codewords(version);
+        let mut bits = BitWriter::new(&mut data[..len]);
+        bits.push(0b0100, 4);
+        bits.push(payload.len() as u32, if version <= 9 { 8 } else { 16 });
+        for &byte in payload {
+            bits.push(u32::from(byte), 8);
+        }
+
+        // Terminator, byte alignment, then alternating pad bytes
+        let capacity = len * 8;
+        bits.push(0, (capacity - bits.len).min(4));
+        let align = (8 - bits.len % 8) % 8;
+        bits.push(0, align);
+        for pad in [0xEC, 0x11]
+            .iter()
+            .cycle()
+            .take(capacity.saturating_sub(bits.len) / 8)
+        {
+            bits.push(*pad, 8);
+        }
+
+        let mut codewords = [0u8; MAX_CODEWORDS];
+        let total = interleave(version, &data[..len], &mut codewords);
+
+        let mut builder = Builder::new(version);
+        builder.draw_function_patterns();
+        builder.draw_codewords(&codewords[..total]);
+        Some(builder.finish())
+    }
+
+    /// Side length in modules, without a quiet zone
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the module at column `x` and row `y` is dark
+    pub(crate) fn get(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+}
+
+/// Number of bits a byte-mode segment of `len` bytes takes in `version`
+fn data_bits(version: usize, len: usize) -> usize {
+    let count_bits = if version <= 9 { 8 } else { 16 };
+    4 + count_bits + len * 8
+}
+
+/// Number of codewords in `version`, data and error correction together
+fn raw_codewords(version: usize) -> usize {
+    let mut modules = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let alignments = version / 7 + 2;
+        modules -= (25 * alignments - 10) * alignments - 55;
+        if version >= 7 {
+            modules -= 36;
+        }
+    }
+    modules / 8
+}
+
+/// Number of data codewords in `version` at level M
+fn data_codewords(version: usize) -> usize {
+    raw_codewords(version) - usize::from(ECC_PER_BLOCK[version]) * usize::from(BLOCKS[version])
+}
+
+/// Splits `data` into blocks, appends error correction to each and
+/// interleaves the result into `out`, returning its length
+fn interleave(version: usize, data: &[u8], out: &mut [u8; MAX_CODEWORDS]) -> usize {
+    let blocks = usize::from(BLOCKS[version]);
+    let ecc_len = usize::from(ECC_PER_BLOCK[version]);
+    let raw = raw_codewords(version);
+    let short_blocks = blocks - raw % blocks;
+    let short_data = raw / blocks - ecc_len;
+
+    // Long blocks hold one more data codeword and come last
+    let data_len = |block: usize| short_data + usize::from(block >= short_blocks);
+    let data_start = |block: usize| block * short_data + block.saturating_sub(short_blocks);
+
+    let divisor = rs_divisor(ecc_len);
+    let mut ecc = [0u8; MAX_CODEWORDS];
+    for block in 0..blocks {
+        let start = data_start(block);
+        let remainder = rs_remainder(&data[start..start + data_len(block)], &divisor[..ecc_len]);
+        ecc[block * ecc_len..][..ecc_len].copy_from_slice(&remainder[..ecc_len]);
+    }
+
+    let mut len = 0;
+    for i in 0..=short_data {
+        for block in (0..blocks).filter(|&b| i < data_len(b)) {
+            out[len] = data[data_start(block) + i];
+            len += 1;
+        }
+    }
+    for i in 0..ecc_len {
+        for block in 0..blocks {
+            out[len] = ecc[block * ecc_len + i];
+            len += 1;
+        }
+    }
+    len
+}
+
+/// Largest error correction block length
+const MAX_ECC: usize = 30;
+
+/// Reed-Solomon generator polynomial of the given degree, highest term
+/// first and the leading 1 omitted
+fn rs_divisor(degree: usize) -> [u8; MAX_ECC] {
+    let mut result = [0u8; MAX_ECC];
+    result[degree - 1] = 1;
+
+    let mut root = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_mul(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_mul(root, 0x02);
+    }
+    result
+}
+
+/// Remainder of `data` divided by `divisor`, i.e. its error correction
+fn rs_remainder(data: &[u8], divisor: &[u8]) -> [u8; MAX_ECC] {
+    let degree = divisor.len();
+    let mut result = [0u8; MAX_ECC];
+
+    for &byte in data {
+        let factor = byte ^ result[0];
+        result.copy_within(1..degree, 0);
+        result[degree - 1] = 0;
+        for (r, &d) in result.iter_mut().zip(divisor) {
+            *r ^= gf_mul(d, factor);
+        }
+    }
+    result
+}
+
+/// Multiplication in GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1
+fn gf_mul(x: u8, y: u8) -> u8 {
+    let mut z: u16 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11D);
+        z ^= u16::from((y >> i) & 1) * u16::from(x);
+    }
+    z as u8
+}
+
+/// Appends big-endian bit fields to a codeword buffer
+struct BitWriter<'a> {
+    out: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> BitWriter<'a> {
+    fn new(out: &'a mut [u8]) -> Self {
+        Self { out, len: 0 }
+    }
+
+    fn push(&mut self, value: u32, bits: usize) {
+        for i in (0..bits).rev() {
+            if (value >> i) & 1 != 0 {
+                self.out[self.len / 8] |= 0x80 >> (self.len % 8);
+            }
+            self.len += 1;
+        }
+    }
+}
+
+/// Symbol under construction, tracking which modules are function patterns
+struct Builder {
+    version: usize,
+    matrix: QrMatrix,
+    function: [bool; MAX_MODULES],
+}
+
+impl Builder {
+    fn new(version: usize) -> Self {
+        Self {
+            version,
+            matrix: QrMatrix {
+                size: version * 4 + 17,
+                modules: [false; MAX_MODULES],
+            },
+            function: [false; MAX_MODULES],
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.matrix.size
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        let index = y * self.size() + x;
+        self.matrix.modules[index] = dark;
+        self.function[index] = true;
+    }
+
+    fn draw_function_patterns(&mut self) {
+        let size = self.size();
+
+        for i in 0..size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+
+        self.draw_finder(3, 3);
+        self.draw_finder(size - 4, 3);
+        self.draw_finder(3, size - 4);
+
+        let (positions, count) = alignment_positions(self.version);
+        let last = count.saturating_sub(1);
+        for i in 0..count {
+            for j in 0..count {
+                let corner = [(0, 0), (0, last), (last, 0)].contains(&(i, j));
+                if !corner {
+                    self.draw_alignment(positions[i], positions[j]);
+                }
+            }
+        }
+
+        // Reserve the format area; the real bits are drawn with the mask
+        self.draw_format(0);
+        self.draw_version();
+    }
+
+    fn draw_finder(&mut self, x: usize, y: usize) {
+        let size = self.size() as isize;
+        for dy in -4isize..=4 {
+            for dx in -4isize..=4 {
+                let (xx, yy) = (x as isize + dx, y as isize + dy);
+                if (0..size).contains(&xx) && (0..size).contains(&yy) {
+                    let distance = dx.abs().max(dy.abs());
+                    self.set_function(xx as usize, yy as usize, distance != 2 && distance != 4);
+                }
+            }
+        }
+    }
+
+    fn draw_alignment(&mut self, x: usize, y: usize) {
+        for dy in 0..5usize {
+            for dx in 0..5usize {
+                let distance = dx.abs_diff(2).max(dy.abs_diff(2));
+                self.set_function(x + dx - 2, y + dy - 2, distance != 1);
+            }
+        }
+    }
+
+    fn draw_format(&mut self, mask: u8) {
+        let data = FORMAT_LEVEL_M << 3 | u32::from(mask);
+        let mut rem = data;
+        for _ in 0..10 {
+            rem = (rem << 1) ^ ((rem >> 9) * 0x537);
+        }
+        let bits = (data << 10 | rem) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 != 0;
+        let size = self.size();
+
+        // Copy around the top-left finder
+        for i in 0..6 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+
+        // Copy split between the other two finders
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        self.set_function(8, size - 8, true);
+    }
+
+    fn draw_version(&mut self) {
+        if self.version < 7 {
+            return;
+        }
+
+        let bits = version_bits(self.version);
+        let size = self.size();
+        for i in 0..18 {
+            let dark = (bits >> i) & 1 != 0;
+            let (a, b) = (size - 11 + i % 3, i / 3);
+            self.set_function(a, b, dark);
+            self.set_function(b, a, dark);
+        }
+    }
+
+    /// Places codewords in the zigzag order, skipping function modules
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let size = self.size();
+        let mut i = 0;
+        let mut right = size - 1;
+
+        loop {
+            // The vertical timing pattern shifts the column pairs left
+            if right == 6 {
+                right = 5;
+            }
+            for vert in 0..size {
+                for j in 0..2 {
+                    let x = right - j;
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward { size - 1 - vert } else { vert };
+                    let index = y * size + x;
+                    if !self.function[index] && i < codewords.len() * 8 {
+                        self.matrix.modules[index] = (codewords[i / 8] >> (7 - i % 8)) & 1 != 0;
+                        i += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u8) {
+        let size = self.size();
+        for y in 0..size {
+            for x in 0..size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                let index = y * size + x;
+                self.matrix.modules[index] ^= invert && !self.function[index];
+            }
+        }
+    }
+
+    /// Applies the mask with the lowest penalty and draws its format bits
+    fn finish(mut self) -> QrMatrix {
+        let mut best = (u32::MAX, 0);
+        for mask in 0..8 {
+            self.apply_mask(mask);
+            self.draw_format(mask);
+            let penalty = penalty(&self.matrix);
+            if penalty < best.0 {
+                best = (penalty, mask);
+            }
+            // Masking is an XOR, so applying it again undoes it
+            self.apply_mask(mask);
+        }
+
+        self.apply_mask(best.1);
+        self.draw_format(best.1);
+        self.matrix
+    }
+}
+
+/// Centers of alignment patterns along each axis, and how many there are
+fn alignment_positions(version: usize) -> ([usize; 7], usize) {
+    let mut positions = [0; 7];
+    if version == 1 {
+        return (positions, 0);
+    }
+
+    let count = version / 7 + 2;
+    let step = (version * 8 + count * 3 + 5) / (count * 4 - 4) * 2;
+    positions[0] = 6;
+    let mut position = version * 4 + 17 - 7;
+    for i in (1..count).rev() {
+        positions[i] = position;
+        position -= step;
+    }
+    (positions, count)
+}
+
+/// Version information bits with their BCH error correction
+fn version_bits(version: usize) -> u32 {
+    let mut rem = version as u32;
+    for _ in 0..12 {
+        rem = (rem << 1) ^ ((rem >> 11) * 0x1F25);
+    }
+    (version as u32) << 12 | rem
+}
+
+// Penalty weights from the standard
+const PENALTY_RUN: u32 = 3;
+const PENALTY_BOX: u32 = 3;
+const PENALTY_FINDER: u32 = 40;
+const PENALTY_BALANCE: u32 = 10;
+
+/// Mask evaluation score; lower is easier to scan
+fn penalty(matrix: &QrMatrix) -> u32 {
+    let size = matrix.size;
+    let mut result = 0;
+
+    // Runs and finder-like patterns along rows, then columns
+    for transpose in [false, true] {
+        for a in 0..size {
+            let module = |b: usize| {
+                if transpose {
+                    matrix.get(a, b)
+                } else {
+                    matrix.get(b, a)
+                }
+            };
+            let mut runs = RunHistory::new(size);
+            let mut color = false;
+            let mut run = 0;
+            for b in 0..size {
+                if module(b) == color {
+                    run += 1;
+                    if run == 5 {
+                        result += PENALTY_RUN;
+                    } else if run > 5 {
+                        result += 1;
+                    }
+                } else {
+                    runs.push(run);
+                    if !color {
+                        result += runs.finder_patterns() * PENALTY_FINDER;
+                    }
+                    color = module(b);
+                    run = 1;
+                }
+            }
+            result += runs.terminate(color, run) * PENALTY_FINDER;
+        }
+    }
+
+    // 2×2 boxes of one color
+    for y in 0..size - 1 {
+        for x in 0..size - 1 {
+            let color = matrix.get(x, y);
+            if color == matrix.get(x + 1, y)
+                && color == matrix.get(x, y + 1)
+                && color == matrix.get(x + 1, y + 1)
+            {
+                result += PENALTY_BOX;
+            }
+        }
+    }
+
+    // Balance of dark and light modules
+    let total = size * size;
+    let dark = matrix.modules[..total].iter().filter(|&&m| m).count();
+    let k = (dark * 20).abs_diff(total * 10).div_ceil(total) - 1;
+    result + k as u32 * PENALTY_BALANCE
+}
+
+/// Lengths of the most recent runs in a row or column, newest first
+struct RunHistory {
+    size: usize,
+    runs: [usize; 7],
+}
+
+impl RunHistory {
+    fn new(size: usize) -> Self {
+        Self { size, runs: [0; 7] }
+    }
+
+    fn push(&mut self, mut run: usize) {
+        // The light quiet zone extends the first run
+        if self.runs[0] == 0 {
+            run += self.size;
+        }
+        self.runs.copy_within(0..6, 1);
+        self.runs[0] = run;
+    }
+
+    /// Counts 1:1:3:1:1 finder-like patterns with light space on either side
+    fn finder_patterns(&self) -> u32 {
+        let r = &self.runs;
+        let n = r[1];
+        let core = n > 0 && r[2] == n && r[3] == n * 3 && r[4] == n && r[5] == n;
+        u32::from(core && r[0] >= n * 4 && r[6] >= n)
+            + u32::from(core && r[6] >= n * 4 && r[0] >= n)
+    }
+
+    /// Closes the line against the light quiet zone and counts patterns
+    fn terminate(&mut self, color: bool, mut run: usize) -> u32 {
+        if color {
+            self.push(run);
+            run = 0;
+        }
+        self.push(run + self.size);
+        self.finder_patterns()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capacity_table() {
+        // Known level M data capacities
+        assert_eq!(data_codewords(1), 16);
+        assert_eq!(data_codewords(5), 86);
+        assert_eq!(data_codewords(10), 216);
+        assert_eq!(data_codewords(40), 2334);
+        assert_eq!(raw_codewords(40), MAX_CODEWORDS);
+
+        assert!(data_bits(40, MAX_PAYLOAD) <= data_codewords(40) * 8);
+        assert!(QrMatrix::encode(&[b'a'; MAX_PAYLOAD]).is_some());
+        assert!(QrMatrix::encode(&[b'a'; MAX_PAYLOAD + 1]).is_none());
+    }
+
+    #[test]
+    fn test_reed_solomon() {
+        // "HELLO WORLD" at 1-M
+        let data = [
+            32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+        ];
+        let ecc = rs_remainder(&data, &rs_divisor(10)[..10]);
+        assert_eq!(ecc[..10], [196, 35, 39, 119, 235, 215, 231, 226, 93, 23]);
+    }
+
+    #[test]
+    fn test_bch_codes() {
+        assert_eq!(version_bits(7), 0x07C94);
+        assert_eq!(version_bits(40), 0x28C69);
+        assert_eq!(alignment_positions(2), ([6, 18, 0, 0, 0, 0, 0], 2));
+        assert_eq!(alignment_positions(32), ([6, 34, 60, 86, 112, 138, 0], 6));
+    }
+
+    #[test]
+    fn test_structure() {
+        let qr = QrMatrix::encode("▖▖▖▌".as_bytes()).unwrap();
+        let size = qr.size();
+        assert_eq!(size, 21);
+
+        // Finder pattern corners and the always-dark module
+        for (x, y) in [(0, 0), (size - 7, 0), (0, size - 7)] {
+            assert!(qr.get(x, y) && qr.get(x + 6, y + 6) && qr.get(x + 3, y + 3));
+            assert!(!qr.get(x + 1, y + 1));
+        }
+        assert!(qr.get(8, size - 8));
+
+        // Both copies of the format information agree
+        let first: [bool; 8] = core::array::from_fn(|i| qr.get(8, [0, 1, 2, 3, 4, 5, 7, 8][i]));
+        let second: [bool; 8] = core::array::from_fn(|i| qr.get(size - 1 - i, 8));
+        assert_eq!(first, second);
+    }
+}